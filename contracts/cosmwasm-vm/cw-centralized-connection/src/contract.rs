@@ -1,5 +1,10 @@
 use cosmwasm_std::{coins, Addr, BankMsg, Event, SubMsgResult, Uint128};
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{
+    network_address::NetId,
+    xcall_connection_msg::{result_sequence_no, QueryMsg as XcallConnectionQueryMsg, SignableMsg},
+    xcall_msg::NackReason,
+};
+use sha3::Digest;
 
 use super::*;
 
@@ -17,8 +22,10 @@ impl<'a> CwCentralizedConnection<'a> {
     ) -> Result<Response, ContractError> {
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+        self.store_admin(deps.storage, _info.sender)?;
+
         let relayer = deps.api.addr_validate(&msg.relayer)?;
-        self.store_admin(deps.storage, relayer)?;
+        self.store_relayer(deps.storage, relayer, vec![])?;
 
         let xcall_address = deps.api.addr_validate(&msg.xcall_address)?;
         self.store_xcall(deps.storage, xcall_address)?;
@@ -35,6 +42,7 @@ impl<'a> CwCentralizedConnection<'a> {
     pub fn send_message(
         &mut self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         to: NetId,
         sn: i64,
@@ -42,13 +50,14 @@ impl<'a> CwCentralizedConnection<'a> {
     ) -> Result<Response, ContractError> {
         self.ensure_xcall(deps.storage, info.sender)?;
 
-        let next_conn_sn = self.get_next_conn_sn(deps.storage)?;
-
-        let mut fee = 0;
+        let next_conn_sn = self.get_next_conn_sn(deps.storage, to.clone())?;
 
-        if sn >= 0 {
-            fee = self.get_fee(deps.storage, to.clone(), sn > 0)?.into();
-        }
+        let quote = if sn >= 0 {
+            self.quote_fee(deps.as_ref(), to.clone(), sn > 0)
+        } else {
+            FeeQuote::default()
+        };
+        let fee = quote.total();
 
         let value = self.get_amount_for_denom(&info.funds, self.denom(deps.storage));
 
@@ -56,34 +65,73 @@ impl<'a> CwCentralizedConnection<'a> {
             return Err(ContractError::InsufficientFunds);
         }
 
-        Ok(Response::new()
-            .add_attribute("action", "send_message")
-            .add_event(
-                Event::new("Message")
-                    .add_attribute("targetNetwork", to.to_string())
-                    .add_attribute("connSn", next_conn_sn.to_string())
-                    .add_attribute("msg", self.hex_encode(msg)),
-            ))
+        // the reserved part comes from the quote that set the charge, which `value` covers
+        let response_fee = quote.response_fee;
+        let mut network_fees = self.get_network_fees(deps.storage, to.clone());
+        network_fees.message_fees += value - response_fee;
+        network_fees.response_fees += response_fee;
+        network_fees.reserved += response_fee;
+        self.store_network_fees(deps.storage, to.clone(), &network_fees)?;
+        if response_fee > 0 {
+            self.store_response_fee_deposit(deps.storage, to.clone(), sn as u128, response_fee)?;
+        }
+        self.track_sent_message(deps.storage, &env, to.clone(), next_conn_sn, sn)?;
+        self.log_message(deps.storage, &env, next_conn_sn, to.clone(), sn, &msg)?;
+
+        let mut res = Response::new().add_attribute("action", "send_message");
+        if let Some(err) = quote.oracle_error {
+            res = res.add_attribute("fee_oracle_error", err);
+        }
+        Ok(res.add_event(
+            Event::new("Message")
+                .add_attribute("targetNetwork", to.to_string())
+                .add_attribute("connSn", next_conn_sn.to_string())
+                .add_attribute("msg", self.hex_encode(msg)),
+        ))
     }
 
     pub fn recv_message(
         &mut self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         src_network: NetId,
         conn_sn: u128,
         msg: String,
     ) -> Result<Response, ContractError> {
-        self.ensure_admin(deps.storage, info.sender)?;
+        self.ensure_network_relayer(deps.storage, info.sender.clone(), &src_network)?;
 
         let hex_string_trimmed = msg.trim_start_matches("0x");
         let bytes = hex::decode(hex_string_trimmed).expect("Failed to decode to vec<u8>");
 
         let vec_msg: Vec<u8> = Binary(bytes).into();
+        let ordered = self.is_ordered_delivery(deps.storage, src_network.clone());
+        if ordered {
+            // resumes a walk cut short by its bound before the expected conn_sn is read
+            self.advance_contiguous_conn_sn(deps.storage, src_network.clone())?;
+        }
         if self.get_receipt(deps.as_ref().storage, src_network.clone(), conn_sn) {
             return Err(ContractError::DuplicateMessage);
         }
+        if ordered {
+            let expected = self.get_contiguous_conn_sn(deps.storage, src_network.clone()) + 1;
+            if conn_sn != expected {
+                return Err(ContractError::OutOfOrderMessage { expected });
+            }
+        }
         self.store_receipt(deps.storage, src_network.clone(), conn_sn)?;
+        self.track_conn_sn(deps.storage, src_network.clone(), conn_sn)?;
+        self.record_delivery(deps.storage, src_network.clone(), &info.sender)?;
+        if self.get_bond(deps.storage, &info.sender).total() > 0 {
+            let delivery = Delivery {
+                relayer: info.sender.clone(),
+                msg_hash: hex::encode(keccak256(&vec_msg).finalize()),
+                delivered_at: env.block.time.seconds(),
+            };
+            self.store_delivery(deps.storage, src_network.clone(), conn_sn, &delivery)?;
+        }
+
+        self.track_received_message(deps.storage, &env, src_network.clone(), conn_sn, &vec_msg)?;
 
         let xcall_submessage =
             self.call_xcall_handle_message(deps.storage, &src_network, vec_msg)?;
@@ -91,35 +139,662 @@ impl<'a> CwCentralizedConnection<'a> {
         Ok(Response::new().add_submessage(xcall_submessage))
     }
 
-    pub fn claim_fees(
-        &self,
+    /// Records why the message of `sn` could not be delivered and fails it on xCall with the
+    /// typed reason, enabling its rollback.
+    pub fn nack_message(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sn: u128,
+        reason_code: u8,
+        evidence: String,
+    ) -> Result<Response, ContractError> {
+        self.ensure_relayer(deps.storage, info.sender)?;
+        let reason = NackReason::try_from(reason_code)
+            .map_err(|_| ContractError::InvalidNackReason { code: reason_code })?;
+        if self.get_nack(deps.storage, sn).is_some() {
+            return Err(ContractError::AlreadyNacked);
+        }
+
+        let nack = NackRecord {
+            sn,
+            reason,
+            evidence,
+        };
+        self.store_nack(deps.storage, &nack)?;
+        self.resolve_pending_request(deps.storage, sn)?;
+        let xcall_submessage = self.call_xcall_handle_nack(deps.storage, sn, reason)?;
+
+        Ok(Response::new().add_submessage(xcall_submessage).add_event(
+            Event::new("NackMessage")
+                .add_attribute("sn", sn.to_string())
+                .add_attribute("reason", reason.as_str())
+                .add_attribute("evidence", nack.evidence),
+        ))
+    }
+
+    /// Records `conn_sn` as received from `src_network`, moving the highest and the contiguous
+    /// conn_sn of the network forward. The contiguous conn_sn is advanced on every receipt so a
+    /// walk cut short by its bound is resumed by the next message.
+    fn track_conn_sn(
+        &mut self,
+        store: &mut dyn Storage,
+        src_network: NetId,
+        conn_sn: u128,
+    ) -> Result<(), ContractError> {
+        if conn_sn > self.get_highest_conn_sn(store, src_network.clone()) {
+            self.store_highest_conn_sn(store, src_network.clone(), conn_sn)?;
+        }
+        self.advance_contiguous_conn_sn(store, src_network)?;
+        Ok(())
+    }
+
+    /// Records `conn_sn` as the last message sent to `to`, counting a request that expects a
+    /// response as pending until its result is received or it is reverted.
+    fn track_sent_message(
+        &mut self,
+        store: &mut dyn Storage,
+        env: &Env,
+        to: NetId,
+        conn_sn: u128,
+        sn: i64,
+    ) -> Result<(), ContractError> {
+        let mut status = self.get_network_status(store, to.clone());
+        status.last_sent_conn_sn = conn_sn;
+        status.last_sent_height = env.block.height;
+        if sn > 0 {
+            self.store_pending_request(store, sn as u128, to.clone())?;
+            status.pending_reverts += 1;
+        }
+        self.store_network_status(store, to, &status)?;
+        Ok(())
+    }
+
+    /// Records `conn_sn` as the last message received from `src_network`, resolving the pending
+    /// request `msg` is the result of and releasing its response fee to the relayer.
+    fn track_received_message(
+        &mut self,
+        store: &mut dyn Storage,
+        env: &Env,
+        src_network: NetId,
+        conn_sn: u128,
+        msg: &[u8],
+    ) -> Result<(), ContractError> {
+        let mut status = self.get_network_status(store, src_network.clone());
+        status.last_received_conn_sn = conn_sn;
+        status.last_received_height = env.block.height;
+        self.store_network_status(store, src_network.clone(), &status)?;
+
+        if let Some(sn) = result_sequence_no(msg) {
+            self.release_response_fee(store, src_network.clone(), sn)?;
+            if self.get_pending_request(store, sn) == Some(src_network) {
+                self.resolve_pending_request(store, sn)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes the response fee deposited for `sn` claimable by the relayer, a response having been
+    /// delivered for it.
+    fn release_response_fee(
+        &mut self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        sn: u128,
+    ) -> Result<(), ContractError> {
+        let amount = self.take_response_fee_deposit(store, nid.clone(), sn);
+        if amount > 0 {
+            let mut fees = self.get_network_fees(store, nid.clone());
+            fees.reserved = fees.reserved.saturating_sub(amount);
+            self.store_network_fees(store, nid, &fees)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_pending_request(
+        &mut self,
+        store: &mut dyn Storage,
+        sn: u128,
+    ) -> Result<(), ContractError> {
+        if let Some(nid) = self.get_pending_request(store, sn) {
+            self.remove_pending_request(store, sn);
+            let mut status = self.get_network_status(store, nid.clone());
+            status.pending_reverts = status.pending_reverts.saturating_sub(1);
+            self.store_network_status(store, nid, &status)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the contiguous conn_sn of `src_network` over at most
+    /// `MAX_CONTIGUOUS_CONN_SN_ADVANCE` of the receipts that follow it, pruning them as the
+    /// contiguous conn_sn covers them from then on.
+    fn advance_contiguous_conn_sn(
+        &mut self,
+        store: &mut dyn Storage,
+        src_network: NetId,
+    ) -> Result<u128, ContractError> {
+        let mut contiguous = self.get_contiguous_conn_sn(store, src_network.clone());
+        let mut steps = 0;
+        while steps < MAX_CONTIGUOUS_CONN_SN_ADVANCE
+            && self.get_receipt(store, src_network.clone(), contiguous + 1)
+        {
+            contiguous += 1;
+            steps += 1;
+            self.remove_receipt(store, src_network.clone(), contiguous);
+        }
+        self.store_contiguous_conn_sn(store, src_network, contiguous)?;
+        Ok(contiguous)
+    }
+
+    /// Folds up to `limit` of the receipts stored from `nid` before they were pruned into its
+    /// contiguous conn_sn, and prunes up to `limit` of its deliveries past the fraud-proof window.
+    /// Receipts above a gap and deliveries following one still open are kept, and calling it
+    /// again continues with the ones left.
+    pub fn compact_receipts(
+        &mut self,
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
+        nid: NetId,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let limit = limit
+            .unwrap_or(MAX_COMPACT_RECEIPTS_LIMIT)
+            .min(MAX_COMPACT_RECEIPTS_LIMIT) as usize;
+
+        if let Some(last) = self.get_last_receipt_conn_sn(deps.storage, nid.clone())? {
+            if last > self.get_highest_conn_sn(deps.storage, nid.clone()) {
+                self.store_highest_conn_sn(deps.storage, nid.clone(), last)?;
+            }
+        }
+        let contiguous = self.get_contiguous_conn_sn(deps.storage, nid.clone());
+        let covered = self.get_receipt_conn_sns(deps.storage, nid.clone(), contiguous, limit)?;
+        for conn_sn in covered.iter() {
+            self.remove_receipt(deps.storage, nid.clone(), *conn_sn);
+        }
+        let contiguous = self.advance_contiguous_conn_sn(deps.storage, nid.clone())?;
+
+        let window = self
+            .get_bond_config(deps.storage)
+            .map_or(0, |config| config.fraud_proof_window);
+        let expired = self.get_expired_delivery_conn_sns(
+            deps.storage,
+            nid.clone(),
+            env.block.time.seconds().saturating_sub(window),
+            limit,
+        )?;
+        for conn_sn in expired.iter() {
+            self.remove_delivery(deps.storage, nid.clone(), *conn_sn);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "compact_receipts")
+            .add_attribute("network_id", nid.to_string())
+            .add_attribute("removed", covered.len().to_string())
+            .add_attribute("pruned_deliveries", expired.len().to_string())
+            .add_attribute("contiguous_conn_sn", contiguous.to_string()))
+    }
+
+    /// Switches `src_network` between ordered delivery, where only the conn_sn following the
+    /// contiguous one is accepted, and the default delivery in any order. Ordered delivery relies
+    /// on the connection of `src_network` numbering its messages per destination, as a sequence
+    /// shared by all destinations leaves gaps here that are never filled.
+    pub fn set_ordered_delivery(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        src_network: NetId,
+        ordered: bool,
     ) -> Result<Response, ContractError> {
         self.ensure_admin(deps.storage, info.sender)?;
-        let contract_balance = self.get_balance(&deps, env, self.denom(deps.storage));
+        self.store_ordered_delivery(deps.storage, src_network.clone(), ordered)?;
+        let contiguous = self.advance_contiguous_conn_sn(deps.storage, src_network.clone())?;
+        Ok(Response::new()
+            .add_attribute("action", "set_ordered_delivery")
+            .add_attribute("src_network", src_network.to_string())
+            .add_attribute("ordered", ordered.to_string())
+            .add_attribute("next_conn_sn", (contiguous + 1).to_string()))
+    }
+
+    pub fn get_delivery_status(&self, store: &dyn Storage, src_network: NetId) -> DeliveryStatus {
+        DeliveryStatus {
+            ordered: self.is_ordered_delivery(store, src_network.clone()),
+            contiguous_conn_sn: self.get_contiguous_conn_sn(store, src_network.clone()),
+            highest_conn_sn: self.get_highest_conn_sn(store, src_network),
+        }
+    }
+
+    /// Returns up to `limit` conn_sns between the contiguous and the highest conn_sn received
+    /// from `src_network` that have no receipt yet.
+    pub fn get_missing_conn_sns(
+        &self,
+        store: &dyn Storage,
+        src_network: NetId,
+        limit: Option<u32>,
+    ) -> Vec<u128> {
+        let limit = limit
+            .unwrap_or(MAX_MISSING_CONN_SN_LIMIT)
+            .min(MAX_MISSING_CONN_SN_LIMIT) as usize;
+        let highest = self.get_highest_conn_sn(store, src_network.clone());
+        let mut conn_sn = self.get_contiguous_conn_sn(store, src_network.clone()) + 1;
+
+        let mut missing = vec![];
+        while conn_sn < highest && missing.len() < limit {
+            if !self.get_receipt(store, src_network.clone(), conn_sn) {
+                missing.push(conn_sn);
+            }
+            conn_sn += 1;
+        }
+        missing
+    }
+
+    /// Keeps the sent message in the outbound message log when it is enabled, dropping the one
+    /// that no longer fits in the last `capacity` conn_sns of `to`.
+    fn log_message(
+        &mut self,
+        store: &mut dyn Storage,
+        env: &Env,
+        conn_sn: u128,
+        to: NetId,
+        sn: i64,
+        msg: &[u8],
+    ) -> Result<(), ContractError> {
+        let capacity = self.get_message_log_capacity(store) as u128;
+        if capacity == 0 {
+            return Ok(());
+        }
+
+        let message = OutboundMessage {
+            conn_sn,
+            to: to.clone(),
+            sn,
+            msg_hash: hex::encode(keccak256(msg).finalize()),
+            height: env.block.height,
+        };
+        self.store_message(store, &message)?;
+        self.remove_messages_below(store, to, (conn_sn + 1).saturating_sub(capacity))?;
+        Ok(())
+    }
+
+    /// Sets how many of the most recent messages sent to each destination are kept in the
+    /// outbound message log, zero disabling it. Messages that no longer fit are removed.
+    pub fn set_message_log_capacity(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        capacity: u32,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        if capacity > MAX_MESSAGE_LOG_CAPACITY {
+            return Err(ContractError::InvalidMessageLogCapacity {
+                max: MAX_MESSAGE_LOG_CAPACITY,
+            });
+        }
+
+        self.store_message_log_capacity(deps.storage, capacity)?;
+        for to in self.get_conn_sn_networks(deps.storage)? {
+            let conn_sn = self.get_network_conn_sn(deps.storage, to.clone());
+            let below = (conn_sn + 1).saturating_sub(capacity.into());
+            self.remove_messages_below(deps.storage, to, below)?;
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_message_log_capacity")
+            .add_attribute("capacity", capacity.to_string()))
+    }
+
+    /// Returns up to `limit` of the messages logged for `to` starting from conn_sn `from`.
+    pub fn get_logged_messages(
+        &self,
+        store: &dyn Storage,
+        to: NetId,
+        from: u128,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<OutboundMessage>> {
+        let limit = limit.unwrap_or(MAX_MESSAGES_LIMIT).min(MAX_MESSAGES_LIMIT) as usize;
+        self.get_messages(store, to, from, limit)
+    }
+
+    /// Makes `relayer` the one earning the fees of messages sent to `nid` as it delivers from it.
+    /// The pending fees of `nid` are credited to the relayer that delivered before it, so a
+    /// failover does not move fees the previous relayer has earned.
+    fn record_delivery(
+        &mut self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        relayer: &Addr,
+    ) -> StdResult<()> {
+        if self.get_fee_relayer(store, nid.clone()).as_ref() == Some(relayer) {
+            return Ok(());
+        }
+        self.settle_fee_relayer(store, nid.clone())?;
+        self.store_fee_relayer(store, nid, relayer)
+    }
+
+    /// Credits the pending fees of `nid` to the relayer that delivered from it last.
+    pub fn settle_fee_relayer(&mut self, store: &mut dyn Storage, nid: NetId) -> StdResult<()> {
+        if let Some(previous) = self.get_fee_relayer(store, nid.clone()) {
+            let mut fees = self.get_network_fees(store, nid.clone());
+            let amount = fees.unclaimed();
+            if amount > 0 {
+                fees.claimed += amount;
+                self.store_network_fees(store, nid, &fees)?;
+                let credited = self.get_relayer_fees(store, &previous) + amount;
+                self.store_relayer_fees(store, &previous, credited)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the fees `relayer` can claim: the ones credited to it and the pending fees of
+    /// every network it is the fee relayer of.
+    pub fn get_claimable_fees(&self, store: &dyn Storage, relayer: &Addr) -> StdResult<u128> {
+        let mut amount = self.get_relayer_fees(store, relayer);
+        for (nid, fees) in self.get_all_network_fees(store)? {
+            if self.is_fee_relayer(store, relayer, &nid) {
+                amount += fees.unclaimed();
+            }
+        }
+        Ok(amount)
+    }
+
+    pub fn claim_fees(
+        &mut self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let relayer = info.sender;
+        let mut amount = self.get_relayer_fees(deps.storage, &relayer);
+        if amount == 0 {
+            self.ensure_relayer(deps.storage, relayer.clone())?;
+        }
+        self.store_relayer_fees(deps.storage, &relayer, 0)?;
+
+        for (nid, mut fees) in self.get_all_network_fees(deps.storage)? {
+            if !self.is_fee_relayer(deps.storage, &relayer, &nid) {
+                continue;
+            }
+            let unclaimed = fees.unclaimed();
+            amount += unclaimed;
+            fees.claimed += unclaimed;
+            self.store_network_fees(deps.storage, nid, &fees)?;
+        }
+        if amount == 0 {
+            return Err(ContractError::NoFeesToClaim);
+        }
+
         let msg = BankMsg::Send {
-            to_address: self.query_admin(deps.storage)?.to_string(),
-            amount: coins(contract_balance, self.denom(deps.storage)),
+            to_address: relayer.to_string(),
+            amount: coins(amount, self.denom(deps.storage)),
         };
         Ok(Response::new()
             .add_attribute("action", "claim fees")
+            .add_attribute("amount", amount.to_string())
+            .add_message(msg))
+    }
+
+    pub fn claim_network_fees(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+    ) -> Result<Response, ContractError> {
+        self.ensure_relayer(deps.storage, info.sender.clone())?;
+        if !self.is_fee_relayer(deps.storage, &info.sender, &nid) {
+            return Err(ContractError::RelayerNotAllowed {
+                nid: nid.to_string(),
+            });
+        }
+
+        let mut fees = self.get_network_fees(deps.storage, nid.clone());
+        let amount = fees.unclaimed();
+        if amount == 0 {
+            return Err(ContractError::NoFeesToClaim);
+        }
+        fees.claimed += amount;
+        self.store_network_fees(deps.storage, nid.clone(), &fees)?;
+
+        let msg = BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount, self.denom(deps.storage)),
+        };
+        Ok(Response::new()
+            .add_attribute("action", "claim network fees")
+            .add_attribute("network_id", nid.to_string())
+            .add_attribute("amount", amount.to_string())
+            .add_message(msg))
+    }
+
+    pub fn refund_response_fee(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+        sn: u128,
+        to: String,
+    ) -> Result<Response, ContractError> {
+        self.ensure_xcall(deps.storage, info.sender)?;
+        let to = deps.api.addr_validate(&to)?;
+
+        let amount = self.take_response_fee_deposit(deps.storage, nid.clone(), sn);
+        if amount == 0 {
+            return Ok(Response::new().add_attribute("action", "refund_response_fee"));
+        }
+
+        let mut fees = self.get_network_fees(deps.storage, nid.clone());
+        fees.reserved = fees.reserved.saturating_sub(amount);
+        fees.response_fees -= amount;
+        self.store_network_fees(deps.storage, nid, &fees)?;
+
+        let msg = BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount, self.denom(deps.storage)),
+        };
+        Ok(Response::new()
+            .add_attribute("action", "refund_response_fee")
+            .add_attribute("sn", sn.to_string())
+            .add_attribute("amount", amount.to_string())
             .add_message(msg))
     }
 
     pub fn revert_message(
-        &self,
+        &mut self,
         deps: DepsMut,
         info: MessageInfo,
         sn: u128,
     ) -> Result<Response, ContractError> {
-        self.ensure_admin(deps.storage, info.sender)?;
+        self.ensure_relayer(deps.storage, info.sender)?;
+        self.resolve_pending_request(deps.storage, sn)?;
         let xcall_submessage = self.call_xcall_handle_error(deps.storage, sn)?;
 
         Ok(Response::new().add_submessage(xcall_submessage))
     }
 
+    /// Enables relayer bonding with `attesters` whose signatures prove the messages sent on
+    /// their source network.
+    pub fn set_bond_config(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        attesters: Vec<Vec<u8>>,
+        threshold: u8,
+        slash_recipient: String,
+        unbonding_period: u64,
+        fraud_proof_window: u64,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        if threshold < 1 {
+            return Err(ContractError::InvalidThreshold {
+                msg: "threshold should be at least 1".to_string(),
+            });
+        }
+        if attesters.len() < threshold as usize {
+            return Err(ContractError::InvalidThreshold {
+                msg: "threshold should be at most the size of attesters".to_string(),
+            });
+        }
+        // a delivery still open to fraud proofs must find the bond of its relayer in place
+        if unbonding_period < fraud_proof_window {
+            return Err(ContractError::UnbondingPeriodTooShort { fraud_proof_window });
+        }
+        let config = BondConfig {
+            attesters,
+            threshold,
+            slash_recipient: deps.api.addr_validate(&slash_recipient)?,
+            unbonding_period,
+            fraud_proof_window,
+        };
+        self.store_bond_config(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_bond_config")
+            .add_attribute("threshold", threshold.to_string())
+            .add_attribute("slash_recipient", slash_recipient))
+    }
+
+    /// Locks the funds sent by the relayer as its bond, its deliveries are slashable from then
+    /// on.
+    pub fn bond(&mut self, deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        if self.get_bond_config(deps.storage).is_none() {
+            return Err(ContractError::BondingDisabled);
+        }
+        self.ensure_relayer(deps.storage, info.sender.clone())?;
+        let amount = self.get_amount_for_denom(&info.funds, self.denom(deps.storage));
+        if amount == 0 {
+            return Err(ContractError::InsufficientFunds);
+        }
+
+        let mut bond = self.get_bond(deps.storage, &info.sender);
+        bond.bonded += amount;
+        self.store_bond(deps.storage, &info.sender, &bond)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "bond")
+            .add_attribute("relayer", info.sender)
+            .add_attribute("amount", amount.to_string()))
+    }
+
+    /// Starts unbonding `amount` of the sender's bond. It stays slashable until withdrawn, and
+    /// unbonding more postpones the release of the whole unbonding amount.
+    pub fn unbond(
+        &mut self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let config = self
+            .get_bond_config(deps.storage)
+            .ok_or(ContractError::BondingDisabled)?;
+        let mut bond = self.get_bond(deps.storage, &info.sender);
+        if amount == 0 || bond.bonded < amount {
+            return Err(ContractError::NoBond);
+        }
+
+        bond.bonded -= amount;
+        bond.unbonding += amount;
+        bond.release_at = env.block.time.seconds() + config.unbonding_period;
+        self.store_bond(deps.storage, &info.sender, &bond)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "unbond")
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("release_at", bond.release_at.to_string()))
+    }
+
+    pub fn withdraw_bond(
+        &mut self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let mut bond = self.get_bond(deps.storage, &info.sender);
+        if bond.unbonding == 0 {
+            return Err(ContractError::NoBond);
+        }
+        if env.block.time.seconds() < bond.release_at {
+            return Err(ContractError::BondLocked {
+                release_at: bond.release_at,
+            });
+        }
+
+        let amount = bond.unbonding;
+        bond.unbonding = 0;
+        self.store_bond(deps.storage, &info.sender, &bond)?;
+
+        let msg = BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount, self.denom(deps.storage)),
+        };
+        Ok(Response::new()
+            .add_attribute("action", "withdraw_bond")
+            .add_attribute("amount", amount.to_string())
+            .add_message(msg))
+    }
+
+    /// Slashes the bond of the relayer that delivered `(src_network, conn_sn)` when the
+    /// attesters signed a different `msg` for it within the fraud-proof window.
+    pub fn submit_fraud_proof(
+        &mut self,
+        deps: DepsMut,
+        env: Env,
+        src_network: NetId,
+        conn_sn: u128,
+        msg: String,
+        signatures: Vec<Vec<u8>>,
+    ) -> Result<Response, ContractError> {
+        let config = self
+            .get_bond_config(deps.storage)
+            .ok_or(ContractError::BondingDisabled)?;
+        let delivery = self
+            .get_delivery(deps.storage, src_network.clone(), conn_sn)
+            .ok_or(ContractError::DeliveryNotFound)?;
+        if env.block.time.seconds() >= delivery.delivered_at + config.fraud_proof_window {
+            return Err(ContractError::DeliveryExpired);
+        }
+
+        let msg = hex::decode(msg.trim_start_matches("0x"))
+            .map_err(|e| ContractError::InvalidHexData { msg: e.to_string() })?;
+        if hex::encode(keccak256(&msg).finalize()) == delivery.msg_hash {
+            return Err(ContractError::NoFraud);
+        }
+
+        let signed_msg = SignableMsg {
+            src_network: src_network.to_string(),
+            conn_sn,
+            data: msg,
+            dst_network: self.get_network_id(deps.as_ref())?,
+        }
+        .encode_utf8_bytes();
+        self.verify_attestations(
+            deps.as_ref(),
+            &config.attesters,
+            config.threshold,
+            signed_msg,
+            signatures,
+        )?;
+
+        let amount = self.get_bond(deps.storage, &delivery.relayer).total();
+        self.store_bond(deps.storage, &delivery.relayer, &RelayerBond::default())?;
+        self.remove_delivery(deps.storage, src_network.clone(), conn_sn);
+
+        let mut res = Response::new().add_event(
+            Event::new("RelayerSlashed")
+                .add_attribute("relayer", delivery.relayer.to_string())
+                .add_attribute("src_network", src_network.to_string())
+                .add_attribute("conn_sn", conn_sn.to_string())
+                .add_attribute("amount", amount.to_string()),
+        );
+        if amount > 0 {
+            res = res.add_message(BankMsg::Send {
+                to_address: config.slash_recipient.to_string(),
+                amount: coins(amount, self.denom(deps.storage)),
+            });
+        }
+        Ok(res)
+    }
+
     pub fn set_admin(
         &mut self,
         deps: DepsMut,
@@ -132,6 +807,53 @@ impl<'a> CwCentralizedConnection<'a> {
         Ok(Response::new().add_attribute("action", "set_admin"))
     }
 
+    /// Replaces the relayer set with `address`, allowed for every network.
+    pub fn set_relayer(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: Addr,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let relayer = deps.api.addr_validate(address.as_str())?;
+        self.clear_relayers(deps.storage)?;
+        self.store_relayer(deps.storage, relayer, vec![])?;
+        Ok(Response::new().add_attribute("action", "set_relayer"))
+    }
+
+    /// Adds `address` to the relayer set, or updates its networks when already present. An empty
+    /// `networks` allows the relayer to deliver from every network.
+    pub fn add_relayer(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: Addr,
+        networks: Vec<NetId>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let relayer = deps.api.addr_validate(address.as_str())?;
+        self.store_relayer(deps.storage, relayer.clone(), networks)?;
+        Ok(Response::new()
+            .add_attribute("action", "add_relayer")
+            .add_attribute("relayer", relayer.to_string()))
+    }
+
+    pub fn remove_relayer(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: Addr,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        if self.get_relayer(deps.storage, &address).is_none() {
+            return Err(ContractError::RelayerNotFound);
+        }
+        self.remove_relayer_entry(deps.storage, &address)?;
+        Ok(Response::new()
+            .add_attribute("action", "remove_relayer")
+            .add_attribute("relayer", address.to_string()))
+    }
+
     pub fn set_fee(
         &mut self,
         deps: DepsMut,
@@ -158,6 +880,100 @@ impl<'a> CwCentralizedConnection<'a> {
         Ok(fee.into())
     }
 
+    /// Returns the total of `quote_fee`.
+    pub fn calculate_fee(
+        &self,
+        deps: Deps,
+        network_id: NetId,
+        response: bool,
+    ) -> Result<Uint128, ContractError> {
+        Ok(self.quote_fee(deps, network_id, response).total().into())
+    }
+
+    /// Quotes the fee of a message to `network_id`. With a fee oracle set, its quote bounded to
+    /// the floor and cap is charged and the response part is what it adds to the quote without
+    /// a response. The stored fees are quoted when no oracle is set or its query fails, in which
+    /// case the error is kept in the quote.
+    pub fn quote_fee(&self, deps: Deps, network_id: NetId, response: bool) -> FeeQuote {
+        let oracle = match self.get_fee_oracle(deps.storage) {
+            Some(oracle) => oracle,
+            None => return self.stored_fee_quote(deps.storage, network_id, response, None),
+        };
+        let query_oracle = |response: bool| {
+            let query = XcallConnectionQueryMsg::GetFee {
+                nid: network_id.clone(),
+                response,
+            };
+            deps.querier
+                .query_wasm_smart::<u128>(oracle.address.clone(), &query)
+                .map(|fee| fee.clamp(oracle.floor, oracle.cap))
+        };
+
+        let quote = query_oracle(false).and_then(|message_fee| {
+            let total = if response {
+                query_oracle(true)?
+            } else {
+                message_fee
+            };
+            let message_fee = message_fee.min(total);
+            Ok(FeeQuote {
+                message_fee,
+                response_fee: total - message_fee,
+                oracle_error: None,
+            })
+        });
+        match quote {
+            Ok(quote) => quote,
+            Err(err) => {
+                self.stored_fee_quote(deps.storage, network_id, response, Some(err.to_string()))
+            }
+        }
+    }
+
+    fn stored_fee_quote(
+        &self,
+        store: &dyn Storage,
+        network_id: NetId,
+        response: bool,
+        oracle_error: Option<String>,
+    ) -> FeeQuote {
+        let response_fee = if response {
+            self.query_response_fee(store, network_id.clone())
+        } else {
+            0
+        };
+        FeeQuote {
+            message_fee: self.query_message_fee(store, network_id),
+            response_fee,
+            oracle_error,
+        }
+    }
+
+    pub fn set_fee_oracle(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: Option<String>,
+        floor: u128,
+        cap: u128,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        if floor > cap {
+            return Err(ContractError::InvalidFeeBounds);
+        }
+
+        let oracle = match address {
+            Some(address) => Some(FeeOracle {
+                address: deps.api.addr_validate(&address)?,
+                floor,
+                cap,
+            }),
+            None => None,
+        };
+        self.store_fee_oracle(deps.storage, oracle)?;
+        Ok(Response::new().add_attribute("action", "set_fee_oracle"))
+    }
+
     fn xcall_handle_message_reply(
         &self,
         _deps: DepsMut,
@@ -192,10 +1008,27 @@ impl<'a> CwCentralizedConnection<'a> {
         }
     }
 
+    fn xcall_handle_nack_reply(
+        &self,
+        _deps: DepsMut,
+        message: Reply,
+    ) -> Result<Response, ContractError> {
+        match message.result {
+            SubMsgResult::Ok(_) => Ok(Response::new()
+                .add_attribute("action", "call_message")
+                .add_attribute("method", "xcall_handle_nack_reply")),
+            SubMsgResult::Err(error) => Err(ContractError::ReplyError {
+                code: message.id,
+                msg: error,
+            }),
+        }
+    }
+
     pub fn reply(&self, deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
         match msg.id {
             XCALL_HANDLE_MESSAGE_REPLY_ID => self.xcall_handle_message_reply(deps, msg),
             XCALL_HANDLE_ERROR_REPLY_ID => self.xcall_handle_error_reply(deps, msg),
+            XCALL_HANDLE_NACK_REPLY_ID => self.xcall_handle_nack_reply(deps, msg),
             _ => Err(ContractError::ReplyError {
                 code: msg.id,
                 msg: "Unknown".to_string(),
@@ -204,13 +1037,42 @@ impl<'a> CwCentralizedConnection<'a> {
     }
 
     pub fn migrate(
-        &self,
+        &mut self,
         deps: DepsMut,
-        _env: Env,
-        _msg: MigrateMsg,
+        env: Env,
+        msg: MigrateMsg,
     ) -> Result<Response, ContractError> {
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)
             .map_err(ContractError::Std)?;
+        // deployments predating the relayer set keep their relayer, or their admin when they
+        // predate the relayer role, allowed for every network. The fees they hold were not
+        // tracked per network and are credited to that relayer.
+        if self.get_relayers(deps.storage)?.is_empty() {
+            let legacy: Item<Addr> = Item::new(StorageKey::Relayer.as_str());
+            let relayer = match legacy.may_load(deps.storage)? {
+                Some(relayer) => relayer,
+                None => self.query_admin(deps.storage)?,
+            };
+            legacy.remove(deps.storage);
+            let balance = deps
+                .querier
+                .query_balance(&env.contract.address, self.denom(deps.storage))?;
+            let credited = self.get_relayer_fees(deps.storage, &relayer) + balance.amount.u128();
+            self.store_relayer_fees(deps.storage, &relayer, credited)?;
+            self.store_relayer(deps.storage, relayer, vec![])?;
+        }
+        // connections numbered their messages across destinations before each destination got
+        // its own sequence, the gaps this left below the conn_sn they continued from are never
+        // filled. The receipts it covers are folded in by `compact_receipts`.
+        for (src_network, conn_sn) in msg.legacy_conn_sns {
+            if conn_sn > self.get_contiguous_conn_sn(deps.storage, src_network.clone()) {
+                self.store_contiguous_conn_sn(deps.storage, src_network.clone(), conn_sn)?;
+            }
+            if conn_sn > self.get_highest_conn_sn(deps.storage, src_network.clone()) {
+                self.store_highest_conn_sn(deps.storage, src_network.clone(), conn_sn)?;
+            }
+            self.advance_contiguous_conn_sn(deps.storage, src_network)?;
+        }
         Ok(Response::default().add_attribute("migrate", "successful"))
     }
 }
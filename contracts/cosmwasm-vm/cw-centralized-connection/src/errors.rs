@@ -8,14 +8,54 @@ pub enum ContractError {
     Unauthorized {},
     #[error("Invalid Address {address}")]
     InvalidAddress { address: String },
-    #[error("Only Relayer(Admin)")]
+    #[error("Only Admin")]
     OnlyAdmin,
+    #[error("Only Relayer")]
+    OnlyRelayer,
+    #[error("Relayer Not Allowed For {nid}")]
+    RelayerNotAllowed { nid: String },
+    #[error("Relayer Not Found")]
+    RelayerNotFound,
     #[error("Only XCall")]
     OnlyXCall,
     #[error("Duplicate Message")]
     DuplicateMessage,
+    #[error("Out Of Order Message, Expected Conn Sn {expected}")]
+    OutOfOrderMessage { expected: u128 },
     #[error("InsufficientFunds")]
     InsufficientFunds,
     #[error("ERR_REPLY_ERROR|{code:?}|{msg:?}")]
     ReplyError { code: u64, msg: String },
+    #[error("No Fees To Claim")]
+    NoFeesToClaim,
+    #[error("Invalid Fee Bounds")]
+    InvalidFeeBounds,
+    #[error("Invalid Message Log Capacity, Max {max}")]
+    InvalidMessageLogCapacity { max: u32 },
+    #[error("Invalid Nack Reason {code}")]
+    InvalidNackReason { code: u8 },
+    #[error("Message Already Nacked")]
+    AlreadyNacked,
+    #[error("Bonding Disabled")]
+    BondingDisabled,
+    #[error("Invalid Threshold Value|{msg:?}")]
+    InvalidThreshold { msg: String },
+    #[error("Unbonding Period Shorter Than Fraud Proof Window {fraud_proof_window}")]
+    UnbondingPeriodTooShort { fraud_proof_window: u64 },
+    #[error("No Bond")]
+    NoBond,
+    #[error("Bond Locked Until {release_at}")]
+    BondLocked { release_at: u64 },
+    #[error("Delivery Not Found")]
+    DeliveryNotFound,
+    #[error("Delivery Expired")]
+    DeliveryExpired,
+    #[error("Delivered Message Matches Attested Message")]
+    NoFraud,
+    #[error("Insufficient Signatures")]
+    InsufficientSignatures,
+    #[error("Invalid Signature")]
+    InvalidSignature,
+    #[error("Invalid Hex Data|{msg:?}")]
+    InvalidHexData { msg: String },
 }
@@ -1,10 +1,37 @@
-use cosmwasm_std::{ensure_eq, Addr, BalanceResponse, BankQuery, Coin};
-use cw_xcall_lib::network_address::NetId;
+use std::str::FromStr;
+
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{ensure_eq, Addr, BalanceResponse, BankQuery, Coin, QueryRequest};
+use cw_xcall_lib::{
+    network_address::{NetId, NetworkAddress},
+    xcall_msg::NackReason,
+};
+use sha3::{Digest, Keccak256};
 
 pub const XCALL_HANDLE_MESSAGE_REPLY_ID: u64 = 1;
 pub const XCALL_HANDLE_ERROR_REPLY_ID: u64 = 2;
+pub const XCALL_HANDLE_NACK_REPLY_ID: u64 = 3;
+pub const MAX_MISSING_CONN_SN_LIMIT: u32 = 100;
+pub const MAX_CONTIGUOUS_CONN_SN_ADVANCE: u32 = 100;
+pub const MAX_COMPACT_RECEIPTS_LIMIT: u32 = 100;
+pub const MAX_MESSAGE_LOG_CAPACITY: u32 = 1000;
+pub const MAX_MESSAGES_LIMIT: u32 = 100;
 use super::*;
 
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum XcallQueryMsg {
+    #[returns(String)]
+    GetNetworkAddress {},
+}
+
+pub fn keccak256(input: &[u8]) -> Keccak256 {
+    use sha3::{Digest, Keccak256};
+    let mut hasher = Keccak256::new();
+    hasher.update(input);
+    hasher
+}
+
 impl<'a> CwCentralizedConnection<'a> {
     pub fn ensure_admin(&self, store: &dyn Storage, address: Addr) -> Result<(), ContractError> {
         let admin = self.query_admin(store)?;
@@ -13,6 +40,45 @@ impl<'a> CwCentralizedConnection<'a> {
         Ok(())
     }
 
+    pub fn ensure_relayer(&self, store: &dyn Storage, address: Addr) -> Result<(), ContractError> {
+        if self.get_relayer(store, &address).is_none() {
+            return Err(ContractError::OnlyRelayer);
+        }
+
+        Ok(())
+    }
+
+    pub fn ensure_network_relayer(
+        &self,
+        store: &dyn Storage,
+        address: Addr,
+        nid: &NetId,
+    ) -> Result<(), ContractError> {
+        let relayer = self
+            .get_relayer(store, &address)
+            .ok_or(ContractError::OnlyRelayer)?;
+        if !relayer.allows(nid) {
+            return Err(ContractError::RelayerNotAllowed {
+                nid: nid.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Whether `address` may claim the pending fees of messages sent to `nid`: the relayer that
+    /// last delivered a message from it, or any relayer allowed for it when nothing was delivered
+    /// yet.
+    pub fn is_fee_relayer(&self, store: &dyn Storage, address: &Addr, nid: &NetId) -> bool {
+        match self.get_fee_relayer(store, nid.clone()) {
+            Some(relayer) => relayer == *address,
+            None => self
+                .get_relayer(store, address)
+                .map(|relayer| relayer.allows(nid))
+                .unwrap_or(false),
+        }
+    }
+
     pub fn ensure_xcall(&self, store: &dyn Storage, address: Addr) -> Result<(), ContractError> {
         let xcall = self.query_xcall(store)?;
         ensure_eq!(xcall, address, ContractError::OnlyXCall);
@@ -82,4 +148,81 @@ impl<'a> CwCentralizedConnection<'a> {
         let sub_msg: SubMsg = SubMsg::reply_always(call_message, XCALL_HANDLE_ERROR_REPLY_ID);
         Ok(sub_msg)
     }
+
+    pub fn call_xcall_handle_nack(
+        &self,
+        store: &dyn Storage,
+        sn: u128,
+        reason: NackReason,
+    ) -> Result<SubMsg, ContractError> {
+        let xcall_host = self.query_xcall(store)?;
+        let xcall_msg = cw_xcall_lib::xcall_msg::ExecuteMsg::HandleNack { sn, reason };
+        let call_message: CosmosMsg<Empty> = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: xcall_host.to_string(),
+            msg: to_json_binary(&xcall_msg).unwrap(),
+            funds: vec![],
+        });
+        let sub_msg: SubMsg = SubMsg::reply_always(call_message, XCALL_HANDLE_NACK_REPLY_ID);
+        Ok(sub_msg)
+    }
+
+    pub fn get_network_id(&self, deps: Deps) -> Result<String, ContractError> {
+        let xcall_host = self.query_xcall(deps.storage)?;
+
+        let query_msg = XcallQueryMsg::GetNetworkAddress {};
+
+        let query_request = QueryRequest::Wasm(cosmwasm_std::WasmQuery::Smart {
+            contract_addr: xcall_host.to_string(),
+            msg: to_json_binary(&query_msg).map_err(ContractError::Std)?,
+        });
+
+        let network_address: String = deps
+            .querier
+            .query(&query_request)
+            .map_err(ContractError::Std)?;
+
+        Ok(NetworkAddress::from_str(network_address.as_str())?
+            .nid()
+            .to_string())
+    }
+
+    /// Checks that `threshold` distinct `attesters` signed `signed_msg`.
+    pub fn verify_attestations(
+        &self,
+        deps: Deps,
+        attesters: &[Vec<u8>],
+        threshold: u8,
+        signed_msg: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
+    ) -> Result<(), ContractError> {
+        if signatures.len() < threshold.into() {
+            return Err(ContractError::InsufficientSignatures);
+        }
+
+        let message_hash = keccak256(&signed_msg).finalize().to_vec();
+
+        let mut signers: Vec<Vec<u8>> = vec![];
+        for signature in signatures {
+            if signature.len() != 65 {
+                return Err(ContractError::InvalidSignature);
+            }
+            let mut recovery_code = signature[64];
+            if recovery_code >= 27 {
+                recovery_code -= 27;
+            }
+            if let Ok(pubkey) =
+                deps.api
+                    .secp256k1_recover_pubkey(&message_hash, &signature[0..64], recovery_code)
+            {
+                if attesters.contains(&pubkey) && !signers.contains(&pubkey) {
+                    signers.push(pubkey);
+                    if signers.len() >= threshold.into() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        Err(ContractError::InsufficientSignatures)
+    }
 }
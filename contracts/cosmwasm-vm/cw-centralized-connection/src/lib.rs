@@ -42,16 +42,82 @@ pub fn execute(
     let mut centralized_connection = CwCentralizedConnection::default();
     match msg {
         ExecuteMsg::SendMessage { to, sn, msg } => {
-            centralized_connection.send_message(deps, info, to, sn, msg)
+            centralized_connection.send_message(deps, env, info, to, sn, msg)
         }
         ExecuteMsg::RecvMessage {
             src_network,
             conn_sn,
             msg,
-        } => centralized_connection.recv_message(deps, info, src_network, conn_sn, msg),
+        } => centralized_connection.recv_message(deps, env, info, src_network, conn_sn, msg),
         ExecuteMsg::ClaimFees {} => centralized_connection.claim_fees(deps, env, info),
+        ExecuteMsg::ClaimNetworkFees { nid } => {
+            centralized_connection.claim_network_fees(deps, info, nid)
+        }
+        ExecuteMsg::SetFeeOracle {
+            address,
+            floor,
+            cap,
+        } => centralized_connection.set_fee_oracle(deps, info, address, floor, cap),
+        ExecuteMsg::RefundResponseFee { nid, sn, to } => {
+            centralized_connection.refund_response_fee(deps, info, nid, sn, to)
+        }
         ExecuteMsg::RevertMessage { sn } => centralized_connection.revert_message(deps, info, sn),
+        ExecuteMsg::NackMessage {
+            sn,
+            reason_code,
+            evidence,
+        } => centralized_connection.nack_message(deps, info, sn, reason_code, evidence),
         ExecuteMsg::SetAdmin { address } => centralized_connection.set_admin(deps, info, address),
+        ExecuteMsg::SetRelayer { address } => {
+            centralized_connection.set_relayer(deps, info, address)
+        }
+        ExecuteMsg::AddRelayer { address, networks } => {
+            centralized_connection.add_relayer(deps, info, address, networks)
+        }
+        ExecuteMsg::RemoveRelayer { address } => {
+            centralized_connection.remove_relayer(deps, info, address)
+        }
+        ExecuteMsg::SetOrderedDelivery {
+            src_network,
+            ordered,
+        } => centralized_connection.set_ordered_delivery(deps, info, src_network, ordered),
+        ExecuteMsg::CompactReceipts { nid, limit } => {
+            centralized_connection.compact_receipts(deps, env, info, nid, limit)
+        }
+        ExecuteMsg::SetMessageLogCapacity { capacity } => {
+            centralized_connection.set_message_log_capacity(deps, info, capacity)
+        }
+        ExecuteMsg::SetBondConfig {
+            attesters,
+            threshold,
+            slash_recipient,
+            unbonding_period,
+            fraud_proof_window,
+        } => centralized_connection.set_bond_config(
+            deps,
+            info,
+            attesters,
+            threshold,
+            slash_recipient,
+            unbonding_period,
+            fraud_proof_window,
+        ),
+        ExecuteMsg::Bond {} => centralized_connection.bond(deps, info),
+        ExecuteMsg::Unbond { amount } => centralized_connection.unbond(deps, env, info, amount),
+        ExecuteMsg::WithdrawBond {} => centralized_connection.withdraw_bond(deps, env, info),
+        ExecuteMsg::SubmitFraudProof {
+            src_network,
+            conn_sn,
+            msg,
+            signatures,
+        } => centralized_connection.submit_fraud_proof(
+            deps,
+            env,
+            src_network,
+            conn_sn,
+            msg,
+            signatures,
+        ),
         ExecuteMsg::SetFee {
             network_id,
             message_fee,
@@ -66,8 +132,8 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetFee { nid, response } => to_json_binary(
             &centralized_connection
-                .get_fee(deps.storage, nid, response)
-                .unwrap(),
+                .calculate_fee(deps, nid, response)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
         ),
 
         QueryMsg::GetReceipt {
@@ -77,8 +143,69 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&centralized_connection.get_receipt(deps.storage, src_network, conn_sn))
         }
 
-        QueryMsg::Admin {} => {
-            to_json_binary(&centralized_connection.admin().load(deps.storage).unwrap())
+        QueryMsg::GetDeliveryStatus { src_network } => {
+            to_json_binary(&centralized_connection.get_delivery_status(deps.storage, src_network))
+        }
+
+        QueryMsg::GetMissingConnSns { src_network, limit } => to_json_binary(
+            &centralized_connection.get_missing_conn_sns(deps.storage, src_network, limit),
+        ),
+
+        QueryMsg::GetConnSn { to } => {
+            to_json_binary(&centralized_connection.get_network_conn_sn(deps.storage, to))
+        }
+
+        QueryMsg::GetMessage { to, conn_sn } => {
+            to_json_binary(&centralized_connection.get_message(deps.storage, to, conn_sn))
+        }
+
+        QueryMsg::GetMessages { to, from, limit } => to_json_binary(
+            &centralized_connection.get_logged_messages(deps.storage, to, from, limit)?,
+        ),
+
+        QueryMsg::GetNack { sn } => {
+            to_json_binary(&centralized_connection.get_nack(deps.storage, sn))
+        }
+
+        QueryMsg::GetBondConfig {} => {
+            to_json_binary(&centralized_connection.get_bond_config(deps.storage))
+        }
+
+        QueryMsg::GetBond { address } => {
+            to_json_binary(&centralized_connection.get_bond(deps.storage, &address))
+        }
+
+        QueryMsg::GetDelivery {
+            src_network,
+            conn_sn,
+        } => {
+            to_json_binary(&centralized_connection.get_delivery(deps.storage, src_network, conn_sn))
+        }
+
+        QueryMsg::GetNetworkStatus { nid } => {
+            to_json_binary(&centralized_connection.get_network_status(deps.storage, nid))
+        }
+
+        QueryMsg::GetNetworkFees { nid } => {
+            to_json_binary(&centralized_connection.get_network_fees(deps.storage, nid))
+        }
+
+        QueryMsg::GetFeeOracle {} => {
+            to_json_binary(&centralized_connection.get_fee_oracle(deps.storage))
+        }
+
+        QueryMsg::Admin {} => to_json_binary(&centralized_connection.admin().load(deps.storage)?),
+
+        QueryMsg::GetRelayers {} => {
+            to_json_binary(&centralized_connection.get_relayers(deps.storage)?)
+        }
+
+        QueryMsg::GetClaimableFees { address } => {
+            to_json_binary(&centralized_connection.get_claimable_fees(deps.storage, &address)?)
+        }
+
+        QueryMsg::GetFeeRelayer { nid } => {
+            to_json_binary(&centralized_connection.get_fee_relayer(deps.storage, nid))
         }
     }
 }
@@ -92,6 +219,6 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let centralized_connection = CwCentralizedConnection::default();
+    let mut centralized_connection = CwCentralizedConnection::default();
     centralized_connection.migrate(deps, _env, _msg)
 }
@@ -1,6 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{network_address::NetId, xcall_connection_msg::NetworkStatus};
+
+use crate::types::{
+    BondConfig, Delivery, DeliveryStatus, FeeOracle, NackRecord, NetworkFees, OutboundMessage,
+    RelayerBond, RelayerInfo,
+};
 
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -22,12 +27,69 @@ pub enum ExecuteMsg {
     },
 
     ClaimFees {},
+    ClaimNetworkFees {
+        nid: NetId,
+    },
+    SetFeeOracle {
+        address: Option<String>,
+        floor: u128,
+        cap: u128,
+    },
+    RefundResponseFee {
+        nid: NetId,
+        sn: u128,
+        to: String,
+    },
     RevertMessage {
         sn: u128,
     },
+    NackMessage {
+        sn: u128,
+        reason_code: u8,
+        evidence: String,
+    },
     SetAdmin {
         address: Addr,
     },
+    SetRelayer {
+        address: Addr,
+    },
+    AddRelayer {
+        address: Addr,
+        networks: Vec<NetId>,
+    },
+    RemoveRelayer {
+        address: Addr,
+    },
+    SetOrderedDelivery {
+        src_network: NetId,
+        ordered: bool,
+    },
+    CompactReceipts {
+        nid: NetId,
+        limit: Option<u32>,
+    },
+    SetMessageLogCapacity {
+        capacity: u32,
+    },
+    SetBondConfig {
+        attesters: Vec<Vec<u8>>,
+        threshold: u8,
+        slash_recipient: String,
+        unbonding_period: u64,
+        fraud_proof_window: u64,
+    },
+    Bond {},
+    Unbond {
+        amount: u128,
+    },
+    WithdrawBond {},
+    SubmitFraudProof {
+        src_network: NetId,
+        conn_sn: u128,
+        msg: String,
+        signatures: Vec<Vec<u8>>,
+    },
 }
 
 #[cw_serde]
@@ -40,10 +102,52 @@ pub enum QueryMsg {
     GetFee { nid: NetId, response: bool },
     #[returns(bool)]
     GetReceipt { src_network: NetId, conn_sn: u128 },
+    #[returns(DeliveryStatus)]
+    GetDeliveryStatus { src_network: NetId },
+    #[returns(NetworkStatus)]
+    GetNetworkStatus { nid: NetId },
+    #[returns(Vec<u128>)]
+    GetMissingConnSns {
+        src_network: NetId,
+        limit: Option<u32>,
+    },
+    #[returns(u128)]
+    GetConnSn { to: NetId },
+    #[returns(Option<OutboundMessage>)]
+    GetMessage { to: NetId, conn_sn: u128 },
+    #[returns(Vec<OutboundMessage>)]
+    GetMessages {
+        to: NetId,
+        from: u128,
+        limit: Option<u32>,
+    },
+    #[returns(Option<NackRecord>)]
+    GetNack { sn: u128 },
+    #[returns(Option<BondConfig>)]
+    GetBondConfig {},
+    #[returns(RelayerBond)]
+    GetBond { address: Addr },
+    #[returns(Option<Delivery>)]
+    GetDelivery { src_network: NetId, conn_sn: u128 },
+    #[returns(NetworkFees)]
+    GetNetworkFees { nid: NetId },
+    #[returns(Option<FeeOracle>)]
+    GetFeeOracle {},
     //return address of admin
     #[returns(Addr)]
     Admin {},
+    #[returns(Vec<RelayerInfo>)]
+    GetRelayers {},
+    #[returns(u128)]
+    GetClaimableFees { address: Addr },
+    #[returns(Option<Addr>)]
+    GetFeeRelayer { nid: NetId },
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// The conn_sn each source network continued from when it started numbering its messages per
+    /// destination, the gaps below it are never filled.
+    #[serde(default)]
+    pub legacy_conn_sns: Vec<(NetId, u128)>,
+}
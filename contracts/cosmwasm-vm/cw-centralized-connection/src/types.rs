@@ -1,3 +1,6 @@
+use cosmwasm_std::Addr;
+use cw_xcall_lib::{network_address::NetId, xcall_msg::NackReason};
+
 use super::*;
 
 #[cw_serde]
@@ -7,6 +10,130 @@ pub struct InstantiateMsg {
     pub denom: String,
 }
 
+/// Fees accrued for messages sent to a network, split by message and response fees, the part
+/// of them already claimed by the relayer and the response fees `reserved` until a response is
+/// delivered for them or they are refunded.
+#[cw_serde]
+#[derive(Default)]
+pub struct NetworkFees {
+    pub message_fees: u128,
+    pub response_fees: u128,
+    pub claimed: u128,
+    #[serde(default)]
+    pub reserved: u128,
+}
+
+impl NetworkFees {
+    pub fn unclaimed(&self) -> u128 {
+        (self.message_fees + self.response_fees).saturating_sub(self.claimed + self.reserved)
+    }
+}
+
+/// A relayer allowed to deliver messages from `networks`, or from every network when empty.
+#[cw_serde]
+pub struct RelayerInfo {
+    pub address: Addr,
+    pub networks: Vec<NetId>,
+}
+
+impl RelayerInfo {
+    pub fn allows(&self, nid: &NetId) -> bool {
+        self.networks.is_empty() || self.networks.contains(nid)
+    }
+}
+
+/// Delivery progress of the messages received from a network. `contiguous_conn_sn` is the
+/// highest conn_sn up to which every message was received; in ordered mode only the one right
+/// after it is accepted.
+#[cw_serde]
+pub struct DeliveryStatus {
+    pub ordered: bool,
+    pub contiguous_conn_sn: u128,
+    pub highest_conn_sn: u128,
+}
+
+/// A message kept in the outbound message log, `msg_hash` being the hex encoded keccak256 of
+/// the message and `height` the block it was sent at.
+#[cw_serde]
+pub struct OutboundMessage {
+    pub conn_sn: u128,
+    pub to: NetId,
+    pub sn: i64,
+    pub msg_hash: String,
+    pub height: u64,
+}
+
+/// Why the message of xCall sequence number `sn` could not be delivered, as reported by the
+/// relayer. `evidence` backs the claim, e.g. the hash of the rejected transaction.
+#[cw_serde]
+pub struct NackRecord {
+    pub sn: u128,
+    pub reason: NackReason,
+    pub evidence: String,
+}
+
+/// Optional relayer bonding. A bonded relayer's delivery conflicting with the message
+/// `threshold` of `attesters` signed is a fraud, its bond is slashed to `slash_recipient`. A
+/// delivery can be proven fraudulent for `fraud_proof_window` seconds, and bonds stay slashable
+/// for `unbonding_period` seconds after unbonding, which is at least the fraud proof window.
+#[cw_serde]
+pub struct BondConfig {
+    pub attesters: Vec<Vec<u8>>,
+    pub threshold: u8,
+    pub slash_recipient: Addr,
+    pub unbonding_period: u64,
+    pub fraud_proof_window: u64,
+}
+
+/// Funds a relayer locked in the connection, `unbonding` being withdrawable from `release_at`.
+#[cw_serde]
+#[derive(Default)]
+pub struct RelayerBond {
+    pub bonded: u128,
+    pub unbonding: u128,
+    pub release_at: u64,
+}
+
+impl RelayerBond {
+    pub fn total(&self) -> u128 {
+        self.bonded + self.unbonding
+    }
+}
+
+/// A message delivered by a bonded relayer at `delivered_at` seconds, `msg_hash` being the hex
+/// encoded keccak256 of the message.
+#[cw_serde]
+pub struct Delivery {
+    pub relayer: Addr,
+    pub msg_hash: String,
+    pub delivered_at: u64,
+}
+
+/// Contract answering `xcall_connection_msg::QueryMsg::GetFee` whose quote is used instead of
+/// the stored fees, bounded to `[floor, cap]`.
+#[cw_serde]
+pub struct FeeOracle {
+    pub address: Addr,
+    pub floor: u128,
+    pub cap: u128,
+}
+
+/// Fee charged for a message, `response_fee` being the part reserved for refunds until the
+/// response is delivered. `oracle_error` is set when the stored fees were quoted because the fee
+/// oracle query failed.
+#[derive(Default)]
+pub struct FeeQuote {
+    pub message_fee: u128,
+    pub response_fee: u128,
+    pub oracle_error: Option<String>,
+}
+
+impl FeeQuote {
+    pub fn total(&self) -> u128 {
+        self.message_fee + self.response_fee
+    }
+}
+
 #[cw_serde]
 pub enum StorageKey {
     MessageFee,
@@ -14,8 +141,27 @@ pub enum StorageKey {
     Receipts,
     XCall,
     Admin,
+    Relayer,
+    Relayers,
+    FeeRelayers,
+    RelayerFees,
     ConnSn,
+    NetworkConnSn,
     Denom,
+    NetworkFees,
+    FeeOracle,
+    ResponseFeeDeposits,
+    OrderedNetworks,
+    ContiguousConnSn,
+    HighestConnSn,
+    MessageLog,
+    MessageLogCapacity,
+    NetworkStatus,
+    PendingRequests,
+    Nacks,
+    BondConfig,
+    Bonds,
+    Deliveries,
 }
 
 impl StorageKey {
@@ -26,8 +172,27 @@ impl StorageKey {
             StorageKey::Receipts => "receipts",
             StorageKey::XCall => "xcall",
             StorageKey::Admin => "admin",
+            StorageKey::Relayer => "relayer",
+            StorageKey::Relayers => "relayers",
+            StorageKey::FeeRelayers => "fee_relayers",
+            StorageKey::RelayerFees => "relayer_fees",
             StorageKey::ConnSn => "conn_sn",
+            StorageKey::NetworkConnSn => "network_conn_sn",
             StorageKey::Denom => "denom",
+            StorageKey::NetworkFees => "network_fees",
+            StorageKey::FeeOracle => "fee_oracle",
+            StorageKey::ResponseFeeDeposits => "response_fee_deposits",
+            StorageKey::OrderedNetworks => "ordered_networks",
+            StorageKey::ContiguousConnSn => "contiguous_conn_sn",
+            StorageKey::HighestConnSn => "highest_conn_sn",
+            StorageKey::MessageLog => "message_log",
+            StorageKey::MessageLogCapacity => "message_log_capacity",
+            StorageKey::NetworkStatus => "network_status",
+            StorageKey::PendingRequests => "pending_requests",
+            StorageKey::Nacks => "nacks",
+            StorageKey::BondConfig => "bond_config",
+            StorageKey::Bonds => "bonds",
+            StorageKey::Deliveries => "deliveries",
         }
     }
 }
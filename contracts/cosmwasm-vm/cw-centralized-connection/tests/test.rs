@@ -1,14 +1,29 @@
 pub mod setup;
+use common::rlp::RlpStream;
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, BankMsg, Coin, ContractResult, Event, SystemResult, WasmQuery,
+};
 use cosmwasm_std::{testing::mock_env, Env};
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_info, MockApi, MockQuerier},
     Addr, MemoryStorage, OwnedDeps, Uint128,
 };
-use cosmwasm_std::{Coin, Event};
 use cw_centralized_connection::{
-    execute, msg::ExecuteMsg, state::CwCentralizedConnection, types::InstantiateMsg,
+    execute, keccak256,
+    msg::{ExecuteMsg, MigrateMsg},
+    state::CwCentralizedConnection,
+    types::{
+        DeliveryStatus, InstantiateMsg, NackRecord, OutboundMessage, RelayerBond, RelayerInfo,
+    },
+    XCALL_HANDLE_NACK_REPLY_ID,
+};
+use cw_storage_plus::Item;
+use cw_xcall_lib::{
+    network_address::NetId,
+    xcall_connection_msg::{NetworkStatus, QueryMsg as XcallConnectionQueryMsg, SignableMsg},
+    xcall_msg::NackReason,
 };
-use cw_xcall_lib::network_address::NetId;
+use k256::ecdsa::{SigningKey, VerifyingKey};
 use std::str::FromStr;
 
 const XCALL: &str = "xcall";
@@ -50,12 +65,12 @@ fn test_set_admin() {
         address: Addr::unchecked("admin"),
     };
 
-    let info = mock_info(OWNER, &[]);
+    let info = mock_info(RELAYER, &[]);
 
     let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
-    assert!(res.is_err());
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
 
-    let info = mock_info(RELAYER, &[]);
+    let info = mock_info("sender", &[]);
 
     let res = execute(deps.as_mut(), env, info, msg);
     assert!(res.is_ok());
@@ -64,6 +79,389 @@ fn test_set_admin() {
     assert_eq!(admin, Addr::unchecked("admin"));
 }
 
+#[test]
+fn test_set_relayer() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let msg = ExecuteMsg::SetRelayer {
+        address: Addr::unchecked("new_relayer"),
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]), msg);
+    assert!(res.is_ok());
+
+    let relayers = ctx.get_relayers(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        relayers,
+        vec![RelayerInfo {
+            address: Addr::unchecked("new_relayer"),
+            networks: vec![],
+        }]
+    );
+    let admin = ctx.query_admin(deps.as_ref().storage).unwrap();
+    assert_eq!(admin, Addr::unchecked(OWNER));
+}
+
+#[test]
+fn test_add_and_remove_relayer() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+    let msg = ExecuteMsg::AddRelayer {
+        address: Addr::unchecked("scoped"),
+        networks: vec![nid.clone()],
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    let recv = |src_network: &str, conn_sn: u128| ExecuteMsg::RecvMessage {
+        src_network: NetId::from_str(src_network).unwrap(),
+        conn_sn,
+        msg: "".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("scoped", &[]),
+        recv("other", 1),
+    );
+    assert_eq!(
+        "Relayer Not Allowed For other",
+        res.unwrap_err().to_string()
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("scoped", &[]),
+        recv("nid", 1),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        recv("other", 1),
+    )
+    .unwrap();
+    let send = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 0,
+        msg: vec![],
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[Coin::new(100, DENOM)]),
+        send,
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::RemoveRelayer {
+        address: Addr::unchecked("scoped"),
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(ctx.get_fee_relayer(deps.as_ref().storage, nid), None);
+    // the fees of the network it delivered from stay with the removed relayer
+    assert_eq!(
+        ctx.get_claimable_fees(deps.as_ref().storage, &Addr::unchecked("scoped"))
+            .unwrap(),
+        100
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg);
+    assert_eq!("Relayer Not Found", res.unwrap_err().to_string());
+
+    let res = execute(deps.as_mut(), env, mock_info("scoped", &[]), recv("nid", 2));
+    assert_eq!("Only Relayer", res.unwrap_err().to_string());
+}
+
+#[test]
+fn test_fees_follow_delivering_relayer() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+    let backup = "backup";
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::AddRelayer {
+            address: Addr::unchecked(backup),
+            networks: vec![],
+        },
+    )
+    .unwrap();
+
+    let send = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 0,
+        msg: vec![],
+    };
+    let recv = |conn_sn: u128| ExecuteMsg::RecvMessage {
+        src_network: nid.clone(),
+        conn_sn,
+        msg: "".to_string(),
+    };
+
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(1)).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[Coin::new(100, DENOM)]),
+        send.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        ctx.get_fee_relayer(deps.as_ref().storage, nid.clone()),
+        Some(Addr::unchecked(RELAYER))
+    );
+
+    // the backup relayer takes over without an admin transaction, fees accrued so far stay
+    // with the first relayer
+    execute(deps.as_mut(), env.clone(), mock_info(backup, &[]), recv(2)).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[Coin::new(40, DENOM)]),
+        send,
+    )
+    .unwrap();
+    assert_eq!(
+        ctx.get_fee_relayer(deps.as_ref().storage, nid.clone()),
+        Some(Addr::unchecked(backup))
+    );
+
+    let storage = deps.as_ref().storage;
+    assert_eq!(
+        ctx.get_claimable_fees(storage, &Addr::unchecked(RELAYER))
+            .unwrap(),
+        100
+    );
+    assert_eq!(
+        ctx.get_claimable_fees(storage, &Addr::unchecked(backup))
+            .unwrap(),
+        40
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::ClaimNetworkFees { nid: nid.clone() },
+    );
+    assert_eq!("Relayer Not Allowed For nid", res.unwrap_err().to_string());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::ClaimFees {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: RELAYER.to_string(),
+            amount: coins(100, DENOM),
+        }
+        .into()
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(backup, &[]),
+        ExecuteMsg::ClaimNetworkFees { nid },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: backup.to_string(),
+            amount: coins(40, DENOM),
+        }
+        .into()
+    );
+}
+
+#[test]
+fn test_migrate_keeps_admin_as_relayer() {
+    let (mut deps, env, mut ctx) = instantiate(RELAYER);
+    ctx.relayers()
+        .remove(deps.as_mut().storage, Addr::unchecked(RELAYER));
+
+    ctx.migrate(
+        deps.as_mut(),
+        env,
+        MigrateMsg {
+            legacy_conn_sns: vec![],
+        },
+    )
+    .unwrap();
+
+    let relayer = ctx
+        .get_relayer(deps.as_ref().storage, &Addr::unchecked(RELAYER))
+        .unwrap();
+    assert!(relayer.networks.is_empty());
+    let admin = ctx.query_admin(deps.as_ref().storage).unwrap();
+    assert_eq!(admin, Addr::unchecked(RELAYER));
+}
+
+#[test]
+fn test_migrate_keeps_single_relayer() {
+    let (mut deps, env, mut ctx) = instantiate(OWNER);
+    ctx.relayers()
+        .remove(deps.as_mut().storage, Addr::unchecked(RELAYER));
+    Item::<Addr>::new("relayer")
+        .save(deps.as_mut().storage, &Addr::unchecked("legacy"))
+        .unwrap();
+
+    ctx.migrate(
+        deps.as_mut(),
+        env,
+        MigrateMsg {
+            legacy_conn_sns: vec![],
+        },
+    )
+    .unwrap();
+
+    let relayers = ctx.get_relayers(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        relayers,
+        vec![RelayerInfo {
+            address: Addr::unchecked("legacy"),
+            networks: vec![],
+        }]
+    );
+}
+
+#[test]
+fn test_migrate_credits_untracked_fees_to_relayer() {
+    let (mut deps, env, mut ctx) = instantiate(OWNER);
+    ctx.relayers()
+        .remove(deps.as_mut().storage, Addr::unchecked(RELAYER));
+    Item::<Addr>::new("relayer")
+        .save(deps.as_mut().storage, &Addr::unchecked("legacy"))
+        .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), coins(500, DENOM));
+
+    ctx.migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            legacy_conn_sns: vec![],
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("legacy", &[]),
+        ExecuteMsg::ClaimFees {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: "legacy".to_string(),
+            amount: coins(500, DENOM),
+        }
+        .into()
+    );
+}
+
+#[test]
+fn test_compact_receipts() {
+    let (mut deps, env, mut ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+    let compact = |limit: Option<u32>| ExecuteMsg::CompactReceipts {
+        nid: nid.clone(),
+        limit,
+    };
+    // receipts stored before they were pruned
+    for conn_sn in [1, 2, 3, 5] {
+        ctx.store_receipt(deps.as_mut().storage, nid.clone(), conn_sn)
+            .unwrap();
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        compact(None),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        compact(None),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ctx.get_receipt_keys(deps.as_ref().storage).unwrap(),
+        vec![("nid".to_string(), 5)]
+    );
+    let status = ctx.get_delivery_status(deps.as_ref().storage, nid.clone());
+    assert_eq!(status.contiguous_conn_sn, 3);
+    assert_eq!(status.highest_conn_sn, 5);
+    for conn_sn in [1, 2, 3, 5] {
+        assert!(ctx.get_receipt(deps.as_ref().storage, nid.clone(), conn_sn));
+    }
+    assert!(!ctx.get_receipt(deps.as_ref().storage, nid.clone(), 4));
+
+    // the legacy conn_sn set by a migration leaves the receipts below it behind
+    for conn_sn in [7, 8] {
+        ctx.store_receipt(deps.as_mut().storage, nid.clone(), conn_sn)
+            .unwrap();
+    }
+    let msg = MigrateMsg {
+        legacy_conn_sns: vec![(nid.clone(), 8)],
+    };
+    ctx.migrate(deps.as_mut(), env.clone(), msg).unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        compact(Some(2)),
+    )
+    .unwrap();
+    assert_eq!(
+        ctx.get_receipt_keys(deps.as_ref().storage).unwrap(),
+        vec![("nid".to_string(), 8)]
+    );
+    execute(deps.as_mut(), env, mock_info(OWNER, &[]), compact(Some(2))).unwrap();
+    assert!(ctx
+        .get_receipt_keys(deps.as_ref().storage)
+        .unwrap()
+        .is_empty());
+    assert!(ctx.get_receipt(deps.as_ref().storage, nid, 8));
+}
+
 #[test]
 fn test_set_fee() {
     let (mut deps, env, ctx) = instantiate(OWNER);
@@ -76,12 +474,12 @@ fn test_set_fee() {
         response_fee,
     };
 
-    let info = mock_info(OWNER, &[]);
+    let info = mock_info(RELAYER, &[]);
 
     let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
     assert!(res.is_err());
 
-    let info = mock_info(RELAYER, &[]);
+    let info = mock_info(OWNER, &[]);
 
     let res = execute(deps.as_mut(), env, info, msg);
     assert!(res.is_ok());
@@ -120,6 +518,77 @@ pub fn test_send_message() {
     assert_eq!(res.unwrap().events[0], event);
 }
 
+#[test]
+pub fn test_message_log() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+    let send = |deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>, sn: i64| {
+        let msg = ExecuteMsg::SendMessage {
+            to: NetId::from_str("nid").unwrap(),
+            sn,
+            msg: vec![1, 2, 3],
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(XCALL, &[]), msg).unwrap();
+    };
+
+    // disabled by default
+    send(&mut deps, 0);
+    assert_eq!(ctx.get_message(deps.as_ref().storage, nid.clone(), 1), None);
+
+    let msg = ExecuteMsg::SetMessageLogCapacity { capacity: 2 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::SetMessageLogCapacity { capacity: 1001 },
+    );
+    assert_eq!(
+        "Invalid Message Log Capacity, Max 1000",
+        res.unwrap_err().to_string()
+    );
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    for sn in 1..=3 {
+        send(&mut deps, sn);
+    }
+    assert_eq!(ctx.get_message(deps.as_ref().storage, nid.clone(), 2), None);
+    assert_eq!(
+        ctx.get_message(deps.as_ref().storage, nid.clone(), 4),
+        Some(OutboundMessage {
+            conn_sn: 4,
+            to: NetId::from_str("nid").unwrap(),
+            sn: 3,
+            msg_hash: "f1885eda54b7a053318cd41e2093220dab15d65381b1157a3633a83bfd5c9239"
+                .to_string(),
+            height: env.block.height,
+        })
+    );
+
+    let messages = ctx
+        .get_logged_messages(deps.as_ref().storage, nid.clone(), 0, None)
+        .unwrap();
+    assert_eq!(
+        messages.iter().map(|m| m.conn_sn).collect::<Vec<u128>>(),
+        vec![3, 4]
+    );
+    let messages = ctx
+        .get_logged_messages(deps.as_ref().storage, nid.clone(), 4, Some(10))
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+
+    let msg = ExecuteMsg::SetMessageLogCapacity { capacity: 1 };
+    execute(deps.as_mut(), env, mock_info(OWNER, &[]), msg).unwrap();
+    assert_eq!(ctx.get_message(deps.as_ref().storage, nid.clone(), 3), None);
+    assert!(ctx.get_message(deps.as_ref().storage, nid, 4).is_some());
+}
+
 #[test]
 pub fn test_recv_message() {
     let (mut deps, env, mut _ctx) = instantiate(OWNER);
@@ -135,7 +604,7 @@ pub fn test_recv_message() {
     let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
 
     assert!(res.is_err());
-    assert_eq!("Only Relayer(Admin)", res.unwrap_err().to_string());
+    assert_eq!("Only Relayer", res.unwrap_err().to_string());
 
     let info = mock_info(RELAYER, &[]);
 
@@ -150,6 +619,59 @@ pub fn test_recv_message() {
     assert_eq!("Duplicate Message", res.unwrap_err().to_string());
 }
 
+fn result_message(sn: u128) -> String {
+    let mut result = RlpStream::new_list(2);
+    result.append(&sn).append(&1u8);
+    let mut msg = RlpStream::new_list(2);
+    msg.append(&2u8).append(&result.out().to_vec());
+    hex::encode(msg.out())
+}
+
+#[test]
+pub fn test_network_status() {
+    let (mut deps, mut env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+    let send = |deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>, env: &Env, sn: i64| {
+        let msg = ExecuteMsg::SendMessage {
+            to: nid.clone(),
+            sn,
+            msg: vec![1, 2, 3],
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(XCALL, &[]), msg).unwrap();
+    };
+
+    send(&mut deps, &env, 1);
+    send(&mut deps, &env, 2);
+    send(&mut deps, &env, -5);
+    assert_eq!(
+        ctx.get_network_status(deps.as_ref().storage, nid.clone()),
+        NetworkStatus {
+            last_received_conn_sn: 0,
+            last_received_height: 0,
+            last_sent_conn_sn: 3,
+            last_sent_height: env.block.height,
+            pending_reverts: 2,
+        }
+    );
+
+    env.block.height += 10;
+    let msg = ExecuteMsg::RecvMessage {
+        src_network: nid.clone(),
+        conn_sn: 9,
+        msg: result_message(1),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+    let status = ctx.get_network_status(deps.as_ref().storage, nid.clone());
+    assert_eq!(status.last_received_conn_sn, 9);
+    assert_eq!(status.last_received_height, env.block.height);
+    assert_eq!(status.pending_reverts, 1);
+
+    let msg = ExecuteMsg::RevertMessage { sn: 2 };
+    execute(deps.as_mut(), env, mock_info(RELAYER, &[]), msg).unwrap();
+    let status = ctx.get_network_status(deps.as_ref().storage, nid);
+    assert_eq!(status.pending_reverts, 0);
+}
+
 #[test]
 
 pub fn test_revert_message() {
@@ -169,6 +691,54 @@ pub fn test_revert_message() {
     assert!(res.is_ok());
 }
 
+#[test]
+pub fn test_nack_message() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nack = |reason_code: u8| ExecuteMsg::NackMessage {
+        sn: 1,
+        reason_code,
+        evidence: "0xabc".to_string(),
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), nack(2));
+    assert_eq!("Only Relayer", res.unwrap_err().to_string());
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), nack(4));
+    assert_eq!("Invalid Nack Reason 4", res.unwrap_err().to_string());
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), nack(2)).unwrap();
+    let expected = cw_xcall_lib::xcall_msg::ExecuteMsg::HandleNack {
+        sn: 1,
+        reason: NackReason::ExceededSize,
+    };
+    assert_eq!(res.messages[0].id, XCALL_HANDLE_NACK_REPLY_ID);
+    assert_eq!(
+        res.messages[0].msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: XCALL.to_string(),
+            msg: to_json_binary(&expected).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.events[0],
+        Event::new("NackMessage")
+            .add_attribute("sn", "1")
+            .add_attribute("reason", "exceeded_size")
+            .add_attribute("evidence", "0xabc")
+    );
+    assert_eq!(
+        ctx.get_nack(deps.as_ref().storage, 1),
+        Some(NackRecord {
+            sn: 1,
+            reason: NackReason::ExceededSize,
+            evidence: "0xabc".to_string(),
+        })
+    );
+
+    let res = execute(deps.as_mut(), env, mock_info(RELAYER, &[]), nack(1));
+    assert_eq!("Message Already Nacked", res.unwrap_err().to_string());
+}
+
 #[test]
 
 pub fn test_get_receipts() {
@@ -189,6 +759,222 @@ pub fn test_get_receipts() {
     assert!(receipt);
 }
 
+#[test]
+pub fn test_ordered_delivery() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let src_network = NetId::from_str("nid").unwrap();
+    let recv = |conn_sn: u128| ExecuteMsg::RecvMessage {
+        src_network: src_network.clone(),
+        conn_sn,
+        msg: "".to_string(),
+    };
+
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(1)).unwrap();
+
+    let msg = ExecuteMsg::SetOrderedDelivery {
+        src_network: src_network.clone(),
+        ordered: true,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(3));
+    assert_eq!(
+        "Out Of Order Message, Expected Conn Sn 2",
+        res.unwrap_err().to_string()
+    );
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(2)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(3)).unwrap();
+
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(
+        status,
+        DeliveryStatus {
+            ordered: true,
+            contiguous_conn_sn: 3,
+            highest_conn_sn: 3,
+        }
+    );
+
+    let msg = ExecuteMsg::SetOrderedDelivery {
+        src_network: src_network.clone(),
+        ordered: false,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+    execute(deps.as_mut(), env, mock_info(RELAYER, &[]), recv(5)).unwrap();
+    assert!(
+        !ctx.get_delivery_status(deps.as_ref().storage, src_network)
+            .ordered
+    );
+}
+
+#[test]
+pub fn test_conn_sn_per_destination() {
+    let (mut deps, env, mut ctx) = instantiate(OWNER);
+    // conn_sn shared by all destinations before each got its own sequence
+    ctx.store_conn_sn(deps.as_mut().storage, 5).unwrap();
+
+    let mut send = |to: &str| {
+        let msg = ExecuteMsg::SendMessage {
+            to: NetId::from_str(to).unwrap(),
+            sn: 0,
+            msg: vec![],
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(XCALL, &[]), msg).unwrap();
+        res.events[0]
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "connSn")
+            .unwrap()
+            .value
+            .clone()
+    };
+    assert_eq!(send("nid"), "6");
+    assert_eq!(send("other"), "6");
+    assert_eq!(send("nid"), "7");
+
+    let storage = deps.as_ref().storage;
+    let nid = NetId::from_str("nid").unwrap();
+    assert_eq!(ctx.get_network_conn_sn(storage, nid), 7);
+    assert_eq!(
+        ctx.get_network_conn_sn(storage, NetId::from_str("other").unwrap()),
+        6
+    );
+    assert_eq!(
+        ctx.get_network_conn_sn(storage, NetId::from_str("unused").unwrap()),
+        5
+    );
+}
+
+#[test]
+pub fn test_migrate_closes_legacy_gaps() {
+    let (mut deps, env, mut ctx) = instantiate(OWNER);
+    let src_network = NetId::from_str("nid").unwrap();
+    let recv = |conn_sn: u128| ExecuteMsg::RecvMessage {
+        src_network: src_network.clone(),
+        conn_sn,
+        msg: "".to_string(),
+    };
+
+    // conn_sn 2 was sent to another destination by the shared sequence
+    for conn_sn in [1, 3] {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(RELAYER, &[]),
+            recv(conn_sn),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        ctx.get_missing_conn_sns(deps.as_ref().storage, src_network.clone(), None),
+        vec![2]
+    );
+
+    let msg = MigrateMsg {
+        legacy_conn_sns: vec![(src_network.clone(), 3)],
+    };
+    ctx.migrate(deps.as_mut(), env.clone(), msg).unwrap();
+    let msg = ExecuteMsg::SetOrderedDelivery {
+        src_network: src_network.clone(),
+        ordered: true,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+    execute(deps.as_mut(), env, mock_info(RELAYER, &[]), recv(4)).unwrap();
+
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(status.contiguous_conn_sn, 4);
+    assert!(ctx
+        .get_missing_conn_sns(deps.as_ref().storage, src_network, None)
+        .is_empty());
+}
+
+#[test]
+pub fn test_contiguous_conn_sn_advance_is_bounded() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let src_network = NetId::from_str("nid").unwrap();
+    let recv = |conn_sn: u128| ExecuteMsg::RecvMessage {
+        src_network: src_network.clone(),
+        conn_sn,
+        msg: "".to_string(),
+    };
+
+    for conn_sn in 2..=150 {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(RELAYER, &[]),
+            recv(conn_sn),
+        )
+        .unwrap();
+    }
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(1)).unwrap();
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(status.contiguous_conn_sn, 100);
+
+    // the next message resumes the walk
+    execute(deps.as_mut(), env, mock_info(RELAYER, &[]), recv(200)).unwrap();
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network);
+    assert_eq!(status.contiguous_conn_sn, 150);
+}
+
+#[test]
+pub fn test_missing_conn_sns() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let src_network = NetId::from_str("nid").unwrap();
+
+    for conn_sn in [1, 2, 4, 7, 8] {
+        let msg = ExecuteMsg::RecvMessage {
+            src_network: src_network.clone(),
+            conn_sn,
+            msg: "".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+    }
+
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(status.contiguous_conn_sn, 2);
+    assert_eq!(status.highest_conn_sn, 8);
+    // receipts covered by the contiguous conn_sn are pruned
+    assert_eq!(
+        ctx.get_receipt_keys(deps.as_ref().storage).unwrap(),
+        vec![
+            ("nid".to_string(), 4),
+            ("nid".to_string(), 7),
+            ("nid".to_string(), 8)
+        ]
+    );
+    assert!(ctx.get_receipt(deps.as_ref().storage, src_network.clone(), 1));
+    assert_eq!(
+        ctx.get_missing_conn_sns(deps.as_ref().storage, src_network.clone(), None),
+        vec![3, 5, 6]
+    );
+    assert_eq!(
+        ctx.get_missing_conn_sns(deps.as_ref().storage, src_network.clone(), Some(2)),
+        vec![3, 5]
+    );
+
+    let msg = ExecuteMsg::RecvMessage {
+        src_network: src_network.clone(),
+        conn_sn: 3,
+        msg: "".to_string(),
+    };
+    execute(deps.as_mut(), env, mock_info(RELAYER, &[]), msg).unwrap();
+
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(status.contiguous_conn_sn, 4);
+    assert_eq!(
+        ctx.get_missing_conn_sns(deps.as_ref().storage, src_network, None),
+        vec![5, 6]
+    );
+}
+
 #[test]
 pub fn test_claim_fees() {
     let (mut deps, env, _ctx) = instantiate(OWNER);
@@ -196,7 +982,15 @@ pub fn test_claim_fees() {
     let info = mock_info(OWNER, &[]);
     let res = execute(deps.as_mut(), env.clone(), info, claim_msg.clone());
     assert!(res.is_err());
-    assert_eq!("Only Relayer(Admin)", res.unwrap_err().to_string());
+    assert_eq!("Only Relayer", res.unwrap_err().to_string());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        claim_msg.clone(),
+    );
+    assert_eq!("No Fees To Claim", res.unwrap_err().to_string());
 
     let msg = ExecuteMsg::SendMessage {
         to: NetId::from_str("nid").unwrap(),
@@ -204,16 +998,573 @@ pub fn test_claim_fees() {
         msg: vec![],
     };
 
-    let info = mock_info(XCALL, &[]);
-
-    let _ = execute(deps.as_mut(), env.clone(), info, msg);
-
     let amount: u128 = 100;
     let coin: Coin = Coin {
         denom: DENOM.to_string(),
         amount: Uint128::from(amount),
     };
-    let info = mock_info(RELAYER, &[coin]);
-    let res = execute(deps.as_mut(), env, info, claim_msg);
-    assert!(res.is_ok());
+    let info = mock_info(XCALL, &[coin]);
+
+    let _ = execute(deps.as_mut(), env.clone(), info, msg);
+
+    let info = mock_info(RELAYER, &[]);
+    let res = execute(deps.as_mut(), env, info, claim_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: RELAYER.to_string(),
+            amount: coins(amount, DENOM),
+        }
+        .into()
+    );
+}
+
+#[test]
+pub fn test_claim_network_fees() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+
+    let info = mock_info(OWNER, &[]);
+    let msg = ExecuteMsg::SetFee {
+        network_id: nid.clone(),
+        message_fee: 200,
+        response_fee: 100,
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 1,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(350, DENOM)]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid.clone());
+    assert_eq!(fees.message_fees, 250);
+    assert_eq!(fees.response_fees, 100);
+    assert_eq!(fees.reserved, 100);
+    assert_eq!(fees.unclaimed(), 250);
+
+    let claim_msg = ExecuteMsg::ClaimNetworkFees { nid: nid.clone() };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        claim_msg.clone(),
+    );
+    assert!(res.is_err());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        claim_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: RELAYER.to_string(),
+            amount: coins(250, DENOM),
+        }
+        .into()
+    );
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid.clone());
+    assert_eq!(fees.claimed, 250);
+    assert_eq!(fees.unclaimed(), 0);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        claim_msg.clone(),
+    );
+    assert_eq!("No Fees To Claim", res.unwrap_err().to_string());
+
+    // the response fee is released once the result is delivered
+    let msg = ExecuteMsg::RecvMessage {
+        src_network: nid.clone(),
+        conn_sn: 1,
+        msg: result_message(1),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.reserved, 0);
+    assert_eq!(fees.unclaimed(), 100);
+
+    let res = execute(deps.as_mut(), env, mock_info(RELAYER, &[]), claim_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: RELAYER.to_string(),
+            amount: coins(100, DENOM),
+        }
+        .into()
+    );
+}
+
+#[test]
+pub fn test_refund_response_fee() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+
+    let msg = ExecuteMsg::SetFee {
+        network_id: nid.clone(),
+        message_fee: 200,
+        response_fee: 100,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 1,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(300, DENOM)]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let refund_msg = ExecuteMsg::RefundResponseFee {
+        nid: nid.clone(),
+        sn: 1,
+        to: "dapp".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        refund_msg.clone(),
+    );
+    assert_eq!("Only XCall", res.unwrap_err().to_string());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[]),
+        refund_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: "dapp".to_string(),
+            amount: coins(100, DENOM),
+        }
+        .into()
+    );
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.message_fees, 200);
+    assert_eq!(fees.response_fees, 0);
+
+    let res = execute(deps.as_mut(), env, mock_info(XCALL, &[]), refund_msg).unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+pub fn test_refund_response_fee_after_claim() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+
+    let msg = ExecuteMsg::SetFee {
+        network_id: nid.clone(),
+        message_fee: 200,
+        response_fee: 100,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 1,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(300, DENOM)]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::ClaimFees {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: RELAYER.to_string(),
+            amount: coins(200, DENOM),
+        }
+        .into()
+    );
+
+    let refund_msg = ExecuteMsg::RefundResponseFee {
+        nid: nid.clone(),
+        sn: 1,
+        to: "dapp".to_string(),
+    };
+    let res = execute(deps.as_mut(), env, mock_info(XCALL, &[]), refund_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: "dapp".to_string(),
+            amount: coins(100, DENOM),
+        }
+        .into()
+    );
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.reserved, 0);
+    assert_eq!(fees.unclaimed(), 0);
+}
+
+#[test]
+pub fn test_fee_oracle() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+
+    let msg = ExecuteMsg::SetFee {
+        network_id: nid.clone(),
+        message_fee: 200,
+        response_fee: 100,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    deps.querier.update_wasm(|r| match r {
+        WasmQuery::Smart {
+            contract_addr: _,
+            msg,
+        } => {
+            let fee: u128 = match from_json(msg).unwrap() {
+                XcallConnectionQueryMsg::GetFee {
+                    response: false, ..
+                } => 400,
+                _ => 5000,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&fee).unwrap()))
+        }
+        _ => todo!(),
+    });
+
+    let msg = ExecuteMsg::SetFeeOracle {
+        address: Some("oracle".to_string()),
+        floor: 100,
+        cap: 1000,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    let fee = ctx.calculate_fee(deps.as_ref(), nid.clone(), true).unwrap();
+    assert_eq!(fee, Uint128::from(1000_u128));
+
+    // the reserved response fee is what the oracle adds for a response
+    let msg = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 1,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(1000, DENOM)]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid.clone());
+    assert_eq!(fees.message_fees, 400);
+    assert_eq!(fees.reserved, 600);
+
+    let msg = ExecuteMsg::SetFeeOracle {
+        address: None,
+        floor: 0,
+        cap: 0,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+    assert!(ctx.get_fee_oracle(deps.as_ref().storage).is_none());
+
+    let fee = ctx.calculate_fee(deps.as_ref(), nid, true).unwrap();
+    assert_eq!(fee, Uint128::from(300_u128));
+
+    let msg = ExecuteMsg::SetFeeOracle {
+        address: Some("oracle".to_string()),
+        floor: 1000,
+        cap: 100,
+    };
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]), msg);
+    assert_eq!("Invalid Fee Bounds", res.unwrap_err().to_string());
+}
+
+#[test]
+pub fn test_fee_oracle_error_falls_back_to_stored_fee() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let nid = NetId::from_str("nid").unwrap();
+
+    let msg = ExecuteMsg::SetFee {
+        network_id: nid.clone(),
+        message_fee: 200,
+        response_fee: 100,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+    let msg = ExecuteMsg::SetFeeOracle {
+        address: Some("oracle".to_string()),
+        floor: 100,
+        cap: 1000,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    deps.querier
+        .update_wasm(|_| SystemResult::Ok(ContractResult::Err("oracle unavailable".to_string())));
+
+    let msg = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 1,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(300, DENOM)]);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "fee_oracle_error"));
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.message_fees, 200);
+    assert_eq!(fees.reserved, 100);
+}
+
+fn setup_bonding(
+    deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+    env: &Env,
+) -> SigningKey {
+    deps.querier.update_wasm(|r| match r {
+        WasmQuery::Smart {
+            contract_addr: _,
+            msg: _,
+        } => SystemResult::Ok(ContractResult::Ok(to_json_binary("archway/xcall").unwrap())),
+        _ => todo!(),
+    });
+
+    let attester = SigningKey::from_bytes(&[1u8; 32].into()).unwrap();
+    let msg = ExecuteMsg::SetBondConfig {
+        attesters: vec![VerifyingKey::from(&attester)
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec()],
+        threshold: 1,
+        slash_recipient: "treasury".to_string(),
+        unbonding_period: 100,
+        fraud_proof_window: 50,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+    execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &coins(1000, DENOM)),
+        ExecuteMsg::Bond {},
+    )
+    .unwrap();
+    attester
+}
+
+#[test]
+pub fn test_set_bond_config_rejects_short_unbonding_period() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let bond_config = |unbonding_period: u64| ExecuteMsg::SetBondConfig {
+        attesters: vec![vec![1u8; 65]],
+        threshold: 1,
+        slash_recipient: "treasury".to_string(),
+        unbonding_period,
+        fraud_proof_window: 50,
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        bond_config(49),
+    );
+    assert_eq!(
+        "Unbonding Period Shorter Than Fraud Proof Window 50",
+        res.unwrap_err().to_string()
+    );
+    assert!(ctx.get_bond_config(deps.as_ref().storage).is_none());
+
+    execute(deps.as_mut(), env, mock_info(OWNER, &[]), bond_config(50)).unwrap();
+    let config = ctx.get_bond_config(deps.as_ref().storage).unwrap();
+    assert_eq!(config.unbonding_period, 50);
+}
+
+fn attest(attester: &SigningKey, src_network: &str, conn_sn: u128, msg: &[u8]) -> Vec<u8> {
+    let signed_msg = SignableMsg {
+        src_network: src_network.to_string(),
+        conn_sn,
+        data: msg.to_vec(),
+        dst_network: "archway".to_string(),
+    }
+    .encode_utf8_bytes();
+    let (signature, recovery_code) = attester
+        .sign_digest_recoverable(keccak256(&signed_msg))
+        .unwrap();
+    let mut signature = signature.to_vec();
+    signature.push(recovery_code.to_byte());
+    signature
+}
+
+#[test]
+pub fn test_slash_bonded_relayer() {
+    let (mut deps, env, ctx) = instantiate(OWNER);
+    let attester = setup_bonding(&mut deps, &env);
+    let src_network = NetId::from_str("nid").unwrap();
+
+    let msg = ExecuteMsg::RecvMessage {
+        src_network: src_network.clone(),
+        conn_sn: 1,
+        msg: hex::encode("hello"),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+    assert!(ctx
+        .get_delivery(deps.as_ref().storage, src_network.clone(), 1)
+        .is_some());
+
+    let proof = |msg: &str| ExecuteMsg::SubmitFraudProof {
+        src_network: src_network.clone(),
+        conn_sn: 1,
+        msg: hex::encode(msg),
+        signatures: vec![attest(&attester, "nid", 1, msg.as_bytes())],
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        proof("hello"),
+    );
+    assert_eq!(
+        "Delivered Message Matches Attested Message",
+        res.unwrap_err().to_string()
+    );
+
+    let mut forged = proof("bye");
+    if let ExecuteMsg::SubmitFraudProof { signatures, .. } = &mut forged {
+        *signatures = vec![attest(&attester, "nid", 2, b"bye")];
+    }
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), forged);
+    assert_eq!("Insufficient Signatures", res.unwrap_err().to_string());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        proof("bye"),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        cosmwasm_std::CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: coins(1000, DENOM),
+        })
+    );
+    assert_eq!(
+        ctx.get_bond(deps.as_ref().storage, &Addr::unchecked(RELAYER)),
+        RelayerBond::default()
+    );
+
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), proof("bye"));
+    assert_eq!("Delivery Not Found", res.unwrap_err().to_string());
+}
+
+#[test]
+pub fn test_deliveries_expire_after_fraud_proof_window() {
+    let (mut deps, mut env, ctx) = instantiate(OWNER);
+    let attester = setup_bonding(&mut deps, &env);
+    let src_network = NetId::from_str("nid").unwrap();
+    let recv = |conn_sn: u128| ExecuteMsg::RecvMessage {
+        src_network: src_network.clone(),
+        conn_sn,
+        msg: hex::encode("hello"),
+    };
+
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(1)).unwrap();
+    env.block.time = env.block.time.plus_seconds(30);
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(2)).unwrap();
+    env.block.time = env.block.time.plus_seconds(20);
+
+    let msg = ExecuteMsg::SubmitFraudProof {
+        src_network: src_network.clone(),
+        conn_sn: 1,
+        msg: hex::encode("bye"),
+        signatures: vec![attest(&attester, "nid", 1, b"bye")],
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg);
+    assert_eq!("Delivery Expired", res.unwrap_err().to_string());
+
+    let msg = ExecuteMsg::CompactReceipts {
+        nid: src_network.clone(),
+        limit: None,
+    };
+    let res = execute(deps.as_mut(), env, mock_info(OWNER, &[]), msg).unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::Attribute::new("pruned_deliveries", "1")));
+    assert!(ctx
+        .get_delivery(deps.as_ref().storage, src_network.clone(), 1)
+        .is_none());
+    assert!(ctx
+        .get_delivery(deps.as_ref().storage, src_network, 2)
+        .is_some());
+}
+
+#[test]
+pub fn test_unbond() {
+    let (mut deps, mut env, ctx) = instantiate(OWNER);
+    setup_bonding(&mut deps, &env);
+    let relayer = Addr::unchecked(RELAYER);
+
+    let msg = ExecuteMsg::Unbond { amount: 400 };
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+    assert_eq!(
+        ctx.get_bond(deps.as_ref().storage, &relayer),
+        RelayerBond {
+            bonded: 600,
+            unbonding: 400,
+            release_at: env.block.time.seconds() + 100,
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::WithdrawBond {},
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .starts_with("Bond Locked Until"));
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::WithdrawBond {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        cosmwasm_std::CosmosMsg::Bank(BankMsg::Send {
+            to_address: RELAYER.to_string(),
+            amount: coins(400, DENOM),
+        })
+    );
+    assert_eq!(ctx.get_bond(deps.as_ref().storage, &relayer).total(), 600);
 }
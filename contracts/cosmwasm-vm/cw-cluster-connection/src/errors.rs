@@ -12,14 +12,30 @@ pub enum ContractError {
     OnlyAdmin,
     #[error("Only Relayer")]
     OnlyRelayer,
+    #[error("Relayer Not Allowed For {nid}")]
+    RelayerNotAllowed { nid: String },
+    #[error("Relayer Not Found")]
+    RelayerNotFound,
     #[error("Only XCall")]
     OnlyXCall,
     #[error("Duplicate Message")]
     DuplicateMessage,
+    #[error("Out Of Order Message, Expected Conn Sn {expected}")]
+    OutOfOrderMessage { expected: u128 },
     #[error("InsufficientFunds")]
     InsufficientFunds,
     #[error("ERR_REPLY_ERROR|{code:?}|{msg:?}")]
     ReplyError { code: u64, msg: String },
+    #[error("No Fees To Claim")]
+    NoFeesToClaim,
+    #[error("Invalid Fee Bounds")]
+    InvalidFeeBounds,
+    #[error("Invalid Message Log Capacity, Max {max}")]
+    InvalidMessageLogCapacity { max: u32 },
+    #[error("Invalid Nack Reason {code}")]
+    InvalidNackReason { code: u8 },
+    #[error("Message Already Nacked")]
+    AlreadyNacked,
     #[error("Insufficient Signatures")]
     InsufficientSignatures,
     #[error("Invalid Signature")]
@@ -2,7 +2,10 @@ use std::{collections::HashMap, str::FromStr};
 
 use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::{ensure_eq, Addr, BalanceResponse, BankQuery, Coin, QueryRequest};
-use cw_xcall_lib::network_address::{NetId, NetworkAddress};
+use cw_xcall_lib::{
+    network_address::{NetId, NetworkAddress},
+    xcall_msg::NackReason,
+};
 use sha2::Digest;
 use sha3::Keccak256;
 
@@ -31,12 +34,44 @@ impl<'a> ClusterConnection<'a> {
     }
 
     pub fn ensure_relayer(&self, store: &dyn Storage, address: Addr) -> Result<(), ContractError> {
-        let relayer = self.get_relayer(store)?;
-        ensure_eq!(relayer, address, ContractError::OnlyRelayer);
+        if self.get_relayer(store, &address).is_none() {
+            return Err(ContractError::OnlyRelayer);
+        }
 
         Ok(())
     }
 
+    pub fn ensure_network_relayer(
+        &self,
+        store: &dyn Storage,
+        address: Addr,
+        nid: &NetId,
+    ) -> Result<(), ContractError> {
+        let relayer = self
+            .get_relayer(store, &address)
+            .ok_or(ContractError::OnlyRelayer)?;
+        if !relayer.allows(nid) {
+            return Err(ContractError::RelayerNotAllowed {
+                nid: nid.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Whether `address` may claim the pending fees of messages sent to `nid`: the relayer that
+    /// last delivered a message from it, or any relayer allowed for it when nothing was delivered
+    /// yet.
+    pub fn is_fee_relayer(&self, store: &dyn Storage, address: &Addr, nid: &NetId) -> bool {
+        match self.get_fee_relayer(store, nid.clone()) {
+            Some(relayer) => relayer == *address,
+            None => self
+                .get_relayer(store, address)
+                .map(|relayer| relayer.allows(nid))
+                .unwrap_or(false),
+        }
+    }
+
     pub fn ensure_xcall(&self, store: &dyn Storage, address: Addr) -> Result<(), ContractError> {
         let xcall = self.get_xcall(store)?;
         ensure_eq!(xcall, address, ContractError::OnlyXCall);
@@ -115,6 +150,23 @@ impl<'a> ClusterConnection<'a> {
         Ok(sub_msg)
     }
 
+    pub fn call_xcall_handle_nack(
+        &self,
+        store: &dyn Storage,
+        sn: u128,
+        reason: NackReason,
+    ) -> Result<SubMsg, ContractError> {
+        let xcall_host = self.get_xcall(store)?;
+        let xcall_msg = cw_xcall_lib::xcall_msg::ExecuteMsg::HandleNack { sn, reason };
+        let call_message: CosmosMsg<Empty> = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: xcall_host.to_string(),
+            msg: to_json_binary(&xcall_msg).unwrap(),
+            funds: vec![],
+        });
+        let sub_msg: SubMsg = SubMsg::new(call_message);
+        Ok(sub_msg)
+    }
+
     pub fn verify_signatures(
         &self,
         deps: Deps,
@@ -44,6 +44,12 @@ pub fn execute(
 
         ExecuteMsg::SetRelayer { address } => conn.set_relayer(deps, info, address),
 
+        ExecuteMsg::AddRelayer { address, networks } => {
+            conn.add_relayer(deps, info, address, networks)
+        }
+
+        ExecuteMsg::RemoveRelayer { address } => conn.remove_relayer(deps, info, address),
+
         ExecuteMsg::SetValidators {
             validators,
             threshold,
@@ -53,6 +59,17 @@ pub fn execute(
             conn.set_signature_threshold(deps, info, threshold)
         }
 
+        ExecuteMsg::SetOrderedDelivery {
+            src_network,
+            ordered,
+        } => conn.set_ordered_delivery(deps, info, src_network, ordered),
+
+        ExecuteMsg::CompactReceipts { nid, limit } => conn.compact_receipts(deps, info, nid, limit),
+
+        ExecuteMsg::SetMessageLogCapacity { capacity } => {
+            conn.set_message_log_capacity(deps, info, capacity)
+        }
+
         ExecuteMsg::SetFee {
             network_id,
             message_fee,
@@ -61,14 +78,32 @@ pub fn execute(
 
         ExecuteMsg::ClaimFees {} => conn.claim_fees(deps, env, info),
 
-        ExecuteMsg::SendMessage { to, sn, msg } => conn.send_message(deps, info, to, sn, msg),
+        ExecuteMsg::ClaimNetworkFees { nid } => conn.claim_network_fees(deps, info, nid),
+
+        ExecuteMsg::SetFeeOracle {
+            address,
+            floor,
+            cap,
+        } => conn.set_fee_oracle(deps, info, address, floor, cap),
+
+        ExecuteMsg::RefundResponseFee { nid, sn, to } => {
+            conn.refund_response_fee(deps, info, nid, sn, to)
+        }
+
+        ExecuteMsg::SendMessage { to, sn, msg } => conn.send_message(deps, env, info, to, sn, msg),
 
         ExecuteMsg::RecvMessage {
             src_network,
             conn_sn,
             msg,
             signatures,
-        } => conn.recv_message(deps, info, src_network, conn_sn, msg, signatures),
+        } => conn.recv_message(deps, env, info, src_network, conn_sn, msg, signatures),
+
+        ExecuteMsg::NackMessage {
+            sn,
+            reason_code,
+            evidence,
+        } => conn.nack_message(deps, info, sn, reason_code, evidence),
     }
 }
 
@@ -77,7 +112,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let conn = ClusterConnection::default();
     match msg {
         QueryMsg::GetFee { nid, response } => {
-            to_json_binary(&conn.get_fee(deps.storage, nid, response).unwrap())
+            let fee = conn
+                .calculate_fee(deps, nid, response)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+            to_json_binary(&fee)
         }
 
         QueryMsg::GetReceipt {
@@ -85,16 +123,55 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             conn_sn,
         } => to_json_binary(&conn.get_receipt(deps.storage, src_network, conn_sn)),
 
+        QueryMsg::GetDeliveryStatus { src_network } => {
+            to_json_binary(&conn.get_delivery_status(deps.storage, src_network))
+        }
+
+        QueryMsg::GetMissingConnSns { src_network, limit } => {
+            to_json_binary(&conn.get_missing_conn_sns(deps.storage, src_network, limit))
+        }
+
+        QueryMsg::GetConnSn { to } => to_json_binary(&conn.get_network_conn_sn(deps.storage, to)),
+
+        QueryMsg::GetMessage { to, conn_sn } => {
+            to_json_binary(&conn.get_message(deps.storage, to, conn_sn))
+        }
+
+        QueryMsg::GetMessages { to, from, limit } => {
+            to_json_binary(&conn.get_logged_messages(deps.storage, to, from, limit)?)
+        }
+
+        QueryMsg::GetNetworkStatus { nid } => {
+            to_json_binary(&conn.get_network_status(deps.storage, nid))
+        }
+
+        QueryMsg::GetNetworkFees { nid } => {
+            to_json_binary(&conn.get_network_fees(deps.storage, nid))
+        }
+
+        QueryMsg::GetFeeOracle {} => to_json_binary(&conn.get_fee_oracle(deps.storage)),
+
+        QueryMsg::GetNack { sn } => to_json_binary(&conn.get_nack(deps.storage, sn)),
+
         QueryMsg::GetAdmin {} => {
-            let admin = conn.get_admin(deps.storage).unwrap();
+            let admin = conn
+                .get_admin(deps.storage)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
             to_json_binary(&admin)
         }
 
-        QueryMsg::GetRelayer {} => {
-            let relayer = conn.get_relayer(deps.storage).unwrap();
-            to_json_binary(&relayer)
+        QueryMsg::GetRelayers {} => {
+            let relayers = conn.get_relayers(deps.storage)?;
+            to_json_binary(&relayers)
+        }
+
+        QueryMsg::GetClaimableFees { address } => {
+            let fees = conn.get_claimable_fees(deps.storage, &address)?;
+            to_json_binary(&fees)
         }
 
+        QueryMsg::GetFeeRelayer { nid } => to_json_binary(&conn.get_fee_relayer(deps.storage, nid)),
+
         QueryMsg::GetValidators {} => {
             let validators = conn.get_validators(deps.storage)?;
             to_json_binary(&validators)
@@ -109,6 +186,6 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let conn = ClusterConnection::default();
+    let mut conn = ClusterConnection::default();
     conn.migrate(deps, _env, _msg)
 }
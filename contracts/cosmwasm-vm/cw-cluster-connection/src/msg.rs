@@ -1,6 +1,10 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{network_address::NetId, xcall_connection_msg::NetworkStatus};
+
+use crate::types::{
+    DeliveryStatus, FeeOracle, NackRecord, NetworkFees, OutboundMessage, RelayerInfo,
+};
 
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -12,6 +16,15 @@ pub enum ExecuteMsg {
         address: Addr,
     },
 
+    AddRelayer {
+        address: Addr,
+        networks: Vec<NetId>,
+    },
+
+    RemoveRelayer {
+        address: Addr,
+    },
+
     SetValidators {
         validators: Vec<Vec<u8>>,
         threshold: u8,
@@ -21,6 +34,20 @@ pub enum ExecuteMsg {
         threshold: u8,
     },
 
+    SetOrderedDelivery {
+        src_network: NetId,
+        ordered: bool,
+    },
+
+    CompactReceipts {
+        nid: NetId,
+        limit: Option<u32>,
+    },
+
+    SetMessageLogCapacity {
+        capacity: u32,
+    },
+
     SetFee {
         network_id: NetId,
         message_fee: u128,
@@ -28,6 +55,19 @@ pub enum ExecuteMsg {
     },
 
     ClaimFees {},
+    ClaimNetworkFees {
+        nid: NetId,
+    },
+    SetFeeOracle {
+        address: Option<String>,
+        floor: u128,
+        cap: u128,
+    },
+    RefundResponseFee {
+        nid: NetId,
+        sn: u128,
+        to: String,
+    },
 
     SendMessage {
         to: NetId,
@@ -41,6 +81,12 @@ pub enum ExecuteMsg {
         msg: String,
         signatures: Vec<Vec<u8>>,
     },
+
+    NackMessage {
+        sn: u128,
+        reason_code: u8,
+        evidence: String,
+    },
 }
 
 #[cw_serde]
@@ -51,12 +97,43 @@ pub enum QueryMsg {
 
     #[returns(bool)]
     GetReceipt { src_network: NetId, conn_sn: u128 },
+    #[returns(DeliveryStatus)]
+    GetDeliveryStatus { src_network: NetId },
+    #[returns(NetworkStatus)]
+    GetNetworkStatus { nid: NetId },
+    #[returns(Vec<u128>)]
+    GetMissingConnSns {
+        src_network: NetId,
+        limit: Option<u32>,
+    },
+    #[returns(u128)]
+    GetConnSn { to: NetId },
+    #[returns(Option<OutboundMessage>)]
+    GetMessage { to: NetId, conn_sn: u128 },
+    #[returns(Vec<OutboundMessage>)]
+    GetMessages {
+        to: NetId,
+        from: u128,
+        limit: Option<u32>,
+    },
+    #[returns(NetworkFees)]
+    GetNetworkFees { nid: NetId },
+    #[returns(Option<FeeOracle>)]
+    GetFeeOracle {},
+    #[returns(Option<NackRecord>)]
+    GetNack { sn: u128 },
 
     #[returns(Addr)]
     GetAdmin {},
 
-    #[returns(Addr)]
-    GetRelayer {},
+    #[returns(Vec<RelayerInfo>)]
+    GetRelayers {},
+
+    #[returns(u128)]
+    GetClaimableFees { address: Addr },
+
+    #[returns(Option<Addr>)]
+    GetFeeRelayer { nid: NetId },
 
     #[returns(Vec<String>)]
     GetValidators {},
@@ -66,4 +143,9 @@ pub enum QueryMsg {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// The conn_sn each source network continued from when it started numbering its messages per
+    /// destination, the gaps below it are never filled.
+    #[serde(default)]
+    pub legacy_conn_sns: Vec<(NetId, u128)>,
+}
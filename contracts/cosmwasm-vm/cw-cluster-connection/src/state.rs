@@ -1,14 +1,18 @@
 use cosmwasm_std::Addr;
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{network_address::NetId, xcall_connection_msg::NetworkStatus};
 
-use crate::types::StorageKey;
+use cw_storage_plus::Bound;
+
+use crate::types::{FeeOracle, NackRecord, NetworkFees, OutboundMessage, RelayerInfo, StorageKey};
 
 use super::*;
 
 pub struct ClusterConnection<'a> {
     xcall: Item<'a, Addr>,
     admin: Item<'a, Addr>,
-    relayer: Item<'a, Addr>,
+    relayers: Map<'a, Addr, Vec<NetId>>,
+    fee_relayers: Map<'a, NetId, Addr>,
+    relayer_fees: Map<'a, Addr, u128>,
     validators: Map<'a, Vec<u8>, bool>,
     signature_threshold: Item<'a, u8>,
 
@@ -16,9 +20,21 @@ pub struct ClusterConnection<'a> {
     response_fee: Map<'a, NetId, u128>,
 
     conn_sn: Item<'a, u128>,
+    network_conn_sn: Map<'a, NetId, u128>,
     receipts: Map<'a, (String, u128), bool>,
 
     denom: Item<'a, String>,
+    network_fees: Map<'a, NetId, NetworkFees>,
+    fee_oracle: Item<'a, FeeOracle>,
+    response_fee_deposits: Map<'a, (String, u128), u128>,
+    ordered_networks: Map<'a, NetId, bool>,
+    contiguous_conn_sn: Map<'a, NetId, u128>,
+    highest_conn_sn: Map<'a, NetId, u128>,
+    message_log: Map<'a, (String, u128), OutboundMessage>,
+    message_log_capacity: Item<'a, u32>,
+    network_status: Map<'a, NetId, NetworkStatus>,
+    pending_requests: Map<'a, u128, NetId>,
+    nacks: Map<'a, u128, NackRecord>,
 }
 
 impl<'a> Default for ClusterConnection<'a> {
@@ -32,7 +48,9 @@ impl<'a> ClusterConnection<'a> {
         Self {
             xcall: Item::new(StorageKey::XCall.as_str()),
             admin: Item::new(StorageKey::Admin.as_str()),
-            relayer: Item::new(StorageKey::Relayer.as_str()),
+            relayers: Map::new(StorageKey::Relayers.as_str()),
+            fee_relayers: Map::new(StorageKey::FeeRelayers.as_str()),
+            relayer_fees: Map::new(StorageKey::RelayerFees.as_str()),
             validators: Map::new(StorageKey::Validators.as_str()),
             signature_threshold: Item::new(StorageKey::SignatureThreshold.as_str()),
 
@@ -40,9 +58,21 @@ impl<'a> ClusterConnection<'a> {
             response_fee: Map::new(StorageKey::ResponseFee.as_str()),
 
             conn_sn: Item::new(StorageKey::ConnSn.as_str()),
+            network_conn_sn: Map::new(StorageKey::NetworkConnSn.as_str()),
             receipts: Map::new(StorageKey::Receipts.as_str()),
 
             denom: Item::new(StorageKey::Denom.as_str()),
+            network_fees: Map::new(StorageKey::NetworkFees.as_str()),
+            fee_oracle: Item::new(StorageKey::FeeOracle.as_str()),
+            response_fee_deposits: Map::new(StorageKey::ResponseFeeDeposits.as_str()),
+            ordered_networks: Map::new(StorageKey::OrderedNetworks.as_str()),
+            contiguous_conn_sn: Map::new(StorageKey::ContiguousConnSn.as_str()),
+            highest_conn_sn: Map::new(StorageKey::HighestConnSn.as_str()),
+            message_log: Map::new(StorageKey::MessageLog.as_str()),
+            message_log_capacity: Item::new(StorageKey::MessageLogCapacity.as_str()),
+            network_status: Map::new(StorageKey::NetworkStatus.as_str()),
+            pending_requests: Map::new(StorageKey::PendingRequests.as_str()),
+            nacks: Map::new(StorageKey::Nacks.as_str()),
         }
     }
 
@@ -64,13 +94,96 @@ impl<'a> ClusterConnection<'a> {
         Ok(self.admin.load(store)?)
     }
 
-    pub fn store_relayer(&mut self, store: &mut dyn Storage, address: Addr) -> StdResult<()> {
-        self.relayer.save(store, &address)?;
+    pub fn store_relayer(
+        &mut self,
+        store: &mut dyn Storage,
+        address: Addr,
+        networks: Vec<NetId>,
+    ) -> StdResult<()> {
+        self.relayers.save(store, address, &networks)?;
+        Ok(())
+    }
+
+    /// Removes `address` from the relayer set and from the networks whose fees it was entitled
+    /// to, crediting it their pending fees. Fees credited to it stay claimable.
+    pub fn remove_relayer_entry(
+        &mut self,
+        store: &mut dyn Storage,
+        address: &Addr,
+    ) -> StdResult<()> {
+        self.relayers.remove(store, address.clone());
+        let networks = self
+            .fee_relayers
+            .range(store, None, None, cosmwasm_std::Order::Ascending)
+            .filter_map(|entry| match entry {
+                Ok((nid, relayer)) if relayer == *address => Some(Ok(nid)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<StdResult<Vec<NetId>>>()?;
+        for nid in networks {
+            self.settle_fee_relayer(store, nid.clone())?;
+            self.fee_relayers.remove(store, nid);
+        }
+        Ok(())
+    }
+
+    pub fn clear_relayers(&mut self, store: &mut dyn Storage) -> StdResult<()> {
+        for relayer in self.get_relayers(store)? {
+            self.remove_relayer_entry(store, &relayer.address)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_relayer(&self, store: &dyn Storage, address: &Addr) -> Option<RelayerInfo> {
+        self.relayers
+            .may_load(store, address.clone())
+            .unwrap_or(None)
+            .map(|networks| RelayerInfo {
+                address: address.clone(),
+                networks,
+            })
+    }
+
+    pub fn get_relayers(&self, store: &dyn Storage) -> StdResult<Vec<RelayerInfo>> {
+        self.relayers
+            .range(store, None, None, cosmwasm_std::Order::Ascending)
+            .map(|entry| entry.map(|(address, networks)| RelayerInfo { address, networks }))
+            .collect()
+    }
+
+    pub fn relayers(&self) -> &Map<'a, Addr, Vec<NetId>> {
+        &self.relayers
+    }
+
+    /// Returns the relayer entitled to the pending fees of `nid`, the last one that delivered a
+    /// message from it.
+    pub fn get_fee_relayer(&self, store: &dyn Storage, nid: NetId) -> Option<Addr> {
+        self.fee_relayers.may_load(store, nid).unwrap_or(None)
+    }
+
+    pub fn store_fee_relayer(
+        &mut self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        relayer: &Addr,
+    ) -> StdResult<()> {
+        self.fee_relayers.save(store, nid, relayer)?;
         Ok(())
     }
 
-    pub fn get_relayer(&self, store: &dyn Storage) -> Result<Addr, ContractError> {
-        Ok(self.relayer.load(store)?)
+    pub fn get_relayer_fees(&self, store: &dyn Storage, relayer: &Addr) -> u128 {
+        self.relayer_fees.load(store, relayer.clone()).unwrap_or(0)
+    }
+
+    pub fn store_relayer_fees(
+        &mut self,
+        store: &mut dyn Storage,
+        relayer: &Addr,
+        amount: u128,
+    ) -> StdResult<()> {
+        self.relayer_fees.save(store, relayer.clone(), &amount)?;
+        Ok(())
     }
 
     pub fn store_conn_sn(&mut self, store: &mut dyn Storage, sn: u128) -> StdResult<()> {
@@ -78,13 +191,39 @@ impl<'a> ClusterConnection<'a> {
         Ok(())
     }
 
-    pub fn get_next_conn_sn(&self, store: &mut dyn Storage) -> Result<u128, ContractError> {
-        let mut connsn = self.conn_sn.load(store).unwrap_or(0);
-        connsn += 1;
-        self.conn_sn.save(store, &connsn)?;
+    /// Returns the conn_sn shared by all destinations before each got its own sequence.
+    pub fn get_conn_sn(&self, store: &dyn Storage) -> u128 {
+        self.conn_sn.load(store).unwrap_or(0)
+    }
+
+    /// Returns the conn_sn of the last message sent to `to`. A destination no message was sent
+    /// to since it got its own sequence starts from the conn_sn shared by all destinations
+    /// before, so none of the conn_sns it may have received is used again.
+    pub fn get_network_conn_sn(&self, store: &dyn Storage, to: NetId) -> u128 {
+        match self.network_conn_sn.may_load(store, to).unwrap_or(None) {
+            Some(conn_sn) => conn_sn,
+            None => self.get_conn_sn(store),
+        }
+    }
+
+    /// Returns the next conn_sn of the messages sent to `to`. Every destination has its own
+    /// sequence so the conn_sns it receives from this connection have no gaps.
+    pub fn get_next_conn_sn(
+        &self,
+        store: &mut dyn Storage,
+        to: NetId,
+    ) -> Result<u128, ContractError> {
+        let connsn = self.get_network_conn_sn(store, to.clone()) + 1;
+        self.network_conn_sn.save(store, to, &connsn)?;
         Ok(connsn)
     }
 
+    pub fn get_conn_sn_networks(&self, store: &dyn Storage) -> StdResult<Vec<NetId>> {
+        self.network_conn_sn
+            .keys(store, None, None, cosmwasm_std::Order::Ascending)
+            .collect()
+    }
+
     pub fn store_fee(
         &mut self,
         store: &mut dyn Storage,
@@ -115,12 +254,220 @@ impl<'a> ClusterConnection<'a> {
         Ok(())
     }
 
+    /// Receipts up to the contiguous conn_sn of a network are pruned, the contiguous conn_sn is
+    /// their low-watermark and only the receipts above it are stored.
     pub fn get_receipt(&self, store: &dyn Storage, src_network: NetId, sn: u128) -> bool {
+        if sn > 0 && sn <= self.get_contiguous_conn_sn(store, src_network.clone()) {
+            return true;
+        }
         self.receipts
             .load(store, (src_network.to_string(), sn))
             .unwrap_or(false)
     }
 
+    pub fn remove_receipt(&mut self, store: &mut dyn Storage, src_network: NetId, sn: u128) {
+        self.receipts.remove(store, (src_network.to_string(), sn));
+    }
+
+    pub fn get_receipt_keys(&self, store: &dyn Storage) -> StdResult<Vec<(String, u128)>> {
+        self.receipts
+            .keys(store, None, None, cosmwasm_std::Order::Ascending)
+            .collect()
+    }
+
+    /// Returns up to `limit` conn_sns of the receipts stored for `src_network` up to `max`.
+    pub fn get_receipt_conn_sns(
+        &self,
+        store: &dyn Storage,
+        src_network: NetId,
+        max: u128,
+        limit: usize,
+    ) -> StdResult<Vec<u128>> {
+        self.receipts
+            .prefix(src_network.to_string())
+            .keys(
+                store,
+                None,
+                Some(Bound::inclusive(max)),
+                cosmwasm_std::Order::Ascending,
+            )
+            .take(limit)
+            .collect()
+    }
+
+    pub fn get_last_receipt_conn_sn(
+        &self,
+        store: &dyn Storage,
+        src_network: NetId,
+    ) -> StdResult<Option<u128>> {
+        self.receipts
+            .prefix(src_network.to_string())
+            .keys(store, None, None, cosmwasm_std::Order::Descending)
+            .next()
+            .transpose()
+    }
+
+    pub fn is_ordered_delivery(&self, store: &dyn Storage, src_network: NetId) -> bool {
+        self.ordered_networks
+            .load(store, src_network)
+            .unwrap_or(false)
+    }
+
+    pub fn store_ordered_delivery(
+        &mut self,
+        store: &mut dyn Storage,
+        src_network: NetId,
+        ordered: bool,
+    ) -> StdResult<()> {
+        if ordered {
+            self.ordered_networks.save(store, src_network, &true)
+        } else {
+            self.ordered_networks.remove(store, src_network);
+            Ok(())
+        }
+    }
+
+    pub fn get_contiguous_conn_sn(&self, store: &dyn Storage, src_network: NetId) -> u128 {
+        self.contiguous_conn_sn
+            .load(store, src_network)
+            .unwrap_or(0)
+    }
+
+    pub fn store_contiguous_conn_sn(
+        &mut self,
+        store: &mut dyn Storage,
+        src_network: NetId,
+        conn_sn: u128,
+    ) -> StdResult<()> {
+        self.contiguous_conn_sn.save(store, src_network, &conn_sn)
+    }
+
+    pub fn get_highest_conn_sn(&self, store: &dyn Storage, src_network: NetId) -> u128 {
+        self.highest_conn_sn.load(store, src_network).unwrap_or(0)
+    }
+
+    pub fn store_highest_conn_sn(
+        &mut self,
+        store: &mut dyn Storage,
+        src_network: NetId,
+        conn_sn: u128,
+    ) -> StdResult<()> {
+        self.highest_conn_sn.save(store, src_network, &conn_sn)
+    }
+
+    pub fn get_network_status(&self, store: &dyn Storage, nid: NetId) -> NetworkStatus {
+        self.network_status.load(store, nid).unwrap_or_default()
+    }
+
+    pub fn store_network_status(
+        &mut self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        status: &NetworkStatus,
+    ) -> StdResult<()> {
+        self.network_status.save(store, nid, status)
+    }
+
+    pub fn get_pending_request(&self, store: &dyn Storage, sn: u128) -> Option<NetId> {
+        self.pending_requests.may_load(store, sn).unwrap_or(None)
+    }
+
+    pub fn store_pending_request(
+        &mut self,
+        store: &mut dyn Storage,
+        sn: u128,
+        nid: NetId,
+    ) -> StdResult<()> {
+        self.pending_requests.save(store, sn, &nid)
+    }
+
+    pub fn remove_pending_request(&mut self, store: &mut dyn Storage, sn: u128) {
+        self.pending_requests.remove(store, sn);
+    }
+
+    pub fn get_nack(&self, store: &dyn Storage, sn: u128) -> Option<NackRecord> {
+        self.nacks.may_load(store, sn).unwrap_or(None)
+    }
+
+    pub fn store_nack(&mut self, store: &mut dyn Storage, nack: &NackRecord) -> StdResult<()> {
+        self.nacks.save(store, nack.sn, nack)
+    }
+
+    pub fn get_message_log_capacity(&self, store: &dyn Storage) -> u32 {
+        self.message_log_capacity.load(store).unwrap_or(0)
+    }
+
+    pub fn store_message_log_capacity(
+        &mut self,
+        store: &mut dyn Storage,
+        capacity: u32,
+    ) -> StdResult<()> {
+        self.message_log_capacity.save(store, &capacity)
+    }
+
+    pub fn get_message(
+        &self,
+        store: &dyn Storage,
+        to: NetId,
+        conn_sn: u128,
+    ) -> Option<OutboundMessage> {
+        self.message_log
+            .may_load(store, (to.to_string(), conn_sn))
+            .unwrap_or(None)
+    }
+
+    pub fn get_messages(
+        &self,
+        store: &dyn Storage,
+        to: NetId,
+        from: u128,
+        limit: usize,
+    ) -> StdResult<Vec<OutboundMessage>> {
+        self.message_log
+            .prefix(to.to_string())
+            .range(
+                store,
+                Some(Bound::inclusive(from)),
+                None,
+                cosmwasm_std::Order::Ascending,
+            )
+            .take(limit)
+            .map(|entry| entry.map(|(_, message)| message))
+            .collect()
+    }
+
+    pub fn store_message(
+        &mut self,
+        store: &mut dyn Storage,
+        message: &OutboundMessage,
+    ) -> StdResult<()> {
+        self.message_log
+            .save(store, (message.to.to_string(), message.conn_sn), message)
+    }
+
+    /// Removes the logged messages sent to `to` with a conn_sn below `conn_sn`.
+    pub fn remove_messages_below(
+        &mut self,
+        store: &mut dyn Storage,
+        to: NetId,
+        conn_sn: u128,
+    ) -> StdResult<()> {
+        let keys = self
+            .message_log
+            .prefix(to.to_string())
+            .keys(
+                store,
+                None,
+                Some(Bound::exclusive(conn_sn)),
+                cosmwasm_std::Order::Ascending,
+            )
+            .collect::<StdResult<Vec<u128>>>()?;
+        for key in keys {
+            self.message_log.remove(store, (to.to_string(), key));
+        }
+        Ok(())
+    }
+
     pub fn store_denom(&mut self, store: &mut dyn Storage, denom: String) -> StdResult<()> {
         self.denom.save(store, &denom)?;
         Ok(())
@@ -185,4 +532,70 @@ impl<'a> ClusterConnection<'a> {
     pub fn get_signature_threshold(&self, store: &dyn Storage) -> u8 {
         self.signature_threshold.load(store).unwrap()
     }
+
+    pub fn get_network_fees(&self, store: &dyn Storage, nid: NetId) -> NetworkFees {
+        self.network_fees.load(store, nid).unwrap_or_default()
+    }
+
+    pub fn store_network_fees(
+        &mut self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        fees: &NetworkFees,
+    ) -> StdResult<()> {
+        self.network_fees.save(store, nid, fees)?;
+        Ok(())
+    }
+
+    pub fn get_all_network_fees(
+        &self,
+        store: &dyn Storage,
+    ) -> StdResult<Vec<(NetId, NetworkFees)>> {
+        self.network_fees
+            .range(store, None, None, cosmwasm_std::Order::Ascending)
+            .collect()
+    }
+
+    pub fn store_fee_oracle(
+        &mut self,
+        store: &mut dyn Storage,
+        oracle: Option<FeeOracle>,
+    ) -> StdResult<()> {
+        match oracle {
+            Some(oracle) => self.fee_oracle.save(store, &oracle)?,
+            None => self.fee_oracle.remove(store),
+        }
+        Ok(())
+    }
+
+    pub fn get_fee_oracle(&self, store: &dyn Storage) -> Option<FeeOracle> {
+        self.fee_oracle.may_load(store).unwrap_or(None)
+    }
+
+    pub fn store_response_fee_deposit(
+        &mut self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        sn: u128,
+        amount: u128,
+    ) -> StdResult<()> {
+        self.response_fee_deposits
+            .save(store, (nid.to_string(), sn), &amount)?;
+        Ok(())
+    }
+
+    pub fn take_response_fee_deposit(
+        &mut self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        sn: u128,
+    ) -> u128 {
+        let key = (nid.to_string(), sn);
+        let amount = self
+            .response_fee_deposits
+            .load(store, key.clone())
+            .unwrap_or(0);
+        self.response_fee_deposits.remove(store, key);
+        amount
+    }
 }
@@ -1,3 +1,6 @@
+use cosmwasm_std::Addr;
+use cw_xcall_lib::{network_address::NetId, xcall_msg::NackReason};
+
 use super::*;
 
 #[cw_serde]
@@ -7,11 +10,101 @@ pub struct InstantiateMsg {
     pub denom: String,
 }
 
+/// Fees accrued for messages sent to a network, split by message and response fees, the part
+/// of them already claimed by the relayer and the response fees `reserved` until a response is
+/// delivered for them or they are refunded.
+#[cw_serde]
+#[derive(Default)]
+pub struct NetworkFees {
+    pub message_fees: u128,
+    pub response_fees: u128,
+    pub claimed: u128,
+    #[serde(default)]
+    pub reserved: u128,
+}
+
+impl NetworkFees {
+    pub fn unclaimed(&self) -> u128 {
+        (self.message_fees + self.response_fees).saturating_sub(self.claimed + self.reserved)
+    }
+}
+
+/// A relayer allowed to deliver messages from `networks`, or from every network when empty.
+#[cw_serde]
+pub struct RelayerInfo {
+    pub address: Addr,
+    pub networks: Vec<NetId>,
+}
+
+impl RelayerInfo {
+    pub fn allows(&self, nid: &NetId) -> bool {
+        self.networks.is_empty() || self.networks.contains(nid)
+    }
+}
+
+/// Delivery progress of the messages received from a network. `contiguous_conn_sn` is the
+/// highest conn_sn up to which every message was received; in ordered mode only the one right
+/// after it is accepted.
+#[cw_serde]
+pub struct DeliveryStatus {
+    pub ordered: bool,
+    pub contiguous_conn_sn: u128,
+    pub highest_conn_sn: u128,
+}
+
+/// A message kept in the outbound message log, `msg_hash` being the hex encoded keccak256 of
+/// the message and `height` the block it was sent at.
+#[cw_serde]
+pub struct OutboundMessage {
+    pub conn_sn: u128,
+    pub to: NetId,
+    pub sn: i64,
+    pub msg_hash: String,
+    pub height: u64,
+}
+
+/// Contract answering `xcall_connection_msg::QueryMsg::GetFee` whose quote is used instead of
+/// the stored fees, bounded to `[floor, cap]`.
+#[cw_serde]
+pub struct FeeOracle {
+    pub address: Addr,
+    pub floor: u128,
+    pub cap: u128,
+}
+
+/// Fee charged for a message, `response_fee` being the part reserved for refunds until the
+/// response is delivered. `oracle_error` is set when the stored fees were quoted because the fee
+/// oracle query failed.
+#[derive(Default)]
+pub struct FeeQuote {
+    pub message_fee: u128,
+    pub response_fee: u128,
+    pub oracle_error: Option<String>,
+}
+
+impl FeeQuote {
+    pub fn total(&self) -> u128 {
+        self.message_fee + self.response_fee
+    }
+}
+
+/// Why the message of xCall sequence number `sn` could not be delivered, as reported by the
+/// relayer. `evidence` backs the claim, e.g. the hash of the rejected transaction.
+#[cw_serde]
+pub struct NackRecord {
+    pub sn: u128,
+    pub reason: NackReason,
+    pub evidence: String,
+}
+
 #[cw_serde]
 pub enum StorageKey {
     XCall,
     Admin,
     Relayer,
+    Relayers,
+    FeeRelayers,
+    RelayerFees,
     Validators,
     SignatureThreshold,
 
@@ -19,9 +112,21 @@ pub enum StorageKey {
     ResponseFee,
 
     ConnSn,
+    NetworkConnSn,
     Receipts,
 
     Denom,
+    NetworkFees,
+    FeeOracle,
+    ResponseFeeDeposits,
+    OrderedNetworks,
+    ContiguousConnSn,
+    HighestConnSn,
+    MessageLog,
+    MessageLogCapacity,
+    NetworkStatus,
+    PendingRequests,
+    Nacks,
 }
 
 impl StorageKey {
@@ -30,6 +135,9 @@ impl StorageKey {
             StorageKey::XCall => "xcall",
             StorageKey::Admin => "admin",
             StorageKey::Relayer => "relayer",
+            StorageKey::Relayers => "relayers",
+            StorageKey::FeeRelayers => "fee_relayers",
+            StorageKey::RelayerFees => "relayer_fees",
             StorageKey::Validators => "validators",
             StorageKey::SignatureThreshold => "signature_threshold",
 
@@ -37,50 +145,21 @@ impl StorageKey {
             StorageKey::ResponseFee => "response_fee",
 
             StorageKey::ConnSn => "conn_sn",
+            StorageKey::NetworkConnSn => "network_conn_sn",
             StorageKey::Receipts => "receipts",
 
             StorageKey::Denom => "denom",
+            StorageKey::NetworkFees => "network_fees",
+            StorageKey::FeeOracle => "fee_oracle",
+            StorageKey::ResponseFeeDeposits => "response_fee_deposits",
+            StorageKey::OrderedNetworks => "ordered_networks",
+            StorageKey::ContiguousConnSn => "contiguous_conn_sn",
+            StorageKey::HighestConnSn => "highest_conn_sn",
+            StorageKey::MessageLog => "message_log",
+            StorageKey::MessageLogCapacity => "message_log_capacity",
+            StorageKey::NetworkStatus => "network_status",
+            StorageKey::PendingRequests => "pending_requests",
+            StorageKey::Nacks => "nacks",
         }
     }
 }
-
-pub struct SignableMsg {
-    pub src_network: String,
-    pub conn_sn: u128,
-    pub data: Vec<u8>,
-    pub dst_network: String,
-}
-impl SignableMsg {
-    pub fn encode_utf8_bytes(&self) -> Vec<u8> {
-        let mut encoded_bytes = Vec::new();
-
-        encoded_bytes.extend(self.src_network.as_bytes());
-
-        encoded_bytes.extend(self.conn_sn.to_string().as_bytes());
-
-        encoded_bytes.extend(self.data.to_vec());
-
-        encoded_bytes.extend(self.dst_network.as_bytes());
-
-        encoded_bytes
-    }
-}
-
-#[test]
-pub fn test_signable_msg_utf8_bytes() {
-    let signed_msg = SignableMsg {
-        src_network: "0x2.icon".to_string(),
-        conn_sn: 128,
-        data: "hello".as_bytes().to_vec(),
-        dst_network: "archway".to_string(),
-    };
-
-    let expected_encoded_hex_str = "3078322e69636f6e31323868656c6c6f61726368776179".to_string();
-    let expected_encoded_bytes = hex::decode(expected_encoded_hex_str).unwrap();
-
-    assert_eq!(
-        expected_encoded_bytes,
-        signed_msg.encode_utf8_bytes(),
-        "test failed"
-    );
-}
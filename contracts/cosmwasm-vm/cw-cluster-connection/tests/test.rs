@@ -1,15 +1,24 @@
 pub mod setup;
+use cluster_connection::keccak256;
 use cluster_connection::{
-    execute, msg::ExecuteMsg, state::ClusterConnection, types::InstantiateMsg,
+    execute,
+    msg::{ExecuteMsg, MigrateMsg},
+    state::ClusterConnection,
+    types::{DeliveryStatus, InstantiateMsg, NackRecord, OutboundMessage, RelayerInfo},
+};
+use cosmwasm_std::{
+    from_json, to_json_binary, Coin, ContractInfoResponse, Event, SystemResult, WasmQuery,
 };
-use cluster_connection::{keccak256, SignableMsg};
 use cosmwasm_std::{testing::mock_env, ContractResult, Env};
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_info, MockApi, MockQuerier},
     Addr, MemoryStorage, OwnedDeps, Uint128,
 };
-use cosmwasm_std::{to_json_binary, Coin, ContractInfoResponse, Event, SystemResult, WasmQuery};
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{
+    network_address::NetId,
+    xcall_connection_msg::{NetworkStatus, QueryMsg as XcallConnectionQueryMsg, SignableMsg},
+    xcall_msg::NackReason,
+};
 use k256::{ecdsa::SigningKey, ecdsa::VerifyingKey, elliptic_curve::rand_core::OsRng};
 use std::str::FromStr;
 
@@ -90,8 +99,212 @@ fn test_set_relayer() {
     let res = execute(deps.as_mut(), env, msg_info, msg);
     assert!(res.is_ok());
 
-    let relayer = ctx.get_relayer(deps.as_mut().storage).unwrap();
-    assert_eq!(relayer, new_relayer);
+    let relayers = ctx.get_relayers(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        relayers,
+        vec![RelayerInfo {
+            address: new_relayer,
+            networks: vec![],
+        }]
+    );
+}
+
+#[test]
+fn test_add_and_remove_relayer() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let nid = NetId::from_str("0x2.icon").unwrap();
+    let scoped = Addr::unchecked("scoped");
+
+    let msg = ExecuteMsg::AddRelayer {
+        address: scoped.clone(),
+        networks: vec![nid.clone()],
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg);
+    assert!(res.is_ok());
+    assert_eq!(ctx.get_relayers(deps.as_ref().storage).unwrap().len(), 2);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(scoped.as_str(), &[]),
+        ExecuteMsg::RecvMessage {
+            src_network: NetId::from_str("0x1.eth").unwrap(),
+            conn_sn: 1,
+            msg: string_to_hex("hello"),
+            signatures: vec![],
+        },
+    );
+    assert_eq!(
+        "Relayer Not Allowed For 0x1.eth",
+        res.unwrap_err().to_string()
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(scoped.as_str(), &[]),
+        ExecuteMsg::ClaimNetworkFees {
+            nid: NetId::from_str("0x1.eth").unwrap(),
+        },
+    );
+    assert_eq!(
+        "Relayer Not Allowed For 0x1.eth",
+        res.unwrap_err().to_string()
+    );
+
+    let msg = ExecuteMsg::RemoveRelayer {
+        address: scoped.clone(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        msg.clone(),
+    );
+    assert!(res.is_ok());
+    assert!(ctx.get_relayer(deps.as_ref().storage, &scoped).is_none());
+
+    let res = execute(deps.as_mut(), env, mock_info(ADMIN, &[]), msg);
+    assert_eq!("Relayer Not Found", res.unwrap_err().to_string());
+}
+
+#[test]
+fn test_fees_follow_delivering_relayer() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let nid = NetId::from_str("0x2.icon").unwrap();
+    let backup = Addr::unchecked("backup");
+
+    deps.querier.update_wasm(|r: &WasmQuery| match r {
+        WasmQuery::Smart {
+            contract_addr: _,
+            msg: _,
+        } => SystemResult::Ok(ContractResult::Ok(to_json_binary("archway/xcall").unwrap())),
+        WasmQuery::ContractInfo { contract_addr: _ } => SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&ContractInfoResponse::default()).unwrap(),
+        )),
+        _ => todo!(),
+    });
+
+    let signing_key = SigningKey::random(&mut OsRng);
+    let pubkey = VerifyingKey::from(&signing_key)
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetValidators {
+            validators: vec![pubkey],
+            threshold: 1,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::AddRelayer {
+            address: backup.clone(),
+            networks: vec![],
+        },
+    )
+    .unwrap();
+
+    let recv = |conn_sn: u128| {
+        let data = b"hello".to_vec();
+        let signed_msg = SignableMsg {
+            src_network: nid.to_string(),
+            conn_sn,
+            data: data.clone(),
+            dst_network: "archway".to_string(),
+        };
+        let digest = keccak256(&signed_msg.encode_utf8_bytes());
+        let (signature, recovery_code) = signing_key.sign_digest_recoverable(digest).unwrap();
+        let mut signature = signature.to_vec();
+        signature.push(recovery_code.to_byte());
+        ExecuteMsg::RecvMessage {
+            src_network: nid.clone(),
+            conn_sn,
+            msg: hex::encode(data),
+            signatures: vec![signature],
+        }
+    };
+    let send = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 0,
+        msg: vec![],
+    };
+
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), recv(1)).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[Coin::new(100, DENOM)]),
+        send.clone(),
+    )
+    .unwrap();
+
+    // the backup relayer takes over without an admin transaction, fees accrued so far stay
+    // with the first relayer
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(backup.as_str(), &[]),
+        recv(2),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(XCALL, &[Coin::new(40, DENOM)]),
+        send,
+    )
+    .unwrap();
+
+    let storage = deps.as_ref().storage;
+    assert_eq!(ctx.get_fee_relayer(storage, nid), Some(backup.clone()));
+    assert_eq!(
+        ctx.get_claimable_fees(storage, &Addr::unchecked(RELAYER))
+            .unwrap(),
+        100
+    );
+    assert_eq!(ctx.get_claimable_fees(storage, &backup).unwrap(), 40);
+}
+
+#[test]
+fn test_migrate_credits_untracked_fees_to_relayer() {
+    let (mut deps, env, mut ctx) = instantiate(ADMIN);
+    ctx.remove_relayer_entry(deps.as_mut().storage, &Addr::unchecked(RELAYER))
+        .unwrap();
+    cw_storage_plus::Item::<Addr>::new("relayer")
+        .save(deps.as_mut().storage, &Addr::unchecked("legacy"))
+        .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![Coin::new(500, DENOM)]);
+
+    ctx.migrate(
+        deps.as_mut(),
+        env,
+        MigrateMsg {
+            legacy_conn_sns: vec![],
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        ctx.get_claimable_fees(deps.as_ref().storage, &Addr::unchecked("legacy"))
+            .unwrap(),
+        500
+    );
 }
 
 #[test]
@@ -348,6 +561,255 @@ pub fn test_claim_fees() {
     assert!(res.is_ok());
 }
 
+#[test]
+pub fn test_claim_network_fees() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+
+    let nid = NetId::from_str("0x2.icon").unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::SetFee {
+            network_id: nid.clone(),
+            message_fee: 200,
+            response_fee: 100,
+        },
+    );
+    assert!(res.is_ok());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[Coin::new(300, DENOM)]),
+        ExecuteMsg::SendMessage {
+            to: nid.clone(),
+            sn: 1,
+            msg: vec![],
+        },
+    );
+    assert!(res.is_ok());
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid.clone());
+    assert_eq!(fees.message_fees, 200);
+    assert_eq!(fees.response_fees, 100);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ClaimNetworkFees { nid: nid.clone() },
+    );
+    assert_eq!("Only Relayer", res.unwrap_err().to_string());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::ClaimNetworkFees { nid: nid.clone() },
+    );
+    assert!(res.is_ok());
+
+    // the response fee stays reserved until a response is delivered or it is refunded
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.claimed, 200);
+    assert_eq!(fees.reserved, 100);
+    assert_eq!(fees.unclaimed(), 0);
+}
+
+#[test]
+pub fn test_refund_response_fee() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+
+    let nid = NetId::from_str("0x2.icon").unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::SetFee {
+            network_id: nid.clone(),
+            message_fee: 200,
+            response_fee: 100,
+        },
+    );
+    assert!(res.is_ok());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[Coin::new(300, DENOM)]),
+        ExecuteMsg::SendMessage {
+            to: nid.clone(),
+            sn: 1,
+            msg: vec![],
+        },
+    );
+    assert!(res.is_ok());
+
+    let refund_msg = ExecuteMsg::RefundResponseFee {
+        nid: nid.clone(),
+        sn: 1,
+        to: "dapp".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        refund_msg.clone(),
+    );
+    assert_eq!("Only XCall", res.unwrap_err().to_string());
+
+    let res = execute(deps.as_mut(), env, mock_info(XCALL, &[]), refund_msg);
+    assert_eq!(res.unwrap().attributes[2].value, "100");
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.message_fees, 200);
+    assert_eq!(fees.response_fees, 0);
+}
+
+#[test]
+pub fn test_refund_response_fee_after_claim() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+
+    let nid = NetId::from_str("0x2.icon").unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::SetFee {
+            network_id: nid.clone(),
+            message_fee: 200,
+            response_fee: 100,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XCALL, &[Coin::new(300, DENOM)]),
+        ExecuteMsg::SendMessage {
+            to: nid.clone(),
+            sn: 1,
+            msg: vec![],
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::ClaimFees {},
+    );
+    assert_eq!(res.unwrap().attributes[1].value, "200");
+
+    let refund_msg = ExecuteMsg::RefundResponseFee {
+        nid: nid.clone(),
+        sn: 1,
+        to: "dapp".to_string(),
+    };
+    let res = execute(deps.as_mut(), env, mock_info(XCALL, &[]), refund_msg);
+    assert_eq!(res.unwrap().attributes[2].value, "100");
+
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.reserved, 0);
+    assert_eq!(fees.unclaimed(), 0);
+}
+
+#[test]
+pub fn test_set_fee_oracle() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+
+    let msg = ExecuteMsg::SetFeeOracle {
+        address: Some("oracle".to_string()),
+        floor: 100,
+        cap: 1000,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+
+    let res = execute(deps.as_mut(), env, mock_info(ADMIN, &[]), msg);
+    assert!(res.is_ok());
+
+    let oracle = ctx.get_fee_oracle(deps.as_ref().storage).unwrap();
+    assert_eq!(oracle.address, Addr::unchecked("oracle"));
+    assert_eq!(oracle.floor, 100);
+    assert_eq!(oracle.cap, 1000);
+}
+
+#[test]
+pub fn test_fee_oracle_quote_sets_reserved_response_fee() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let nid = NetId::from_str("nid").unwrap();
+
+    let msg = ExecuteMsg::SetFee {
+        network_id: nid.clone(),
+        message_fee: 200,
+        response_fee: 100,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg).unwrap();
+    let msg = ExecuteMsg::SetFeeOracle {
+        address: Some("oracle".to_string()),
+        floor: 100,
+        cap: 1000,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    deps.querier.update_wasm(|r| match r {
+        WasmQuery::Smart {
+            contract_addr: _,
+            msg,
+        } => {
+            let fee: u128 = match from_json(msg).unwrap() {
+                XcallConnectionQueryMsg::GetFee {
+                    response: false, ..
+                } => 400,
+                _ => 700,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&fee).unwrap()))
+        }
+        _ => todo!(),
+    });
+
+    let msg = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 1,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(700, DENOM)]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert!(!res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "fee_oracle_error"));
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid.clone());
+    assert_eq!(fees.message_fees, 400);
+    assert_eq!(fees.reserved, 300);
+
+    // the stored fees are charged when the oracle fails, and the failure is reported
+    deps.querier
+        .update_wasm(|_| SystemResult::Ok(ContractResult::Err("oracle unavailable".to_string())));
+    let msg = ExecuteMsg::SendMessage {
+        to: nid.clone(),
+        sn: 2,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(300, DENOM)]);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "fee_oracle_error"));
+    let fees = ctx.get_network_fees(deps.as_ref().storage, nid);
+    assert_eq!(fees.message_fees, 600);
+    assert_eq!(fees.reserved, 400);
+}
+
 #[test]
 pub fn test_send_message() {
     let (mut deps, env, _ctx) = instantiate(ADMIN);
@@ -372,6 +834,47 @@ pub fn test_send_message() {
     assert_eq!(res.unwrap().events[0], event);
 }
 
+#[test]
+pub fn test_send_message_logged() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let msg = ExecuteMsg::SetMessageLogCapacity { capacity: 2 };
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    for sn in 1..=3 {
+        let msg = ExecuteMsg::SendMessage {
+            to: NetId::from_str("nid").unwrap(),
+            sn,
+            msg: vec![1, 2, 3],
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(XCALL, &[]), msg).unwrap();
+    }
+
+    let nid = NetId::from_str("nid").unwrap();
+    assert_eq!(ctx.get_message(deps.as_ref().storage, nid.clone(), 1), None);
+    assert_eq!(
+        ctx.get_logged_messages(deps.as_ref().storage, nid, 0, None)
+            .unwrap(),
+        vec![
+            OutboundMessage {
+                conn_sn: 2,
+                to: NetId::from_str("nid").unwrap(),
+                sn: 2,
+                msg_hash: "f1885eda54b7a053318cd41e2093220dab15d65381b1157a3633a83bfd5c9239"
+                    .to_string(),
+                height: env.block.height,
+            },
+            OutboundMessage {
+                conn_sn: 3,
+                to: NetId::from_str("nid").unwrap(),
+                sn: 3,
+                msg_hash: "f1885eda54b7a053318cd41e2093220dab15d65381b1157a3633a83bfd5c9239"
+                    .to_string(),
+                height: env.block.height,
+            },
+        ]
+    );
+}
+
 #[test]
 pub fn test_send_message_unauthorized() {
     let (mut deps, env, _ctx) = instantiate(ADMIN);
@@ -486,6 +989,270 @@ pub fn test_recv_message() {
     assert!(receipt);
 }
 
+#[test]
+pub fn test_nack_message() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let nack = |reason_code: u8| ExecuteMsg::NackMessage {
+        sn: 1,
+        reason_code,
+        evidence: "0xabc".to_string(),
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), nack(2));
+    assert_eq!("Only Relayer", res.unwrap_err().to_string());
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), nack(4));
+    assert_eq!("Invalid Nack Reason 4", res.unwrap_err().to_string());
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), nack(3)).unwrap();
+    let expected = cw_xcall_lib::xcall_msg::ExecuteMsg::HandleNack {
+        sn: 1,
+        reason: NackReason::NetworkHalted,
+    };
+    assert_eq!(
+        res.messages[0].msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: XCALL.to_string(),
+            msg: to_json_binary(&expected).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.events[0],
+        Event::new("NackMessage")
+            .add_attribute("sn", "1")
+            .add_attribute("reason", "network_halted")
+            .add_attribute("evidence", "0xabc")
+    );
+    assert_eq!(
+        ctx.get_nack(deps.as_ref().storage, 1),
+        Some(NackRecord {
+            sn: 1,
+            reason: NackReason::NetworkHalted,
+            evidence: "0xabc".to_string(),
+        })
+    );
+
+    let res = execute(deps.as_mut(), env, mock_info(RELAYER, &[]), nack(1));
+    assert_eq!("Message Already Nacked", res.unwrap_err().to_string());
+}
+
+#[test]
+pub fn test_recv_message_ordered_delivery() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let src_network = NetId::from_str("0x2.icon").unwrap();
+
+    let msg = ExecuteMsg::SetOrderedDelivery {
+        src_network: src_network.clone(),
+        ordered: true,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::RecvMessage {
+        src_network: src_network.clone(),
+        conn_sn: 2,
+        msg: string_to_hex("hello"),
+        signatures: vec![],
+    };
+    let res = execute(deps.as_mut(), env, mock_info(RELAYER, &[]), msg);
+    assert_eq!(
+        "Out Of Order Message, Expected Conn Sn 1",
+        res.unwrap_err().to_string()
+    );
+
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(
+        status,
+        DeliveryStatus {
+            ordered: true,
+            contiguous_conn_sn: 0,
+            highest_conn_sn: 0,
+        }
+    );
+    assert!(ctx
+        .get_missing_conn_sns(deps.as_ref().storage, src_network, None)
+        .is_empty());
+}
+
+#[test]
+pub fn test_conn_sn_per_destination() {
+    let (mut deps, env, mut ctx) = instantiate(ADMIN);
+    // conn_sn shared by all destinations before each got its own sequence
+    ctx.store_conn_sn(deps.as_mut().storage, 5).unwrap();
+
+    let mut send = |to: &str| {
+        let msg = ExecuteMsg::SendMessage {
+            to: NetId::from_str(to).unwrap(),
+            sn: 0,
+            msg: vec![],
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(XCALL, &[]), msg).unwrap();
+        res.events[0]
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "connSn")
+            .unwrap()
+            .value
+            .clone()
+    };
+    assert_eq!(send("nid"), "6");
+    assert_eq!(send("other"), "6");
+    assert_eq!(send("nid"), "7");
+
+    let storage = deps.as_ref().storage;
+    let nid = NetId::from_str("nid").unwrap();
+    assert_eq!(ctx.get_network_conn_sn(storage, nid), 7);
+    assert_eq!(
+        ctx.get_network_conn_sn(storage, NetId::from_str("other").unwrap()),
+        6
+    );
+    assert_eq!(
+        ctx.get_network_conn_sn(storage, NetId::from_str("unused").unwrap()),
+        5
+    );
+}
+
+#[test]
+pub fn test_migrate_closes_legacy_gaps() {
+    let (mut deps, env, mut ctx) = instantiate(ADMIN);
+    let src_network = NetId::from_str("0x2.icon").unwrap();
+
+    // conn_sn 2 was sent to another destination by the shared sequence
+    for conn_sn in [1, 3] {
+        ctx.store_receipt(deps.as_mut().storage, src_network.clone(), conn_sn)
+            .unwrap();
+    }
+    ctx.store_highest_conn_sn(deps.as_mut().storage, src_network.clone(), 3)
+        .unwrap();
+    let msg = ExecuteMsg::SetOrderedDelivery {
+        src_network: src_network.clone(),
+        ordered: true,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg).unwrap();
+    assert_eq!(
+        ctx.get_missing_conn_sns(deps.as_ref().storage, src_network.clone(), None),
+        vec![2]
+    );
+
+    let msg = MigrateMsg {
+        legacy_conn_sns: vec![(src_network.clone(), 3)],
+    };
+    ctx.migrate(deps.as_mut(), env, msg).unwrap();
+
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(status.contiguous_conn_sn, 3);
+    assert_eq!(status.highest_conn_sn, 3);
+    assert!(ctx
+        .get_missing_conn_sns(deps.as_ref().storage, src_network.clone(), None)
+        .is_empty());
+    assert!(ctx.get_receipt(deps.as_ref().storage, src_network, 2));
+}
+
+#[test]
+pub fn test_contiguous_conn_sn_advance_is_bounded() {
+    let (mut deps, env, mut ctx) = instantiate(ADMIN);
+    let src_network = NetId::from_str("0x2.icon").unwrap();
+    let ordered = ExecuteMsg::SetOrderedDelivery {
+        src_network: src_network.clone(),
+        ordered: true,
+    };
+
+    for conn_sn in 1..=150 {
+        ctx.store_receipt(deps.as_mut().storage, src_network.clone(), conn_sn)
+            .unwrap();
+    }
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ordered.clone(),
+    )
+    .unwrap();
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network.clone());
+    assert_eq!(status.contiguous_conn_sn, 100);
+
+    // calling it again resumes the walk
+    execute(deps.as_mut(), env, mock_info(ADMIN, &[]), ordered).unwrap();
+    let status = ctx.get_delivery_status(deps.as_ref().storage, src_network);
+    assert_eq!(status.contiguous_conn_sn, 150);
+}
+
+#[test]
+fn test_compact_receipts() {
+    let (mut deps, env, mut ctx) = instantiate(ADMIN);
+    let nid = NetId::from_str("0x2.icon").unwrap();
+    let compact = |limit: Option<u32>| ExecuteMsg::CompactReceipts {
+        nid: nid.clone(),
+        limit,
+    };
+    // receipts stored before they were pruned
+    for conn_sn in [1, 2, 3, 5] {
+        ctx.store_receipt(deps.as_mut().storage, nid.clone(), conn_sn)
+            .unwrap();
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RELAYER, &[]),
+        compact(None),
+    );
+    assert_eq!("Only Admin", res.unwrap_err().to_string());
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        compact(None),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ctx.get_receipt_keys(deps.as_ref().storage).unwrap(),
+        vec![("0x2.icon".to_string(), 5)]
+    );
+    let status = ctx.get_delivery_status(deps.as_ref().storage, nid.clone());
+    assert_eq!(status.contiguous_conn_sn, 3);
+    assert_eq!(status.highest_conn_sn, 5);
+    for conn_sn in [1, 2, 3, 5] {
+        assert!(ctx.get_receipt(deps.as_ref().storage, nid.clone(), conn_sn));
+    }
+    assert!(!ctx.get_receipt(deps.as_ref().storage, nid.clone(), 4));
+
+    // the legacy conn_sn set by a migration leaves the receipts below it behind
+    for conn_sn in [7, 8] {
+        ctx.store_receipt(deps.as_mut().storage, nid.clone(), conn_sn)
+            .unwrap();
+    }
+    let msg = MigrateMsg {
+        legacy_conn_sns: vec![(nid.clone(), 8)],
+    };
+    ctx.migrate(deps.as_mut(), env.clone(), msg).unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        compact(Some(2)),
+    )
+    .unwrap();
+    assert_eq!(
+        ctx.get_receipt_keys(deps.as_ref().storage).unwrap(),
+        vec![("0x2.icon".to_string(), 8)]
+    );
+    execute(deps.as_mut(), env, mock_info(ADMIN, &[]), compact(Some(2))).unwrap();
+    assert!(ctx
+        .get_receipt_keys(deps.as_ref().storage)
+        .unwrap()
+        .is_empty());
+    assert!(ctx.get_receipt(deps.as_ref().storage, nid, 8));
+}
+
 #[test]
 pub fn test_recv_message_signatures_insufficient() {
     let (mut deps, env, ctx) = instantiate(ADMIN);
@@ -567,3 +1334,27 @@ fn string_to_hex(input: &str) -> String {
         .map(|b| format!("{:02x}", b))
         .collect()
 }
+
+#[test]
+pub fn test_network_status_tracks_sent_messages() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    for sn in [1, -2] {
+        let msg = ExecuteMsg::SendMessage {
+            to: NetId::from_str("nid").unwrap(),
+            sn,
+            msg: vec![1, 2, 3],
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(XCALL, &[]), msg).unwrap();
+    }
+
+    assert_eq!(
+        ctx.get_network_status(deps.as_ref().storage, NetId::from_str("nid").unwrap()),
+        NetworkStatus {
+            last_received_conn_sn: 0,
+            last_received_height: 0,
+            last_sent_conn_sn: 2,
+            last_sent_height: env.block.height,
+            pending_reverts: 1,
+        }
+    );
+}
@@ -444,6 +444,25 @@ fn test_call_message_re_execute() {
     let _ = call_execute_call_message(&mut ctx, 1, data);
 }
 
+#[test]
+fn test_call_message_with_response_returns_dapp_data() {
+    let mut ctx = setup_test();
+
+    let data = "test".as_bytes().to_vec();
+    let resp = test_call_message(&mut ctx, data.clone(), MessageType::CallMessageWithResponse);
+    assert!(resp.is_ok());
+
+    // the dapp echoes the data, returned to the sender as set by the dapp
+    let message: CSMessage = CSMessageResult::new(
+        1,
+        CallServiceResponseType::CallServiceResponseSuccess,
+        Some(data),
+    )
+    .into();
+    let event = get_event(&resp.unwrap(), "wasm-write_acknowledgement").unwrap();
+    assert_eq!(&hex::encode(message.as_bytes()), event.get("data").unwrap());
+}
+
 #[test]
 fn test_persistent_call_message_success() {
     let mut ctx = setup_test();
@@ -2,6 +2,7 @@ use std::str::from_utf8;
 
 use cosmwasm_std::SubMsg;
 use cw_xcall_lib::message::call_message_persisted::CallMessagePersisted;
+use cw_xcall_lib::message::call_message_response::CallMessageWithResponse;
 use cw_xcall_lib::message::msg_type::MessageType;
 use cw_xcall_lib::message::AnyMessage;
 use cw_xcall_lib::message::{
@@ -113,6 +114,12 @@ impl<'a> CwMockService<'a> {
             } else {
                 return Err(ContractError::InvalidRollbackMessage);
             }
+        } else if message_type == MessageType::CallMessageWithResponse as u64 {
+            if let Some(rollback) = rollback {
+                AnyMessage::CallMessageWithResponse(CallMessageWithResponse { data, rollback })
+            } else {
+                return Err(ContractError::InvalidRollbackMessage);
+            }
         } else if message_type == MessageType::CallMessage as u64 {
             AnyMessage::CallMessage(CallMessage { data })
         } else {
@@ -191,9 +198,24 @@ impl<'a> CwMockService<'a> {
                     .unwrap();
                 res = res.add_submessage(submsg)
             }
+            // the data is echoed back to the sender of a `CallMessageWithResponse`
             Ok(res
+                .set_data(data.clone())
                 .add_attribute("from", from.to_string())
                 .add_attribute("data", msg_data))
         }
     }
+
+    pub fn handle_call_response(
+        &self,
+        from: NetworkAddress,
+        sn: u128,
+        data: Vec<u8>,
+    ) -> Result<Response, ContractError> {
+        Ok(Response::new()
+            .add_attribute("action", "CallResponseReceived")
+            .add_attribute("from", from.to_string())
+            .add_attribute("sn", sn.to_string())
+            .add_attribute("data", format!("{:?}", data)))
+    }
 }
@@ -60,6 +60,9 @@ pub fn execute(
             data,
             protocols,
         } => call_service.handle_call_message(deps, info, from, data, protocols),
+        ExecuteMsg::HandleCallResponse { from, sn, data } => {
+            call_service.handle_call_response(from, sn, data)
+        }
         ExecuteMsg::AddConnection {
             src_endpoint,
             dest_endpoint,
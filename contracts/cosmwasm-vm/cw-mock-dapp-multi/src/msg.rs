@@ -23,6 +23,11 @@ pub enum ExecuteMsg {
         data: Vec<u8>,
         protocols: Vec<String>,
     },
+    HandleCallResponse {
+        from: NetworkAddress,
+        sn: u128,
+        data: Vec<u8>,
+    },
     AddConnection {
         src_endpoint: String,
         dest_endpoint: String,
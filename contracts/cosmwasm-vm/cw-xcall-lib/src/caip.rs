@@ -0,0 +1,326 @@
+use thiserror::Error;
+
+use crate::network_address::{NetworkAddress, NetworkAddressError};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CaipError {
+    #[error("InvalidCaip10")]
+    InvalidCaip10,
+    #[error("UnknownChain")]
+    UnknownChain,
+    #[error("UnknownNetwork")]
+    UnknownNetwork,
+    #[error("InvalidAccount")]
+    InvalidAccount,
+    #[error("InvalidNetworkAddress {0}")]
+    InvalidNetworkAddress(#[from] NetworkAddressError),
+}
+
+/// How the accounts of a network are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFamily {
+    /// `0x` followed by 20 hex encoded bytes.
+    Hex,
+    /// `hx` for wallets or `cx` for contracts followed by 20 hex encoded bytes.
+    Icon,
+    /// Bech32 with the human readable part of the network.
+    Bech32(&'static str),
+    /// A base58 encoded 32 byte public key.
+    Base58,
+    /// A Stellar account or contract strkey.
+    Strkey,
+}
+
+/// A network known by its xcall network id and its CAIP-2 chain id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Network {
+    pub nid: &'static str,
+    pub caip2: &'static str,
+    pub family: AddressFamily,
+}
+
+pub const NETWORKS: &[Network] = &[
+    Network {
+        nid: "0x1.icon",
+        caip2: "icon:0x1",
+        family: AddressFamily::Icon,
+    },
+    Network {
+        nid: "0x2.icon",
+        caip2: "icon:0x2",
+        family: AddressFamily::Icon,
+    },
+    Network {
+        nid: "0x1.eth",
+        caip2: "eip155:1",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0xa4b1.arbitrum",
+        caip2: "eip155:42161",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0xa.optimism",
+        caip2: "eip155:10",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0x2105.base",
+        caip2: "eip155:8453",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0x38.bsc",
+        caip2: "eip155:56",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0xa86a.avax",
+        caip2: "eip155:43114",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "archway-1",
+        caip2: "cosmos:archway-1",
+        family: AddressFamily::Bech32("archway"),
+    },
+    Network {
+        nid: "injective-1",
+        caip2: "cosmos:injective-1",
+        family: AddressFamily::Bech32("inj"),
+    },
+    Network {
+        nid: "solana",
+        caip2: "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp",
+        family: AddressFamily::Base58,
+    },
+    Network {
+        nid: "stellar",
+        caip2: "stellar:pubnet",
+        family: AddressFamily::Strkey,
+    },
+];
+
+impl Network {
+    pub fn by_nid(nid: &str) -> Result<&'static Network, CaipError> {
+        NETWORKS
+            .iter()
+            .find(|network| network.nid == nid)
+            .ok_or(CaipError::UnknownNetwork)
+    }
+
+    pub fn by_caip2(caip2: &str) -> Result<&'static Network, CaipError> {
+        NETWORKS
+            .iter()
+            .find(|network| network.caip2 == caip2)
+            .ok_or(CaipError::UnknownChain)
+    }
+}
+
+/// Converts a network address to its CAIP-10 account id, the account being normalized.
+pub fn to_caip10(address: &NetworkAddress) -> Result<String, CaipError> {
+    address.validate()?;
+    let network = Network::by_nid(address.nid().as_str())?;
+    let account = network.family.normalize(address.account().as_str())?;
+    Ok(format!("{}:{}", network.caip2, account))
+}
+
+/// Converts a CAIP-10 account id of a known chain to a network address, the account being
+/// normalized.
+pub fn from_caip10(value: &str) -> Result<NetworkAddress, CaipError> {
+    let mut parts = value.split(':');
+    let (namespace, reference, account) =
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(namespace), Some(reference), Some(account), None)
+                if !namespace.is_empty() && !reference.is_empty() && !account.is_empty() =>
+            {
+                (namespace, reference, account)
+            }
+            _ => return Err(CaipError::InvalidCaip10),
+        };
+    let network = Network::by_caip2(&format!("{namespace}:{reference}"))?;
+    let account = network.family.normalize(account)?;
+    Ok(NetworkAddress::try_new(network.nid, &account)?)
+}
+
+impl AddressFamily {
+    /// Returns the canonical form of `account`, failing if it is not an account of the family.
+    pub fn normalize(&self, account: &str) -> Result<String, CaipError> {
+        match self {
+            AddressFamily::Hex => hex_account(account, &["0x"]),
+            AddressFamily::Icon => hex_account(account, &["hx", "cx"]),
+            AddressFamily::Bech32(hrp) => bech32_account(account, hrp),
+            AddressFamily::Base58 => base58_account(account),
+            AddressFamily::Strkey => strkey_account(account),
+        }
+        .ok_or(CaipError::InvalidAccount)
+    }
+}
+
+fn hex_account(account: &str, prefixes: &[&str]) -> Option<String> {
+    let account = account.to_ascii_lowercase();
+    let digits = prefixes
+        .iter()
+        .find_map(|prefix| account.strip_prefix(prefix))?;
+    (digits.len() == 40 && digits.bytes().all(|c| c.is_ascii_hexdigit())).then_some(account)
+}
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn bech32_polymod_step(checksum: u32, value: u8) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let top = checksum >> 25;
+    let mut checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+    for (i, generator) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            checksum ^= generator;
+        }
+    }
+    checksum
+}
+
+fn bech32_account(account: &str, hrp: &str) -> Option<String> {
+    // bech32 is either lowercase or uppercase
+    if account.bytes().any(|c| c.is_ascii_lowercase())
+        && account.bytes().any(|c| c.is_ascii_uppercase())
+    {
+        return None;
+    }
+    let account = account.to_ascii_lowercase();
+    let (prefix, data) = account.rsplit_once('1')?;
+    if prefix != hrp || data.len() < 6 {
+        return None;
+    }
+
+    let mut checksum = 1;
+    for c in prefix.bytes() {
+        checksum = bech32_polymod_step(checksum, c >> 5);
+    }
+    checksum = bech32_polymod_step(checksum, 0);
+    for c in prefix.bytes() {
+        checksum = bech32_polymod_step(checksum, c & 31);
+    }
+    for c in data.bytes() {
+        let value = BECH32_CHARSET.iter().position(|&v| v == c)?;
+        checksum = bech32_polymod_step(checksum, value as u8);
+    }
+    (checksum == 1).then_some(account)
+}
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base58_account(account: &str) -> Option<String> {
+    let mut key = [0_u8; 32];
+    for c in account.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&v| v == c)? as u32;
+        for byte in key.iter_mut().rev() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        if carry > 0 {
+            return None;
+        }
+    }
+    // every leading zero byte of the key is encoded as a leading `1`
+    let zeros = key.iter().take_while(|&&byte| byte == 0).count();
+    let ones = account.bytes().take_while(|&c| c == b'1').count();
+    (!account.is_empty() && zeros == ones).then(|| account.to_owned())
+}
+
+const STRKEY_ACCOUNT: u8 = 6 << 3;
+const STRKEY_CONTRACT: u8 = 2 << 3;
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc = 0_u16;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn strkey_account(account: &str) -> Option<String> {
+    let account = account.to_ascii_uppercase();
+    if account.len() != 56 {
+        return None;
+    }
+
+    // a version byte, the 32 byte key and a crc16 checksum, base32 encoded
+    let mut data = [0_u8; 35];
+    let (mut buffer, mut bits, mut index) = (0_u32, 0_u32, 0);
+    for c in account.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            data[index] = (buffer >> bits) as u8;
+            buffer &= (1 << bits) - 1;
+            index += 1;
+        }
+    }
+
+    let checksum = u16::from_le_bytes([data[33], data[34]]);
+    let valid = (data[0] == STRKEY_ACCOUNT || data[0] == STRKEY_CONTRACT)
+        && crc16_xmodem(&data[..33]) == checksum;
+    valid.then_some(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const VECTORS: &str = include_str!("../../../../test-vectors/caip/addresses.txt");
+
+    #[test]
+    fn test_caip_vectors() {
+        for line in VECTORS.lines() {
+            let line = line.split('#').next().unwrap();
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            match fields[..] {
+                ["valid", caip10, address] => {
+                    let address = NetworkAddress::from_str(address).unwrap();
+                    assert_eq!(from_caip10(caip10), Ok(address.clone()), "{caip10}");
+                    assert_eq!(to_caip10(&address), Ok(caip10.to_string()), "{caip10}");
+                }
+                ["normalized", caip10, address] => {
+                    let address = NetworkAddress::from_str(address).unwrap();
+                    assert_eq!(from_caip10(caip10), Ok(address), "{caip10}");
+                }
+                ["invalid", caip10, error] => {
+                    let converted = from_caip10(caip10).map_err(|error| error.to_string());
+                    assert_eq!(converted, Err(error.to_string()), "{caip10}");
+                }
+                ["unmapped", address, error] => {
+                    let address = NetworkAddress::from_str(address).unwrap();
+                    let converted = to_caip10(&address).map_err(|error| error.to_string());
+                    assert_eq!(converted, Err(error.to_string()), "{address:?}");
+                }
+                [] => {}
+                _ => panic!("unknown vector {line}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_networks_are_unique() {
+        for (i, network) in NETWORKS.iter().enumerate() {
+            assert_eq!(Network::by_nid(network.nid), Ok(&NETWORKS[i]));
+            assert_eq!(Network::by_caip2(network.caip2), Ok(&NETWORKS[i]));
+        }
+    }
+}
@@ -4,5 +4,14 @@ use crate::network_address::NetworkAddress;
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    HandleCallMessage { from: NetworkAddress, data: Vec<u8> },
+    HandleCallMessage {
+        from: NetworkAddress,
+        data: Vec<u8>,
+    },
+    /// Delivers the data returned by `from` for the `CallMessageWithResponse` of `sn`.
+    HandleCallResponse {
+        from: NetworkAddress,
+        sn: u128,
+        data: Vec<u8>,
+    },
 }
@@ -9,4 +9,10 @@ pub enum ExecuteMsg {
         data: Vec<u8>,
         protocols: Vec<String>,
     },
+    /// Delivers the data returned by `from` for the `CallMessageWithResponse` of `sn`.
+    HandleCallResponse {
+        from: NetworkAddress,
+        sn: u128,
+        data: Vec<u8>,
+    },
 }
@@ -1,6 +1,8 @@
+pub mod caip;
 pub mod dapp_msg;
 pub mod dapp_multi_msg;
 pub mod message;
 pub mod network_address;
+pub mod strict_rlp;
 pub mod xcall_connection_msg;
 pub mod xcall_msg;
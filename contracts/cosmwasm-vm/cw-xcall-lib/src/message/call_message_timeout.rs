@@ -0,0 +1,77 @@
+use common::rlp::{self, Decodable, DecoderError, Encodable, RlpStream};
+use cosmwasm_schema::cw_serde;
+
+use super::msg_trait::IMessage;
+use crate::strict_rlp;
+
+/// A call with a rollback that the destination refuses to execute from `timeout_height` or
+/// `timeout_timestamp` (seconds since the unix epoch) on, answering it with a failure instead. A
+/// zero deadline is not checked, at least one of them must be set.
+#[cw_serde]
+pub struct CallMessageWithTimeout {
+    pub data: Vec<u8>,
+    pub rollback: Vec<u8>,
+    pub timeout_height: u64,
+    pub timeout_timestamp: u64,
+}
+
+impl Encodable for CallMessageWithTimeout {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream
+            .begin_list(4)
+            .append(&self.data)
+            .append(&self.rollback)
+            .append(&self.timeout_height)
+            .append(&self.timeout_timestamp);
+    }
+}
+
+impl Decodable for CallMessageWithTimeout {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        Ok(Self {
+            data: rlp.val_at(0)?,
+            rollback: rlp.val_at(1)?,
+            timeout_height: strict_rlp::decode_u64(&rlp.at(2)?)?,
+            timeout_timestamp: strict_rlp::decode_u64(&rlp.at(3)?)?,
+        })
+    }
+}
+
+impl IMessage for CallMessageWithTimeout {
+    fn rollback(&self) -> Option<Vec<u8>> {
+        Some(self.rollback.clone())
+    }
+
+    fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, DecoderError> {
+        Ok(rlp::encode(self).to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::rlp::Rlp;
+
+    use super::*;
+
+    #[test]
+    fn test_call_message_with_timeout() {
+        let msg = CallMessageWithTimeout {
+            data: vec![1, 2, 3],
+            rollback: vec![1, 2, 3],
+            timeout_height: 1000,
+            timeout_timestamp: 0,
+        };
+
+        let encoded = msg.rlp_bytes().to_vec();
+        let decoded = CallMessageWithTimeout::decode(&Rlp::new(&encoded)).unwrap();
+
+        assert_eq!(msg, decoded);
+        assert_eq!(msg.rollback().unwrap(), msg.rollback);
+        assert_eq!(msg.data(), msg.data);
+        assert_eq!(msg.to_bytes().unwrap(), encoded)
+    }
+}
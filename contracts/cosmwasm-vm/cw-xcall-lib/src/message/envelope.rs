@@ -1,16 +1,24 @@
 use common::rlp::{self, Decodable, DecoderError, Encodable};
 use cosmwasm_schema::cw_serde;
 
+use crate::strict_rlp;
+
 use super::{
     call_message::CallMessage, call_message_persisted::CallMessagePersisted,
-    call_message_rollback::CallMessageWithRollback, msg_trait::IMessage, msg_type::MessageType,
+    call_message_response::CallMessageWithResponse, call_message_rollback::CallMessageWithRollback,
+    call_message_timeout::CallMessageWithTimeout, msg_trait::IMessage, msg_type::MessageType,
     AnyMessage,
 };
+
+/// `gas_limit` bounds the gas the destination dapp may use to execute the message, a zero limit
+/// leaves it unbounded and is not encoded.
 #[cw_serde]
 pub struct Envelope {
     pub message: AnyMessage,
     pub sources: Vec<String>,
     pub destinations: Vec<String>,
+    #[serde(default)]
+    pub gas_limit: u64,
 }
 
 impl Envelope {
@@ -19,13 +27,14 @@ impl Envelope {
             message: msg,
             sources,
             destinations,
+            gas_limit: 0,
         }
     }
 }
 
 impl Encodable for Envelope {
     fn rlp_append(&self, stream: &mut common::rlp::RlpStream) {
-        stream.begin_list(4);
+        stream.begin_list(if self.gas_limit > 0 { 5 } else { 4 });
         stream.append(&Into::<u8>::into(self.message.msg_type().clone()));
         stream.append(&self.message.to_bytes().unwrap());
         stream.begin_list(self.sources.len());
@@ -36,13 +45,16 @@ impl Encodable for Envelope {
         for dest in self.destinations.iter() {
             stream.append(dest);
         }
+        if self.gas_limit > 0 {
+            stream.append(&self.gas_limit);
+        }
     }
 }
 
 impl Decodable for Envelope {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         let msg_int: u8 = rlp.val_at(0)?;
-        let msg_type = MessageType::from(msg_int);
+        let msg_type = MessageType::try_from(msg_int)?;
         let message_bytes: Vec<u8> = rlp.val_at(1)?;
         let message = decode_message(msg_type, message_bytes)?;
 
@@ -50,11 +62,17 @@ impl Decodable for Envelope {
         let sources: Vec<String> = sources.as_list()?;
         let destinations = rlp.at(3)?;
         let destinations: Vec<String> = destinations.as_list()?;
+        let gas_limit = match rlp.item_count()? {
+            4 => 0,
+            5 => strict_rlp::decode_u64(&rlp.at(4)?)?,
+            _ => return Err(DecoderError::RlpIncorrectListLen),
+        };
 
         Ok(Envelope {
             message,
             sources,
             destinations,
+            gas_limit,
         })
     }
 }
@@ -73,6 +91,14 @@ pub fn decode_message(msg_type: MessageType, bytes: Vec<u8>) -> Result<AnyMessag
             let msg: CallMessagePersisted = rlp::decode(&bytes)?;
             Ok(AnyMessage::CallMessagePersisted(msg))
         }
+        MessageType::CallMessageWithTimeout => {
+            let msg: CallMessageWithTimeout = rlp::decode(&bytes)?;
+            Ok(AnyMessage::CallMessageWithTimeout(msg))
+        }
+        MessageType::CallMessageWithResponse => {
+            let msg: CallMessageWithResponse = rlp::decode(&bytes)?;
+            Ok(AnyMessage::CallMessageWithResponse(msg))
+        }
     }
 }
 
@@ -80,6 +106,55 @@ pub fn decode_message(msg_type: MessageType, bytes: Vec<u8>) -> Result<AnyMessag
 mod tests {
     use super::*;
 
+    use serde_json::Value;
+
+    use crate::message::msg_type::UNSUPPORTED_MESSAGE_TYPE;
+
+    const VECTORS: &str = include_str!("../../../../../test-vectors/xcall/messages.json");
+
+    fn bytes(value: &Value) -> Vec<u8> {
+        hex::decode(value.as_str().unwrap()).unwrap()
+    }
+
+    fn strings(value: &Value) -> Vec<String> {
+        let values = value.as_array().unwrap();
+        values
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    fn any_message(msg_type: &str, value: &Value) -> AnyMessage {
+        let data = bytes(&value["data"]);
+        match msg_type {
+            "CallMessage" => AnyMessage::CallMessage(CallMessage { data }),
+            "CallMessageWithRollback" => {
+                AnyMessage::CallMessageWithRollback(CallMessageWithRollback {
+                    data,
+                    rollback: bytes(&value["rollback"]),
+                })
+            }
+            "CallMessagePersisted" => {
+                AnyMessage::CallMessagePersisted(CallMessagePersisted { data })
+            }
+            "CallMessageWithTimeout" => {
+                AnyMessage::CallMessageWithTimeout(CallMessageWithTimeout {
+                    data,
+                    rollback: bytes(&value["rollback"]),
+                    timeout_height: value["timeout_height"].as_u64().unwrap(),
+                    timeout_timestamp: value["timeout_timestamp"].as_u64().unwrap(),
+                })
+            }
+            "CallMessageWithResponse" => {
+                AnyMessage::CallMessageWithResponse(CallMessageWithResponse {
+                    data,
+                    rollback: bytes(&value["rollback"]),
+                })
+            }
+            _ => panic!("unknown message type {}", msg_type),
+        }
+    }
+
     #[test]
     fn test_envelope_call_message() {
         let msg = AnyMessage::CallMessage(CallMessage {
@@ -141,4 +216,94 @@ mod tests {
         assert_eq!(decoded.data(), vec![1, 2, 3]);
         assert_eq!(decoded.to_bytes().unwrap(), encoded)
     }
+
+    #[test]
+    fn test_envelope_call_message_with_timeout() {
+        let msg = AnyMessage::CallMessageWithTimeout(CallMessageWithTimeout {
+            data: vec![1, 2, 3],
+            rollback: vec![1, 2, 3],
+            timeout_height: 0,
+            timeout_timestamp: 1_700_000_000,
+        });
+        let sources = vec!["src".to_string()];
+        let destinations = vec!["dst".to_string()];
+        let envelope = Envelope::new(msg.clone(), sources, destinations);
+
+        let encoded = envelope.rlp_bytes().to_vec();
+        let decoded = Envelope::decode(&rlp::Rlp::new(&encoded)).unwrap();
+        assert_eq!(envelope, decoded);
+
+        let encoded = msg.to_bytes().unwrap();
+        let decoded = decode_message(MessageType::CallMessageWithTimeout, encoded.clone()).unwrap();
+        assert_eq!(decoded.rollback(), Some(vec![1, 2, 3]));
+        assert_eq!(decoded.data(), vec![1, 2, 3]);
+        assert_eq!(decoded.to_bytes().unwrap(), encoded)
+    }
+
+    #[test]
+    fn test_envelope_with_gas_limit() {
+        let msg = AnyMessage::CallMessage(CallMessage {
+            data: vec![1, 2, 3],
+        });
+        let mut envelope = Envelope::new(msg, vec![], vec![]);
+        let without_limit = envelope.rlp_bytes().to_vec();
+        assert_eq!(rlp::Rlp::new(&without_limit).item_count(), Ok(4));
+
+        envelope.gas_limit = 200_000;
+        let encoded = envelope.rlp_bytes().to_vec();
+        assert_eq!(rlp::Rlp::new(&encoded).item_count(), Ok(5));
+        assert_eq!(rlp::decode::<Envelope>(&encoded), Ok(envelope));
+    }
+
+    #[test]
+    fn test_golden_vectors() {
+        let vectors: Vec<Value> = serde_json::from_str(VECTORS).unwrap();
+
+        for vector in vectors.iter() {
+            let name = vector["name"].as_str().unwrap();
+            let value = &vector["value"];
+            let encoded = bytes(&vector["hex"]);
+
+            match vector["type"].as_str().unwrap() {
+                "Envelope" => {
+                    let message = &value["message"];
+                    let envelope = Envelope::new(
+                        any_message(message["type"].as_str().unwrap(), message),
+                        strings(&value["sources"]),
+                        strings(&value["destinations"]),
+                    );
+                    assert_eq!(envelope.rlp_bytes().to_vec(), encoded, "{}", name);
+                    assert_eq!(rlp::decode::<Envelope>(&encoded), Ok(envelope), "{}", name);
+                }
+                "CallMessage"
+                | "CallMessageWithRollback"
+                | "CallMessagePersisted"
+                | "CallMessageWithTimeout"
+                | "CallMessageWithResponse" => {
+                    let msg = any_message(vector["type"].as_str().unwrap(), value);
+                    assert_eq!(msg.to_bytes().unwrap(), encoded, "{}", name);
+                    let decoded = decode_message(msg.msg_type().clone(), encoded.clone());
+                    assert_eq!(decoded, Ok(msg), "{}", name);
+                }
+                // wire types of the xcall contract, tested there
+                _ => continue,
+            }
+            assert!(strict_rlp::validate(&encoded).is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_envelope_unsupported_message_type() {
+        let message = CallMessage {
+            data: vec![1, 2, 3],
+        };
+        let mut stream = rlp::RlpStream::new_list(4);
+        stream.append(&7_u8);
+        stream.append(&rlp::encode(&message).to_vec());
+        stream.begin_list(0);
+        stream.begin_list(0);
+
+        let decoded = rlp::decode::<Envelope>(&stream.out());
+        assert_eq!(decoded, Err(DecoderError::Custom(UNSUPPORTED_MESSAGE_TYPE)));
+    }
 }
@@ -1,10 +1,15 @@
+use common::rlp::DecoderError;
 use serde::Serialize;
 
+pub const UNSUPPORTED_MESSAGE_TYPE: &str = "unsupported message type";
+
 #[derive(Clone, Debug, Serialize, serde::Deserialize, PartialEq, Eq)]
 pub enum MessageType {
     CallMessage = 0,
     CallMessageWithRollback = 1,
     CallMessagePersisted = 2,
+    CallMessageWithTimeout = 3,
+    CallMessageWithResponse = 4,
 }
 
 impl From<MessageType> for u8 {
@@ -13,17 +18,25 @@ impl From<MessageType> for u8 {
             MessageType::CallMessage => 0,
             MessageType::CallMessageWithRollback => 1,
             MessageType::CallMessagePersisted => 2,
+            MessageType::CallMessageWithTimeout => 3,
+            MessageType::CallMessageWithResponse => 4,
         }
     }
 }
 
-impl From<u8> for MessageType {
-    fn from(value: u8) -> Self {
+/// Fails with a decode error on the message types of newer versions, so that receivers can
+/// answer them instead of aborting.
+impl TryFrom<u8> for MessageType {
+    type Error = DecoderError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => MessageType::CallMessage,
-            1 => MessageType::CallMessageWithRollback,
-            2 => MessageType::CallMessagePersisted,
-            _ => panic!("unsupported message type"),
+            0 => Ok(MessageType::CallMessage),
+            1 => Ok(MessageType::CallMessageWithRollback),
+            2 => Ok(MessageType::CallMessagePersisted),
+            3 => Ok(MessageType::CallMessageWithTimeout),
+            4 => Ok(MessageType::CallMessageWithResponse),
+            _ => Err(DecoderError::Custom(UNSUPPORTED_MESSAGE_TYPE)),
         }
     }
 }
@@ -33,19 +46,43 @@ impl MessageType {
         self.clone().into()
     }
     pub fn from_int(val: u8) -> Self {
-        MessageType::from(val)
+        MessageType::try_from(val).expect(UNSUPPORTED_MESSAGE_TYPE)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::message::msg_type::MessageType;
+    use common::rlp::DecoderError;
+
+    use crate::message::msg_type::{MessageType, UNSUPPORTED_MESSAGE_TYPE};
 
     #[test]
     fn test_message_type_for_u8() {
-        assert_eq!(MessageType::CallMessage, 0.into());
-        assert_eq!(MessageType::CallMessagePersisted, 2.into());
-        assert_eq!(MessageType::CallMessageWithRollback, 1.into())
+        assert_eq!(MessageType::try_from(0_u8), Ok(MessageType::CallMessage));
+        assert_eq!(
+            MessageType::try_from(2_u8),
+            Ok(MessageType::CallMessagePersisted)
+        );
+        assert_eq!(
+            MessageType::try_from(1_u8),
+            Ok(MessageType::CallMessageWithRollback)
+        );
+        assert_eq!(
+            MessageType::try_from(3_u8),
+            Ok(MessageType::CallMessageWithTimeout)
+        );
+        assert_eq!(
+            MessageType::try_from(4_u8),
+            Ok(MessageType::CallMessageWithResponse)
+        )
+    }
+
+    #[test]
+    fn test_message_type_for_unknown_u8() {
+        assert_eq!(
+            MessageType::try_from(7_u8),
+            Err(DecoderError::Custom(UNSUPPORTED_MESSAGE_TYPE))
+        );
     }
 
     #[test]
@@ -61,6 +98,6 @@ mod tests {
     #[test]
     #[should_panic(expected = "unsupported message type")]
     fn test_message_type_from_int_fail() {
-        MessageType::from_int(4);
+        MessageType::from_int(7);
     }
 }
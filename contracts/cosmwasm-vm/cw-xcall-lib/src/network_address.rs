@@ -3,6 +3,26 @@ use std::str::FromStr;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, StdError};
 use cw_storage_plus::{Key, KeyDeserialize, PrimaryKey};
+use thiserror::Error;
+
+pub const MAX_NID_LENGTH: usize = 64;
+pub const MAX_ACCOUNT_LENGTH: usize = 128;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum NetworkAddressError {
+    #[error("MissingSeparator")]
+    MissingSeparator,
+    #[error("TooManySeparators")]
+    TooManySeparators,
+    #[error("EmptyNetworkId")]
+    EmptyNetworkId,
+    #[error("EmptyAccount")]
+    EmptyAccount,
+    #[error("NetworkIdTooLong")]
+    NetworkIdTooLong,
+    #[error("AccountTooLong")]
+    AccountTooLong,
+}
 
 #[cw_serde]
 #[derive(Eq)]
@@ -65,26 +85,67 @@ impl KeyDeserialize for NetId {
 pub struct NetworkAddress(String);
 
 impl NetworkAddress {
+    /// Joins parts known to be valid, such as the network id and an address of this chain; use
+    /// `try_new` for parts received from a caller.
     pub fn new(nid: &str, address: &str) -> Self {
         Self(format!("{}/{}", nid, address))
     }
+
+    pub fn try_new(nid: &str, address: &str) -> Result<Self, NetworkAddressError> {
+        Self::parse(&format!("{}/{}", nid, address))
+    }
+
+    /// Parses a network id and an account separated by a single `/`, neither being empty nor
+    /// longer than `MAX_NID_LENGTH` and `MAX_ACCOUNT_LENGTH` bytes.
+    pub fn parse(value: &str) -> Result<Self, NetworkAddressError> {
+        let (nid, account) = split(value)?;
+        if nid.is_empty() {
+            return Err(NetworkAddressError::EmptyNetworkId);
+        }
+        if account.is_empty() {
+            return Err(NetworkAddressError::EmptyAccount);
+        }
+        if nid.len() > MAX_NID_LENGTH {
+            return Err(NetworkAddressError::NetworkIdTooLong);
+        }
+        if account.len() > MAX_ACCOUNT_LENGTH {
+            return Err(NetworkAddressError::AccountTooLong);
+        }
+        Ok(Self(value.to_owned()))
+    }
+
+    /// Checks an address that was not parsed, such as one deserialized from a message.
+    pub fn validate(&self) -> Result<(), NetworkAddressError> {
+        Self::parse(&self.0).map(|_| ())
+    }
+
     pub fn nid(&self) -> NetId {
         NetId(self.get_parts()[0].to_string())
     }
 
+    /// The account is an address of the network `nid`, it is not validated against this chain.
     pub fn account(&self) -> Addr {
         Addr::unchecked(self.get_parts()[1])
     }
 
     pub fn get_parts(&self) -> Vec<&str> {
-        let parts = self.0.split('/').collect::<Vec<&str>>();
-        if parts.len() != 2 {
-            panic!("Invalid Network Address");
+        match split(&self.0) {
+            Ok((nid, account)) => vec![nid, account],
+            Err(error) => panic!("Invalid Network Address: {error}"),
         }
-        parts
     }
 }
 
+fn split(value: &str) -> Result<(&str, &str), NetworkAddressError> {
+    let (nid, account) = value
+        .split_once('/')
+        .ok_or(NetworkAddressError::MissingSeparator)?;
+    if account.contains('/') {
+        return Err(NetworkAddressError::TooManySeparators);
+    }
+    Ok((nid, account))
+}
+
 impl ToString for NetworkAddress {
     fn to_string(&self) -> String {
         self.0.to_string()
@@ -95,13 +156,45 @@ impl FromStr for NetworkAddress {
     type Err = StdError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split('/').collect::<Vec<&str>>();
-        if parts.len() != 2 {
-            return Err(StdError::GenericErr {
-                msg: "Invalid Network Address".to_owned(),
-            });
+        Self::parse(s).map_err(|error| StdError::GenericErr {
+            msg: format!("Invalid Network Address: {error}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTORS: &str = include_str!("../../../../test-vectors/network-address/addresses.txt");
+
+    #[test]
+    fn test_network_address_vectors() {
+        for line in VECTORS.lines() {
+            let line = line.split('#').next().unwrap();
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            match fields[..] {
+                ["valid", address, nid, account] => {
+                    let parsed = NetworkAddress::parse(address).unwrap();
+                    assert_eq!(parsed.nid().as_str(), nid, "{address}");
+                    assert_eq!(parsed.account().as_str(), account, "{address}");
+                    assert_eq!(NetworkAddress::try_new(nid, account), Ok(parsed));
+                }
+                ["invalid", address, error] => {
+                    let parsed = NetworkAddress::parse(address).map_err(|error| error.to_string());
+                    assert_eq!(parsed, Err(error.to_string()), "{address}");
+                    assert!(NetworkAddress::from_str(address).is_err(), "{address}");
+                    assert!(NetworkAddress(address.to_string()).validate().is_err());
+                }
+                [] => {}
+                _ => panic!("unknown vector {line}"),
+            }
         }
-        let na = format!("{}/{}", parts[0], parts[1]);
-        Ok(Self(na))
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Network Address: MissingSeparator")]
+    fn test_nid_of_unparsed_address() {
+        NetworkAddress("0x1.icon".to_string()).nid();
     }
 }
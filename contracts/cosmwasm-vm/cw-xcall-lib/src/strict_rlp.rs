@@ -0,0 +1,161 @@
+//! Strict decoding on top of `common::rlp`, accepting only the canonical encoding of a value so
+//! that a message has a single valid byte representation and a single hash.
+
+use core::mem;
+
+use common::rlp::{DecoderError, Rlp};
+
+/// Checks that `bytes` holds a single item in canonical form, the items of nested lists included.
+pub fn validate(bytes: &[u8]) -> Result<(), DecoderError> {
+    let end = validate_item(bytes, 0)?;
+    if end != bytes.len() {
+        return Err(DecoderError::RlpIsTooBig);
+    }
+    Ok(())
+}
+
+fn validate_item(bytes: &[u8], start: usize) -> Result<usize, DecoderError> {
+    let prefix = *bytes.get(start).ok_or(DecoderError::RlpIsTooShort)?;
+    let (is_list, offset, len) = match prefix {
+        0x00..=0x7f => return Ok(start + 1),
+        0x80..=0xb7 => {
+            let len = (prefix - 0x80) as usize;
+            if len == 1 && bytes.get(start + 1).map_or(false, |byte| *byte < 0x80) {
+                return Err(DecoderError::RlpInvalidIndirection);
+            }
+            (false, start + 1, len)
+        }
+        0xb8..=0xbf => {
+            let len_of_len = (prefix - 0xb7) as usize;
+            let len = decode_long_length(bytes, start + 1, len_of_len)?;
+            (false, start + 1 + len_of_len, len)
+        }
+        0xc0..=0xf7 => (true, start + 1, (prefix - 0xc0) as usize),
+        // 0xf800 encodes null
+        0xf8 if bytes.get(start + 1) == Some(&0) => return Ok(start + 2),
+        _ => {
+            let len_of_len = (prefix - 0xf7) as usize;
+            let len = decode_long_length(bytes, start + 1, len_of_len)?;
+            (true, start + 1 + len_of_len, len)
+        }
+    };
+
+    let end = offset
+        .checked_add(len)
+        .ok_or(DecoderError::RlpInvalidLength)?;
+    if end > bytes.len() {
+        return Err(DecoderError::RlpIsTooShort);
+    }
+
+    if is_list {
+        let mut next = offset;
+        while next < end {
+            next = validate_item(&bytes[..end], next)?;
+        }
+    }
+    Ok(end)
+}
+
+fn decode_long_length(
+    bytes: &[u8],
+    start: usize,
+    len_of_len: usize,
+) -> Result<usize, DecoderError> {
+    let len_bytes = bytes
+        .get(start..start + len_of_len)
+        .ok_or(DecoderError::RlpIsTooShort)?;
+    if len_bytes[0] == 0 {
+        return Err(DecoderError::RlpDataLenWithZeroPrefix);
+    }
+    if len_of_len > mem::size_of::<usize>() {
+        return Err(DecoderError::RlpIsTooBig);
+    }
+
+    let len = len_bytes
+        .iter()
+        .fold(0_usize, |len, byte| (len << 8) | *byte as usize);
+    if len <= 55 {
+        return Err(DecoderError::RlpInvalidIndirection);
+    }
+    Ok(len)
+}
+
+/// Decodes an unsigned integer encoded as its minimal two's complement big endian bytes, zero
+/// being a single zero byte.
+pub fn decode_u128(rlp: &Rlp) -> Result<u128, DecoderError> {
+    validate(rlp.as_raw())?;
+    if !rlp.is_data() {
+        return Err(DecoderError::RlpExpectedToBeData);
+    }
+
+    let value = match rlp.data()? {
+        [first, ..] if *first >= 0x80 => Err(DecoderError::Custom("Non canonical integer")),
+        [0, next, ..] if *next < 0x80 => Err(DecoderError::Custom("Non canonical integer")),
+        [0, rest @ ..] if !rest.is_empty() => Ok(rest),
+        [] => Err(DecoderError::Custom("Non canonical integer")),
+        bytes => Ok(bytes),
+    }?;
+
+    if value.len() > mem::size_of::<u128>() {
+        return Err(DecoderError::RlpIsTooBig);
+    }
+    Ok(value
+        .iter()
+        .fold(0_u128, |num, byte| (num << 8) | *byte as u128))
+}
+
+pub fn decode_u8(rlp: &Rlp) -> Result<u8, DecoderError> {
+    u8::try_from(decode_u128(rlp)?).map_err(|_| DecoderError::RlpIsTooBig)
+}
+
+pub fn decode_u64(rlp: &Rlp) -> Result<u64, DecoderError> {
+    u64::try_from(decode_u128(rlp)?).map_err(|_| DecoderError::RlpIsTooBig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTORS: &str = include_str!("../../../../test-vectors/rlp/canonical.txt");
+
+    #[test]
+    fn test_canonical_vectors() {
+        for line in VECTORS.lines() {
+            let line = line.split('#').next().unwrap();
+            let mut fields = line.split_whitespace();
+            let (verdict, kind, hex) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(verdict), Some(kind), Some(hex)) => (verdict, kind, hex),
+                _ => continue,
+            };
+            let bytes = hex::decode(hex).unwrap();
+
+            match (verdict, kind) {
+                ("valid", "item") => assert_eq!(validate(&bytes), Ok(()), "{hex}"),
+                ("invalid", "item") => assert!(validate(&bytes).is_err(), "{hex}"),
+                ("valid", "uint") => {
+                    let value: u128 = fields.next().unwrap().parse().unwrap();
+                    assert_eq!(decode_u128(&Rlp::new(&bytes)), Ok(value), "{hex}")
+                }
+                ("invalid", "uint") => assert!(decode_u128(&Rlp::new(&bytes)).is_err(), "{hex}"),
+                _ => panic!("unknown vector {line}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_decode_encoded_integers() {
+        for num in [
+            0_u128,
+            1,
+            127,
+            128,
+            255,
+            256,
+            u32::MAX as u128,
+            u128::MAX >> 1,
+        ] {
+            let encoded = common::rlp::encode(&num).to_vec();
+            assert_eq!(decode_u128(&Rlp::new(&encoded)), Ok(num));
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use common::rlp::Rlp;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 use crate::network_address::NetId;
@@ -5,6 +6,7 @@ use crate::network_address::NetId;
 #[cw_serde]
 pub enum ExecuteMsg {
     SendMessage { to: NetId, sn: i64, msg: Vec<u8> },
+    RefundResponseFee { nid: NetId, sn: u128, to: String },
 }
 
 #[cw_serde]
@@ -15,4 +17,87 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(u64)]
     GetFee { nid: NetId, response: bool },
+    #[returns(NetworkStatus)]
+    GetNetworkStatus { nid: NetId },
+}
+
+/// Liveness of the route of a connection to a network: the last message received from and sent
+/// to it with the block heights they were processed at, and the requests sent to it that neither
+/// got a result nor were reverted yet.
+#[cw_serde]
+#[derive(Default)]
+pub struct NetworkStatus {
+    pub last_received_conn_sn: u128,
+    pub last_received_height: u64,
+    pub last_sent_conn_sn: u128,
+    pub last_sent_height: u64,
+    pub pending_reverts: u64,
+}
+
+/// Returns the sequence number of the request an xCall result message answers, or `None` when
+/// `msg` is not a result.
+pub fn result_sequence_no(msg: &[u8]) -> Option<u128> {
+    let rlp = Rlp::new(msg);
+    let msg_type: u8 = rlp.val_at(0).ok()?;
+    if msg_type != 2 {
+        return None;
+    }
+    let payload: Vec<u8> = rlp.val_at(1).ok()?;
+    Rlp::new(&payload).val_at(0).ok()
+}
+
+/// The bytes a connection's signers sign for a message received from `src_network`, the
+/// validators of the cluster connection and the attesters of the centralized connection alike.
+pub struct SignableMsg {
+    pub src_network: String,
+    pub conn_sn: u128,
+    pub data: Vec<u8>,
+    pub dst_network: String,
+}
+
+impl SignableMsg {
+    pub fn encode_utf8_bytes(&self) -> Vec<u8> {
+        let mut encoded_bytes = Vec::new();
+        encoded_bytes.extend(self.src_network.as_bytes());
+        encoded_bytes.extend(self.conn_sn.to_string().as_bytes());
+        encoded_bytes.extend(self.data.to_vec());
+        encoded_bytes.extend(self.dst_network.as_bytes());
+        encoded_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::rlp::RlpStream;
+
+    use super::{result_sequence_no, SignableMsg};
+
+    fn message(msg_type: u8, sn: u128) -> Vec<u8> {
+        let mut payload = RlpStream::new_list(2);
+        payload.append(&sn).append(&1u8);
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&msg_type).append(&payload.out().to_vec());
+        stream.out().to_vec()
+    }
+
+    #[test]
+    fn test_result_sequence_no() {
+        assert_eq!(result_sequence_no(&message(2, 7)), Some(7));
+        assert_eq!(result_sequence_no(&message(1, 7)), None);
+        assert_eq!(result_sequence_no(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_signable_msg_utf8_bytes() {
+        let signed_msg = SignableMsg {
+            src_network: "0x2.icon".to_string(),
+            conn_sn: 128,
+            data: "hello".as_bytes().to_vec(),
+            dst_network: "archway".to_string(),
+        };
+
+        let expected_encoded_bytes =
+            hex::decode("3078322e69636f6e31323868656c6c6f61726368776179").unwrap();
+        assert_eq!(expected_encoded_bytes, signed_msg.encode_utf8_bytes());
+    }
 }
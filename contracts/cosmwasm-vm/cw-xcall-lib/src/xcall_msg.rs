@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, StdError};
 
 use crate::{
     message::envelope::Envelope,
@@ -17,6 +17,9 @@ pub enum ExecuteMsg {
     SetProtocolFeeHandler {
         address: String,
     },
+    SetProtocolFeeSplits {
+        splits: Vec<(String, u16)>,
+    },
 
     SendCallMessage {
         to: NetworkAddress,
@@ -37,6 +40,10 @@ pub enum ExecuteMsg {
     HandleError {
         sn: u128,
     },
+    HandleNack {
+        sn: u128,
+        reason: NackReason,
+    },
     ExecuteCall {
         request_id: u128,
         data: Vec<u8>,
@@ -45,8 +52,81 @@ pub enum ExecuteMsg {
     ExecuteRollback {
         sequence_no: u128,
     },
+    ExecuteResponse {
+        sequence_no: u128,
+    },
     SetDefaultConnection {
         nid: NetId,
         address: Addr,
     },
+    DepositFeeBalance {
+        address: Option<String>,
+    },
+    WithdrawFeeBalance {
+        amount: u128,
+    },
+    ClaimResponseFeeRefund {
+        sn: u128,
+    },
+    RegisterConnection {
+        address: String,
+        connection_type: ConnectionType,
+        networks: Vec<NetId>,
+    },
+    SetConnectionEnabled {
+        address: String,
+        enabled: bool,
+    },
+}
+
+/// Kind of connection contract registered in xcall.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum ConnectionType {
+    Centralized,
+    Cluster,
+    Ibc,
+}
+
+/// Why a connection could not deliver a message to its destination, reported by the relayer
+/// instead of a response.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum NackReason {
+    DestinationRejected,
+    ExceededSize,
+    NetworkHalted,
+}
+
+impl NackReason {
+    pub fn code(&self) -> u8 {
+        match self {
+            NackReason::DestinationRejected => 1,
+            NackReason::ExceededSize => 2,
+            NackReason::NetworkHalted => 3,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NackReason::DestinationRejected => "destination_rejected",
+            NackReason::ExceededSize => "exceeded_size",
+            NackReason::NetworkHalted => "network_halted",
+        }
+    }
+}
+
+impl TryFrom<u8> for NackReason {
+    type Error = StdError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(NackReason::DestinationRejected),
+            2 => Ok(NackReason::ExceededSize),
+            3 => Ok(NackReason::NetworkHalted),
+            _ => Err(StdError::generic_err(format!(
+                "Invalid Nack Reason {value}"
+            ))),
+        }
+    }
 }
@@ -3,13 +3,16 @@ use common::rlp;
 use cosmwasm_std::{
     to_json_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, QueryRequest, SubMsg, WasmMsg,
 };
-use cosmwasm_std::{MessageInfo, Response};
+use cosmwasm_std::{MessageInfo, Reply, Response, SubMsgResult};
 use cw_xcall_lib::network_address::NetId;
-use cw_xcall_lib::xcall_connection_msg;
+use cw_xcall_lib::xcall_connection_msg::{self, NetworkStatus};
+use cw_xcall_lib::xcall_msg::ConnectionType;
 
 use crate::{
     error::ContractError,
-    state::{CwCallService, SEND_CALL_MESSAGE_REPLY_ID},
+    msg::ConnectionStatus,
+    state::{CwCallService, REFUND_RESPONSE_FEE_REPLY_ID, SEND_CALL_MESSAGE_REPLY_ID},
+    types::connection::ConnectionInfo,
 };
 
 impl<'a> CwCallService<'a> {
@@ -40,6 +43,72 @@ impl<'a> CwCallService<'a> {
         Ok(submessage)
     }
 
+    /// Reclaims the response fee prepaid for `sn` once the sequence number was resolved. Every
+    /// connection the message was sent through is asked to return the share of the fee it did not
+    /// deliver a response for to the original sender. A connection failing the refund, e.g. one
+    /// that does not hold response fees, does not fail the others.
+    ///
+    /// Arguments:
+    ///
+    /// * `deps`: mutable dependencies of the contract.
+    /// * `info`: message info of the original sender of the call.
+    /// * `sn`: sequence number of the call whose response fee is refunded.
+    ///
+    /// Returns:
+    ///
+    /// a `Result<Response, ContractError>` with a refund submessage for each connection, failing
+    /// with `ResponseFeeNotRefundable` while the sequence number is unresolved.
+    pub fn claim_response_fee_refund(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sn: u128,
+    ) -> Result<Response, ContractError> {
+        let record = self
+            .get_response_fee_record(deps.storage, sn)
+            .map_err(|_| ContractError::ResponseFeeNotRefundable { sn })?;
+        if record.payer != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        if !record.refundable {
+            return Err(ContractError::ResponseFeeNotRefundable { sn });
+        }
+        self.remove_response_fee_record(deps.storage, sn);
+
+        let messages = record
+            .sources
+            .iter()
+            .map(|source| {
+                let message = xcall_connection_msg::ExecuteMsg::RefundResponseFee {
+                    nid: record.nid.clone(),
+                    sn,
+                    to: record.payer.to_string(),
+                };
+                let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: source.to_string(),
+                    msg: to_json_binary(&message).map_err(ContractError::Std)?,
+                    funds: vec![],
+                });
+                Ok(SubMsg::reply_on_error(msg, REFUND_RESPONSE_FEE_REPLY_ID))
+            })
+            .collect::<Result<Vec<SubMsg>, ContractError>>()?;
+
+        Ok(Response::new()
+            .add_submessages(messages)
+            .add_attribute("method", "claim_response_fee_refund")
+            .add_attribute("sn", sn.to_string()))
+    }
+
+    /// Ignores a connection failing to refund a response fee, its state being reverted.
+    pub fn refund_response_fee_reply(&self, msg: Reply) -> Result<Response, ContractError> {
+        match msg.result {
+            SubMsgResult::Ok(_) => Ok(Response::new()),
+            SubMsgResult::Err(error) => Ok(Response::new()
+                .add_attribute("method", "refund_response_fee_reply")
+                .add_attribute("error", error)),
+        }
+    }
+
     pub fn query_connection_fee(
         &self,
         deps: Deps,
@@ -63,6 +132,42 @@ impl<'a> CwCallService<'a> {
         Ok(fee)
     }
 
+    pub fn query_network_status(
+        &self,
+        deps: Deps,
+        nid: NetId,
+        address: &str,
+    ) -> Result<NetworkStatus, ContractError> {
+        let query_message = xcall_connection_msg::QueryMsg::GetNetworkStatus { nid };
+
+        let query_request = QueryRequest::Wasm(cosmwasm_std::WasmQuery::Smart {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&query_message).map_err(ContractError::Std)?,
+        });
+        let status: NetworkStatus = deps
+            .querier
+            .query(&query_request)
+            .map_err(ContractError::Std)?;
+        Ok(status)
+    }
+
+    /// Collects the route status of every default connection, so stalled routes can be spotted
+    /// from a single query.
+    pub fn get_network_statuses(&self, deps: Deps) -> Result<Vec<ConnectionStatus>, ContractError> {
+        let mut statuses = vec![];
+        for (nid, address) in self.get_default_connections(deps.storage)? {
+            let status = self
+                .query_network_status(deps, nid.clone(), address.as_str())
+                .ok();
+            statuses.push(ConnectionStatus {
+                nid,
+                connection: address.to_string(),
+                status,
+            });
+        }
+        Ok(statuses)
+    }
+
     pub fn set_default_connection(
         &self,
         deps: DepsMut,
@@ -72,8 +177,115 @@ impl<'a> CwCallService<'a> {
     ) -> Result<Response, ContractError> {
         self.ensure_admin(deps.storage, info.sender)?;
         deps.api.addr_validate(address.as_str())?;
+        self.ensure_registered_connections(deps.as_ref(), &nid, &[address.to_string()])?;
         self.store_default_connection(deps.storage, nid, address)?;
 
         Ok(Response::new().add_attribute("method", "set_default_connection"))
     }
+
+    /// Adds `address` to the connection registry, or replaces its entry, as an enabled connection
+    /// serving `networks`. Once a connection is registered, calls are only accepted through
+    /// registered connections.
+    ///
+    /// Arguments:
+    ///
+    /// * `deps`: mutable dependencies of the contract.
+    /// * `info`: message info, the sender must be the admin.
+    /// * `address`: address of the connection contract.
+    /// * `connection_type`: kind of the connection.
+    /// * `networks`: networks the connection can deliver messages to.
+    ///
+    /// Returns:
+    ///
+    /// a `Result<Response, ContractError>` failing with `OnlyAdmin` for any other sender.
+    pub fn register_connection(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        connection_type: ConnectionType,
+        networks: Vec<NetId>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        let connection = ConnectionInfo {
+            address: address.clone(),
+            connection_type,
+            networks,
+            enabled: true,
+        };
+        self.store_connection_info(deps.storage, &connection)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "register_connection")
+            .add_attribute("address", address))
+    }
+
+    pub fn set_connection_enabled(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        enabled: bool,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        let mut connection = self.get_connection_info(deps.storage, &address).ok_or(
+            ContractError::UnregisteredConnection {
+                address: address.to_string(),
+            },
+        )?;
+        connection.enabled = enabled;
+        self.store_connection_info(deps.storage, &connection)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_connection_enabled")
+            .add_attribute("address", address)
+            .add_attribute("enabled", enabled.to_string()))
+    }
+
+    /// Checks that every connection in `sources` is registered, enabled and serves `nid`. Any
+    /// source is accepted while the registry is empty.
+    pub fn ensure_registered_connections(
+        &self,
+        deps: Deps,
+        nid: &NetId,
+        sources: &[String],
+    ) -> Result<(), ContractError> {
+        if !self.has_registered_connections(deps.storage) {
+            return Ok(());
+        }
+        for source in sources {
+            let address = deps.api.addr_validate(source)?;
+            let connection = self.get_connection_info(deps.storage, &address).ok_or(
+                ContractError::UnregisteredConnection {
+                    address: source.clone(),
+                },
+            )?;
+            if !connection.enabled {
+                return Err(ContractError::ConnectionDisabled {
+                    address: source.clone(),
+                });
+            }
+            if !connection.supports(nid) {
+                return Err(ContractError::UnsupportedNetwork {
+                    address: source.clone(),
+                    nid: nid.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_connections(
+        &self,
+        deps: Deps,
+        nid: Option<NetId>,
+    ) -> Result<Vec<ConnectionInfo>, ContractError> {
+        let connections = self.get_registered_connections(deps.storage)?;
+        Ok(connections
+            .into_iter()
+            .filter(|connection| nid.as_ref().map_or(true, |nid| connection.supports(nid)))
+            .collect())
+    }
 }
@@ -82,6 +82,9 @@ impl<'a> CwCallService<'a> {
             ExecuteMsg::SetProtocolFeeHandler { address } => {
                 self.set_protocol_feehandler(deps, &info, address)
             }
+            ExecuteMsg::SetProtocolFeeSplits { splits } => {
+                self.set_protocol_fee_splits(deps, &info, splits)
+            }
             ExecuteMsg::SendCallMessage {
                 to,
                 sources,
@@ -99,15 +102,36 @@ impl<'a> CwCallService<'a> {
                 self.handle_message(deps, info, from_nid, msg)
             }
             ExecuteMsg::HandleError { sn } => self.handle_error(deps, info, sn),
+            ExecuteMsg::HandleNack { sn, reason } => self.handle_nack(deps, info, sn, reason),
             ExecuteMsg::ExecuteCall { request_id, data } => {
-                self.execute_call(deps, info, request_id, data)
+                self.execute_call(deps, env, info, request_id, data)
             }
             ExecuteMsg::ExecuteRollback { sequence_no } => {
                 self.execute_rollback(deps, env, info, sequence_no)
             }
+            ExecuteMsg::ExecuteResponse { sequence_no } => {
+                self.execute_response(deps, info, sequence_no)
+            }
             ExecuteMsg::SetDefaultConnection { nid, address } => {
                 self.set_default_connection(deps, info, nid, address)
             }
+            ExecuteMsg::DepositFeeBalance { address } => {
+                self.deposit_fee_balance(deps, info, address)
+            }
+            ExecuteMsg::WithdrawFeeBalance { amount } => {
+                self.withdraw_fee_balance(deps, info, amount)
+            }
+            ExecuteMsg::ClaimResponseFeeRefund { sn } => {
+                self.claim_response_fee_refund(deps, info, sn)
+            }
+            ExecuteMsg::RegisterConnection {
+                address,
+                connection_type,
+                networks,
+            } => self.register_connection(deps, info, address, connection_type, networks),
+            ExecuteMsg::SetConnectionEnabled { address, enabled } => {
+                self.set_connection_enabled(deps, info, address, enabled)
+            }
         }
     }
 
@@ -145,6 +169,9 @@ impl<'a> CwCallService<'a> {
             QueryMsg::GetProtocolFeeHandler {} => {
                 to_json_binary(&self.get_protocol_feehandler(deps))
             }
+            QueryMsg::GetProtocolFeeSplits {} => {
+                to_json_binary(&self.get_protocol_fee_splits(deps))
+            }
             QueryMsg::GetNetworkAddress {} => {
                 to_json_binary(&self.get_own_network_address(deps.storage, &env).unwrap())
             }
@@ -163,6 +190,24 @@ impl<'a> CwCallService<'a> {
                     .get_fee(deps, nid, rollback, sources.unwrap_or(vec![]))
                     .unwrap(),
             ),
+            QueryMsg::GetFeeBalance { address } => {
+                let address = deps.api.addr_validate(&address)?;
+                to_json_binary(&self.get_fee_balance(deps.storage, &address))
+            }
+            QueryMsg::GetNetworkStatuses {} => to_json_binary(
+                &self
+                    .get_network_statuses(deps)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?,
+            ),
+            QueryMsg::GetConnection { address } => {
+                let address = deps.api.addr_validate(&address)?;
+                to_json_binary(&self.get_connection_info(deps.storage, &address))
+            }
+            QueryMsg::GetConnections { nid } => to_json_binary(
+                &self
+                    .get_connections(deps, nid)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?,
+            ),
         }
     }
     /// This function handles different types of reply messages and calls corresponding functions based on
@@ -189,6 +234,7 @@ impl<'a> CwCallService<'a> {
     pub fn reply(&self, deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
         match msg.id {
             EXECUTE_CALL_ID => self.execute_call_reply(deps, env, msg),
+            REFUND_RESPONSE_FEE_REPLY_ID => self.refund_response_fee_reply(msg),
             _ => Err(ContractError::ReplyError {
                 code: msg.id,
                 msg: "Unknown".to_string(),
@@ -32,6 +32,31 @@ impl<'a> CwCallService<'a> {
         Ok(submessage)
     }
 
+    /// Builds the message delivering the `data` returned by `from` for the request `sn` of the
+    /// dapp `to`.
+    pub fn call_dapp_handle_response(
+        &self,
+        info: MessageInfo,
+        to: Addr,
+        from: NetworkAddress,
+        sn: u128,
+        data: Vec<u8>,
+        protocols: &[String],
+    ) -> Result<SubMsg, ContractError> {
+        let msg = if protocols.is_empty() {
+            to_json_binary(&dapp_msg::ExecuteMsg::HandleCallResponse { from, sn, data })
+        } else {
+            to_json_binary(&dapp_multi_msg::ExecuteMsg::HandleCallResponse { from, sn, data })
+        }
+        .map_err(ContractError::Std)?;
+
+        Ok(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: to.to_string(),
+            msg,
+            funds: info.funds,
+        })))
+    }
+
     pub fn get_handle_message(
         &self,
         from: NetworkAddress,
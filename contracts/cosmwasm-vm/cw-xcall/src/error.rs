@@ -1,4 +1,5 @@
 use super::*;
+use cw_xcall_lib::network_address::NetworkAddressError;
 
 #[derive(Error, Debug)]
 pub enum ContractError {
@@ -44,8 +45,26 @@ pub enum ContractError {
     CallAlreadyInProgress,
     #[error("MessageTypeNotAllowed")]
     MessageTypeNotAllowed,
+    #[error("InvalidTimeout")]
+    InvalidTimeout,
     #[error("InvalidReplyReceived")]
     InvalidReplyReceived,
     #[error("CallRequest Not Found For {sn}")]
     CallRequestNotFound { sn: u128 },
+    #[error("CallResponse Not Found For {sn}")]
+    CallResponseNotFound { sn: u128 },
+    #[error("CallReplyNotAllowed {sn}")]
+    CallReplyNotAllowed { sn: u128 },
+    #[error("InvalidFeeSplits")]
+    InvalidFeeSplits,
+    #[error("ResponseFeeNotRefundable {sn}")]
+    ResponseFeeNotRefundable { sn: u128 },
+    #[error("UnregisteredConnection {address}")]
+    UnregisteredConnection { address: String },
+    #[error("ConnectionDisabled {address}")]
+    ConnectionDisabled { address: String },
+    #[error("UnsupportedNetwork {nid} for {address}")]
+    UnsupportedNetwork { address: String, nid: String },
+    #[error("InvalidNetworkAddress {0}")]
+    InvalidNetworkAddress(#[from] NetworkAddressError),
 }
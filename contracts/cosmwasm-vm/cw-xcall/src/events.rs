@@ -111,6 +111,26 @@ pub fn event_rollback_message(sequence_no: u128) -> Event {
     Event::new("RollbackMessage ").add_attribute("sn", sequence_no.to_string())
 }
 
+/// Creates the event of the data returned for the `CallMessageWithResponse` of `sequence_no`,
+/// waiting to be delivered by `execute_response`.
+pub fn event_call_response_message(sequence_no: u128) -> Event {
+    Event::new("CallResponseMessage").add_attribute("sn", sequence_no.to_string())
+}
+
+/// Creates the event of the request `sequence_no` of `from` dropped because its `msg_type` is not
+/// supported.
+pub fn event_unsupported_request(from: String, sequence_no: u128, msg_type: u8) -> Event {
+    Event::new("UnsupportedRequest")
+        .add_attribute("from", from)
+        .add_attribute("sn", sequence_no.to_string())
+        .add_attribute("msgType", msg_type.to_string())
+}
+
+/// Creates the event of the data returned for `sequence_no` delivered to its sender.
+pub fn event_call_response_executed(sequence_no: u128) -> Event {
+    Event::new("CallResponseExecuted").add_attribute("sn", sequence_no.to_string())
+}
+
 /// This Rust function creates an event with attributes for a response message.
 ///
 /// Arguments:
@@ -129,3 +149,11 @@ pub fn event_response_message(sequence_no: u128, response_code: u8) -> Event {
         .add_attribute("sn", sequence_no.to_string())
         .add_attribute("code", response_code.to_string())
 }
+
+/// Adds the `reason` a message was nacked for to a response or rollback event.
+pub fn with_nack_reason(event: Event, reason: Option<NackReason>) -> Event {
+    match reason {
+        Some(reason) => event.add_attribute("reason", reason.as_str()),
+        None => event,
+    }
+}
@@ -1,5 +1,7 @@
 use common::{rlp, utils::keccak256};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Reply, Response, SubMsg};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, SubMsg};
+use cw_utils::parse_execute_response_data;
+use cw_xcall_lib::{message::msg_type::MessageType, network_address::NetworkAddress};
 
 use crate::{
     error::ContractError,
@@ -35,6 +37,7 @@ impl<'a> CwCallService<'a> {
     pub fn execute_call(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         request_id: u128,
         data: Vec<u8>,
@@ -49,7 +52,11 @@ impl<'a> CwCallService<'a> {
             return Err(ContractError::DataMismatch);
         }
 
-        let sub_msg = self.call_dapp_handle_message(
+        if proxy_requests.has_timed_out(env.block.height, env.block.time.seconds()) {
+            return self.reject_timed_out_call(deps, request_id);
+        }
+
+        let mut sub_msg = self.call_dapp_handle_message(
             info,
             proxy_requests.to().clone(),
             proxy_requests.from().clone(),
@@ -57,6 +64,10 @@ impl<'a> CwCallService<'a> {
             proxy_requests.protocols().clone(),
             EXECUTE_CALL_ID,
         )?;
+        // running out of the gas limit of the sender fails the call, replied as an error
+        if proxy_requests.gas_limit() > 0 {
+            sub_msg.gas_limit = Some(proxy_requests.gas_limit());
+        }
 
         self.store_execute_request_id(deps.storage, request_id)?;
 
@@ -82,12 +93,31 @@ impl<'a> CwCallService<'a> {
             .map(|msg| rlp::encode(&msg).to_vec());
 
         let (response, event) = match msg.result {
-            cosmwasm_std::SubMsgResult::Ok(_res) => {
+            cosmwasm_std::SubMsgResult::Ok(res) => {
                 let code = CallServiceResponseType::CallServiceResponseSuccess.into();
+                // the data set by the dapp is returned in place of a reply, unwrapped from the
+                // MsgExecuteContractResponse the chain wraps it in. A reply queued as well would
+                // be lost, so the call is reverted and stays pending instead.
+                let message = if request.msg_type() == MessageType::CallMessageWithResponse {
+                    if reply.is_some() {
+                        return Err(ContractError::CallReplyNotAllowed {
+                            sn: request.sequence_no(),
+                        });
+                    }
+                    match res.data {
+                        Some(data) => parse_execute_response_data(&data)
+                            .map_err(|err| StdError::generic_err(err.to_string()))?
+                            .data
+                            .map(|data| data.to_vec()),
+                        None => None,
+                    }
+                } else {
+                    reply
+                };
                 let message_response = CSMessageResult::new(
                     request.sequence_no(),
                     CallServiceResponseType::CallServiceResponseSuccess,
-                    reply,
+                    message,
                 );
 
                 let event = event_call_executed(req_id, code, "success");
@@ -109,27 +139,14 @@ impl<'a> CwCallService<'a> {
             }
         };
         let mut submsgs: Vec<SubMsg> = vec![];
-        let sn: i64 = -(request.sequence_no() as i64);
         if request.need_response() {
-            let message: CSMessage = response.into();
-            let mut reply_address = request.protocols().clone();
-            let from = request.from().clone();
-            if request.protocols().is_empty() {
-                let default_connection = self.get_default_connection(deps.storage, from.nid())?;
-                reply_address = vec![default_connection.to_string()];
-            }
-            submsgs = reply_address
-                .iter()
-                .map(|to| {
-                    self.call_connection_send_message(
-                        &deps.api.addr_validate(to)?,
-                        vec![],
-                        from.nid(),
-                        sn,
-                        &message,
-                    )
-                })
-                .collect::<Result<Vec<SubMsg>, ContractError>>()?;
+            submsgs = self.send_result(
+                deps.as_ref(),
+                request.from(),
+                request.protocols(),
+                request.sequence_no(),
+                response,
+            )?;
         }
 
         Ok(Response::new()
@@ -138,4 +155,63 @@ impl<'a> CwCallService<'a> {
             .add_attribute("method", "execute_callback")
             .add_event(event))
     }
+
+    /// Answers a `CallMessageWithTimeout` executed after its deadline with a failure, without
+    /// calling the dapp, so the source rolls it back.
+    fn reject_timed_out_call(
+        &self,
+        deps: DepsMut,
+        request_id: u128,
+    ) -> Result<Response, ContractError> {
+        let request = self.get_proxy_request(deps.storage, request_id)?;
+        self.remove_proxy_request(deps.storage, request_id);
+
+        let code = CallServiceResponseType::CallServiceResponseFailure;
+        let result = CSMessageResult::new(request.sequence_no(), code.clone(), None);
+        let submsgs = self.send_result(
+            deps.as_ref(),
+            request.from(),
+            request.protocols(),
+            request.sequence_no(),
+            result,
+        )?;
+
+        Ok(Response::new()
+            .add_submessages(submsgs)
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "execute_call")
+            .add_event(event_call_executed(request_id, code.into(), "timed out")))
+    }
+
+    /// Sends the `result` of the request `sn` of `from` back through the protocols the request was
+    /// delivered by, or through the default connection of its network when it named none.
+    pub fn send_result(
+        &self,
+        deps: Deps,
+        from: &NetworkAddress,
+        protocols: &Vec<String>,
+        sn: u128,
+        result: CSMessageResult,
+    ) -> Result<Vec<SubMsg>, ContractError> {
+        let message: CSMessage = result.into();
+        let mut reply_address = protocols.clone();
+        if protocols.is_empty() {
+            let default_connection = self.get_default_connection(deps.storage, from.nid())?;
+            reply_address = vec![default_connection.to_string()];
+        }
+
+        let sn: i64 = -(sn as i64);
+        reply_address
+            .iter()
+            .map(|to| {
+                self.call_connection_send_message(
+                    &deps.api.addr_validate(to)?,
+                    vec![],
+                    from.nid(),
+                    sn,
+                    &message,
+                )
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,41 @@
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+use crate::error::ContractError;
+use crate::events::event_call_response_executed;
+use crate::state::CwCallService;
+
+impl<'a> CwCallService<'a> {
+    /// Delivers the data returned for the `CallMessageWithResponse` of `sequence_no` to the dapp
+    /// that sent it, through its `handle_call_response`.
+    pub fn execute_response(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sequence_no: u128,
+    ) -> Result<Response, ContractError> {
+        let call_request = self
+            .get_call_request(deps.storage, sequence_no)
+            .map_err(|_e| ContractError::CallResponseNotFound { sn: sequence_no })?;
+        let data = call_request
+            .response()
+            .cloned()
+            .ok_or(ContractError::CallResponseNotFound { sn: sequence_no })?;
+        self.cleanup_request(deps.storage, sequence_no);
+
+        let sub_msg = self.call_dapp_handle_response(
+            info,
+            // the original caller is stored as from in call request
+            call_request.from().clone(),
+            call_request.to().clone(),
+            sequence_no,
+            data,
+            call_request.protocols(),
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "execute_response")
+            .add_event(event_call_response_executed(sequence_no))
+            .add_submessage(sub_msg))
+    }
+}
@@ -0,0 +1,99 @@
+use cosmwasm_std::{coins, BankMsg, OverflowError, OverflowOperation};
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Credits the native funds attached to the message to the prepaid fee balance of `address`,
+    /// or of the sender when no address is given. The balance is later used to pay connection and
+    /// protocol fees for calls sent by that address without attached funds.
+    ///
+    /// Arguments:
+    ///
+    /// * `deps`: mutable dependencies of the contract.
+    /// * `info`: message info carrying the sender and the deposited funds.
+    /// * `address`: optional dapp address to credit, defaults to the sender.
+    ///
+    /// Returns:
+    ///
+    /// a `Result<Response, ContractError>`, failing with `InsufficientFunds` when nothing of the
+    /// configured denom was attached and with an overflow error when the balance would overflow.
+    pub fn deposit_fee_balance(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let account = match address {
+            Some(address) => deps.api.addr_validate(&address)?,
+            None => info.sender.clone(),
+        };
+        let amount = self.get_total_paid(deps.as_ref(), &info.funds)?;
+        if amount == 0 {
+            return Err(ContractError::InsufficientFunds);
+        }
+
+        let balance = self.get_fee_balance(deps.storage, &account);
+        let balance = balance.checked_add(amount).ok_or_else(|| {
+            StdError::overflow(OverflowError::new(OverflowOperation::Add, balance, amount))
+        })?;
+        self.store_fee_balance(deps.storage, &account, balance)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "deposit_fee_balance")
+            .add_attribute("address", account.to_string())
+            .add_attribute("balance", balance.to_string()))
+    }
+
+    /// Returns `amount` from the prepaid fee balance of the sender back to the sender.
+    ///
+    /// Arguments:
+    ///
+    /// * `deps`: mutable dependencies of the contract.
+    /// * `info`: message info carrying the sender whose balance is withdrawn.
+    /// * `amount`: amount of the configured denom to withdraw.
+    ///
+    /// Returns:
+    ///
+    /// a `Result<Response, ContractError>` with a bank message transferring the amount, failing with
+    /// `InsufficientFunds` when the balance is lower than the requested amount.
+    pub fn withdraw_fee_balance(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let config = self.get_config(deps.storage)?;
+        let balance = self.get_fee_balance(deps.storage, &info.sender);
+        if amount == 0 || amount > balance {
+            return Err(ContractError::InsufficientFunds);
+        }
+        self.store_fee_balance(deps.storage, &info.sender, balance - amount)?;
+
+        let msg = BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount, config.denom),
+        };
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("method", "withdraw_fee_balance")
+            .add_attribute("address", info.sender.to_string())
+            .add_attribute("balance", (balance - amount).to_string()))
+    }
+
+    /// Deducts `amount` from the prepaid fee balance of `account` to pay for a call sent without
+    /// attached funds.
+    pub fn draw_fee_balance(
+        &self,
+        store: &mut dyn Storage,
+        account: &Addr,
+        amount: u128,
+    ) -> Result<u128, ContractError> {
+        let balance = self.get_fee_balance(store, account);
+        if balance < amount {
+            return Err(ContractError::InsufficientFunds);
+        }
+        self.store_fee_balance(store, account, balance - amount)?;
+        Ok(amount)
+    }
+}
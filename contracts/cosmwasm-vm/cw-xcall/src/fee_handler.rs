@@ -1,3 +1,7 @@
+use cosmwasm_std::{coins, BankMsg};
+
+use crate::state::MAX_BASIS_POINTS;
+
 use super::*;
 
 impl<'a> CwCallService<'a> {
@@ -47,6 +51,91 @@ impl<'a> CwCallService<'a> {
     pub fn get_protocol_feehandler(&self, deps: Deps) -> String {
         self.query_feehandler(deps.storage).unwrap()
     }
+
+    /// This function sets the list of recipients the protocol fee is split between, each with its
+    /// share in basis points. The shares must add up to `MAX_BASIS_POINTS`; an empty list sends the
+    /// whole protocol fee to the fee handler again.
+    ///
+    /// Arguments:
+    ///
+    /// * `deps`: mutable dependencies of the contract.
+    /// * `info`: message info of the sender, who must be the admin.
+    /// * `splits`: list of `(address, basis_points)` recipients.
+    ///
+    /// Returns:
+    ///
+    /// a `Result<Response, ContractError>`, failing with `InvalidFeeSplits` when a share is zero or
+    /// the shares do not add up to `MAX_BASIS_POINTS`.
+    pub fn set_protocol_fee_splits(
+        &self,
+        deps: DepsMut,
+        info: &MessageInfo,
+        splits: Vec<(String, u16)>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender.clone())?;
+
+        let mut total: u32 = 0;
+        for (address, basis_points) in splits.iter() {
+            deps.api.addr_validate(address)?;
+            if *basis_points == 0 {
+                return Err(ContractError::InvalidFeeSplits);
+            }
+            total += *basis_points as u32;
+        }
+        if !splits.is_empty() && total != MAX_BASIS_POINTS as u32 {
+            return Err(ContractError::InvalidFeeSplits);
+        }
+
+        self.store_fee_splits(deps.storage, splits)?;
+        Ok(Response::new().add_attribute("method", "set_protocol_fee_splits"))
+    }
+
+    pub fn get_protocol_fee_splits(&self, deps: Deps) -> Vec<(String, u16)> {
+        self.get_fee_splits(deps.storage)
+    }
+
+    /// This function builds the bank messages paying out `amount` of collected fees. The amount is
+    /// split between the configured recipients by their basis points, and whatever is left after
+    /// rounding goes to the fee handler.
+    ///
+    /// Arguments:
+    ///
+    /// * `store`: contract storage holding the fee handler and splits.
+    /// * `amount`: total amount of fees to distribute.
+    /// * `denom`: denom of the fees.
+    ///
+    /// Returns:
+    ///
+    /// a `Result` with the list of `BankMsg` transfers, empty when `amount` is zero.
+    pub fn distribute_protocol_fee(
+        &self,
+        store: &dyn Storage,
+        amount: u128,
+        denom: &str,
+    ) -> Result<Vec<BankMsg>, ContractError> {
+        let mut msgs = vec![];
+        let mut remaining = amount;
+        for (address, basis_points) in self.get_fee_splits(store) {
+            let share = amount * basis_points as u128 / MAX_BASIS_POINTS;
+            if share > 0 {
+                remaining -= share;
+                msgs.push(BankMsg::Send {
+                    to_address: address,
+                    amount: coins(share, denom),
+                });
+            }
+        }
+
+        if remaining > 0 {
+            let fee_handler = self.query_feehandler(store)?;
+            msgs.push(BankMsg::Send {
+                to_address: fee_handler,
+                amount: coins(remaining, denom),
+            });
+        }
+
+        Ok(msgs)
+    }
 }
 
 impl<'a> CwCallService<'a> {
@@ -1,5 +1,13 @@
 use common::{rlp, utils::keccak256};
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{
+    network_address::{NetId, NetworkAddress},
+    strict_rlp,
+};
+
+use crate::{
+    events::{event_call_response_message, event_unsupported_request},
+    types::request::UnsupportedRequest,
+};
 
 use super::*;
 
@@ -16,7 +24,17 @@ impl<'a> CwCallService<'a> {
             return Err(ContractError::ProtocolsMismatch);
         }
 
+        // only canonical encodings are accepted so the hash of a message is the same for every
+        // connection delivering it
+        strict_rlp::validate(&message).map_err(|error| ContractError::DecodeFailed {
+            error: error.to_string(),
+        })?;
         let call_service_message: CSMessage = CSMessage::try_from(message)?;
+        strict_rlp::validate(call_service_message.payload()).map_err(|error| {
+            ContractError::DecodeFailed {
+                error: error.to_string(),
+            }
+        })?;
         match call_service_message.message_type() {
             CSMessageType::CSMessageRequest => {
                 self.handle_request(deps, info, from_nid, call_service_message.payload())
@@ -66,37 +84,35 @@ impl<'a> CwCallService<'a> {
         src_net: NetId,
         data: &[u8],
     ) -> Result<Response, ContractError> {
-        let request: CSMessageRequest = rlp::decode(data).unwrap();
+        let request = match CSMessageRequest::try_from(data) {
+            Ok(request) => request,
+            Err(error) => {
+                return match rlp::decode::<UnsupportedRequest>(data) {
+                    Ok(request) => {
+                        self.handle_unsupported_request(deps, info, src_net, data, request)
+                    }
+                    Err(_) => Err(error),
+                }
+            }
+        };
 
         let from = request.from().clone();
-        if from.nid() != src_net {
-            return Err(ContractError::ProtocolsMismatch);
-        }
-        let source = info.sender.to_string();
-        let source_valid =
-            self.is_valid_source(deps.as_ref().storage, src_net, &source, request.protocols())?;
-        if !source_valid {
-            return Err(ContractError::ProtocolsMismatch);
-        }
+        self.ensure_request_source(
+            deps.storage,
+            src_net,
+            &from,
+            &info.sender,
+            request.protocols(),
+        )?;
 
         let to = deps.api.addr_validate(request.to().as_str())?;
 
-        if request.protocols().len() > 1 {
-            let key = keccak256(data).to_vec();
-            let caller = info.sender;
-            self.save_pending_requests(deps.storage, key.clone(), caller.to_string())?;
-            let registered =
-                self.get_pending_requests_by_hash(deps.as_ref().storage, key.clone())?;
-
-            if registered.len() != request.protocols().len() {
-                return Ok(Response::new());
-            }
-
-            self.remove_pending_request_by_hash(deps.storage, key)?;
+        if !self.collect_request(deps.storage, &info.sender, request.protocols(), data)? {
+            return Ok(Response::new());
         }
         let request_id = self.increment_last_request_id(deps.storage)?;
 
-        let req = CSMessageRequest::new(
+        let mut req = CSMessageRequest::new(
             request.from().clone(),
             request.to().clone(),
             request.sequence_no(),
@@ -104,6 +120,8 @@ impl<'a> CwCallService<'a> {
             keccak256(request.data().unwrap()).to_vec(),
             request.protocols().clone(),
         );
+        req.set_timeout(request.timeout_height(), request.timeout_timestamp());
+        req.set_gas_limit(request.gas_limit());
         self.store_proxy_request(deps.storage, request_id, &req)?;
 
         let event = event_call_message(
@@ -120,13 +138,104 @@ impl<'a> CwCallService<'a> {
             .add_event(event))
     }
 
+    /// Drops a request of a message type this version does not support. The request does not say
+    /// whether its sender waits for a result, and a result nobody waits for fails on the source,
+    /// so none is sent: the request is only reported with an `UnsupportedRequest` event.
+    fn handle_unsupported_request(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        src_net: NetId,
+        data: &[u8],
+        request: UnsupportedRequest,
+    ) -> Result<Response, ContractError> {
+        self.ensure_request_source(
+            deps.storage,
+            src_net,
+            &request.from,
+            &info.sender,
+            &request.protocols,
+        )?;
+        if !self.collect_request(deps.storage, &info.sender, &request.protocols, data)? {
+            return Ok(Response::new());
+        }
+
+        let event = event_unsupported_request(
+            request.from.to_string(),
+            request.sequence_no,
+            request.msg_type,
+        );
+
+        Ok(Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "handle_request")
+            .add_attribute("unsupported_message_type", request.msg_type.to_string())
+            .add_event(event))
+    }
+
+    /// Checks that `sender` may deliver a request of `from`, received from `src_net` through
+    /// `protocols`.
+    fn ensure_request_source(
+        &self,
+        store: &dyn Storage,
+        src_net: NetId,
+        from: &NetworkAddress,
+        sender: &Addr,
+        protocols: &Vec<String>,
+    ) -> Result<(), ContractError> {
+        if from.nid() != src_net {
+            return Err(ContractError::ProtocolsMismatch);
+        }
+        let source = sender.to_string();
+        if !self.is_valid_source(store, src_net, &source, protocols)? {
+            return Err(ContractError::ProtocolsMismatch);
+        }
+        Ok(())
+    }
+
+    /// Records the delivery of a request by `sender`, returning whether every one of its
+    /// `protocols` delivered it.
+    fn collect_request(
+        &self,
+        store: &mut dyn Storage,
+        sender: &Addr,
+        protocols: &Vec<String>,
+        data: &[u8],
+    ) -> Result<bool, ContractError> {
+        if protocols.len() <= 1 {
+            return Ok(true);
+        }
+
+        let key = keccak256(data).to_vec();
+        self.save_pending_requests(store, key.clone(), sender.to_string())?;
+        let registered = self.get_pending_requests_by_hash(store, key.clone())?;
+        if registered.len() != protocols.len() {
+            return Ok(false);
+        }
+
+        self.remove_pending_request_by_hash(store, key)?;
+        Ok(true)
+    }
+
     pub fn handle_result(
         &self,
         deps: DepsMut,
         info: MessageInfo,
         data: &[u8],
     ) -> Result<Response, ContractError> {
-        let result: CSMessageResult = rlp::decode(data).unwrap();
+        self.process_result(deps, info, data, None)
+    }
+
+    /// Resolves the sequence number of a result, making the response fee prepaid for it
+    /// refundable. The `reason` of a nack is added to the emitted events.
+    fn process_result(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        data: &[u8],
+        reason: Option<NackReason>,
+    ) -> Result<Response, ContractError> {
+        let result = CSMessageResult::try_from(data)?;
 
         let response_sequence_no = result.sequence_no();
 
@@ -160,19 +269,30 @@ impl<'a> CwCallService<'a> {
 
             self.remove_pending_responses_by_hash(deps.storage, key)?;
         }
-        let response_event = event_response_message(
-            response_sequence_no,
-            (result.response_code().clone()).into(),
+        self.resolve_response_fee(deps.storage, response_sequence_no)?;
+
+        let response_event = with_nack_reason(
+            event_response_message(
+                response_sequence_no,
+                (result.response_code().clone()).into(),
+            ),
+            reason,
         );
 
         match result.response_code() {
             CallServiceResponseType::CallServiceResponseSuccess => {
-                self.cleanup_request(deps.storage, response_sequence_no);
                 self.set_successful_response(deps.storage, response_sequence_no)?;
                 let mut res = Response::new()
                     .add_attribute("action", "call_service")
                     .add_attribute("method", "handle_response")
                     .add_event(response_event);
+                // the returned data is kept with the request until delivered by `execute_response`
+                if call_request.expects_response() {
+                    call_request.set_response(result.message().clone());
+                    self.store_call_request(deps.storage, response_sequence_no, &call_request)?;
+                    return Ok(res.add_event(event_call_response_message(response_sequence_no)));
+                }
+                self.cleanup_request(deps.storage, response_sequence_no);
                 if result.get_message().is_some() {
                     let reply = result.get_message().unwrap();
                     let event = self.handle_reply(deps, call_request, reply)?;
@@ -187,7 +307,8 @@ impl<'a> CwCallService<'a> {
                 call_request.set_enabled();
                 self.store_call_request(deps.storage, response_sequence_no, &call_request)?;
 
-                let rollback_event = event_rollback_message(response_sequence_no);
+                let rollback_event =
+                    with_nack_reason(event_rollback_message(response_sequence_no), reason);
 
                 Ok(Response::new()
                     .add_attribute("action", "call_service")
@@ -242,6 +363,34 @@ impl<'a> CwCallService<'a> {
             CallServiceResponseType::CallServiceResponseFailure,
             None,
         );
-        self.handle_result(deps, info, &rlp::encode(&msg))
+        self.process_result(deps, info, &rlp::encode(&msg), None)
+    }
+
+    /// Fails the message of `sn` like `handle_error`, with the `reason` the connection could
+    /// not deliver it for.
+    pub fn handle_nack(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sn: u128,
+        reason: NackReason,
+    ) -> Result<Response, ContractError> {
+        let msg = CSMessageResult::new(
+            sn,
+            CallServiceResponseType::CallServiceResponseFailure,
+            None,
+        );
+        self.process_result(deps, info, &rlp::encode(&msg), Some(reason))
+    }
+
+    /// Lets the original sender reclaim the response fee of `sn` once it is resolved. The
+    /// connections keep the fee reserved until they deliver a response for `sn`, so only the
+    /// part no response was delivered for, e.g. on a revert, is refunded.
+    fn resolve_response_fee(&self, store: &mut dyn Storage, sn: u128) -> Result<(), ContractError> {
+        if let Ok(mut record) = self.get_response_fee_record(store, sn) {
+            record.refundable = true;
+            return self.store_response_fee_record(store, sn, &record);
+        }
+        Ok(())
     }
 }
@@ -6,7 +6,9 @@ pub mod dapp;
 pub mod error;
 pub mod events;
 pub mod execute_call;
+pub mod execute_response;
 pub mod execute_rollback;
+pub mod fee_balance;
 pub mod fee_handler;
 pub mod fees;
 pub mod handle_call_message;
@@ -20,10 +22,10 @@ use crate::{
     error::ContractError,
     events::{
         event_call_message, event_response_message, event_rollback_message,
-        event_xcall_message_sent,
+        event_xcall_message_sent, with_nack_reason,
     },
     msg::{InstantiateMsg, QueryMsg},
-    state::{CwCallService, EXECUTE_CALL_ID},
+    state::{CwCallService, EXECUTE_CALL_ID, REFUND_RESPONSE_FEE_REPLY_ID},
     types::{
         message::{CSMessage, CSMessageType},
         request::CSMessageRequest,
@@ -41,7 +43,7 @@ use cosmwasm_std::{
 
 use cw2::set_contract_version;
 use cw_storage_plus::{Item, Map};
-use cw_xcall_lib::xcall_msg::ExecuteMsg;
+use cw_xcall_lib::xcall_msg::{ExecuteMsg, NackReason};
 
 use serde::Serialize;
 use thiserror::Error;
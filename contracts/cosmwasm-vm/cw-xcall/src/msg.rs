@@ -1,4 +1,6 @@
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{network_address::NetId, xcall_connection_msg::NetworkStatus};
+
+use crate::types::connection::ConnectionInfo;
 
 use super::*;
 #[cw_serde]
@@ -33,4 +35,23 @@ pub enum QueryMsg {
         rollback: bool,
         sources: Option<Vec<String>>,
     },
+    #[returns(Vec<(String, u16)>)]
+    GetProtocolFeeSplits {},
+    #[returns(u128)]
+    GetFeeBalance { address: String },
+    #[returns(Vec<ConnectionStatus>)]
+    GetNetworkStatuses {},
+    #[returns(Option<ConnectionInfo>)]
+    GetConnection { address: String },
+    #[returns(Vec<ConnectionInfo>)]
+    GetConnections { nid: Option<NetId> },
+}
+
+/// Route status reported by the default connection of `nid`, `None` when the connection does not
+/// answer `GetNetworkStatus`.
+#[cw_serde]
+pub struct ConnectionStatus {
+    pub nid: NetId,
+    pub connection: String,
+    pub status: Option<NetworkStatus>,
 }
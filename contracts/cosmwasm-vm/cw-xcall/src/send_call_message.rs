@@ -1,4 +1,4 @@
-use cosmwasm_std::{coins, BankMsg};
+use cosmwasm_std::coins;
 use cw_xcall_lib::message::call_message::CallMessage;
 use cw_xcall_lib::message::msg_trait::IMessage;
 
@@ -6,7 +6,10 @@ use cw_xcall_lib::message::AnyMessage;
 use cw_xcall_lib::message::{call_message_rollback::CallMessageWithRollback, envelope::Envelope};
 use cw_xcall_lib::network_address::{NetId, NetworkAddress};
 
-use crate::{assertion::is_contract, types::LOG_PREFIX};
+use crate::{
+    assertion::is_contract,
+    types::{response_fee::ResponseFeeRecord, LOG_PREFIX},
+};
 
 use super::*;
 
@@ -38,8 +41,10 @@ impl<'a> CwCallService<'a> {
         &self,
         deps: Deps,
         caller: &Addr,
+        to: &NetworkAddress,
         envelope: &Envelope,
     ) -> Result<(), ContractError> {
+        self.ensure_registered_connections(deps, &to.nid(), &envelope.sources)?;
         match &envelope.message {
             AnyMessage::CallMessage(_m) => Ok(()),
             AnyMessage::CallMessageWithRollback(m) => {
@@ -50,6 +55,23 @@ impl<'a> CwCallService<'a> {
                 Ok(())
             }
             AnyMessage::CallMessagePersisted(_) => Ok(()),
+            AnyMessage::CallMessageWithResponse(m) => {
+                if !is_contract(deps.querier, caller) {
+                    return Err(ContractError::RollbackNotPossible);
+                }
+                self.ensure_rollback_length(&m.rollback)?;
+                Ok(())
+            }
+            AnyMessage::CallMessageWithTimeout(m) => {
+                if !is_contract(deps.querier, caller) {
+                    return Err(ContractError::RollbackNotPossible);
+                }
+                self.ensure_rollback_length(&m.rollback)?;
+                if m.timeout_height == 0 && m.timeout_timestamp == 0 {
+                    return Err(ContractError::InvalidTimeout);
+                }
+                Ok(())
+            }
         }
     }
 
@@ -60,10 +82,11 @@ impl<'a> CwCallService<'a> {
         to: NetworkAddress,
         envelope: Envelope,
     ) -> Result<Response, ContractError> {
+        to.validate()?;
         let caller = info.sender.clone();
         let config = self.get_config(deps.as_ref().storage)?;
         let nid = config.network_id;
-        self.validate_payload(deps.as_ref(), &caller, &envelope)?;
+        self.validate_payload(deps.as_ref(), &caller, &to, &envelope)?;
 
         let sequence_no = self.get_next_sn(deps.storage)?;
 
@@ -71,17 +94,20 @@ impl<'a> CwCallService<'a> {
 
         if envelope.message.rollback().is_some() {
             let rollback_data = envelope.message.rollback().unwrap();
-            let request = Rollback::new(
+            let mut request = Rollback::new(
                 caller.clone(),
                 to.clone(),
                 envelope.sources.clone(),
                 rollback_data,
                 false,
             );
+            if let AnyMessage::CallMessageWithResponse(_) = &envelope.message {
+                request.set_expects_response();
+            }
 
             self.store_call_request(deps.storage, sequence_no, &request)?;
         }
-        let call_request = CSMessageRequest::new(
+        let mut call_request = CSMessageRequest::new(
             from,
             to.account(),
             sequence_no,
@@ -89,6 +115,10 @@ impl<'a> CwCallService<'a> {
             envelope.message.data(),
             envelope.destinations,
         );
+        if let AnyMessage::CallMessageWithTimeout(m) = &envelope.message {
+            call_request.set_timeout(m.timeout_height, m.timeout_timestamp);
+        }
+        call_request.set_gas_limit(envelope.gas_limit);
         let need_response = call_request.need_response();
 
         let event = event_xcall_message_sent(caller.to_string(), to.to_string(), sequence_no);
@@ -104,7 +134,8 @@ impl<'a> CwCallService<'a> {
         let mut confirmed_sources = envelope.sources;
         if confirmed_sources.is_empty() {
             let default = self.get_default_connection(deps.as_ref().storage, to.nid())?;
-            confirmed_sources = vec![default.to_string()]
+            confirmed_sources = vec![default.to_string()];
+            self.ensure_registered_connections(deps.as_ref(), &to.nid(), &confirmed_sources)?;
         }
         let message: CSMessage = call_request.into();
         let sn: i64 = if need_response { sequence_no as i64 } else { 0 };
@@ -129,28 +160,36 @@ impl<'a> CwCallService<'a> {
             })
             .collect::<Result<Vec<SubMsg>, ContractError>>()?;
 
-        let total_paid = self.get_total_paid(deps.as_ref(), &info.funds)?;
-        let fee_handler = self.fee_handler().load(deps.storage)?;
+        if need_response {
+            let record = ResponseFeeRecord {
+                payer: caller.clone(),
+                nid: to.nid(),
+                sources: confirmed_sources,
+                refundable: false,
+            };
+            self.store_response_fee_record(deps.storage, sequence_no, &record)?;
+        }
+
+        let mut total_paid = self.get_total_paid(deps.as_ref(), &info.funds)?;
         let protocol_fee = self.get_protocol_fee(deps.as_ref().storage);
         let total_fee_required = protocol_fee + total_spent;
 
+        // fees of calls sent without funds are drawn from the caller's prepaid balance
+        if total_paid == 0 && total_fee_required > 0 {
+            total_paid = self.draw_fee_balance(deps.storage, &caller, total_fee_required)?;
+        }
+
         if total_paid < total_fee_required {
             return Err(ContractError::InsufficientFunds);
         }
         let remaining = total_paid - total_spent;
 
         println!("{LOG_PREFIX} Sent Bank Message");
-        let mut res = self
+        let fee_msgs = self.distribute_protocol_fee(deps.storage, remaining, &config.denom)?;
+        let res = self
             .send_call_response(event, sequence_no)
-            .add_submessages(submessages);
-
-        if remaining > 0 {
-            let msg = BankMsg::Send {
-                to_address: fee_handler,
-                amount: coins(remaining, config.denom),
-            };
-            res = res.add_message(msg);
-        }
+            .add_submessages(submessages)
+            .add_messages(fee_msgs);
 
         Ok(res)
     }
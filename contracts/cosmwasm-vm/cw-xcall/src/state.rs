@@ -3,7 +3,7 @@ use cw_storage_plus::{KeyDeserialize, PrimaryKey};
 use cw_xcall_lib::network_address::NetId;
 use serde::de::DeserializeOwned;
 
-use crate::types::config::Config;
+use crate::types::{config::Config, connection::ConnectionInfo, response_fee::ResponseFeeRecord};
 
 use super::*;
 
@@ -13,6 +13,8 @@ pub const MAX_ROLLBACK_SIZE: u64 = 1024;
 pub const EXECUTE_CALL_ID: u64 = 0;
 pub const EXECUTE_ROLLBACK_ID: u64 = 1;
 pub const SEND_CALL_MESSAGE_REPLY_ID: u64 = 2;
+pub const REFUND_RESPONSE_FEE_REPLY_ID: u64 = 3;
+pub const MAX_BASIS_POINTS: u128 = 10_000;
 
 pub struct CwCallService<'a> {
     sn: Item<'a, u128>,
@@ -24,11 +26,15 @@ pub struct CwCallService<'a> {
     fee_handler: Item<'a, String>,
     protocol_fee: Item<'a, u128>,
     default_connections: Map<'a, NetId, Addr>,
+    connection_registry: Map<'a, Addr, ConnectionInfo>,
     pending_requests: Map<'a, (Vec<u8>, String), bool>,
     pending_responses: Map<'a, (Vec<u8>, String), bool>,
     successful_responses: Map<'a, u128, bool>,
     callback_data: Map<'a, u64, Vec<u8>>,
     call_reply: Item<'a, CSMessageRequest>,
+    fee_balances: Map<'a, Addr, u128>,
+    fee_splits: Item<'a, Vec<(String, u16)>>,
+    response_fees: Map<'a, u128, ResponseFeeRecord>,
 }
 
 impl<'a> Default for CwCallService<'a> {
@@ -48,12 +54,16 @@ impl<'a> CwCallService<'a> {
             fee_handler: Item::new(StorageKey::FeeHandler.as_str()),
             protocol_fee: Item::new(StorageKey::ProtocolFee.as_str()),
             default_connections: Map::new(StorageKey::DefaultConnections.as_str()),
+            connection_registry: Map::new(StorageKey::ConnectionRegistry.as_str()),
             pending_requests: Map::new(StorageKey::PendingRequests.as_str()),
             pending_responses: Map::new(StorageKey::PendingResponses.as_str()),
             successful_responses: Map::new(StorageKey::SuccessfulResponses.as_str()),
             config: Item::new(StorageKey::Config.as_str()),
             callback_data: Map::new(StorageKey::Callbackdata.as_str()),
             call_reply: Item::new(StorageKey::CallReply.as_str()),
+            fee_balances: Map::new(StorageKey::FeeBalances.as_str()),
+            fee_splits: Item::new(StorageKey::FeeSplits.as_str()),
+            response_fees: Map::new(StorageKey::ResponseFees.as_str()),
         }
     }
 
@@ -182,6 +192,16 @@ impl<'a> CwCallService<'a> {
             .save(store, nid, &address)
             .map_err(ContractError::Std)
     }
+    pub fn get_default_connections(
+        &self,
+        store: &dyn Storage,
+    ) -> Result<Vec<(NetId, Addr)>, ContractError> {
+        self.default_connections
+            .range(store, None, None, Order::Ascending)
+            .collect::<Result<Vec<(NetId, Addr)>, StdError>>()
+            .map_err(ContractError::Std)
+    }
+
     pub fn get_default_connection(
         &self,
         store: &dyn Storage,
@@ -192,6 +212,38 @@ impl<'a> CwCallService<'a> {
             .map_err(ContractError::Std)
     }
 
+    pub fn get_connection_info(
+        &self,
+        store: &dyn Storage,
+        address: &Addr,
+    ) -> Option<ConnectionInfo> {
+        self.connection_registry.load(store, address.clone()).ok()
+    }
+
+    pub fn store_connection_info(
+        &self,
+        store: &mut dyn Storage,
+        connection: &ConnectionInfo,
+    ) -> Result<(), ContractError> {
+        self.connection_registry
+            .save(store, connection.address.clone(), connection)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn get_registered_connections(
+        &self,
+        store: &dyn Storage,
+    ) -> Result<Vec<ConnectionInfo>, ContractError> {
+        self.get_all_values::<Addr, ConnectionInfo>(store, &self.connection_registry)
+    }
+
+    pub fn has_registered_connections(&self, store: &dyn Storage) -> bool {
+        self.connection_registry
+            .keys(store, None, None, Order::Ascending)
+            .next()
+            .is_some()
+    }
+
     pub fn get_pending_requests_by_hash(
         &self,
         store: &dyn Storage,
@@ -320,6 +372,64 @@ impl<'a> CwCallService<'a> {
             .map_err(ContractError::Std)
     }
 
+    pub fn get_fee_splits(&self, store: &dyn Storage) -> Vec<(String, u16)> {
+        self.fee_splits.load(store).unwrap_or_default()
+    }
+
+    pub fn store_fee_splits(
+        &self,
+        store: &mut dyn Storage,
+        splits: Vec<(String, u16)>,
+    ) -> Result<(), ContractError> {
+        self.fee_splits
+            .save(store, &splits)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn get_fee_balance(&self, store: &dyn Storage, account: &Addr) -> u128 {
+        self.fee_balances.load(store, account.clone()).unwrap_or(0)
+    }
+
+    pub fn store_fee_balance(
+        &self,
+        store: &mut dyn Storage,
+        account: &Addr,
+        balance: u128,
+    ) -> Result<(), ContractError> {
+        if balance == 0 {
+            self.fee_balances.remove(store, account.clone());
+            return Ok(());
+        }
+        self.fee_balances
+            .save(store, account.clone(), &balance)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn get_response_fee_record(
+        &self,
+        store: &dyn Storage,
+        sn: u128,
+    ) -> Result<ResponseFeeRecord, ContractError> {
+        self.response_fees
+            .load(store, sn)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn store_response_fee_record(
+        &self,
+        store: &mut dyn Storage,
+        sn: u128,
+        record: &ResponseFeeRecord,
+    ) -> Result<(), ContractError> {
+        self.response_fees
+            .save(store, sn, record)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn remove_response_fee_record(&self, store: &mut dyn Storage, sn: u128) {
+        self.response_fees.remove(store, sn)
+    }
+
     pub fn get_successful_response(&self, store: &dyn Storage, sn: u128) -> bool {
         self.successful_responses.load(store, sn).unwrap_or(false)
     }
@@ -0,0 +1,20 @@
+use cosmwasm_std::Addr;
+use cw_xcall_lib::{network_address::NetId, xcall_msg::ConnectionType};
+
+use super::*;
+
+/// Connection registered by the admin. Once the registry holds a connection, only enabled
+/// connections supporting the destination network are accepted as sources of a call.
+#[cw_serde]
+pub struct ConnectionInfo {
+    pub address: Addr,
+    pub connection_type: ConnectionType,
+    pub networks: Vec<NetId>,
+    pub enabled: bool,
+}
+
+impl ConnectionInfo {
+    pub fn supports(&self, nid: &NetId) -> bool {
+        self.networks.contains(nid)
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use cw_xcall_lib::strict_rlp;
 
 #[cw_serde]
 pub enum CSMessageType {
@@ -44,7 +45,7 @@ impl Encodable for CSMessage {
 
 impl Decodable for CSMessage {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
-        let msg_type: u8 = rlp.val_at(0)?;
+        let msg_type = strict_rlp::decode_u8(&rlp.at(0)?)?;
 
         Ok(Self {
             message_type: match msg_type {
@@ -1,6 +1,8 @@
 pub mod config;
+pub mod connection;
 pub mod message;
 pub mod request;
+pub mod response_fee;
 pub mod result;
 pub mod rollback;
 pub mod storage_keys;
@@ -1,7 +1,7 @@
 use super::*;
 use common::rlp::Nullable;
 use cosmwasm_std::Addr;
-use cw_xcall_lib::{message::msg_type::MessageType, network_address::NetworkAddress};
+use cw_xcall_lib::{message::msg_type::MessageType, network_address::NetworkAddress, strict_rlp};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
@@ -13,6 +13,12 @@ pub struct CSMessageRequest {
     protocols: Vec<String>,
     msg_type: MessageType,
     data: Nullable<Vec<u8>>,
+    #[serde(default)]
+    timeout_height: u64,
+    #[serde(default)]
+    timeout_timestamp: u64,
+    #[serde(default)]
+    gas_limit: u64,
 }
 
 impl CSMessageRequest {
@@ -35,6 +41,9 @@ impl CSMessageRequest {
             msg_type,
             data: Nullable::new(data_bytes),
             protocols,
+            timeout_height: 0,
+            timeout_timestamp: 0,
+            gas_limit: 0,
         }
     }
 
@@ -56,6 +65,38 @@ impl CSMessageRequest {
 
     pub fn need_response(&self) -> bool {
         self.msg_type == MessageType::CallMessageWithRollback
+            || self.msg_type == MessageType::CallMessageWithTimeout
+            || self.msg_type == MessageType::CallMessageWithResponse
+    }
+
+    pub fn timeout_height(&self) -> u64 {
+        self.timeout_height
+    }
+
+    pub fn timeout_timestamp(&self) -> u64 {
+        self.timeout_timestamp
+    }
+
+    /// Sets the deadline of a `CallMessageWithTimeout`, only encoded for that message type.
+    pub fn set_timeout(&mut self, timeout_height: u64, timeout_timestamp: u64) {
+        self.timeout_height = timeout_height;
+        self.timeout_timestamp = timeout_timestamp;
+    }
+
+    /// Whether the request may no longer be executed at `height` and `timestamp` (seconds since
+    /// the unix epoch) of this chain. A zero deadline is not checked.
+    pub fn has_timed_out(&self, height: u64, timestamp: u64) -> bool {
+        (self.timeout_height > 0 && height >= self.timeout_height)
+            || (self.timeout_timestamp > 0 && timestamp >= self.timeout_timestamp)
+    }
+
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    /// Sets the gas the destination dapp may use to execute the request, zero for no limit.
+    pub fn set_gas_limit(&mut self, gas_limit: u64) {
+        self.gas_limit = gas_limit;
     }
 
     pub fn allow_retry(&self) -> bool {
@@ -80,9 +121,17 @@ impl CSMessageRequest {
     }
 }
 
+// the deadline of a `CallMessageWithTimeout` is appended to the fields of the other message
+// types, which keep their encoding, followed by the gas limit when there is one
 impl Encodable for CSMessageRequest {
     fn rlp_append(&self, stream: &mut rlp::RlpStream) {
-        stream.begin_list(6);
+        let with_timeout = self.msg_type == MessageType::CallMessageWithTimeout;
+        let fields = if with_timeout { 8 } else { 6 };
+        stream.begin_list(if self.gas_limit > 0 {
+            fields + 1
+        } else {
+            fields
+        });
         stream.append(&self.from.to_string());
         stream.append(&self.to.to_string());
         stream.append(&self.sequence_no);
@@ -92,6 +141,13 @@ impl Encodable for CSMessageRequest {
         for protocol in self.protocols.iter() {
             stream.append(protocol);
         }
+        if with_timeout {
+            stream.append(&self.timeout_height);
+            stream.append(&self.timeout_timestamp);
+        }
+        if self.gas_limit > 0 {
+            stream.append(&self.gas_limit);
+        }
     }
 }
 
@@ -101,15 +157,39 @@ impl Decodable for CSMessageRequest {
         let list: Vec<String> = rlp_protocols.as_list()?;
         let str_from: String = rlp.val_at(0)?;
         let to_str: String = rlp.val_at(1)?;
-        let msg_type_int: u8 = rlp.val_at(3)?;
+        let msg_type = MessageType::try_from(strict_rlp::decode_u8(&rlp.at(3)?)?)?;
+
+        let with_timeout = msg_type == MessageType::CallMessageWithTimeout;
+        let fields = if with_timeout { 8 } else { 6 };
+        let gas_limit = match rlp.item_count()? {
+            count if count == fields => 0,
+            count if count == fields + 1 => match strict_rlp::decode_u64(&rlp.at(fields)?)? {
+                // a zero limit is not encoded
+                0 => return Err(rlp::DecoderError::Custom("Zero gas limit")),
+                gas_limit => gas_limit,
+            },
+            _ => return Err(rlp::DecoderError::RlpIncorrectListLen),
+        };
+        let (timeout_height, timeout_timestamp) = if with_timeout {
+            (
+                strict_rlp::decode_u64(&rlp.at(6)?)?,
+                strict_rlp::decode_u64(&rlp.at(7)?)?,
+            )
+        } else {
+            (0, 0)
+        };
+
         Ok(Self {
             from: NetworkAddress::from_str(&str_from)
                 .map_err(|_e| rlp::DecoderError::RlpInvalidLength)?,
             to: Addr::unchecked(to_str),
-            sequence_no: rlp.val_at(2)?,
-            msg_type: MessageType::from_int(msg_type_int),
+            sequence_no: strict_rlp::decode_u128(&rlp.at(2)?)?,
+            msg_type,
             data: rlp.val_at(4)?,
             protocols: list,
+            timeout_height,
+            timeout_timestamp,
+            gas_limit,
         })
     }
 }
@@ -134,6 +214,34 @@ impl TryFrom<&[u8]> for CSMessageRequest {
     }
 }
 
+/// The fields of a request whose message type this version does not support, a newer chain
+/// having sent it, needed to answer it with a failure.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnsupportedRequest {
+    pub from: NetworkAddress,
+    pub sequence_no: u128,
+    pub msg_type: u8,
+    pub protocols: Vec<String>,
+}
+
+impl Decodable for UnsupportedRequest {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let msg_type = strict_rlp::decode_u8(&rlp.at(3)?)?;
+        if MessageType::try_from(msg_type).is_ok() {
+            return Err(rlp::DecoderError::Custom("Supported message type"));
+        }
+
+        let str_from: String = rlp.val_at(0)?;
+        Ok(Self {
+            from: NetworkAddress::from_str(&str_from)
+                .map_err(|_e| rlp::DecoderError::RlpInvalidLength)?,
+            sequence_no: strict_rlp::decode_u128(&rlp.at(2)?)?,
+            msg_type,
+            protocols: rlp.at(5)?.as_list()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -220,6 +328,56 @@ mod tests {
         assert_eq!("f84b8b3078312e4554482f307861aa63783030303030303030303030303030303030303030303030303030303030303030303030303031303215018474657374cc836162638363646583656667",hex::encode(encoded));
     }
 
+    #[test]
+    fn test_csmessage_request_with_timeout() {
+        let mut msg = CSMessageRequest::new(
+            NetworkAddress::from_str("0x1.ETH/0xa").unwrap(),
+            Addr::unchecked("cx0000000000000000000000000000000000000102"),
+            21,
+            MessageType::CallMessageWithTimeout,
+            hex::decode("74657374").unwrap(),
+            vec![],
+        );
+        msg.set_timeout(100, 0);
+
+        let encoded = rlp::encode(&msg).to_vec();
+        assert_eq!(rlp::Rlp::new(&encoded).item_count(), Ok(8));
+        assert_eq!(CSMessageRequest::try_from(&encoded).unwrap(), msg);
+
+        // a zero timestamp is not checked
+        assert!(!msg.has_timed_out(99, u64::MAX));
+        assert!(msg.has_timed_out(100, 0));
+    }
+
+    #[test]
+    fn test_csmessage_request_with_gas_limit() {
+        let mut msg = CSMessageRequest::new(
+            NetworkAddress::from_str("0x1.ETH/0xa").unwrap(),
+            Addr::unchecked("cx0000000000000000000000000000000000000102"),
+            21,
+            MessageType::CallMessageWithTimeout,
+            hex::decode("74657374").unwrap(),
+            vec![],
+        );
+        msg.set_timeout(100, 0);
+        msg.set_gas_limit(200_000);
+
+        let encoded = rlp::encode(&msg).to_vec();
+        assert_eq!(rlp::Rlp::new(&encoded).item_count(), Ok(9));
+        assert_eq!(CSMessageRequest::try_from(&encoded).unwrap(), msg);
+
+        // a zero limit is omitted rather than encoded
+        let mut stream = RlpStream::new_list(7);
+        stream.append(&"0x1.ETH/0xa".to_string());
+        stream.append(&"cx0000000000000000000000000000000000000102".to_string());
+        stream.append(&21_u128);
+        stream.append(&0_u8);
+        stream.append(&hex::decode("74657374").unwrap());
+        stream.begin_list(0);
+        stream.append(&0_u64);
+        assert!(CSMessageRequest::try_from(&stream.out().to_vec()).is_err());
+    }
+
     #[test]
     fn test_network_address() {
         let addr = NetworkAddress::from_str("0x1.ETH/0xa").unwrap();
@@ -0,0 +1,15 @@
+use cosmwasm_std::Addr;
+use cw_xcall_lib::network_address::NetId;
+
+use super::*;
+
+/// Tracks who prepaid the response fee of a sequence number and through which connections, so
+/// the fee can be refunded when the sequence number is resolved without a response being
+/// delivered.
+#[cw_serde]
+pub struct ResponseFeeRecord {
+    pub payer: Addr,
+    pub nid: NetId,
+    pub sources: Vec<String>,
+    pub refundable: bool,
+}
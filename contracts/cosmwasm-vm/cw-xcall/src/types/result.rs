@@ -1,4 +1,5 @@
 use super::*;
+use cw_xcall_lib::strict_rlp;
 
 #[cw_serde]
 pub enum CallServiceResponseType {
@@ -57,6 +58,11 @@ impl CSMessageResult {
         self.response_code = response_code;
     }
 
+    /// The raw message: the encoded reply, or the data returned for a `CallMessageWithResponse`.
+    pub fn message(&self) -> &Vec<u8> {
+        &self.message
+    }
+
     pub fn get_message(&self) -> Option<CSMessageRequest> {
         if self.message.is_empty() {
             return None;
@@ -83,10 +89,10 @@ impl Encodable for CSMessageResult {
 
 impl Decodable for CSMessageResult {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
-        let code: u8 = rlp.val_at(1)?;
+        let code = strict_rlp::decode_u8(&rlp.at(1)?)?;
 
         Ok(Self {
-            sequence_no: rlp.val_at(0)?,
+            sequence_no: strict_rlp::decode_u128(&rlp.at(0)?)?,
             response_code: CallServiceResponseType::try_from(code)?,
             message: rlp.val_at(2).unwrap_or_default(),
         })
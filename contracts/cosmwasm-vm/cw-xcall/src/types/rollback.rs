@@ -11,6 +11,10 @@ pub struct Rollback {
     protocols: Vec<String>,
     rollback: Vec<u8>,
     enabled: bool,
+    #[serde(default)]
+    expects_response: bool,
+    #[serde(default)]
+    response: Option<Vec<u8>>,
 }
 
 impl Rollback {
@@ -27,6 +31,8 @@ impl Rollback {
             rollback,
             protocols,
             enabled,
+            expects_response: false,
+            response: None,
         }
     }
 
@@ -57,4 +63,23 @@ impl Rollback {
     pub fn set_enabled(&mut self) {
         self.enabled = true;
     }
+
+    /// Whether the request is a `CallMessageWithResponse`, its successful result carrying the
+    /// data returned by the destination.
+    pub fn expects_response(&self) -> bool {
+        self.expects_response
+    }
+
+    pub fn set_expects_response(&mut self) {
+        self.expects_response = true;
+    }
+
+    /// The data returned by the destination, until delivered by `execute_response`.
+    pub fn response(&self) -> Option<&Vec<u8>> {
+        self.response.as_ref()
+    }
+
+    pub fn set_response(&mut self, data: Vec<u8>) {
+        self.response = Some(data);
+    }
 }
@@ -12,12 +12,16 @@ pub enum StorageKey {
     ProtocolFee,
     DefaultConnections,
     Connections,
+    ConnectionRegistry,
     PendingRequests,
     PendingResponses,
     SuccessfulResponses,
     Config,
     Callbackdata,
     CallReply,
+    FeeBalances,
+    FeeSplits,
+    ResponseFees,
 }
 
 impl StorageKey {
@@ -33,12 +37,16 @@ impl StorageKey {
             StorageKey::ProtocolFee => "protocol_fee",
             StorageKey::DefaultConnections => "default_connections",
             StorageKey::Connections => "connections",
+            StorageKey::ConnectionRegistry => "connection_registry",
             StorageKey::PendingRequests => "pending_requests",
             StorageKey::PendingResponses => "pending_responses",
             StorageKey::SuccessfulResponses => "successful_responses",
             StorageKey::Config => "config",
             StorageKey::Callbackdata => "callback_data",
             StorageKey::CallReply => "call_reply",
+            StorageKey::FeeBalances => "fee_balances",
+            StorageKey::FeeSplits => "fee_splits",
+            StorageKey::ResponseFees => "response_fees",
         }
     }
 }
@@ -16,8 +16,8 @@ use cw_xcall::{
 };
 use cw_xcall_lib::{
     message::{
-        call_message_persisted::CallMessagePersisted, envelope::Envelope, msg_type::MessageType,
-        AnyMessage,
+        call_message_persisted::CallMessagePersisted, call_message_timeout::CallMessageWithTimeout,
+        envelope::Envelope, msg_type::MessageType, AnyMessage,
     },
     network_address::{NetId, NetworkAddress},
 };
@@ -280,6 +280,87 @@ fn send_packet_success_needresponse() {
     assert!(!result.enabled())
 }
 
+#[test]
+#[should_panic(expected = "InvalidTimeout")]
+fn send_packet_failure_due_missing_timeout() {
+    let mut mock_deps = deps();
+
+    let mock_info = create_mock_info(MOCK_CONTRACT_ADDR, "arch", 2000);
+
+    let contract = CwCallService::default();
+    contract
+        .instantiate(
+            mock_deps.as_mut(),
+            mock_env(),
+            mock_info.clone(),
+            cw_xcall::msg::InstantiateMsg {
+                network_id: "nid".to_string(),
+                denom: "arch".to_string(),
+            },
+        )
+        .unwrap();
+
+    mock_deps.querier.update_wasm(|r| match r {
+        WasmQuery::ContractInfo { contract_addr: _ } => SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&ContractInfoResponse::default()).unwrap(),
+        )),
+        _ => todo!(),
+    });
+
+    let envelope = Envelope::new(
+        AnyMessage::CallMessageWithTimeout(CallMessageWithTimeout {
+            data: vec![1, 2, 3],
+            rollback: vec![1, 2, 3],
+            timeout_height: 0,
+            timeout_timestamp: 0,
+        }),
+        vec![],
+        vec![],
+    );
+    contract
+        .send_call(
+            mock_deps.as_mut(),
+            mock_info,
+            NetworkAddress::new("btp", MOCK_CONTRACT_TO_ADDR),
+            envelope,
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "InvalidNetworkAddress(TooManySeparators)")]
+fn send_packet_failure_due_invalid_to() {
+    let mut mock_deps = deps();
+
+    let mock_info = create_mock_info(MOCK_CONTRACT_ADDR, "arch", 2000);
+
+    let contract = CwCallService::default();
+    contract
+        .instantiate(
+            mock_deps.as_mut(),
+            mock_env(),
+            mock_info.clone(),
+            cw_xcall::msg::InstantiateMsg {
+                network_id: "nid".to_string(),
+                denom: "arch".to_string(),
+            },
+        )
+        .unwrap();
+
+    contract
+        .send_call_message(
+            mock_deps.as_mut(),
+            mock_info,
+            mock_env(),
+            NetworkAddress::new("btp", "cosmos/contract"),
+            vec![1, 2, 3],
+            None,
+            vec![],
+            vec![],
+        )
+        .unwrap();
+}
+
 #[test]
 #[should_panic(expected = "InsufficientFunds")]
 fn send_packet_fail_insufficient_funds() {
@@ -8,20 +8,23 @@ use setup::{test::*, *};
 use std::str::FromStr;
 
 use cosmwasm_std::{
+    from_json,
     testing::{mock_env, MOCK_CONTRACT_ADDR},
-    to_json_binary, Addr, Event, Reply, SubMsgResponse, SubMsgResult,
+    to_json_binary, Addr, ContractResult, Event, Reply, SubMsgResponse, SubMsgResult, SystemError,
+    SystemResult, WasmQuery,
 };
 use cw_xcall::{
     execute, instantiate, migrate,
-    msg::{InstantiateMsg, QueryMsg},
+    msg::{ConnectionStatus, InstantiateMsg, QueryMsg},
     query, reply,
     state::CwCallService,
-    types::{request::CSMessageRequest, rollback::Rollback},
+    types::{connection::ConnectionInfo, request::CSMessageRequest, rollback::Rollback},
 };
 use cw_xcall_lib::{
     message::msg_type::MessageType,
     network_address::{NetId, NetworkAddress},
-    xcall_msg::ExecuteMsg,
+    xcall_connection_msg::NetworkStatus,
+    xcall_msg::{ConnectionType, ExecuteMsg},
 };
 
 #[test]
@@ -387,3 +390,155 @@ fn test_execute_call_reply() {
         .unwrap();
     assert_eq!(res.attributes[1].value, "execute_callback")
 }
+
+#[test]
+fn test_get_network_statuses() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+    let contract = CwCallService::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    contract
+        .store_default_connection(
+            deps.as_mut().storage,
+            NetId::from_str("icon").unwrap(),
+            Addr::unchecked("legacy"),
+        )
+        .unwrap();
+
+    let status = NetworkStatus {
+        last_received_conn_sn: 4,
+        last_received_height: 100,
+        last_sent_conn_sn: 7,
+        last_sent_height: 120,
+        pending_reverts: 1,
+    };
+    let reported = status.clone();
+    deps.querier.update_wasm(move |r| match r {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "centralized" => {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&reported).unwrap()))
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::GetNetworkStatuses {}).unwrap();
+    let statuses: Vec<ConnectionStatus> = from_json(res).unwrap();
+    assert_eq!(
+        statuses,
+        vec![
+            ConnectionStatus {
+                nid: NetId::from_str("archway").unwrap(),
+                connection: "centralized".to_string(),
+                status: Some(status),
+            },
+            ConnectionStatus {
+                nid: NetId::from_str("icon").unwrap(),
+                connection: "legacy".to_string(),
+                status: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_connection_registry() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+    let contract = CwCallService::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let send = |sources: Vec<&str>, nid: &str| ExecuteMsg::SendCallMessage {
+        to: get_dummy_network_address(nid),
+        data: vec![1, 2, 3],
+        rollback: None,
+        sources: Some(sources.into_iter().map(|s| s.to_string()).collect()),
+        destinations: None,
+    };
+
+    let msg = ExecuteMsg::RegisterConnection {
+        address: "centralized".to_string(),
+        connection_type: ConnectionType::Centralized,
+        networks: vec![NetId::from_str("archway").unwrap()],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        create_mock_info("user", "icx", 0),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "OnlyAdmin");
+    execute(deps.as_mut(), mock_env(), ctx.info.clone(), msg).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        ctx.info.clone(),
+        send(vec!["centralised"], "archway"),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "UnregisteredConnection centralised");
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        ctx.info.clone(),
+        send(vec!["centralized"], "icon"),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "UnsupportedNetwork icon for centralized");
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        ctx.info.clone(),
+        send(vec!["centralized"], "archway"),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::SetDefaultConnection {
+        nid: NetId::from_str("icon").unwrap(),
+        address: Addr::unchecked("centralized"),
+    };
+    let err = execute(deps.as_mut(), mock_env(), ctx.info.clone(), msg).unwrap_err();
+    assert_eq!(err.to_string(), "UnsupportedNetwork icon for centralized");
+
+    let msg = ExecuteMsg::SetConnectionEnabled {
+        address: "centralized".to_string(),
+        enabled: false,
+    };
+    execute(deps.as_mut(), mock_env(), ctx.info.clone(), msg).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        ctx.info.clone(),
+        send(vec!["centralized"], "archway"),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "ConnectionDisabled centralized");
+
+    // the default connection is held to the registry too
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        ctx.info.clone(),
+        send(vec![], "archway"),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "ConnectionDisabled centralized");
+
+    let query_msg = QueryMsg::GetConnections {
+        nid: Some(NetId::from_str("archway").unwrap()),
+    };
+    let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+    let connections: Vec<ConnectionInfo> = from_json(res).unwrap();
+    assert_eq!(
+        connections,
+        vec![ConnectionInfo {
+            address: Addr::unchecked("centralized"),
+            connection_type: ConnectionType::Centralized,
+            networks: vec![NetId::from_str("archway").unwrap()],
+            enabled: false,
+        }]
+    );
+}
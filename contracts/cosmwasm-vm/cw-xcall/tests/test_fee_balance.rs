@@ -0,0 +1,235 @@
+mod account;
+mod setup;
+
+use setup::test::*;
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    from_json,
+    testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR},
+    to_json_binary, Addr, BankMsg, Binary, ContractInfoResponse, ContractResult, CosmosMsg,
+    SystemError, SystemResult, WasmQuery,
+};
+use cw_xcall::{msg::QueryMsg, state::CwCallService};
+use cw_xcall_lib::{
+    network_address::{NetId, NetworkAddress},
+    xcall_msg::ExecuteMsg,
+};
+
+const MOCK_CONTRACT_TO_ADDR: &str = "cosmoscontract";
+
+fn instantiate(contract: &CwCallService, deps: cosmwasm_std::DepsMut) {
+    contract
+        .instantiate(
+            deps,
+            mock_env(),
+            mock_info("admin", &[]),
+            cw_xcall::msg::InstantiateMsg {
+                network_id: "nid".to_string(),
+                denom: "arch".to_string(),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn deposit_and_query_fee_balance() {
+    let mut mock_deps = deps();
+    let mut contract = CwCallService::default();
+    instantiate(&contract, mock_deps.as_mut());
+
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env(),
+            create_mock_info(MOCK_CONTRACT_ADDR, "arch", 500),
+            ExecuteMsg::DepositFeeBalance { address: None },
+        )
+        .unwrap();
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env(),
+            create_mock_info("sponsor", "arch", 100),
+            ExecuteMsg::DepositFeeBalance {
+                address: Some(MOCK_CONTRACT_ADDR.to_string()),
+            },
+        )
+        .unwrap();
+
+    let result = contract
+        .query(
+            mock_deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetFeeBalance {
+                address: MOCK_CONTRACT_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+    let result: u128 = from_json(result).unwrap();
+    assert_eq!(result, 600);
+}
+
+#[test]
+#[should_panic(expected = "InsufficientFunds")]
+fn deposit_fee_balance_without_funds() {
+    let mut mock_deps = deps();
+    let contract = CwCallService::default();
+    instantiate(&contract, mock_deps.as_mut());
+
+    contract
+        .deposit_fee_balance(
+            mock_deps.as_mut(),
+            create_mock_info(MOCK_CONTRACT_ADDR, "uatom", 500),
+            None,
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Overflow")]
+fn deposit_fee_balance_overflow() {
+    let mut mock_deps = deps();
+    let contract = CwCallService::default();
+    instantiate(&contract, mock_deps.as_mut());
+
+    contract
+        .store_fee_balance(
+            mock_deps.as_mut().storage,
+            &Addr::unchecked(MOCK_CONTRACT_ADDR),
+            u128::MAX,
+        )
+        .unwrap();
+
+    contract
+        .deposit_fee_balance(
+            mock_deps.as_mut(),
+            create_mock_info(MOCK_CONTRACT_ADDR, "arch", 1),
+            None,
+        )
+        .unwrap();
+}
+
+#[test]
+fn withdraw_fee_balance() {
+    let mut mock_deps = deps();
+    let contract = CwCallService::default();
+    instantiate(&contract, mock_deps.as_mut());
+
+    contract
+        .deposit_fee_balance(
+            mock_deps.as_mut(),
+            create_mock_info(MOCK_CONTRACT_ADDR, "arch", 500),
+            None,
+        )
+        .unwrap();
+
+    let res = contract
+        .withdraw_fee_balance(mock_deps.as_mut(), mock_info(MOCK_CONTRACT_ADDR, &[]), 200)
+        .unwrap();
+
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: MOCK_CONTRACT_ADDR.to_string(),
+            amount: cosmwasm_std::coins(200, "arch"),
+        })
+    );
+    assert_eq!(
+        contract.get_fee_balance(
+            mock_deps.as_ref().storage,
+            &Addr::unchecked(MOCK_CONTRACT_ADDR)
+        ),
+        300
+    );
+}
+
+#[test]
+#[should_panic(expected = "InsufficientFunds")]
+fn withdraw_fee_balance_exceeding_balance() {
+    let mut mock_deps = deps();
+    let contract = CwCallService::default();
+    instantiate(&contract, mock_deps.as_mut());
+
+    contract
+        .deposit_fee_balance(
+            mock_deps.as_mut(),
+            create_mock_info(MOCK_CONTRACT_ADDR, "arch", 500),
+            None,
+        )
+        .unwrap();
+
+    contract
+        .withdraw_fee_balance(mock_deps.as_mut(), mock_info(MOCK_CONTRACT_ADDR, &[]), 501)
+        .unwrap();
+}
+
+#[test]
+fn send_call_drawn_from_fee_balance() {
+    let mut mock_deps = deps();
+    let contract = CwCallService::default();
+    instantiate(&contract, mock_deps.as_mut());
+
+    contract.sn().save(mock_deps.as_mut().storage, &0).unwrap();
+    contract
+        .store_protocol_fee(mock_deps.as_mut().storage, 5)
+        .unwrap();
+
+    mock_deps.querier.update_wasm(|r| {
+        let constract1 = Addr::unchecked(MOCK_CONTRACT_ADDR);
+        let mut storage1 = HashMap::<Binary, Binary>::default();
+        storage1.insert(b"the key".into(), b"the value".into());
+        match r {
+            WasmQuery::ContractInfo { contract_addr } => {
+                if *contract_addr == constract1 {
+                    let response = ContractInfoResponse::new(0, "test");
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+                } else {
+                    SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    })
+                }
+            }
+            WasmQuery::Smart {
+                contract_addr: _,
+                msg: _,
+            } => SystemResult::Ok(ContractResult::Ok(to_json_binary(&10_u128).unwrap())),
+            _ => todo!(),
+        }
+    });
+
+    contract
+        .store_default_connection(
+            mock_deps.as_mut().storage,
+            NetId::from("btp".to_owned()),
+            Addr::unchecked("hostaddress"),
+        )
+        .unwrap();
+
+    contract
+        .deposit_fee_balance(
+            mock_deps.as_mut(),
+            create_mock_info(MOCK_CONTRACT_ADDR, "arch", 100),
+            None,
+        )
+        .unwrap();
+
+    contract
+        .send_call_message(
+            mock_deps.as_mut(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            mock_env(),
+            NetworkAddress::new("btp", MOCK_CONTRACT_TO_ADDR),
+            vec![1, 2, 3],
+            Some(vec![1, 2, 3]),
+            vec![],
+            vec![],
+        )
+        .unwrap();
+
+    let balance = contract.get_fee_balance(
+        mock_deps.as_ref().storage,
+        &Addr::unchecked(MOCK_CONTRACT_ADDR),
+    );
+    assert_eq!(balance, 85);
+}
@@ -1,9 +1,9 @@
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    from_json,
+    coins, from_json,
     testing::{mock_dependencies, mock_env, mock_info},
-    to_json_binary, Addr, Coin, ContractResult, SystemResult, WasmQuery,
+    to_json_binary, Addr, BankMsg, Coin, ContractResult, SystemResult, WasmQuery,
 };
 use cw_xcall::{msg::QueryMsg, state::CwCallService};
 pub mod account;
@@ -205,3 +205,95 @@ fn get_fee() {
     let result: u128 = from_json(result).unwrap();
     assert_eq!("223", result.to_string());
 }
+
+#[test]
+fn set_protocol_fee_splits() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::new();
+
+    contract
+        .set_admin(
+            deps.as_mut().storage,
+            Addr::unchecked(admin_one().to_string()),
+        )
+        .unwrap();
+    let info = mock_info(&admin_one().to_string(), &[]);
+    let splits = vec![("alice".to_string(), 7000), ("bob".to_string(), 3000)];
+    contract
+        .set_protocol_fee_splits(deps.as_mut(), &info, splits.clone())
+        .unwrap();
+
+    let result = contract
+        .query(deps.as_ref(), mock_env(), QueryMsg::GetProtocolFeeSplits {})
+        .unwrap();
+    let result: Vec<(String, u16)> = from_json(result).unwrap();
+    assert_eq!(splits, result);
+}
+
+#[test]
+#[should_panic(expected = "InvalidFeeSplits")]
+fn set_protocol_fee_splits_not_adding_up() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::new();
+
+    contract
+        .set_admin(
+            deps.as_mut().storage,
+            Addr::unchecked(admin_one().to_string()),
+        )
+        .unwrap();
+    let info = mock_info(&admin_one().to_string(), &[]);
+    contract
+        .set_protocol_fee_splits(
+            deps.as_mut(),
+            &info,
+            vec![("alice".to_string(), 7000), ("bob".to_string(), 2000)],
+        )
+        .unwrap();
+}
+
+#[test]
+fn distribute_protocol_fee_by_splits() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::new();
+
+    contract
+        .set_admin(
+            deps.as_mut().storage,
+            Addr::unchecked(admin_one().to_string()),
+        )
+        .unwrap();
+    contract
+        .fee_handler()
+        .save(&mut deps.storage, &"xyz".to_string())
+        .unwrap();
+    let info = mock_info(&admin_one().to_string(), &[]);
+    contract
+        .set_protocol_fee_splits(
+            deps.as_mut(),
+            &info,
+            vec![("alice".to_string(), 6667), ("bob".to_string(), 3333)],
+        )
+        .unwrap();
+
+    let msgs = contract
+        .distribute_protocol_fee(deps.as_ref().storage, 100, "arch")
+        .unwrap();
+    assert_eq!(
+        msgs,
+        vec![
+            BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(66, "arch"),
+            },
+            BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(33, "arch"),
+            },
+            BankMsg::Send {
+                to_address: "xyz".to_string(),
+                amount: coins(1, "arch"),
+            },
+        ]
+    );
+}
@@ -0,0 +1,99 @@
+use std::str::FromStr;
+
+use common::rlp;
+use cosmwasm_std::Addr;
+use cw_xcall::types::{
+    message::{CSMessage, CSMessageType},
+    request::CSMessageRequest,
+    result::{CSMessageResult, CallServiceResponseType},
+};
+use cw_xcall_lib::{message::msg_type::MessageType, network_address::NetworkAddress};
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("../../../../test-vectors/xcall/messages.json");
+
+fn bytes(value: &Value) -> Vec<u8> {
+    hex::decode(value.as_str().unwrap()).unwrap()
+}
+
+fn sn(value: &Value) -> u128 {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+fn request(value: &Value) -> CSMessageRequest {
+    let mut request = CSMessageRequest::new(
+        NetworkAddress::from_str(value["from"].as_str().unwrap()).unwrap(),
+        Addr::unchecked(value["to"].as_str().unwrap()),
+        sn(&value["sn"]),
+        MessageType::from_int(value["msg_type"].as_u64().unwrap() as u8),
+        bytes(&value["data"]),
+        value["protocols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|protocol| protocol.as_str().unwrap().to_string())
+            .collect(),
+    );
+    if let (Some(height), Some(timestamp)) = (
+        value["timeout_height"].as_u64(),
+        value["timeout_timestamp"].as_u64(),
+    ) {
+        request.set_timeout(height, timestamp);
+    }
+    if let Some(gas_limit) = value["gas_limit"].as_u64() {
+        request.set_gas_limit(gas_limit);
+    }
+    request
+}
+
+fn result(value: &Value) -> CSMessageResult {
+    let message = bytes(&value["message"]);
+    CSMessageResult::new(
+        sn(&value["sn"]),
+        CallServiceResponseType::try_from(value["code"].as_u64().unwrap() as u8).unwrap(),
+        (!message.is_empty()).then_some(message),
+    )
+}
+
+fn message(value: &Value) -> CSMessage {
+    let message_type = match value["type"].as_u64().unwrap() {
+        1 => CSMessageType::CSMessageRequest,
+        2 => CSMessageType::CSMessageResult,
+        _ => panic!("unknown message type"),
+    };
+    CSMessage::new(message_type, bytes(&value["payload"]))
+}
+
+#[test]
+fn test_golden_vectors() {
+    let vectors: Vec<Value> = serde_json::from_str(VECTORS).unwrap();
+
+    for vector in vectors.iter() {
+        let name = vector["name"].as_str().unwrap();
+        let value = &vector["value"];
+        let encoded = bytes(&vector["hex"]);
+
+        match vector["type"].as_str().unwrap() {
+            "CSMessageRequest" => {
+                let expected = request(value);
+                assert_eq!(rlp::encode(&expected).to_vec(), encoded, "{}", name);
+                let decoded = CSMessageRequest::try_from(&encoded).unwrap();
+                assert_eq!(decoded, expected, "{}", name);
+            }
+            "CSMessageResult" => {
+                let expected = result(value);
+                assert_eq!(rlp::encode(&expected).to_vec(), encoded, "{}", name);
+                let decoded = CSMessageResult::try_from(&encoded).unwrap();
+                assert_eq!(decoded, expected, "{}", name);
+            }
+            "CSMessage" => {
+                let expected = message(value);
+                assert_eq!(rlp::encode(&expected).to_vec(), encoded, "{}", name);
+                let decoded = CSMessage::try_from(encoded).unwrap();
+                assert_eq!(decoded, expected, "{}", name);
+            }
+            // envelope and call messages are tested in cw-xcall-lib
+            _ => {}
+        }
+    }
+}
@@ -2,7 +2,7 @@ mod account;
 mod setup;
 use crate::account::alice;
 
-use common::utils::keccak256;
+use common::{rlp::RlpStream, utils::keccak256};
 use schemars::_serde_json::to_string;
 use setup::test::*;
 use setup::*;
@@ -11,19 +11,24 @@ use std::str::FromStr;
 use cosmwasm_std::{
     from_json,
     testing::{mock_dependencies, mock_env, mock_info},
-    to_json_binary, Addr, Coin, CosmosMsg, Reply, SubMsgResponse, SubMsgResult, WasmMsg,
+    to_json_binary, Addr, Coin, CosmosMsg, Reply, ReplyOn, SubMsgResponse, SubMsgResult, WasmMsg,
 };
 use cw_xcall::{
-    state::{CwCallService, EXECUTE_CALL_ID},
+    state::{CwCallService, EXECUTE_CALL_ID, REFUND_RESPONSE_FEE_REPLY_ID},
     types::{
         message::{CSMessage, CSMessageType},
         request::CSMessageRequest,
+        response_fee::ResponseFeeRecord,
+        result::{CSMessageResult, CallServiceResponseType},
         rollback::Rollback,
     },
 };
 use cw_xcall_lib::{
+    dapp_multi_msg,
     message::msg_type::MessageType,
     network_address::{NetId, NetworkAddress},
+    xcall_connection_msg,
+    xcall_msg::NackReason,
 };
 
 #[test]
@@ -60,10 +65,63 @@ fn test_execute_call_with_wrong_data() {
         .unwrap();
 
     cw_callservice
-        .execute_call(deps.as_mut(), info, request_id, data)
+        .execute_call(deps.as_mut(), mock_env(), info, request_id, data)
         .unwrap();
 }
 
+#[test]
+fn test_execute_call_after_timeout() {
+    let mut deps = mock_dependencies();
+
+    let info = mock_info("user1", &[Coin::new(1000, "ucosm")]);
+    let cw_callservice = CwCallService::default();
+    let data = vec![104, 101, 108, 108, 111];
+    let request_id = 123456;
+    let mut proxy_requests = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("88bd05442686be0a5df7da33b6f1089ebfea3769b19dbb2477fe0cd6e0f123t7"),
+        123,
+        MessageType::CallMessageWithTimeout,
+        keccak256(&data).to_vec(),
+        vec!["centralized".to_string()],
+    );
+    let env = mock_env();
+    proxy_requests.set_timeout(0, env.block.time.seconds());
+    cw_callservice
+        .store_proxy_request(deps.as_mut().storage, request_id, &proxy_requests)
+        .unwrap();
+
+    let res = cw_callservice
+        .execute_call(deps.as_mut(), env, info, request_id, data)
+        .unwrap();
+
+    // the dapp is not called, the failure is sent back to the source
+    let result = CSMessageResult::new(
+        123,
+        CallServiceResponseType::CallServiceResponseFailure,
+        None,
+    );
+    let expected = xcall_connection_msg::ExecuteMsg::SendMessage {
+        to: NetId::from_str("nid").unwrap(),
+        sn: -123,
+        msg: CSMessage::from(result).as_bytes(),
+    };
+    assert_eq!(res.messages.len(), 1);
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            assert_eq!(contract_addr, "centralized");
+            assert_eq!(msg, &to_json_binary(&expected).unwrap());
+        }
+        _ => panic!("unexpected submessage"),
+    }
+    assert_eq!(res.events[0].attributes[1].value, "0");
+    assert!(cw_callservice
+        .contains_proxy_request(deps.as_ref().storage, request_id)
+        .is_err());
+}
+
 #[test]
 fn test_execute_call_having_request_id_without_rollback() {
     let mut deps = mock_dependencies();
@@ -85,7 +143,7 @@ fn test_execute_call_having_request_id_without_rollback() {
         .unwrap();
 
     let res = cw_callservice
-        .execute_call(deps.as_mut(), info, request_id, data)
+        .execute_call(deps.as_mut(), mock_env(), info, request_id, data)
         .unwrap();
     match &res.messages[0].msg {
         CosmosMsg::Wasm(WasmMsg::Execute {
@@ -107,6 +165,35 @@ fn test_execute_call_having_request_id_without_rollback() {
     }
 }
 
+#[test]
+fn test_execute_call_with_gas_limit() {
+    let mut deps = mock_dependencies();
+
+    let info = mock_info("user1", &[]);
+    let cw_callservice = CwCallService::default();
+    let data = vec![104, 101, 108, 108, 111];
+    let request_id = 123456;
+    let mut proxy_requests = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("88bd05442686be0a5df7da33b6f1089ebfea3769b19dbb2477fe0cd6e0f123t7"),
+        123,
+        MessageType::CallMessageWithRollback,
+        keccak256(&data).to_vec(),
+        vec![],
+    );
+    proxy_requests.set_gas_limit(200_000);
+    cw_callservice
+        .store_proxy_request(deps.as_mut().storage, request_id, &proxy_requests)
+        .unwrap();
+
+    let res = cw_callservice
+        .execute_call(deps.as_mut(), mock_env(), info, request_id, data)
+        .unwrap();
+
+    assert_eq!(res.messages[0].gas_limit, Some(200_000));
+    assert_eq!(res.messages[0].reply_on, cosmwasm_std::ReplyOn::Always);
+}
+
 #[test]
 fn test_successful_reply_message() {
     let mut mock_deps = deps();
@@ -145,6 +232,94 @@ fn test_successful_reply_message() {
     assert_eq!(response.events[0].attributes[1].value, 1.to_string());
 }
 
+#[test]
+fn test_reply_message_returns_dapp_data() {
+    let mut mock_deps = deps();
+    let contract = CwCallService::default();
+
+    // the data set by the dapp, wrapped in a MsgExecuteContractResponse
+    let msg = Reply {
+        id: EXECUTE_CALL_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: Some(vec![0x0a, 3, 7, 8, 9].into()),
+        }),
+    };
+
+    let request_id = 123456;
+    let proxy_requests = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("88bd05442686be0a5df7da33b6f1089ebfea3769b19dbb2477fe0cd6e0f123t7"),
+        123,
+        MessageType::CallMessageWithResponse,
+        vec![],
+        vec!["centralized".to_string()],
+    );
+    contract
+        .store_proxy_request(mock_deps.as_mut().storage, request_id, &proxy_requests)
+        .unwrap();
+    contract
+        .store_execute_request_id(mock_deps.as_mut().storage, request_id)
+        .unwrap();
+
+    let response = contract.reply(mock_deps.as_mut(), mock_env(), msg).unwrap();
+
+    let result = CSMessageResult::new(
+        123,
+        CallServiceResponseType::CallServiceResponseSuccess,
+        Some(vec![7, 8, 9]),
+    );
+    let expected = xcall_connection_msg::ExecuteMsg::SendMessage {
+        to: NetId::from_str("nid").unwrap(),
+        sn: -123,
+        msg: CSMessage::from(result).as_bytes(),
+    };
+    match &response.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+            assert_eq!(msg, &to_json_binary(&expected).unwrap());
+        }
+        _ => panic!("unexpected submessage"),
+    }
+}
+
+#[test]
+fn test_reply_message_rejects_call_reply_with_response() {
+    let mut mock_deps = deps();
+    let contract = CwCallService::default();
+
+    let msg = Reply {
+        id: EXECUTE_CALL_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: Some(vec![0x0a, 3, 7, 8, 9].into()),
+        }),
+    };
+
+    let request_id = 123456;
+    let proxy_requests = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("88bd05442686be0a5df7da33b6f1089ebfea3769b19dbb2477fe0cd6e0f123t7"),
+        123,
+        MessageType::CallMessageWithResponse,
+        vec![],
+        vec!["centralized".to_string()],
+    );
+    contract
+        .store_proxy_request(mock_deps.as_mut().storage, request_id, &proxy_requests)
+        .unwrap();
+    contract
+        .store_execute_request_id(mock_deps.as_mut().storage, request_id)
+        .unwrap();
+    contract
+        .save_call_reply(mock_deps.as_mut().storage, &proxy_requests)
+        .unwrap();
+
+    let err = contract
+        .reply(mock_deps.as_mut(), mock_env(), msg)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "CallReplyNotAllowed 123");
+}
+
 #[test]
 fn test_failed_reply_message() {
     let mut mock_deps = deps();
@@ -465,6 +640,65 @@ fn test_handle_request_from_multiple_protocols() {
     }
 }
 
+#[test]
+fn test_handle_request_drops_unsupported_message_type() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let mut stream = RlpStream::new_list(6);
+    stream.append(&get_dummy_network_address("archway").to_string());
+    stream.append(&"dapp".to_string());
+    stream.append(&1_u128);
+    stream.append(&7_u8);
+    stream.append(&vec![1_u8, 2, 3]);
+    stream.begin_list(0);
+    let data = stream.out().to_vec();
+
+    let info = create_mock_info("centralized", "icx", 100);
+    let nid = NetId::from_str("archway").unwrap();
+    let res = contract
+        .handle_request(deps.as_mut(), info, nid, &data)
+        .unwrap();
+    assert_eq!(res.attributes[2].value, "7");
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(res.events[0].ty, "UnsupportedRequest");
+    assert_eq!(res.events[0].attributes[1].value, "1");
+    assert_eq!(res.events[0].attributes[2].value, "7");
+}
+
+#[test]
+fn test_handle_message_rejects_non_canonical_encoding() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    let nid = NetId::from_str("archway").unwrap();
+
+    let message_result = get_dummy_result_message();
+    let mut msg =
+        CSMessage::new(CSMessageType::CSMessageResult, message_result.as_bytes()).as_bytes();
+    msg.push(0x00);
+    let err = contract
+        .handle_message(deps.as_mut(), ctx.info.clone(), nid.clone(), msg)
+        .unwrap_err();
+    assert!(err.to_string().starts_with("DecodeFailed"));
+
+    // sequence number 1 with a redundant leading zero
+    let mut stream = RlpStream::new_list(3);
+    stream.append(&vec![0x00_u8, 0x01]);
+    stream.append(&1_u8);
+    stream.append(&Vec::<u8>::new());
+    let msg = CSMessage::new(CSMessageType::CSMessageResult, stream.out().to_vec()).as_bytes();
+    let err = contract
+        .handle_message(deps.as_mut(), ctx.info, nid, msg)
+        .unwrap_err();
+    assert!(err.to_string().starts_with("DecodeFailed"));
+}
+
 #[test]
 #[should_panic(expected = "CallRequestNotFound { sn: 1 }")]
 fn test_handle_call_message_fail_on_invalid_request() {
@@ -553,3 +787,256 @@ fn test_handle_result_on_error_response() {
     let res = contract.handle_result(deps.as_mut(), info, &msg).unwrap();
     assert_eq!(res.attributes[1].value, "handle_response")
 }
+
+#[test]
+fn test_handle_result_keeps_returned_data() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let mut rollback = get_dummy_rollback_data();
+    rollback.set_expects_response();
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = CSMessageResult::new(
+        1,
+        CallServiceResponseType::CallServiceResponseSuccess,
+        Some(vec![7, 8, 9]),
+    );
+    let res = contract
+        .handle_result(deps.as_mut(), info, &msg.as_bytes())
+        .unwrap();
+    assert_eq!(res.events[1].ty, "CallResponseMessage");
+
+    let call_request = contract.get_call_request(&deps.storage, 1).unwrap();
+    assert_eq!(call_request.response(), Some(&vec![7, 8, 9]));
+}
+
+#[test]
+fn test_execute_response() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info(&alice().to_string(), "umlg", 2000);
+
+    let mut rollback = Rollback::new(
+        Addr::unchecked("dapp"),
+        NetworkAddress::new("nid", "mockaddress"),
+        vec!["centralized".to_string()],
+        vec![1, 2, 3],
+        false,
+    );
+    rollback.set_expects_response();
+    rollback.set_response(vec![7, 8, 9]);
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let res = contract.execute_response(deps.as_mut(), info, 1).unwrap();
+    assert_eq!(res.events[0].ty, "CallResponseExecuted");
+
+    let expected = dapp_multi_msg::ExecuteMsg::HandleCallResponse {
+        from: NetworkAddress::new("nid", "mockaddress"),
+        sn: 1,
+        data: vec![7, 8, 9],
+    };
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            assert_eq!(contract_addr, "dapp");
+            assert_eq!(msg, &to_json_binary(&expected).unwrap());
+        }
+        _ => panic!("unexpected submessage"),
+    }
+    assert!(contract.get_call_request(&deps.storage, 1).is_err());
+}
+
+#[test]
+#[should_panic(expected = "CallResponseNotFound { sn: 1 }")]
+fn test_execute_response_before_result() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info(&alice().to_string(), "umlg", 2000);
+
+    let mut rollback = get_dummy_rollback_data();
+    rollback.set_expects_response();
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    contract.execute_response(deps.as_mut(), info, 1).unwrap();
+}
+
+fn store_response_fee_request(deps: cosmwasm_std::DepsMut, contract: &CwCallService) {
+    let rollback = Rollback::new(
+        Addr::unchecked("xcall"),
+        get_dummy_network_address("archway"),
+        vec!["centralized".to_string()],
+        vec![1, 2, 3],
+        false,
+    );
+    contract
+        .store_call_request(deps.storage, 1, &rollback)
+        .unwrap();
+    contract
+        .store_response_fee_record(
+            deps.storage,
+            1,
+            &ResponseFeeRecord {
+                payer: Addr::unchecked("dapp"),
+                nid: NetId::from("archway".to_owned()),
+                sources: vec!["centralized".to_string()],
+                refundable: false,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_claim_response_fee_refund_after_error() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    store_response_fee_request(deps.as_mut(), &contract);
+
+    contract
+        .handle_error(deps.as_mut(), mock_info("centralized", &[]), 1)
+        .unwrap();
+
+    let res = contract
+        .claim_response_fee_refund(deps.as_mut(), mock_info("dapp", &[]), 1)
+        .unwrap();
+
+    let expected = xcall_connection_msg::ExecuteMsg::RefundResponseFee {
+        nid: NetId::from("archway".to_owned()),
+        sn: 1,
+        to: "dapp".to_string(),
+    };
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "centralized".to_string(),
+            msg: to_json_binary(&expected).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Error);
+    assert!(contract
+        .get_response_fee_record(deps.as_ref().storage, 1)
+        .is_err());
+}
+
+#[test]
+fn test_response_fee_refundable_on_result() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    store_response_fee_request(deps.as_mut(), &contract);
+
+    let msg = get_dummy_result_message_failure().as_bytes();
+    contract
+        .handle_result(deps.as_mut(), mock_info("centralized", &[]), &msg)
+        .unwrap();
+
+    assert!(
+        contract
+            .get_response_fee_record(deps.as_ref().storage, 1)
+            .unwrap()
+            .refundable
+    );
+}
+
+#[test]
+fn test_refund_response_fee_failure_is_ignored() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let msg = Reply {
+        id: REFUND_RESPONSE_FEE_REPLY_ID,
+        result: SubMsgResult::Err("Unknown variant refund_response_fee".to_string()),
+    };
+    let res = contract.reply(deps.as_mut(), mock_env(), msg).unwrap();
+
+    assert_eq!(res.attributes[0].value, "refund_response_fee_reply");
+}
+
+#[test]
+#[should_panic(expected = "ResponseFeeNotRefundable")]
+fn test_claim_response_fee_refund_before_resolution() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    store_response_fee_request(deps.as_mut(), &contract);
+
+    contract
+        .claim_response_fee_refund(deps.as_mut(), mock_info("dapp", &[]), 1)
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_claim_response_fee_refund_by_other_sender() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    store_response_fee_request(deps.as_mut(), &contract);
+
+    contract
+        .handle_error(deps.as_mut(), mock_info("centralized", &[]), 1)
+        .unwrap();
+    contract
+        .claim_response_fee_refund(deps.as_mut(), mock_info("alice", &[]), 1)
+        .unwrap();
+}
+
+#[test]
+fn test_handle_nack() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    store_response_fee_request(deps.as_mut(), &contract);
+
+    let res = contract
+        .handle_nack(
+            deps.as_mut(),
+            mock_info("centralized", &[]),
+            1,
+            NackReason::ExceededSize,
+        )
+        .unwrap();
+
+    let response_event = &res.events[0];
+    assert_eq!(response_event.ty, "ResponseMessage");
+    assert!(response_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "reason" && attr.value == "exceeded_size"));
+    let rollback_event = &res.events[1];
+    assert_eq!(rollback_event.ty.trim_end(), "RollbackMessage");
+    assert!(rollback_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "reason" && attr.value == "exceeded_size"));
+
+    assert!(
+        contract
+            .get_response_fee_record(deps.as_ref().storage, 1)
+            .unwrap()
+            .refundable
+    );
+}
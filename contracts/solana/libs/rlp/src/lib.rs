@@ -38,6 +38,7 @@ mod impls;
 mod nullable;
 mod rlpin;
 mod stream;
+pub mod strict;
 mod traits;
 
 use bytes::BytesMut;
@@ -4,4 +4,30 @@ use anchor_lang::prelude::error_code;
 pub enum NetworkError {
     #[msg("Invalid network address")]
     InvalidNetworkAddress,
+    #[msg("Network address has no separator")]
+    MissingSeparator,
+    #[msg("Network address has more than one separator")]
+    TooManySeparators,
+    #[msg("Network id is empty")]
+    EmptyNetworkId,
+    #[msg("Account is empty")]
+    EmptyAccount,
+    #[msg("Network id is too long")]
+    NetworkIdTooLong,
+    #[msg("Account is too long")]
+    AccountTooLong,
+}
+
+#[error_code]
+pub enum CaipError {
+    #[msg("Invalid CAIP-10 account id")]
+    InvalidCaip10,
+    #[msg("Unknown CAIP-2 chain")]
+    UnknownChain,
+    #[msg("Unknown network")]
+    UnknownNetwork,
+    #[msg("Invalid account")]
+    InvalidAccount,
+    #[msg("Invalid network address")]
+    InvalidNetworkAddress,
 }
@@ -1,3 +1,4 @@
+pub mod caip;
 pub mod error;
 pub mod message;
 pub mod network_address;
@@ -0,0 +1,77 @@
+use super::*;
+
+/// A call with a rollback that the destination refuses to execute from `timeout_height` or
+/// `timeout_timestamp` (seconds since the unix epoch) on, answering it with a failure instead. A
+/// zero deadline is not checked, at least one of them must be set.
+#[derive(Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct CallMessageWithTimeout {
+    pub data: Vec<u8>,
+    pub rollback: Vec<u8>,
+    pub timeout_height: u64,
+    pub timeout_timestamp: u64,
+}
+
+impl Encodable for CallMessageWithTimeout {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream
+            .begin_list(4)
+            .append(&self.data)
+            .append(&self.rollback)
+            .append(&self.timeout_height)
+            .append(&self.timeout_timestamp);
+    }
+}
+
+impl Decodable for CallMessageWithTimeout {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        Ok(Self {
+            data: rlp.val_at(0)?,
+            rollback: rlp.val_at(1)?,
+            timeout_height: rlp::strict::decode_u64(&rlp.at(2)?)?,
+            timeout_timestamp: rlp::strict::decode_u64(&rlp.at(3)?)?,
+        })
+    }
+}
+
+impl IMessage for CallMessageWithTimeout {
+    fn rollback(&self) -> Option<Vec<u8>> {
+        Some(self.rollback.clone())
+    }
+
+    fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, DecoderError> {
+        Ok(rlp::encode(self).to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rlp::Encodable;
+    use rlp::Rlp;
+
+    #[test]
+    fn test_encoding_decoding_message() {
+        let original_message = CallMessageWithTimeout {
+            data: vec![0, 11, 255],
+            rollback: vec![1, 2, 3],
+            timeout_height: 1000,
+            timeout_timestamp: 0,
+        };
+
+        let mut stream = RlpStream::new();
+        original_message.rlp_append(&mut stream);
+        let encoded = stream.out();
+
+        let decoded_rlp = Rlp::new(&encoded);
+        let decoded_message = CallMessageWithTimeout::decode(&decoded_rlp).unwrap();
+
+        assert_eq!(decoded_message.data, original_message.data);
+        assert_eq!(decoded_message.rollback, original_message.rollback);
+        assert_eq!(decoded_message.timeout_height, 1000);
+        assert_eq!(decoded_message.timeout_timestamp, 0);
+    }
+}
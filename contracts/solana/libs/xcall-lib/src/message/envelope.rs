@@ -1,10 +1,13 @@
 use super::*;
 
+/// `gas_limit` bounds the gas the destination dapp may use to execute the message, a zero limit
+/// leaves it unbounded and is not encoded.
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct Envelope {
     pub message: AnyMessage,
     pub sources: Vec<String>,
     pub destinations: Vec<String>,
+    pub gas_limit: u64,
 }
 
 impl Envelope {
@@ -13,13 +16,14 @@ impl Envelope {
             message: msg,
             sources,
             destinations,
+            gas_limit: 0,
         }
     }
 }
 
 impl Encodable for Envelope {
     fn rlp_append(&self, stream: &mut rlp::RlpStream) {
-        stream.begin_list(4);
+        stream.begin_list(if self.gas_limit > 0 { 5 } else { 4 });
         stream.append(&Into::<u8>::into(self.message.msg_type().clone()));
         stream.append(&self.message.to_bytes().unwrap());
         stream.begin_list(self.sources.len());
@@ -30,13 +34,16 @@ impl Encodable for Envelope {
         for dest in self.destinations.iter() {
             stream.append(dest);
         }
+        if self.gas_limit > 0 {
+            stream.append(&self.gas_limit);
+        }
     }
 }
 
 impl Decodable for Envelope {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         let msg_int: u8 = rlp.val_at(0)?;
-        let msg_type = MessageType::from(msg_int);
+        let msg_type = MessageType::try_from(msg_int)?;
         let message_bytes: Vec<u8> = rlp.val_at(1)?;
         let message = decode_message(msg_type, message_bytes)?;
 
@@ -44,11 +51,17 @@ impl Decodable for Envelope {
         let sources: Vec<String> = sources.as_list()?;
         let destinations = rlp.at(3)?;
         let destinations: Vec<String> = destinations.as_list()?;
+        let gas_limit = match rlp.item_count()? {
+            4 => 0,
+            5 => rlp::strict::decode_u64(&rlp.at(4)?)?,
+            _ => return Err(DecoderError::RlpIncorrectListLen),
+        };
 
         Ok(Envelope {
             message,
             sources,
             destinations,
+            gas_limit,
         })
     }
 }
@@ -67,6 +80,14 @@ pub fn decode_message(msg_type: MessageType, bytes: Vec<u8>) -> Result<AnyMessag
             let msg: CallMessagePersisted = rlp::decode(&bytes)?;
             Ok(AnyMessage::CallMessagePersisted(msg))
         }
+        MessageType::CallMessageWithTimeout => {
+            let msg: CallMessageWithTimeout = rlp::decode(&bytes)?;
+            Ok(AnyMessage::CallMessageWithTimeout(msg))
+        }
+        MessageType::CallMessageWithResponse => {
+            let msg: CallMessageWithResponse = rlp::decode(&bytes)?;
+            Ok(AnyMessage::CallMessageWithResponse(msg))
+        }
     }
 }
 
@@ -74,6 +95,59 @@ pub fn decode_message(msg_type: MessageType, bytes: Vec<u8>) -> Result<AnyMessag
 mod tests {
     use super::*;
 
+    use serde_json::Value;
+
+    const VECTORS: &str = include_str!("../../../../../../test-vectors/xcall/messages.json");
+
+    fn bytes(value: &Value) -> Vec<u8> {
+        hex::decode(value.as_str().unwrap()).unwrap()
+    }
+
+    fn strings(value: &Value) -> Vec<String> {
+        let values = value.as_array().unwrap();
+        values
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    fn any_message(msg_type: &str, value: &Value) -> AnyMessage {
+        let data = bytes(&value["data"]);
+        match msg_type {
+            "CallMessage" => AnyMessage::CallMessage(CallMessage { data }),
+            "CallMessageWithRollback" => {
+                AnyMessage::CallMessageWithRollback(CallMessageWithRollback {
+                    data,
+                    rollback: bytes(&value["rollback"]),
+                })
+            }
+            "CallMessagePersisted" => {
+                AnyMessage::CallMessagePersisted(CallMessagePersisted { data })
+            }
+            "CallMessageWithTimeout" => {
+                AnyMessage::CallMessageWithTimeout(CallMessageWithTimeout {
+                    data,
+                    rollback: bytes(&value["rollback"]),
+                    timeout_height: value["timeout_height"].as_u64().unwrap(),
+                    timeout_timestamp: value["timeout_timestamp"].as_u64().unwrap(),
+                })
+            }
+            "CallMessageWithResponse" => {
+                AnyMessage::CallMessageWithResponse(CallMessageWithResponse {
+                    data,
+                    rollback: bytes(&value["rollback"]),
+                })
+            }
+            _ => panic!("unknown message type {}", msg_type),
+        }
+    }
+
+    fn assert_message_eq(actual: &AnyMessage, expected: &AnyMessage, name: &str) {
+        assert_eq!(actual.msg_type(), expected.msg_type(), "{}", name);
+        assert_eq!(actual.data(), expected.data(), "{}", name);
+        assert_eq!(actual.rollback(), expected.rollback(), "{}", name);
+    }
+
     #[test]
     fn test_decode_message() {
         let msg_bytes = CallMessagePersisted {
@@ -88,4 +162,81 @@ mod tests {
 
         assert_eq!(decoded_message.data(), msg_bytes.data);
     }
+
+    #[test]
+    fn test_envelope_unsupported_message_type() {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&7_u8);
+        stream.append(&vec![0xc3_u8, 0x83, 1, 2, 3]);
+        stream.begin_list(0);
+        stream.begin_list(0);
+
+        let decoded = rlp::decode::<Envelope>(&stream.out());
+        assert_eq!(
+            decoded.err(),
+            Some(DecoderError::Custom(
+                crate::message::msg_type::UNSUPPORTED_MESSAGE_TYPE
+            ))
+        );
+    }
+
+    #[test]
+    fn test_envelope_with_gas_limit() {
+        let msg = AnyMessage::CallMessage(CallMessage {
+            data: vec![1, 2, 3],
+        });
+        let mut envelope = Envelope::new(msg, vec![], vec![]);
+        let without_limit = rlp::encode(&envelope).to_vec();
+        assert_eq!(rlp::Rlp::new(&without_limit).item_count(), Ok(4));
+
+        envelope.gas_limit = 200_000;
+        let encoded = rlp::encode(&envelope).to_vec();
+        assert_eq!(rlp::Rlp::new(&encoded).item_count(), Ok(5));
+
+        let decoded: Envelope = rlp::decode(&encoded).unwrap();
+        assert_message_eq(&decoded.message, &envelope.message, "gas limit");
+        assert_eq!(decoded.gas_limit, 200_000);
+    }
+
+    #[test]
+    fn test_golden_vectors() {
+        let vectors: Vec<Value> = serde_json::from_str(VECTORS).unwrap();
+
+        for vector in vectors.iter() {
+            let name = vector["name"].as_str().unwrap();
+            let value = &vector["value"];
+            let encoded = bytes(&vector["hex"]);
+
+            match vector["type"].as_str().unwrap() {
+                "Envelope" => {
+                    let message = &value["message"];
+                    let envelope = Envelope::new(
+                        any_message(message["type"].as_str().unwrap(), message),
+                        strings(&value["sources"]),
+                        strings(&value["destinations"]),
+                    );
+                    assert_eq!(rlp::encode(&envelope).to_vec(), encoded, "{}", name);
+
+                    let decoded: Envelope = rlp::decode(&encoded).unwrap();
+                    assert_message_eq(&decoded.message, &envelope.message, name);
+                    assert_eq!(decoded.sources, envelope.sources, "{}", name);
+                    assert_eq!(decoded.destinations, envelope.destinations, "{}", name);
+                }
+                "CallMessage"
+                | "CallMessageWithRollback"
+                | "CallMessagePersisted"
+                | "CallMessageWithTimeout"
+                | "CallMessageWithResponse" => {
+                    let msg = any_message(vector["type"].as_str().unwrap(), value);
+                    assert_eq!(msg.to_bytes().unwrap(), encoded, "{}", name);
+
+                    let decoded = decode_message(msg.msg_type(), encoded.clone()).unwrap();
+                    assert_message_eq(&decoded, &msg, name);
+                }
+                // wire types of the xcall program
+                _ => continue,
+            }
+            assert_eq!(rlp::strict::validate(&encoded), Ok(()), "{}", name);
+        }
+    }
 }
@@ -1,13 +1,16 @@
 use self::{
     call_message::CallMessage, call_message_persisted::CallMessagePersisted,
-    call_message_rollback::CallMessageWithRollback, msg_trait::IMessage, msg_type::MessageType,
+    call_message_response::CallMessageWithResponse, call_message_rollback::CallMessageWithRollback,
+    call_message_timeout::CallMessageWithTimeout, msg_trait::IMessage, msg_type::MessageType,
 };
 use anchor_lang::{prelude::borsh, AnchorDeserialize, AnchorSerialize};
 use rlp::{Decodable, DecoderError, Encodable, RlpStream};
 
 pub mod call_message;
 pub mod call_message_persisted;
+pub mod call_message_response;
 pub mod call_message_rollback;
+pub mod call_message_timeout;
 pub mod envelope;
 pub mod msg_trait;
 pub mod msg_type;
@@ -17,6 +20,8 @@ pub enum AnyMessage {
     CallMessage(CallMessage),
     CallMessageWithRollback(CallMessageWithRollback),
     CallMessagePersisted(CallMessagePersisted),
+    CallMessageWithTimeout(CallMessageWithTimeout),
+    CallMessageWithResponse(CallMessageWithResponse),
 }
 
 impl IMessage for AnyMessage {
@@ -25,6 +30,8 @@ impl IMessage for AnyMessage {
             AnyMessage::CallMessage(m) => m.rollback(),
             AnyMessage::CallMessageWithRollback(m) => m.rollback(),
             AnyMessage::CallMessagePersisted(m) => m.rollback(),
+            AnyMessage::CallMessageWithTimeout(m) => m.rollback(),
+            AnyMessage::CallMessageWithResponse(m) => m.rollback(),
         }
     }
 
@@ -33,6 +40,8 @@ impl IMessage for AnyMessage {
             AnyMessage::CallMessage(m) => m.data(),
             AnyMessage::CallMessageWithRollback(m) => m.data(),
             AnyMessage::CallMessagePersisted(m) => m.data(),
+            AnyMessage::CallMessageWithTimeout(m) => m.data(),
+            AnyMessage::CallMessageWithResponse(m) => m.data(),
         }
     }
 
@@ -41,6 +50,8 @@ impl IMessage for AnyMessage {
             AnyMessage::CallMessage(m) => m.to_bytes(),
             AnyMessage::CallMessageWithRollback(m) => m.to_bytes(),
             AnyMessage::CallMessagePersisted(m) => m.to_bytes(),
+            AnyMessage::CallMessageWithTimeout(m) => m.to_bytes(),
+            AnyMessage::CallMessageWithResponse(m) => m.to_bytes(),
         }
     }
 }
@@ -51,6 +62,8 @@ impl AnyMessage {
             AnyMessage::CallMessage(_m) => MessageType::CallMessage,
             AnyMessage::CallMessageWithRollback(_m) => MessageType::CallMessageWithRollback,
             AnyMessage::CallMessagePersisted(_m) => MessageType::CallMessagePersisted,
+            AnyMessage::CallMessageWithTimeout(_m) => MessageType::CallMessageWithTimeout,
+            AnyMessage::CallMessageWithResponse(_m) => MessageType::CallMessageWithResponse,
         }
     }
 }
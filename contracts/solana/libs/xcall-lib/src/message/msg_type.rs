@@ -1,10 +1,14 @@
-use super::{borsh, AnchorDeserialize, AnchorSerialize};
+use super::{borsh, AnchorDeserialize, AnchorSerialize, DecoderError};
+
+pub const UNSUPPORTED_MESSAGE_TYPE: &str = "unsupported message type";
 
 #[derive(Clone, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
 pub enum MessageType {
     CallMessage = 0,
     CallMessageWithRollback = 1,
     CallMessagePersisted = 2,
+    CallMessageWithTimeout = 3,
+    CallMessageWithResponse = 4,
 }
 
 impl From<MessageType> for u8 {
@@ -13,17 +17,25 @@ impl From<MessageType> for u8 {
             MessageType::CallMessage => 0,
             MessageType::CallMessageWithRollback => 1,
             MessageType::CallMessagePersisted => 2,
+            MessageType::CallMessageWithTimeout => 3,
+            MessageType::CallMessageWithResponse => 4,
         }
     }
 }
 
-impl From<u8> for MessageType {
-    fn from(value: u8) -> Self {
+/// Fails for the message types of newer versions, so they can be answered instead of aborting
+/// the instruction.
+impl TryFrom<u8> for MessageType {
+    type Error = DecoderError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => MessageType::CallMessage,
-            1 => MessageType::CallMessageWithRollback,
-            2 => MessageType::CallMessagePersisted,
-            _ => panic!("unsupported message type"),
+            0 => Ok(MessageType::CallMessage),
+            1 => Ok(MessageType::CallMessageWithRollback),
+            2 => Ok(MessageType::CallMessagePersisted),
+            3 => Ok(MessageType::CallMessageWithTimeout),
+            4 => Ok(MessageType::CallMessageWithResponse),
+            _ => Err(DecoderError::Custom(UNSUPPORTED_MESSAGE_TYPE)),
         }
     }
 }
@@ -33,6 +45,6 @@ impl MessageType {
         self.clone().into()
     }
     pub fn from_int(val: u8) -> Self {
-        MessageType::from(val)
+        MessageType::try_from(val).expect(UNSUPPORTED_MESSAGE_TYPE)
     }
 }
@@ -4,6 +4,9 @@ use anchor_lang::{AnchorDeserialize, AnchorSerialize};
 use crate::error::NetworkError;
 use std::str::FromStr;
 
+pub const MAX_NID_LENGTH: usize = 64;
+pub const MAX_ACCOUNT_LENGTH: usize = 128;
+
 #[derive(Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
 pub struct NetId(String);
 
@@ -37,10 +40,40 @@ impl FromStr for NetId {
 pub struct NetworkAddress(String);
 
 impl NetworkAddress {
+    /// Joins parts known to be valid, such as the network id and an address of this chain; use
+    /// `try_new` for parts received from a caller.
     pub fn new(nid: &str, address: &str) -> Self {
         Self(format!("{}/{}", nid, address))
     }
 
+    pub fn try_new(nid: &str, address: &str) -> Result<Self, NetworkError> {
+        Self::parse(&format!("{}/{}", nid, address))
+    }
+
+    /// Parses a network id and an account separated by a single `/`, neither being empty nor
+    /// longer than `MAX_NID_LENGTH` and `MAX_ACCOUNT_LENGTH` bytes.
+    pub fn parse(value: &str) -> Result<Self, NetworkError> {
+        let (nid, account) = split(value)?;
+        if nid.is_empty() {
+            return Err(NetworkError::EmptyNetworkId);
+        }
+        if account.is_empty() {
+            return Err(NetworkError::EmptyAccount);
+        }
+        if nid.len() > MAX_NID_LENGTH {
+            return Err(NetworkError::NetworkIdTooLong);
+        }
+        if account.len() > MAX_ACCOUNT_LENGTH {
+            return Err(NetworkError::AccountTooLong);
+        }
+        Ok(Self(value.to_owned()))
+    }
+
+    /// Checks an address that was not parsed, such as one deserialized from instruction data.
+    pub fn validate(&self) -> Result<(), NetworkError> {
+        Self::parse(&self.0).map(|_| ())
+    }
+
     pub fn nid(&self) -> String {
         self.get_parts()[0].to_string()
     }
@@ -55,14 +88,23 @@ impl NetworkAddress {
     }
 
     pub fn get_parts(&self) -> Vec<&str> {
-        let parts = self.0.split('/').collect::<Vec<&str>>();
-        if parts.len() != 2 {
-            panic!("Invalid Network Address");
+        match split(&self.0) {
+            Ok((nid, account)) => vec![nid, account],
+            Err(error) => panic!("Invalid Network Address: {:?}", error),
         }
-        parts
     }
 }
 
+fn split(value: &str) -> Result<(&str, &str), NetworkError> {
+    let (nid, account) = value
+        .split_once('/')
+        .ok_or(NetworkError::MissingSeparator)?;
+    if account.contains('/') {
+        return Err(NetworkError::TooManySeparators);
+    }
+    Ok((nid, account))
+}
+
 impl ToString for NetworkAddress {
     fn to_string(&self) -> String {
         self.0.to_string()
@@ -73,12 +115,7 @@ impl FromStr for NetworkAddress {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split('/').collect::<Vec<&str>>();
-        if parts.len() != 2 {
-            return Err(NetworkError::InvalidNetworkAddress);
-        }
-        let na = format!("{}/{}", parts[0], parts[1]);
-        Ok(Self(na))
+        Self::parse(s)
     }
 }
 
@@ -93,4 +130,30 @@ mod tests {
         assert_eq!(String::from("0x1.icon"), parsed.nid().to_string());
         assert_eq!(String::from("hx124324687"), parsed.account());
     }
+
+    const VECTORS: &str = include_str!("../../../../../test-vectors/network-address/addresses.txt");
+
+    #[test]
+    fn test_network_address_vectors() {
+        for line in VECTORS.lines() {
+            let line = line.split('#').next().unwrap();
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            match fields[..] {
+                ["valid", address, nid, account] => {
+                    let parsed = NetworkAddress::parse(address).unwrap();
+                    assert_eq!(parsed.nid(), nid, "{}", address);
+                    assert_eq!(parsed.account(), account, "{}", address);
+                    let joined = NetworkAddress::try_new(nid, account).unwrap();
+                    assert_eq!(joined.to_string(), address);
+                }
+                ["invalid", address, error] => {
+                    let parsed = NetworkAddress::parse(address).map_err(|e| format!("{:?}", e));
+                    assert_eq!(parsed.err(), Some(error.to_string()), "{}", address);
+                    assert!(NetworkAddress(address.to_string()).validate().is_err());
+                }
+                [] => {}
+                _ => panic!("unknown vector {}", line),
+            }
+        }
+    }
 }
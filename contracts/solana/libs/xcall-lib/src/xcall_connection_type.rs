@@ -7,6 +7,11 @@ pub const SEND_MESSAGE_IX: &str = "send_message";
 
 pub const QUERY_SEND_MESSAGE_ACCOUNTS_IX: &str = "query_send_message_accounts";
 
+/// Reason codes a relayer can nack an undeliverable message with
+pub const NACK_REASON_DESTINATION_REJECTED: u8 = 1;
+pub const NACK_REASON_EXCEEDED_SIZE: u8 = 2;
+pub const NACK_REASON_NETWORK_HALTED: u8 = 3;
+
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct SendMessageArgs {
     pub to: String,
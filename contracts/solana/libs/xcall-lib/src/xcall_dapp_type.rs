@@ -8,6 +8,8 @@ pub const HANDLE_CALL_MESSAGE_IX: &str = "handle_call_message";
 
 pub const QUERY_HANDLE_CALL_MESSAGE_IX: &str = "query_handle_call_message_accounts";
 
+pub const HANDLE_CALL_RESPONSE_IX: &str = "handle_call_response";
+
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct HandleCallMessageArgs {
     pub from: NetworkAddress,
@@ -15,8 +17,20 @@ pub struct HandleCallMessageArgs {
     pub protocols: Vec<String>,
 }
 
+/// Arguments of `handle_call_response`, delivering the data returned by `from` for the
+/// `CallMessageWithResponse` sent with the sequence number `sn`.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct HandleCallResponseArgs {
+    pub from: NetworkAddress,
+    pub sn: u128,
+    pub data: Vec<u8>,
+}
+
+/// `data` is carried back to the sender of a `CallMessageWithResponse`, it is ignored for the
+/// other message types.
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct HandleCallMessageResponse {
     pub success: bool,
     pub message: String,
+    pub data: Vec<u8>,
 }
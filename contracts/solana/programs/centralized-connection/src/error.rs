@@ -5,6 +5,30 @@ pub enum ConnectionError {
     #[msg("Only admin")]
     OnlyAdmin,
 
+    #[msg("Only relayer")]
+    OnlyRelayer,
+
+    #[msg("Relayer not allowed for network")]
+    RelayerNotAllowed,
+
+    #[msg("Invalid relayer networks")]
+    InvalidRelayerNetworks,
+
     #[msg("Only xcall")]
     OnlyXcall,
+
+    #[msg("Duplicate message")]
+    DuplicateMessage,
+
+    #[msg("Config already migrated")]
+    ConfigAlreadyMigrated,
+
+    #[msg("Invalid nack reason")]
+    InvalidNackReason,
+
+    #[msg("Invalid fee account")]
+    InvalidFeeAccount,
+
+    #[msg("Network fee already migrated")]
+    NetworkFeeAlreadyMigrated,
 }
@@ -8,3 +8,10 @@ pub struct SendMessage {
     pub connSn: u128,
     pub msg: Vec<u8>,
 }
+
+#[event]
+pub struct NackMessage {
+    pub sn: u128,
+    pub reason: u8,
+    pub evidence: Vec<u8>,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants, contexts::*, error::ConnectionError, helper, id, state::*};
+
+/// Extends a config account created before the relayer role existed or before
+/// the fee relayer was tracked. The former gets the current admin assigned as
+/// the relayer, so existing deployments keep working until a dedicated relayer
+/// is set, and both start without a fee relayer
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config = ctx.accounts.config.to_account_info();
+    if config.owner != &id() {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let legacy = config.data_len() == Config::LEGACY_LEN;
+    if !legacy && config.data_len() != Config::FEE_RELAYER_LEGACY_LEN {
+        return Err(ConnectionError::ConfigAlreadyMigrated.into());
+    }
+
+    let admin_offset = constants::ACCOUNT_DISCRIMINATOR_SIZE;
+    let admin = {
+        let data = config.try_borrow_data()?;
+        Pubkey::try_from(&data[admin_offset..admin_offset + 32])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?
+    };
+    if admin != ctx.accounts.admin.key() {
+        return Err(ConnectionError::OnlyAdmin.into());
+    }
+
+    let rent = Rent::get()?.minimum_balance(Config::LEN);
+    let top_up = rent.saturating_sub(config.lamports());
+    if top_up > 0 {
+        helper::transfer_lamports(
+            &ctx.accounts.admin,
+            &config,
+            &ctx.accounts.system_program,
+            top_up,
+        )?;
+    }
+    config.realloc(Config::LEN, true)?;
+
+    if legacy {
+        let mut data = config.try_borrow_mut_data()?;
+        data[Config::RELAYER_OFFSET..Config::RELAYER_OFFSET + 32].copy_from_slice(admin.as_ref());
+    }
+
+    Ok(())
+}
+
+/// Extends a fee account created before it held the connection sequence of
+/// its network. The network continues from the sequence shared by every
+/// network until then
+pub fn migrate_network_fee(ctx: Context<MigrateNetworkFee>) -> Result<()> {
+    let network_fee = ctx.accounts.network_fee.to_account_info();
+    if network_fee.owner != &id() {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    if network_fee.data_len() != NetworkFee::LEGACY_LEN {
+        return Err(ConnectionError::NetworkFeeAlreadyMigrated.into());
+    }
+
+    let rent = Rent::get()?.minimum_balance(NetworkFee::LEN);
+    let top_up = rent.saturating_sub(network_fee.lamports());
+    if top_up > 0 {
+        helper::transfer_lamports(
+            &ctx.accounts.admin,
+            &network_fee,
+            &ctx.accounts.system_program,
+            top_up,
+        )?;
+    }
+    network_fee.realloc(NetworkFee::LEN, true)?;
+
+    Ok(())
+}
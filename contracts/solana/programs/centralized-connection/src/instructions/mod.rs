@@ -1,3 +1,5 @@
+pub mod migrate;
 pub mod query_accounts;
 
+pub use migrate::*;
 pub use query_accounts::*;
@@ -9,8 +9,13 @@ use crate::{constants, error::*};
 pub struct Config {
     pub admin: Pubkey,
     pub xcall: Pubkey,
+    /// Connection sequence shared by every network before each got its own,
+    /// the networks continue from it
     pub sn: u128,
     pub bump: u8,
+    pub relayer: Pubkey,
+    pub fee_relayer: Pubkey,
+    pub fee_account: Pubkey,
 }
 
 impl Config {
@@ -18,16 +23,32 @@ impl Config {
     pub const SEED_PREFIX: &'static str = "config";
 
     /// Account discriminator + Xcall public key + Admin public key + connection
-    /// sequence + bump
-    pub const LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE + 32 + 32 + 16 + 1 + 1;
+    /// sequence + bump + Relayer public key + Fee relayer public key + Fee
+    /// account public key
+    pub const LEN: usize =
+        constants::ACCOUNT_DISCRIMINATOR_SIZE + 32 + 32 + 16 + 1 + 32 + 32 + 32 + 1;
+
+    /// Size of the config account created before the fee relayer was tracked
+    pub const FEE_RELAYER_LEGACY_LEN: usize =
+        constants::ACCOUNT_DISCRIMINATOR_SIZE + 32 + 32 + 16 + 1 + 32 + 1;
+
+    /// Size of the config account created before the relayer was split from
+    /// the admin
+    pub const LEGACY_LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE + 32 + 32 + 16 + 1 + 1;
+
+    /// Byte offset of the relayer public key inside the account data
+    pub const RELAYER_OFFSET: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE + 32 + 32 + 16 + 1;
 
     /// Creates a new centralized connection `Config` state
-    pub fn new(xcall: Pubkey, admin: Pubkey, bump: u8) -> Self {
+    pub fn new(xcall: Pubkey, admin: Pubkey, relayer: Pubkey, bump: u8) -> Self {
         Self {
             xcall,
             admin,
             sn: 0,
             bump,
+            relayer,
+            fee_relayer: Pubkey::default(),
+            fee_account: Pubkey::default(),
         }
     }
 
@@ -39,6 +60,14 @@ impl Config {
         Ok(())
     }
 
+    /// It throws error if `signer` is not the relayer account
+    pub fn ensure_relayer(&self, signer: Pubkey) -> Result<()> {
+        if self.relayer != signer {
+            return Err(ConnectionError::OnlyRelayer.into());
+        }
+        Ok(())
+    }
+
     /// It throws error if `address` is not an xcall account
     pub fn ensure_xcall(&self, address: Pubkey) -> Result<()> {
         if self.xcall != address {
@@ -47,9 +76,14 @@ impl Config {
         Ok(())
     }
 
-    pub fn get_next_conn_sn(&mut self) -> Result<u128> {
-        self.sn += 1;
-        Ok(self.sn)
+    /// Whether `relayer` earns the fees collected by the config: the relayer
+    /// that delivered a message last, or the config relayer when nothing was
+    /// delivered yet
+    pub fn is_fee_relayer(&self, relayer: Pubkey) -> bool {
+        if self.fee_relayer == Pubkey::default() {
+            return self.relayer == relayer;
+        }
+        self.fee_relayer == relayer
     }
 
     pub fn get_claimable_fees(&self, account: &AccountInfo) -> Result<u64> {
@@ -60,27 +94,86 @@ impl Config {
     }
 }
 
+/// A relayer added by the admin next to the config relayer. It can only deliver
+/// messages from `networks`, or from every network when empty, and holds the
+/// fees it earned until they are claimed
+#[account]
+pub struct Relayer {
+    pub networks: Vec<String>,
+    pub bump: u8,
+}
+
+impl Relayer {
+    /// The Relayer seed phrase to derive it's program-derived address
+    pub const SEED_PREFIX: &'static str = "relayer";
+
+    /// Account discriminator + Networks + bump
+    pub const LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE
+        + 4
+        + constants::MAX_RELAYER_NETWORKS * (4 + constants::MAX_NETWORK_ID_LEN)
+        + 1;
+
+    /// Creates a new `Relayer` state allowed for `networks`
+    pub fn new(networks: Vec<String>, bump: u8) -> Result<Self> {
+        if networks.len() > constants::MAX_RELAYER_NETWORKS
+            || networks
+                .iter()
+                .any(|nid| nid.len() > constants::MAX_NETWORK_ID_LEN)
+        {
+            return Err(ConnectionError::InvalidRelayerNetworks.into());
+        }
+
+        Ok(Self { networks, bump })
+    }
+
+    /// It throws error if the relayer is not allowed to deliver messages from
+    /// `network_id`
+    pub fn ensure_network(&self, network_id: &str) -> Result<()> {
+        if !self.networks.is_empty() && !self.networks.iter().any(|nid| nid == network_id) {
+            return Err(ConnectionError::RelayerNotAllowed.into());
+        }
+        Ok(())
+    }
+
+    pub fn get_claimable_fees(account: &AccountInfo) -> Result<u64> {
+        let rent = Rent::default();
+        let rent_exempt_balance = rent.minimum_balance(Relayer::LEN);
+
+        Ok(account.lamports().saturating_sub(rent_exempt_balance))
+    }
+}
+
+/// The fees of a destination network and the connection sequence of the
+/// messages sent to it
 #[account]
 pub struct NetworkFee {
     pub message_fee: u64,
     pub response_fee: u64,
     pub bump: u8,
+    pub conn_sn: u128,
 }
 
 impl NetworkFee {
     /// The Fee seed phrase to derive it's program-derived address
     pub const SEED_PREFIX: &'static str = "fee";
 
-    /// Account discriminator + Message fee + Response fee + bump
-    pub const LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE + 8 + 8 + 1;
+    /// Account discriminator + Message fee + Response fee + bump + connection
+    /// sequence
+    pub const LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE + 8 + 8 + 1 + 16;
 
-    /// Creates a new `Fee` state for a network_id
-    pub fn new(message_fee: u64, response_fee: u64, bump: u8) -> Self {
-        Self {
-            message_fee,
-            response_fee,
-            bump,
+    /// Size of the fee account created before messages were numbered per
+    /// destination network
+    pub const LEGACY_LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE + 8 + 8 + 1;
+
+    /// Returns the connection sequence of the next message sent to the
+    /// network. The first one continues from `legacy_conn_sn`, the sequence
+    /// shared by every network before each got its own
+    pub fn get_next_conn_sn(&mut self, legacy_conn_sn: u128) -> Result<u128> {
+        if self.conn_sn == 0 {
+            self.conn_sn = legacy_conn_sn;
         }
+        self.conn_sn += 1;
+        Ok(self.conn_sn)
     }
 
     pub fn get(&self, response: bool) -> Result<u64> {
@@ -102,6 +195,33 @@ impl Receipt {
     pub const LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE;
 }
 
+/// The receipt watermark of a source network: every connection sequence up to
+/// `watermark` has been received and its receipt account can be closed. It
+/// only moves over contiguous receipts, so it relies on the source numbering
+/// the messages it sends to each network on their own
+#[account]
+pub struct NetworkReceipts {
+    pub watermark: u128,
+    pub bump: u8,
+}
+
+impl NetworkReceipts {
+    /// The NetworkReceipts seed phrase to derive it's program-derived address
+    pub const SEED_PREFIX: &'static str = "receipts";
+
+    /// Account discriminator + Watermark + bump
+    pub const LEN: usize = constants::ACCOUNT_DISCRIMINATOR_SIZE + 16 + 1;
+
+    /// It throws error if `conn_sn` is covered by the watermark, its receipt
+    /// account may have been closed so it cannot be relied upon
+    pub fn ensure_not_received(&self, conn_sn: u128) -> Result<()> {
+        if conn_sn > 0 && conn_sn <= self.watermark {
+            return Err(ConnectionError::DuplicateMessage.into());
+        }
+        Ok(())
+    }
+}
+
 #[account]
 pub struct Authority {
     pub bump: u8,
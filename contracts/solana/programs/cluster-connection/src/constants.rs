@@ -1 +1,7 @@
 pub const ACCOUNT_DISCRIMINATOR_SIZE: usize = 8;
+
+/// Maximum number of source networks a relayer can be scoped to
+pub const MAX_RELAYER_NETWORKS: usize = 10;
+
+/// Maximum length of a network id a relayer can be scoped to
+pub const MAX_NETWORK_ID_LEN: usize = 32;
@@ -52,6 +52,7 @@ pub struct SendMessage<'info> {
     pub config: Account<'info, Config>,
 
     #[account(
+        mut,
         seeds = [NetworkFee::SEED_PREFIX.as_bytes(), to.as_bytes()],
         bump = network_fee.bump
     )]
@@ -70,7 +71,6 @@ pub struct RevertMessage<'info> {
         mut,
         seeds = [Config::SEED_PREFIX.as_bytes()],
         bump = config.bump,
-        has_one = relayer @ ConnectionError::OnlyRelayer,
     )]
     pub config: Account<'info, Config>,
 
@@ -79,6 +79,46 @@ pub struct RevertMessage<'info> {
         bump = authority.bump
     )]
     pub authority: Account<'info, Authority>,
+
+    /// Relayer account of a relayer added with `add_relayer`, left empty when
+    /// the config relayer signs
+    #[account(
+        mut,
+        seeds = [Relayer::SEED_PREFIX.as_bytes(), relayer.key().as_ref()],
+        bump = relayer_info.bump
+    )]
+    pub relayer_info: Option<Account<'info, Relayer>>,
+}
+
+#[derive(Accounts)]
+#[instruction(src_network: String)]
+pub struct PruneReceipts<'info> {
+    /// Relayer receiving the rent of the closed receipt accounts
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Config
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Receipt watermark of the source network
+    #[account(
+        mut,
+        seeds = [NetworkReceipts::SEED_PREFIX.as_bytes(), src_network.as_bytes()],
+        bump = network_receipts.bump
+    )]
+    pub network_receipts: Account<'info, NetworkReceipts>,
+
+    /// Relayer account of a relayer added with `add_relayer`, left empty when
+    /// the config relayer signs
+    #[account(
+        seeds = [Relayer::SEED_PREFIX.as_bytes(), relayer.key().as_ref()],
+        bump = relayer_info.bump
+    )]
+    pub relayer_info: Option<Account<'info, Relayer>>,
 }
 
 #[derive(Accounts)]
@@ -97,6 +137,63 @@ pub struct SetConfigItem<'info> {
     pub config: Account<'info, Config>,
 }
 
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct AddRelayer<'info> {
+    /// Rent payer
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// System Program: Required to create program-derived address
+    pub system_program: Program<'info, System>,
+
+    /// Config
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ConnectionError::OnlyAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Relayer
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [Relayer::SEED_PREFIX.as_bytes(), address.as_ref()],
+        bump,
+        space = Relayer::LEN
+    )]
+    pub relayer_info: Account<'info, Relayer>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRelayer<'info> {
+    pub admin: Signer<'info>,
+
+    /// Config
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ConnectionError::OnlyAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: the removed relayer, receives the fees held by its relayer
+    /// account which is derived from this key
+    #[account(mut)]
+    pub relayer: UncheckedAccount<'info>,
+
+    /// Relayer
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [Relayer::SEED_PREFIX.as_bytes(), relayer.key().as_ref()],
+        bump = relayer_info.bump
+    )]
+    pub relayer_info: Account<'info, Relayer>,
+}
+
 #[derive(Accounts)]
 #[instruction(network_id: String)]
 pub struct SetFee<'info> {
@@ -122,9 +219,17 @@ pub struct SetFee<'info> {
         mut,
         seeds = [Config::SEED_PREFIX.as_bytes()],
         bump = config.bump,
-        has_one = relayer @ ConnectionError::OnlyRelayer,
     )]
     pub config: Account<'info, Config>,
+
+    /// Relayer account of a relayer added with `add_relayer`, left empty when
+    /// the config relayer signs
+    #[account(
+        mut,
+        seeds = [Relayer::SEED_PREFIX.as_bytes(), relayer.key().as_ref()],
+        bump = relayer_info.bump
+    )]
+    pub relayer_info: Option<Account<'info, Relayer>>,
 }
 
 #[derive(Accounts)]
@@ -149,9 +254,17 @@ pub struct ClaimFees<'info> {
         mut,
         seeds = [Config::SEED_PREFIX.as_bytes()],
         bump = config.bump,
-        has_one = relayer @ ConnectionError::OnlyRelayer,
     )]
     pub config: Account<'info, Config>,
+
+    /// Relayer account of a relayer added with `add_relayer`, left empty when
+    /// the config relayer signs
+    #[account(
+        mut,
+        seeds = [Relayer::SEED_PREFIX.as_bytes(), relayer.key().as_ref()],
+        bump = relayer_info.bump
+    )]
+    pub relayer_info: Option<Account<'info, Relayer>>,
 }
 
 #[derive(Accounts)]
@@ -177,7 +290,6 @@ pub struct ReceiveMessageWithSignatures<'info> {
         mut,
         seeds = [Config::SEED_PREFIX.as_bytes()],
         bump = config.bump,
-        has_one = relayer @ ConnectionError::OnlyRelayer,
     )]
     pub config: Account<'info, Config>,
 
@@ -190,9 +302,91 @@ pub struct ReceiveMessageWithSignatures<'info> {
     )]
     pub receipt: Account<'info, Receipt>,
 
+    /// Receipt watermark of the source network
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        seeds = [NetworkReceipts::SEED_PREFIX.as_bytes(), src_network.as_bytes()],
+        space = NetworkReceipts::LEN,
+        bump
+    )]
+    pub network_receipts: Account<'info, NetworkReceipts>,
+
     #[account(
         seeds = [Authority::SEED_PREFIX.as_bytes()],
         bump = authority.bump
     )]
     pub authority: Account<'info, Authority>,
-}
\ No newline at end of file
+
+    /// Relayer account of a relayer added with `add_relayer`, left empty when
+    /// the config relayer signs
+    #[account(
+        mut,
+        seeds = [Relayer::SEED_PREFIX.as_bytes(), relayer.key().as_ref()],
+        bump = relayer_info.bump
+    )]
+    pub relayer_info: Option<Account<'info, Relayer>>,
+
+    /// CHECK: the config `fee_account`, receives the fees collected by the
+    /// config when the signer is not the relayer that delivered last
+    #[account(mut)]
+    pub fee_account: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(network_id: String)]
+pub struct MigrateNetworkFee<'info> {
+    /// Admin of the config, pays the rent for the extended account
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// System Program: Required to top up the rent of the fee account
+    pub system_program: Program<'info, System>,
+
+    /// Config
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ConnectionError::OnlyAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: fee account created before it held the connection sequence of
+    /// the network, it cannot be deserialized as `NetworkFee` and is validated
+    /// in the instruction
+    #[account(
+        mut,
+        seeds = [NetworkFee::SEED_PREFIX.as_bytes(), network_id.as_bytes()],
+        bump,
+    )]
+    pub network_fee: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(src_network: String)]
+pub struct MigrateNetworkReceipts<'info> {
+    /// Rent payer
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// System Program: Required to create program-derived address
+    pub system_program: Program<'info, System>,
+
+    /// Config
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ConnectionError::OnlyAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Receipt watermark of the source network
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [NetworkReceipts::SEED_PREFIX.as_bytes(), src_network.as_bytes()],
+        space = NetworkReceipts::LEN,
+        bump
+    )]
+    pub network_receipts: Account<'info, NetworkReceipts>,
+}
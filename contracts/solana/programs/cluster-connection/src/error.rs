@@ -8,12 +8,30 @@ pub enum ConnectionError {
     #[msg("Only relayer")]
     OnlyRelayer,
 
+    #[msg("Relayer not allowed for network")]
+    RelayerNotAllowed,
+
+    #[msg("Invalid relayer networks")]
+    InvalidRelayerNetworks,
+
     #[msg("Only xcall")]
     OnlyXcall,
 
+    #[msg("Duplicate message")]
+    DuplicateMessage,
+
     #[msg("Admin Validator Cnnot Be Removed")]
     AdminValidatorCnnotBeRemoved,
 
     #[msg("Validators Must Be Greater Than Threshold")]
     ValidatorsMustBeGreaterThanThreshold,
+
+    #[msg("Invalid nack reason")]
+    InvalidNackReason,
+
+    #[msg("Invalid fee account")]
+    InvalidFeeAccount,
+
+    #[msg("Network fee already migrated")]
+    NetworkFeeAlreadyMigrated,
 }
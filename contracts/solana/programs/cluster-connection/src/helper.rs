@@ -6,7 +6,7 @@ use anchor_lang::{
         keccak::hashv,
         program::{get_return_data, invoke, invoke_signed},
         secp256k1_recover::secp256k1_recover,
-        system_instruction,
+        system_instruction, system_program,
     },
 };
 
@@ -33,6 +33,107 @@ pub fn transfer_lamports<'info>(
     Ok(())
 }
 
+/// It throws error if `relayer` is neither the config relayer nor an added
+/// relayer allowed to deliver messages from `network_id`
+pub fn ensure_relayer(
+    config: &Config,
+    relayer: &Signer,
+    relayer_info: &Option<Account<Relayer>>,
+    network_id: Option<&str>,
+) -> Result<()> {
+    match relayer_info {
+        Some(relayer_info) => match network_id {
+            Some(network_id) => relayer_info.ensure_network(network_id),
+            None => Ok(()),
+        },
+        None => config.ensure_relayer(relayer.key()),
+    }
+}
+
+/// Makes `relayer` the one earning the fees collected by the config as it
+/// delivers a message. The fees collected so far are first moved to
+/// `fee_account`, the account of the relayer that delivered before it, so a
+/// failover does not move fees the previous relayer has earned
+pub fn record_delivery<'info>(
+    config: &mut Account<'info, Config>,
+    relayer: &Signer<'info>,
+    relayer_info: &Option<Account<'info, Relayer>>,
+    fee_account: Option<AccountInfo<'info>>,
+) -> Result<()> {
+    if config.fee_relayer == relayer.key() {
+        return Ok(());
+    }
+    settle_fees(config, fee_account)?;
+
+    config.fee_relayer = relayer.key();
+    config.fee_account = match relayer_info {
+        Some(relayer_info) => relayer_info.key(),
+        None => relayer.key(),
+    };
+
+    Ok(())
+}
+
+/// Moves the fees collected by the config to `fee_account`, which must be the
+/// config `fee_account`: the relayer account of the relayer that delivered last
+/// if it was added with `add_relayer`, or the relayer itself
+pub fn settle_fees<'info>(
+    config: &Account<'info, Config>,
+    fee_account: Option<AccountInfo<'info>>,
+) -> Result<()> {
+    if config.fee_relayer == Pubkey::default() {
+        return Ok(());
+    }
+    let config_info = config.to_account_info();
+    let fee = config.get_claimable_fees(&config_info)?;
+    if fee == 0 {
+        return Ok(());
+    }
+
+    let recipient = match fee_account {
+        Some(account) if account.key() == config.fee_account => account,
+        _ => return Err(ConnectionError::InvalidFeeAccount.into()),
+    };
+    **config_info.try_borrow_mut_lamports()? -= fee;
+    **recipient.try_borrow_mut_lamports()? += fee;
+
+    Ok(())
+}
+
+/// Closes the receipt accounts right above the watermark of `src_network`,
+/// passed in order as `receipts`, and moves their rent to `relayer`. It stops at
+/// the first account that is not the next receipt and returns the new watermark
+pub fn prune_receipts<'info>(
+    network_receipts: &mut Account<'info, NetworkReceipts>,
+    src_network: &str,
+    relayer: &Signer<'info>,
+    receipts: &[AccountInfo<'info>],
+) -> Result<u128> {
+    for receipt in receipts {
+        let conn_sn = network_receipts.watermark + 1;
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                Receipt::SEED_PREFIX.as_bytes(),
+                src_network.as_bytes(),
+                &conn_sn.to_be_bytes(),
+            ],
+            &crate::id(),
+        );
+        if receipt.key() != expected || *receipt.owner != crate::id() {
+            break;
+        }
+
+        **relayer.to_account_info().try_borrow_mut_lamports()? += receipt.lamports();
+        **receipt.try_borrow_mut_lamports()? = 0;
+        receipt.assign(&system_program::ID);
+        receipt.realloc(0, false)?;
+
+        network_receipts.watermark = conn_sn;
+    }
+
+    Ok(network_receipts.watermark)
+}
+
 pub fn get_instruction_data(ix_name: &str, data: Vec<u8>) -> Vec<u8> {
     let preimage = format!("{}:{}", "global", ix_name);
 
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{contexts::*, error::ConnectionError, helper, id, state::*};
+
+/// Extends a fee account created before it held the connection sequence of
+/// its network. The network continues from the sequence shared by every
+/// network until then
+pub fn migrate_network_fee(ctx: Context<MigrateNetworkFee>) -> Result<()> {
+    let network_fee = ctx.accounts.network_fee.to_account_info();
+    if network_fee.owner != &id() {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    if network_fee.data_len() != NetworkFee::LEGACY_LEN {
+        return Err(ConnectionError::NetworkFeeAlreadyMigrated.into());
+    }
+
+    let rent = Rent::get()?.minimum_balance(NetworkFee::LEN);
+    let top_up = rent.saturating_sub(network_fee.lamports());
+    if top_up > 0 {
+        helper::transfer_lamports(
+            &ctx.accounts.admin,
+            &network_fee,
+            &ctx.accounts.system_program,
+            top_up,
+        )?;
+    }
+    network_fee.realloc(NetworkFee::LEN, true)?;
+
+    Ok(())
+}
@@ -53,16 +53,36 @@ pub fn query_recv_message_accounts<'info>(
         ],
         &id(),
     );
+    let (network_receipts, _) = Pubkey::find_program_address(
+        &[
+            NetworkReceipts::SEED_PREFIX.as_bytes(),
+            src_network.as_bytes(),
+        ],
+        &id(),
+    );
     let (authority, _) = Pubkey::find_program_address(
         &[xcall_connection_type::CONNECTION_AUTHORITY_SEED.as_bytes()],
         &id(),
     );
 
+    let fee_account = if config.fee_relayer == Pubkey::default() {
+        id()
+    } else {
+        config.fee_account
+    };
+
     let mut account_metas = vec![
         AccountMetadata::new(system_program::id(), false),
         AccountMetadata::new(config.key(), false),
         AccountMetadata::new(receipt, false),
+        AccountMetadata::new(network_receipts, false),
         AccountMetadata::new(authority, false),
+        // relayer account, left empty for the config relayer and replaced with
+        // the relayer account by relayers added with `add_relayer`
+        AccountMetadata::new(id(), false),
+        // fee account of the relayer that delivered last, left empty when no
+        // relayer delivered yet
+        AccountMetadata::new(fee_account, false),
     ];
 
     let mut xcall_account_metas = vec![AccountMeta::new_readonly(config.key(), true)];
@@ -122,6 +142,8 @@ pub fn query_revert_message_accounts(
         AccountMetadata::new(system_program::id(), false),
         AccountMetadata::new(config.key(), false),
         AccountMetadata::new(authority, false),
+        // relayer account, see `query_recv_message_accounts`
+        AccountMetadata::new(id(), false),
     ];
 
     let mut xcall_account_metas = vec![];
@@ -15,6 +15,9 @@ use instructions::*;
 use state::*;
 
 use xcall_lib::query_account_type::{QueryAccountsPaginateResponse, QueryAccountsResponse};
+use xcall_lib::xcall_connection_type::{
+    NACK_REASON_DESTINATION_REJECTED, NACK_REASON_NETWORK_HALTED,
+};
 
 declare_id!("8oxnXrSmqWJqkb2spZk2uz1cegzPsLy6nJp9XwFhkMD5");
 
@@ -39,7 +42,8 @@ pub mod centralized_connection {
         sn: i64,
         msg: Vec<u8>,
     ) -> Result<()> {
-        let next_conn_sn = ctx.accounts.config.get_next_conn_sn()?;
+        let legacy_conn_sn = ctx.accounts.config.sn;
+        let next_conn_sn = ctx.accounts.network_fee.get_next_conn_sn(legacy_conn_sn)?;
 
         let mut fee = 0;
         if sn >= 0 {
@@ -74,6 +78,27 @@ pub mod centralized_connection {
         sequence_no: u128,
         signatures: Vec<[u8; 65]>,
     ) -> Result<()> {
+        helper::ensure_relayer(
+            &ctx.accounts.config,
+            &ctx.accounts.relayer,
+            &ctx.accounts.relayer_info,
+            Some(&src_network),
+        )?;
+        ctx.accounts.network_receipts.ensure_not_received(conn_sn)?;
+        ctx.accounts.network_receipts.bump = ctx.bumps.network_receipts;
+
+        let fee_account = ctx
+            .accounts
+            .fee_account
+            .as_ref()
+            .map(|account| account.to_account_info());
+        helper::record_delivery(
+            &mut ctx.accounts.config,
+            &ctx.accounts.relayer,
+            &ctx.accounts.relayer_info,
+            fee_account,
+        )?;
+
         helper::call_xcall_handle_message_with_signatures(ctx, src_network, msg, conn_sn, sequence_no, signatures)
     }
 
@@ -81,9 +106,66 @@ pub mod centralized_connection {
         ctx: Context<'_, '_, '_, 'info, RevertMessage<'info>>,
         sequence_no: u128,
     ) -> Result<()> {
+        helper::ensure_relayer(
+            &ctx.accounts.config,
+            &ctx.accounts.relayer,
+            &ctx.accounts.relayer_info,
+            None,
+        )?;
+
         helper::call_xcall_handle_error(ctx, sequence_no)
     }
 
+    /// Nacks a message the relayer could not deliver: emits the reason and the
+    /// evidence, then rolls the message back on xcall the same way as
+    /// `revert_message`, whose `query_revert_message_accounts` lists the accounts
+    pub fn nack_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, RevertMessage<'info>>,
+        sequence_no: u128,
+        reason_code: u8,
+        evidence: Vec<u8>,
+    ) -> Result<()> {
+        helper::ensure_relayer(
+            &ctx.accounts.config,
+            &ctx.accounts.relayer,
+            &ctx.accounts.relayer_info,
+            None,
+        )?;
+        if !(NACK_REASON_DESTINATION_REJECTED..=NACK_REASON_NETWORK_HALTED).contains(&reason_code) {
+            return Err(error::ConnectionError::InvalidNackReason.into());
+        }
+
+        emit!(event::NackMessage {
+            sn: sequence_no,
+            reason: reason_code,
+            evidence
+        });
+
+        helper::call_xcall_handle_error(ctx, sequence_no)
+    }
+
+    /// Closes the receipt accounts of `src_network` passed as remaining accounts,
+    /// in connection sequence order starting right above the receipt watermark,
+    /// and moves the watermark over them. Returns the new watermark
+    pub fn prune_receipts<'info>(
+        ctx: Context<'_, '_, '_, 'info, PruneReceipts<'info>>,
+        src_network: String,
+    ) -> Result<u128> {
+        helper::ensure_relayer(
+            &ctx.accounts.config,
+            &ctx.accounts.relayer,
+            &ctx.accounts.relayer_info,
+            Some(&src_network),
+        )?;
+
+        helper::prune_receipts(
+            &mut ctx.accounts.network_receipts,
+            &src_network,
+            &ctx.accounts.relayer,
+            ctx.remaining_accounts,
+        )
+    }
+
     pub fn set_admin(ctx: Context<SetConfigItem>, account: Pubkey) -> Result<()> {
         let config = ctx.accounts.config.deref_mut();
         config.admin = account;
@@ -98,6 +180,67 @@ pub mod centralized_connection {
         Ok(())
     }
 
+    /// Adds `address` as a relayer allowed to deliver messages from `networks`,
+    /// or from every network when empty. Calling it again for the same address
+    /// replaces its networks
+    #[allow(unused_variables)]
+    pub fn add_relayer(
+        ctx: Context<AddRelayer>,
+        address: Pubkey,
+        networks: Vec<String>,
+    ) -> Result<()> {
+        let relayer_info = Relayer::new(networks, ctx.bumps.relayer_info)?;
+        ctx.accounts.relayer_info.set_inner(relayer_info);
+
+        Ok(())
+    }
+
+    /// Removes a relayer added with `add_relayer`, the fees held by its relayer
+    /// account are sent to the relayer. When it delivered last, the fees the
+    /// config collected are sent along and the config is left without a fee
+    /// relayer
+    pub fn remove_relayer(ctx: Context<RemoveRelayer>) -> Result<()> {
+        let relayer = ctx.accounts.relayer.to_account_info();
+        let relayer_info = ctx.accounts.relayer_info.to_account_info();
+        let config = &mut ctx.accounts.config;
+        if config.fee_relayer == relayer.key() {
+            let fee_account = if config.fee_account == relayer_info.key() {
+                relayer_info
+            } else {
+                relayer
+            };
+            helper::settle_fees(config, Some(fee_account))?;
+            config.fee_relayer = Pubkey::default();
+            config.fee_account = Pubkey::default();
+        }
+
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn migrate_network_fee(ctx: Context<MigrateNetworkFee>, network_id: String) -> Result<()> {
+        instructions::migrate_network_fee(ctx)
+    }
+
+    /// Moves the receipt watermark of `src_network` up to `legacy_conn_sn`, the
+    /// connection sequence the source continued from when it started to number
+    /// the messages of each network on their own. The gaps the shared sequence
+    /// left below it are never filled and would hold the watermark back
+    #[allow(unused_variables)]
+    pub fn migrate_network_receipts(
+        ctx: Context<MigrateNetworkReceipts>,
+        src_network: String,
+        legacy_conn_sn: u128,
+    ) -> Result<()> {
+        let network_receipts = &mut ctx.accounts.network_receipts;
+        network_receipts.bump = ctx.bumps.network_receipts;
+        if legacy_conn_sn > network_receipts.watermark {
+            network_receipts.watermark = legacy_conn_sn;
+        }
+
+        Ok(())
+    }
+
     #[allow(unused_variables)]
     pub fn set_fee(
         ctx: Context<SetFee>,
@@ -105,11 +248,18 @@ pub mod centralized_connection {
         message_fee: u64,
         response_fee: u64,
     ) -> Result<()> {
-        ctx.accounts.network_fee.set_inner(NetworkFee::new(
-            message_fee,
-            response_fee,
-            ctx.bumps.network_fee,
-        ));
+        helper::ensure_relayer(
+            &ctx.accounts.config,
+            &ctx.accounts.relayer,
+            &ctx.accounts.relayer_info,
+            None,
+        )?;
+
+        // the connection sequence of the network is kept
+        let network_fee = &mut ctx.accounts.network_fee;
+        network_fee.message_fee = message_fee;
+        network_fee.response_fee = response_fee;
+        network_fee.bump = ctx.bumps.network_fee;
 
         Ok(())
     }
@@ -139,12 +289,35 @@ pub mod centralized_connection {
         ctx.accounts.network_fee.get(response)
     }
 
+    /// Claims the fees held by the relayer account of the signer, along with the
+    /// fees collected by the config if the signer delivered a message last
     pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
-        let config = ctx.accounts.config.to_account_info();
-        let fee = ctx.accounts.config.get_claimable_fees(&config)?;
+        let relayer = ctx.accounts.relayer.key();
+        let config = &ctx.accounts.config;
 
-        **config.try_borrow_mut_lamports()? -= fee;
-        **ctx.accounts.relayer.try_borrow_mut_lamports()? += fee;
+        let mut fee_accounts = vec![];
+        match &ctx.accounts.relayer_info {
+            Some(relayer_info) => {
+                let account = relayer_info.to_account_info();
+                let fee = Relayer::get_claimable_fees(&account)?;
+                fee_accounts.push((account, fee));
+            }
+            None => {
+                if !config.is_fee_relayer(relayer) {
+                    config.ensure_relayer(relayer)?;
+                }
+            }
+        }
+        if config.is_fee_relayer(relayer) {
+            let account = config.to_account_info();
+            let fee = config.get_claimable_fees(&account)?;
+            fee_accounts.push((account, fee));
+        }
+
+        for (account, fee) in fee_accounts {
+            **account.try_borrow_mut_lamports()? -= fee;
+            **ctx.accounts.relayer.try_borrow_mut_lamports()? += fee;
+        }
 
         Ok(())
     }
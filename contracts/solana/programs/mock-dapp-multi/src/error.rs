@@ -19,4 +19,7 @@ pub enum DappError {
 
     #[msg("Only xcall")]
     OnlyXcall,
+
+    #[msg("Invalid Message Type")]
+    InvalidMessageType,
 }
@@ -12,3 +12,10 @@ pub struct RollbackDataReceived {
     pub ssn: u128,
     pub rollback: Vec<u8>,
 }
+
+#[event]
+pub struct ResponseReceived {
+    pub from: String,
+    pub sn: u128,
+    pub data: Vec<u8>,
+}
@@ -2,13 +2,14 @@ use anchor_lang::{prelude::*, solana_program::hash};
 
 use xcall_lib::message::call_message::CallMessage;
 use xcall_lib::message::call_message_persisted::CallMessagePersisted;
+use xcall_lib::message::call_message_response::CallMessageWithResponse;
 use xcall_lib::message::call_message_rollback::CallMessageWithRollback;
 use xcall_lib::message::{msg_type::*, AnyMessage};
 
 use crate::{CallMessageCtx, DappError};
 
 pub fn  process_message(message_type: u8, data: Vec<u8>, rollback: Vec<u8>) -> Result<AnyMessage> {
-    let msg_type: MessageType = message_type.into();
+    let msg_type = MessageType::try_from(message_type).map_err(|_| DappError::InvalidMessageType)?;
 
     let message = if msg_type == MessageType::CallMessagePersisted {
         AnyMessage::CallMessagePersisted(CallMessagePersisted { data })
@@ -18,6 +19,12 @@ pub fn  process_message(message_type: u8, data: Vec<u8>, rollback: Vec<u8>) -> R
         } else {
             return Err(DappError::InvalidRollbackMessage.into());
         }
+    } else if msg_type == MessageType::CallMessageWithResponse {
+        if rollback.len() > 0 {
+            AnyMessage::CallMessageWithResponse(CallMessageWithResponse { data, rollback })
+        } else {
+            return Err(DappError::InvalidRollbackMessage.into());
+        }
     } else {
         AnyMessage::CallMessage(CallMessage { data })
     };
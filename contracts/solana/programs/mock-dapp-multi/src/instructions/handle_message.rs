@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use xcall_lib::{network_address::NetworkAddress, xcall_dapp_type::HandleCallMessageResponse};
 
-use crate::{error::*, state::*};
+use crate::{error::*, event, state::*};
 
 use std::str;
 
@@ -16,6 +16,7 @@ pub fn handle_call_message<'info>(
         return Ok(HandleCallMessageResponse {
             success: true,
             message: "success".to_owned(),
+            data: vec![],
         });
     };
 
@@ -24,15 +25,37 @@ pub fn handle_call_message<'info>(
         return Ok(HandleCallMessageResponse {
             success: false,
             message: "Revert from dapp".to_owned(),
+            data: vec![],
         });
     }
 
+    // the data is echoed back to the sender of a `CallMessageWithResponse`
     return Ok(HandleCallMessageResponse {
         success: true,
         message: "success".to_owned(),
+        data,
     });
 }
 
+pub fn handle_call_response<'info>(
+    _ctx: Context<'_, '_, '_, 'info, HandleCallMessageCtx<'info>>,
+    from: NetworkAddress,
+    sn: u128,
+    data: Vec<u8>,
+) -> Result<HandleCallMessageResponse> {
+    emit!(event::ResponseReceived {
+        from: from.to_string(),
+        sn,
+        data,
+    });
+
+    Ok(HandleCallMessageResponse {
+        success: true,
+        message: "success".to_owned(),
+        data: vec![],
+    })
+}
+
 #[derive(Accounts)]
 pub struct HandleCallMessageCtx<'info> {
     #[account(mut)]
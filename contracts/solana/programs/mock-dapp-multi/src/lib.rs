@@ -51,6 +51,15 @@ pub mod mock_dapp_multi {
         instructions::handle_message::handle_call_message(ctx, from, data, protocols)
     }
 
+    pub fn handle_call_response<'info>(
+        ctx: Context<'_, '_, '_, 'info, HandleCallMessageCtx<'info>>,
+        from: NetworkAddress,
+        sn: u128,
+        data: Vec<u8>,
+    ) -> Result<xcall_dapp_type::HandleCallMessageResponse> {
+        instructions::handle_message::handle_call_response(ctx, from, sn, data)
+    }
+
     pub fn add_connection(
         ctx: Context<AddConnectionCtx>,
         network_id: String,
@@ -64,7 +64,8 @@ pub fn query_connection_fee<'info>(
 /// - `signer`: The account that signs the transaction.
 /// - `system_program`: The system program account.
 /// - `remaining_accounts`: A slice of `AccountInfo` containing additional accounts required
-///   for the connection, including the connection program, connection configuration, and network fee
+///   for the connection, including the connection program, connection configuration, and network fee.
+///   The network fee account is writable, it holds the connection sequence of the destination
 ///
 /// # Returns
 /// - `Result<()>`: Returns `Ok(())` if the message was successfully sent, otherwise returns an error.
@@ -90,7 +91,7 @@ pub fn call_connection_send_message<'info>(
         AccountMeta::new_readonly(system_program.key(), false),
         AccountMeta::new_readonly(config.key(), true),
         AccountMeta::new(conn_config.key(), false),
-        AccountMeta::new_readonly(network_fee.key(), false),
+        AccountMeta::new(network_fee.key(), false),
     ];
     let account_infos: Vec<AccountInfo<'info>> = vec![
         conn_config.to_account_info(),
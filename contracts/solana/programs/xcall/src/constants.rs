@@ -2,3 +2,6 @@ pub const ACCOUNT_DISCRIMINATOR_SIZE: usize = 8;
 
 pub const MAX_ROLLBACK_SIZE: usize = 1024;
 pub const MAX_DATA_SIZE: usize = 2048;
+
+pub const MAX_FEE_SPLITS: usize = 10;
+pub const MAX_BASIS_POINTS: u128 = 10_000;
@@ -8,7 +8,8 @@ use anchor_lang::{
 use xcall_lib::{
     network_address::NetworkAddress,
     xcall_dapp_type::{
-        self, HandleCallMessageResponse, HANDLE_CALL_MESSAGE_IX, QUERY_HANDLE_CALL_MESSAGE_IX,
+        self, HandleCallMessageResponse, HANDLE_CALL_MESSAGE_IX, HANDLE_CALL_RESPONSE_IX,
+        QUERY_HANDLE_CALL_MESSAGE_IX,
     },
 };
 
@@ -97,7 +98,16 @@ pub fn invoke_handle_call_message_ix<'info>(
 
     let (_, data) = get_return_data().ok_or(XcallError::InvalidResponse)?;
     let mut data_slice: &[u8] = &data;
-    let res = xcall_dapp_type::HandleCallMessageResponse::deserialize(&mut data_slice)?;
+    // dapps built before `data` was added to the response only return its first two fields
+    let res = xcall_dapp_type::HandleCallMessageResponse {
+        success: bool::deserialize(&mut data_slice)?,
+        message: String::deserialize(&mut data_slice)?,
+        data: if data_slice.is_empty() {
+            vec![]
+        } else {
+            Vec::<u8>::deserialize(&mut data_slice)?
+        },
+    };
 
     Ok(res)
 }
@@ -132,6 +142,28 @@ pub fn get_handle_call_message_ix_data(
     Ok(ix_data)
 }
 
+/// Prepares the instruction data to call the `handle_call_response` instruction on a DApp program.
+///
+/// # Arguments
+/// - `from`: The network address of the destination that returned the data.
+/// - `sn`: The sequence number the `CallMessageWithResponse` was sent with.
+/// - `data`: The data returned by the destination.
+///
+/// # Returns
+/// - `Result<Vec<u8>>`: Serialized instruction data for the `handle_call_response` instruction.
+pub fn get_handle_call_response_ix_data(
+    from: NetworkAddress,
+    sn: u128,
+    data: Vec<u8>,
+) -> Result<Vec<u8>> {
+    let mut ix_args_data = vec![];
+    let ix_args = xcall_dapp_type::HandleCallResponseArgs { from, sn, data };
+    ix_args.serialize(&mut ix_args_data)?;
+
+    let ix_data = helper::get_instruction_data(HANDLE_CALL_RESPONSE_IX, ix_args_data);
+    Ok(ix_data)
+}
+
 /// Prepares the instruction data to call the `query_handle_call_message_accounts` instruction
 /// on DApp program.
 ///
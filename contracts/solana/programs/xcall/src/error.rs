@@ -103,4 +103,16 @@ pub enum XcallError {
 
     #[msg("Successful response account must not be specified")]
     SuccessfulResponseAccountMustNotBeSpecified,
+
+    #[msg("Invalid fee splits")]
+    InvalidFeeSplits,
+
+    #[msg("Recipient has unclaimed protocol fee")]
+    UnclaimedProtocolFee,
+
+    #[msg("Invalid timeout")]
+    InvalidTimeout,
+
+    #[msg("Call response not found")]
+    CallResponseNotFound,
 }
@@ -18,6 +18,7 @@ pub struct CallMessage {
     pub data: Vec<u8>,
     pub connection: Pubkey,
     pub connSn: u128,
+    pub gasLimit: u64,
 }
 
 #[event]
@@ -42,3 +43,14 @@ pub struct RollbackMessage {
 pub struct RollbackExecuted {
     pub sn: u128,
 }
+
+#[event]
+pub struct CallResponseMessage {
+    pub sn: u128,
+    pub data: Vec<u8>,
+}
+
+#[event]
+pub struct CallResponseExecuted {
+    pub sn: u128,
+}
@@ -6,9 +6,13 @@ use xcall_lib::message::msg_type::MessageType;
 use crate::{
     connection, dapp,
     error::XcallError,
-    helper,
+    event, helper,
     state::*,
-    types::{message::CSMessage, result::CSMessageResult},
+    types::{
+        message::CSMessage,
+        request::CSMessageRequest,
+        result::{CSMessageResult, CSResponseType},
+    },
 };
 
 /// Executes a call based on a proxy request.
@@ -16,7 +20,12 @@ use crate::{
 /// This function processes a call by verifying the provided data against the request's data hash
 /// and then invoking the `handle_call_message` instruction on the DApp. Depending on the message
 /// type, it handles the response accordingly, potentially sending a result back through the
-/// connection program.
+/// connection program. A `CallMessageWithTimeout` executed after its deadline and a request of a
+/// message type this version does not support are answered with a failure without invoking the
+/// DApp, and the result of a `CallMessageWithResponse` carries the data returned by the DApp. The
+/// gas limit of the request is not enforced here: it is emitted with the `CallMessage` event for
+/// relayers to size the compute budget of the transaction, and a DApp running out of compute fails
+/// the transaction.
 ///
 /// # Parameters
 /// - `ctx`: The context containing all the necessary accounts and program state.
@@ -36,6 +45,31 @@ pub fn execute_call<'info>(
         return Err(XcallError::DataMismatch.into());
     }
 
+    let clock = Clock::get()?;
+    if req.has_timed_out(clock.slot, clock.unix_timestamp as u64) {
+        // the dapp is not called, the source rolls the message back
+        emit!(event::CallExecuted {
+            reqId: req_id,
+            code: CSResponseType::CSResponseFailure.into(),
+            msg: "timed out".to_string(),
+        });
+        return send_result(&ctx, req, CSResponseType::CSResponseFailure, None);
+    }
+
+    // sent by a newer version, every message type added after `CallMessagePersisted` expects a
+    // response so the sender can roll back
+    let msg_type = match req.msg_type() {
+        Ok(msg_type) => msg_type,
+        Err(_) => {
+            emit!(event::CallExecuted {
+                reqId: req_id,
+                code: CSResponseType::CSResponseFailure.into(),
+                msg: "unsupported message type".to_string(),
+            });
+            return send_result(&ctx, req, CSResponseType::CSResponseFailure, None);
+        }
+    };
+
     let dapp_key = Pubkey::from_str(&req.to()).map_err(|_| XcallError::InvalidPubkey)?;
 
     // Prepare the instruction data for the DApp's `handle_call_message` instruction.
@@ -55,7 +89,7 @@ pub fn execute_call<'info>(
     )?;
 
     // Handle the response based on the message type.
-    match req.msg_type() {
+    match msg_type {
         MessageType::CallMessage => {
             dapp::handle_response(req_id, dapp_res)?;
         }
@@ -66,35 +100,54 @@ pub fn execute_call<'info>(
             }
             dapp::handle_response(req_id, dapp_res)?;
         }
-        MessageType::CallMessageWithRollback => {
+        MessageType::CallMessageWithRollback | MessageType::CallMessageWithTimeout => {
             let res_code = dapp::handle_response(req_id, dapp_res)?;
-
-            let result = CSMessageResult::new(req.sequence_no(), res_code, None);
-            let cs_message = rlp::encode(&CSMessage::from(result)).to_vec();
-
-            let ix_data = connection::get_send_message_ix_data(
-                &req.from().nid(),
-                -(req.sequence_no() as i64),
-                cs_message,
-            )?;
-
-            for (i, _) in req.protocols().iter().enumerate() {
-                connection::call_connection_send_message(
-                    i,
-                    &ix_data,
-                    &req.protocols(),
-                    &ctx.accounts.config,
-                    &ctx.accounts.signer,
-                    &ctx.accounts.system_program,
-                    &ctx.remaining_accounts,
-                )?;
-            }
+            send_result(&ctx, req, res_code, None)?;
+        }
+        MessageType::CallMessageWithResponse => {
+            let data = dapp_res.data.clone();
+            let res_code = dapp::handle_response(req_id, dapp_res)?;
+            // the data returned by the dapp is carried back to the sender in the result
+            let message = (res_code == CSResponseType::CSResponseSuccess).then_some(data);
+            send_result(&ctx, req, res_code, message)?;
         }
     }
 
     Ok(())
 }
 
+/// Sends the result of `req` back through the connections the request was delivered by, with the
+/// data returned to the sender of a `CallMessageWithResponse` as its `message`.
+fn send_result<'info>(
+    ctx: &Context<'_, '_, '_, 'info, ExecuteCallCtx<'info>>,
+    req: &CSMessageRequest,
+    res_code: CSResponseType,
+    message: Option<Vec<u8>>,
+) -> Result<()> {
+    let result = CSMessageResult::new(req.sequence_no(), res_code, message);
+    let cs_message = rlp::encode(&CSMessage::from(result)).to_vec();
+
+    let ix_data = connection::get_send_message_ix_data(
+        &req.from().nid(),
+        -(req.sequence_no() as i64),
+        cs_message,
+    )?;
+
+    for (i, _) in req.protocols().iter().enumerate() {
+        connection::call_connection_send_message(
+            i,
+            &ix_data,
+            &req.protocols(),
+            &ctx.accounts.config,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            &ctx.remaining_accounts,
+        )?;
+    }
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(req_id : u128, from_nid: String, conn_sn: u128, connection: Pubkey)]
 pub struct ExecuteCallCtx<'info> {
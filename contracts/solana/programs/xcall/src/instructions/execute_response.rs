@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{dapp, error::XcallError, event, helper, state::*};
+
+/// Delivers the data returned by the destination of a `CallMessageWithResponse` to the DApp that
+/// sent it.
+///
+/// The data is emitted with the `CallResponseMessage` event when the successful result is
+/// received, and checked here against the hash kept in the rollback account. The DApp's
+/// `handle_call_response` instruction is then invoked with the destination network address, and
+/// the rollback account is closed.
+///
+/// # Arguments
+/// - `ctx`: The context containing all the necessary accounts and program state.
+/// - `sn`: The sequence number the message was sent with.
+/// - `data`: The data returned by the destination.
+///
+/// # Returns
+/// - `Result<()>`: Returns `Ok(())` if the response was delivered successfully, or an error if it
+/// failed.
+pub fn execute_response<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteResponseCtx<'info>>,
+    sn: u128,
+    data: Vec<u8>,
+) -> Result<()> {
+    let rollback = &ctx.accounts.rollback_account.rollback;
+    let response = rollback
+        .response()
+        .ok_or(XcallError::CallResponseNotFound)?;
+    if &helper::hash_data(&data) != response {
+        return Err(XcallError::DataMismatch.into());
+    }
+
+    let ix_data = dapp::get_handle_call_response_ix_data(rollback.to().to_owned(), sn, data)?;
+
+    let res = dapp::invoke_handle_call_message_ix(
+        rollback.from().to_owned(),
+        ix_data,
+        &ctx.accounts.config,
+        &ctx.accounts.signer,
+        &ctx.remaining_accounts,
+    )?;
+
+    if !res.success {
+        msg!("Error executing response from dapp: {:?}", res.message);
+        return Err(XcallError::RevertFromDapp.into());
+    }
+
+    emit!(event::CallResponseExecuted { sn });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(sn : u128,)]
+pub struct ExecuteResponseCtx<'info> {
+    /// The account that signs and pays for the transaction. This account is mutable
+    /// because it will be debited for any fees or rent required during the transaction.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The solana system program account, used for creating and managing accounts.
+    pub system_program: Program<'info, System>,
+
+    /// The configuration account, which stores important settings and counters for the program.
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: This is safe because this account is checked against the `config.admin` to ensure
+    /// it is valid.
+    #[account(
+        mut,
+        address = config.admin @ XcallError::OnlyAdmin
+    )]
+    pub admin: AccountInfo<'info>,
+
+    /// The rollback account of the message, holding the hash of the returned data. The account is
+    /// closed after use, with any remaining funds sent to the `admin`.
+    #[account(
+        mut,
+        seeds = [RollbackAccount::SEED_PREFIX.as_bytes(), &sn.to_be_bytes()],
+        bump = rollback_account.bump,
+        close = admin,
+    )]
+    pub rollback_account: Account<'info, RollbackAccount>,
+}
@@ -9,12 +9,97 @@ pub fn set_protocol_fee(ctx: Context<SetFeeCtx>, fee: u64) -> Result<()> {
     Ok(())
 }
 
+/// Sets the protocol fee handler.
+///
+/// While splits are configured the fee splits account stays the fee handler of the config and
+/// the new fee handler is kept in it instead, receiving the remainder left by rounding the shares.
+/// The fee handler can't be replaced while it has an unclaimed remainder.
+///
+/// # Arguments
+/// - `ctx`: The context of the solana program instruction.
+/// - `fee_handler`: The pubkey of the new fee handler.
+///
+/// # Returns
+/// - `Result<()>`: Returns `Ok(())` if the fee handler is updated, otherwise returns an error.
 pub fn set_protocol_fee_handler(ctx: Context<SetFeeHandlerCtx>, fee_handler: Pubkey) -> Result<()> {
-    ctx.accounts.config.set_fee_handler(fee_handler);
+    let (fee_splits_key, _) =
+        Pubkey::find_program_address(&[FeeSplits::SEED_PREFIX.as_bytes()], &crate::id());
+    if ctx.accounts.config.fee_handler != fee_splits_key {
+        ctx.accounts.config.set_fee_handler(fee_handler);
+        return Ok(());
+    }
+
+    let fee_splits = ctx
+        .accounts
+        .fee_splits
+        .as_mut()
+        .ok_or(XcallError::InvalidFeeSplits)?;
+    if fee_splits.remainder > 0 && fee_splits.fee_handler != fee_handler {
+        return Err(XcallError::UnclaimedProtocolFee.into());
+    }
+    fee_splits.fee_handler = fee_handler;
+
+    Ok(())
+}
+
+/// Sets the recipients the protocol fee is split between.
+///
+/// While at least one recipient is configured, the fee splits account itself becomes the protocol
+/// fee handler: the protocol fee of each `send_call` is transferred to it and accrued to the
+/// recipients by their basis points, the remainder left by rounding being accrued to the fee
+/// handler the admin has set. Clearing the splits makes that fee handler the config's again.
+///
+/// # Arguments
+/// - `ctx`: The context of the solana program instruction.
+/// - `splits`: The recipients and their shares in basis points, adding up to `MAX_BASIS_POINTS`.
+///
+/// # Returns
+/// - `Result<()>`: Returns `Ok(())` if the splits are updated, otherwise returns an error.
+pub fn set_protocol_fee_splits(ctx: Context<SetFeeSplitsCtx>, splits: Vec<FeeSplit>) -> Result<()> {
+    let fee_splits_key = ctx.accounts.fee_splits.key();
+    let config = &mut ctx.accounts.config;
+    let fee_splits = &mut ctx.accounts.fee_splits;
+
+    // keep the fee handler set by the admin while the fee splits account takes its place
+    if config.fee_handler != fee_splits_key {
+        fee_splits.fee_handler = config.fee_handler;
+    }
+    let fee_handler = if splits.is_empty() {
+        fee_splits.fee_handler
+    } else {
+        fee_splits_key
+    };
+
+    fee_splits.set(splits, ctx.bumps.fee_splits)?;
+    config.set_fee_handler(fee_handler);
 
     Ok(())
 }
 
+/// Transfers the protocol fee accrued to the signer from the fee splits account to the signer,
+/// the share of a recipient and, for the fee handler, the rounding remainder.
+///
+/// # Arguments
+/// - `ctx`: The context of the solana program instruction.
+///
+/// # Returns
+/// - `Result<u64>`: The amount of lamports claimed.
+pub fn claim_protocol_fee_share(ctx: Context<ClaimFeeShareCtx>) -> Result<u64> {
+    let recipient = &ctx.accounts.recipient;
+    let amount = ctx.accounts.fee_splits.take_accrued(&recipient.key())?;
+
+    if amount > 0 {
+        **ctx
+            .accounts
+            .fee_splits
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= amount;
+        **recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+    }
+
+    Ok(amount)
+}
+
 /// Calculates and retrieves the total fee for a cross-chain message, including the protocol fee
 /// and connection-specific fees.
 ///
@@ -80,6 +165,63 @@ pub struct SetFeeHandlerCtx<'info> {
         address = config.admin @ XcallError::OnlyAdmin
     )]
     pub admin: Signer<'info>,
+
+    /// The fee splits account, required while splits are configured as it then holds the fee
+    /// handler.
+    #[account(
+        mut,
+        seeds = [FeeSplits::SEED_PREFIX.as_bytes()],
+        bump = fee_splits.bump
+    )]
+    pub fee_splits: Option<Account<'info, FeeSplits>>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplitsCtx<'info> {
+    /// The configuration account, which stores important settings for the program.
+    /// This account is mutable because the fee handler of the protocol will be updated.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The fee splits account, created on first use with the `admin` paying for its creation.
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = FeeSplits::SIZE,
+        seeds = [FeeSplits::SEED_PREFIX.as_bytes()],
+        bump
+    )]
+    pub fee_splits: Account<'info, FeeSplits>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the `config.admin` to ensure it is valid.
+    #[account(
+        mut,
+        address = config.admin @ XcallError::OnlyAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// The solana system program account, used for creating and managing accounts.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFeeShareCtx<'info> {
+    /// The fee splits account holding the accrued protocol fees.
+    #[account(
+        mut,
+        seeds = [FeeSplits::SEED_PREFIX.as_bytes()],
+        bump = fee_splits.bump
+    )]
+    pub fee_splits: Account<'info, FeeSplits>,
+
+    /// The fee recipient claiming its accrued share.
+    #[account(mut)]
+    pub recipient: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -1,7 +1,6 @@
 use std::{str::FromStr, vec};
 
 use anchor_lang::prelude::*;
-use xcall_lib::message::msg_type::MessageType;
 
 use crate::{
     connection,
@@ -40,7 +39,7 @@ pub fn handle_forced_rollback<'info>(
         return Err(XcallError::RequestPending.into());
     }
 
-    if req.msg_type() != MessageType::CallMessageWithRollback {
+    if !req.need_response() {
         return Err(XcallError::RollbackNotPossible.into());
     }
 
@@ -44,7 +44,11 @@ pub fn handle_message<'info>(
         return Err(XcallError::ProtocolMismatch.into());
     }
 
+    // only canonical encodings are accepted so the hash of a message is the same for every
+    // connection delivering it
+    rlp::strict::validate(&message).map_err(|_| XcallError::DecodeFailed)?;
     let cs_message: CSMessage = message.try_into()?;
+    rlp::strict::validate(&cs_message.payload).map_err(|_| XcallError::DecodeFailed)?;
     match cs_message.message_type() {
         CSMessageType::CSMessageRequest => {
             if ctx.accounts.pending_response.is_some() {
@@ -141,7 +145,8 @@ pub fn handle_request(
         reqId: req_id,
         data: req.data(),
         connection: source.owner.to_owned(),
-        connSn: conn_sn
+        connSn: conn_sn,
+        gasLimit: req.gas_limit()
     });
 
     let proxy_request = &mut ctx.accounts.proxy_request;
@@ -158,7 +163,8 @@ pub fn handle_request(
 /// This function processes the outcome of a cross-chain operation, performing different
 /// actions depending on whether the result was successful or not. If the operation was successful,
 /// it finalizes the process by closing the rollback account and marking the operation as successful.
-/// If the operation failed, it enables rollback and emits an event indicating a rollback action.
+/// The rollback account of a `CallMessageWithResponse` is kept instead, holding the hash of the
+/// returned data until it is delivered by `execute_response`. If the operation failed, it enables rollback and emits an event indicating a rollback action.
 /// Additionally, it ensures that the appropriate accounts are present or absent based on the outcome.
 ///
 /// # Arguments
@@ -184,8 +190,6 @@ pub fn handle_result(ctx: Context<HandleResultCtx>, payload: &[u8], conn_sn: u12
 
     match response_code {
         CSResponseType::CSResponseSuccess => {
-            rollback_account.close(ctx.accounts.admin.clone())?;
-
             let success_res = ctx
                 .accounts
                 .successful_response
@@ -194,6 +198,26 @@ pub fn handle_result(ctx: Context<HandleResultCtx>, payload: &[u8], conn_sn: u12
 
             success_res.success = true;
 
+            // the returned data is delivered by `execute_response`, given the data back and
+            // checking it against the hash kept in the rollback account
+            if rollback_account.rollback.expects_response() {
+                if proxy_request.is_some() {
+                    return Err(XcallError::ProxyRequestAccountMustNotBeSpecified.into());
+                }
+                let data = result.message_bytes().to_vec();
+                rollback_account
+                    .rollback
+                    .set_response(helper::hash_data(&data));
+
+                emit!(event::CallResponseMessage {
+                    sn: result.sequence_no(),
+                    data,
+                });
+                return Ok(());
+            }
+
+            rollback_account.close(ctx.accounts.admin.clone())?;
+
             if let Some(message) = &mut result.message() {
                 handle_reply(ctx, message, conn_sn)?;
             } else {
@@ -290,7 +314,8 @@ pub fn handle_reply(
         reqId: req_id,
         data: reply.data(),
         connection: ctx.accounts.connection.owner.to_owned(),
-        connSn: conn_sn
+        connSn: conn_sn,
+        gasLimit: reply.gas_limit()
     });
 
     let proxy_request = ctx
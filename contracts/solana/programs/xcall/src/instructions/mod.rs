@@ -1,6 +1,7 @@
 pub mod codec;
 pub mod config;
 pub mod execute_call;
+pub mod execute_response;
 pub mod execute_rollback;
 pub mod fee;
 pub mod handle_forced_rollback;
@@ -11,6 +12,7 @@ pub mod send_message;
 pub use codec::*;
 pub use config::*;
 pub use execute_call::*;
+pub use execute_response::*;
 pub use execute_rollback::*;
 pub use fee::*;
 pub use handle_forced_rollback::*;
@@ -124,6 +124,7 @@ pub fn query_handle_message_accounts(
 
             // Optional proxy request account
             if result.response_code() == &CSResponseType::CSResponseSuccess
+                && !rollback_account.rollback.expects_response()
                 && result.message().is_some()
             {
                 account_metas.push(AccountMetadata::new(proxy_request, false))
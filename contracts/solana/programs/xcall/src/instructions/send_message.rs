@@ -24,7 +24,8 @@ use crate::{
 /// # Arguments
 /// - `ctx`: The context of the solana program instruction
 /// - `message`: The `Envelope` payload, encoded as rlp bytes
-/// - `to`: The target network address where the message is to be sent
+/// - `to`: The target network address where the message is to be sent, rejected with a
+///   `NetworkError` when malformed
 ///
 /// # Returns
 /// - `Result<u128>`: The sequence number of the message if successful, wrapped in a `Result`.
@@ -33,6 +34,7 @@ pub fn send_call<'info>(
     message: Vec<u8>,
     to: NetworkAddress,
 ) -> Result<u128> {
+    to.validate()?;
     let envelope: Envelope = rlp::decode(&message).map_err(|_| XcallError::DecodeFailed)?;
 
     let sequence_no = ctx.accounts.config.get_next_sn();
@@ -61,13 +63,16 @@ pub fn send_call<'info>(
 
     // Handle rollback logic if rollback message is present
     if envelope.message.rollback().is_some() {
-        let rollback = Rollback::new(
+        let mut rollback = Rollback::new(
             from_key,
             to.clone(),
             envelope.sources.clone(),
             envelope.message.rollback().unwrap(),
             false,
         );
+        if let AnyMessage::CallMessageWithResponse(_) = &envelope.message {
+            rollback.set_expects_response();
+        }
 
         let rollback_account = ctx.accounts.rollback_account.as_deref_mut().unwrap();
         rollback_account.set(rollback, ctx.bumps.rollback_account.unwrap());
@@ -75,7 +80,7 @@ pub fn send_call<'info>(
 
     let from = NetworkAddress::new(&config.network_id, &from_key.to_string());
 
-    let request = CSMessageRequest::new(
+    let mut request = CSMessageRequest::new(
         from,
         to.account(),
         sequence_no,
@@ -83,6 +88,10 @@ pub fn send_call<'info>(
         envelope.message.data(),
         envelope.destinations,
     );
+    if let AnyMessage::CallMessageWithTimeout(msg) = &envelope.message {
+        request.set_timeout(msg.timeout_height, msg.timeout_timestamp);
+    }
+    request.set_gas_limit(envelope.gas_limit);
 
     // Determine if a response is needed for the request
     let need_response = request.need_response();
@@ -172,6 +181,27 @@ pub fn validate_payload(
             }
             helper::ensure_rollback_size(&msg.rollback)?;
         }
+        AnyMessage::CallMessageWithTimeout(msg) => {
+            if rollback_account.is_none() {
+                return Err(XcallError::RollbackAccountNotSpecified.into());
+            }
+            if !helper::is_program(sysvar_account_info)? {
+                return Err(XcallError::RollbackNotPossible.into());
+            }
+            helper::ensure_rollback_size(&msg.rollback)?;
+            if msg.timeout_height == 0 && msg.timeout_timestamp == 0 {
+                return Err(XcallError::InvalidTimeout.into());
+            }
+        }
+        AnyMessage::CallMessageWithResponse(msg) => {
+            if rollback_account.is_none() {
+                return Err(XcallError::RollbackAccountNotSpecified.into());
+            }
+            if !helper::is_program(sysvar_account_info)? {
+                return Err(XcallError::RollbackNotPossible.into());
+            }
+            helper::ensure_rollback_size(&msg.rollback)?;
+        }
     }
 
     Ok(())
@@ -192,6 +222,9 @@ pub fn validate_payload(
 /// - `protocol_fee`: The amount of fee, in lamports, to be transferred from the `signer` to
 ///   the `fee_handler`.
 ///
+/// When the fee handler is the fee splits account, the fee is also accrued to the configured
+/// recipients.
+///
 /// # Returns
 /// - `Result<()>`: Returns `Ok(())` if the fee transfer is successful, or an error if the
 ///   transfer fails.
@@ -211,6 +244,16 @@ pub fn claim_protocol_fee<'info>(
         ],
     )?;
 
+    // the fee handler is the fee splits account while splits are configured
+    if fee_handler.owner == &crate::id() {
+        let mut data = fee_handler.try_borrow_mut_data()?;
+        let mut fee_splits = FeeSplits::try_deserialize(&mut &data[..])?;
+        fee_splits.accrue(protocol_fee);
+
+        let mut writer = &mut data[..];
+        fee_splits.try_serialize(&mut writer)?;
+    }
+
     Ok(())
 }
 
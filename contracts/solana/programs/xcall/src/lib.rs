@@ -99,6 +99,41 @@ pub mod xcall {
         instructions::set_protocol_fee_handler(ctx, fee_handler)
     }
 
+    /// Instruction: Set Protocol Fee Splits
+    ///
+    /// Sets the recipients the protocol fee is split between
+    ///
+    /// This function verifies that the signer is an admin of the program and stores the
+    /// recipients with their share in basis points. While splits are configured, protocol fees
+    /// accrue in the fee splits account and each recipient claims its own share.
+    ///
+    /// # Arguments
+    /// - `ctx`: The context of the solana program instruction
+    /// - `splits`: The recipients and their shares, adding up to 10000 basis points, or an empty
+    ///   list to make the fee handler receive the whole protocol fee again.
+    ///
+    /// # Returns
+    /// - `Result<()>`: Returns `Ok(())` if the transaction is successful, or an error if it fails.
+    pub fn set_protocol_fee_splits(
+        ctx: Context<SetFeeSplitsCtx>,
+        splits: Vec<state::FeeSplit>,
+    ) -> Result<()> {
+        instructions::set_protocol_fee_splits(ctx, splits)
+    }
+
+    /// Instruction: Claim Protocol Fee Share
+    ///
+    /// Transfers the protocol fee accrued to the signing recipient
+    ///
+    /// # Arguments
+    /// - `ctx`: The context of the solana program instruction
+    ///
+    /// # Returns
+    /// - `Result<u64>`: The amount of lamports claimed.
+    pub fn claim_protocol_fee_share(ctx: Context<ClaimFeeShareCtx>) -> Result<u64> {
+        instructions::claim_protocol_fee_share(ctx)
+    }
+
     /// Instruction: Send Call
     ///
     /// Sends a cross-chain message to a specified network address.
@@ -409,6 +444,27 @@ pub mod xcall {
         instructions::execute_rollback(ctx, sn)
     }
 
+    /// Instruction: Execute Response
+    ///
+    /// Delivers the data returned by the destination of a `CallMessageWithResponse` to the DApp
+    /// that sent it, by delegating to `instructions::execute_response`.
+    ///
+    /// # Arguments
+    /// - `ctx`: The context containing all the necessary accounts and program state.
+    /// - `sn`: The sequence number the message was sent with.
+    /// - `data`: The returned data, as emitted with the `CallResponseMessage` event.
+    ///
+    /// # Returns
+    /// - `Result<()>`: Returns `Ok(())` if the response was delivered successfully, or an error if
+    /// it failed.
+    pub fn execute_response<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteResponseCtx<'info>>,
+        sn: u128,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::execute_response(ctx, sn, data)
+    }
+
     /// Initiates the handling of a forced rollback for a cross-chain message. This function acts
     /// as a wrapper, calling the inner `handle_forced_rollback` instruction to handle the rollback
     /// process.
@@ -70,6 +70,123 @@ impl Config {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct FeeSplit {
+    pub recipient: Pubkey,
+    pub basis_points: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FeeShare {
+    pub recipient: Pubkey,
+    pub basis_points: u16,
+    pub accrued: u64,
+}
+
+/// Holds the protocol fee recipients and their accrued, unclaimed shares. While splits are
+/// configured this account is the protocol fee handler, so it also holds the fee lamports, and
+/// the fee handler set by the admin is kept here to receive the rounding remainder.
+#[account]
+#[derive(Debug)]
+pub struct FeeSplits {
+    pub shares: Vec<FeeShare>,
+    pub fee_handler: Pubkey,
+    pub remainder: u64,
+    pub bump: u8,
+}
+
+impl FeeSplits {
+    pub const SEED_PREFIX: &'static str = "fee_splits";
+
+    pub const SIZE: usize =
+        ACCOUNT_DISCRIMINATOR_SIZE + 4 + MAX_FEE_SPLITS * (32 + 2 + 8) + 32 + 8 + 1;
+
+    /// Replaces the recipients while keeping the accrued shares of recipients which remain in the
+    /// list. Recipients with unclaimed fees can't be removed.
+    pub fn set(&mut self, splits: Vec<FeeSplit>, bump: u8) -> Result<()> {
+        if splits.len() > MAX_FEE_SPLITS {
+            return Err(XcallError::InvalidFeeSplits.into());
+        }
+        let mut total: u32 = 0;
+        for split in splits.iter() {
+            if split.basis_points == 0 {
+                return Err(XcallError::InvalidFeeSplits.into());
+            }
+            total += split.basis_points as u32;
+        }
+        if !splits.is_empty() && total != MAX_BASIS_POINTS as u32 {
+            return Err(XcallError::InvalidFeeSplits.into());
+        }
+
+        for share in self.shares.iter() {
+            let retained = splits.iter().any(|s| s.recipient == share.recipient);
+            if !retained && share.accrued > 0 {
+                return Err(XcallError::UnclaimedProtocolFee.into());
+            }
+        }
+
+        self.shares = splits
+            .into_iter()
+            .map(|split| FeeShare {
+                accrued: self.share_of(&split.recipient),
+                recipient: split.recipient,
+                basis_points: split.basis_points,
+            })
+            .collect();
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    /// Splits `amount` between the recipients by their basis points, the remainder left by
+    /// rounding goes to the fee handler as it does in the other xcall implementations.
+    pub fn accrue(&mut self, amount: u64) {
+        let mut remaining = amount;
+        for share in self.shares.iter_mut() {
+            let part = (amount as u128 * share.basis_points as u128 / MAX_BASIS_POINTS) as u64;
+            share.accrued += part;
+            remaining -= part;
+        }
+        self.remainder += remaining;
+    }
+
+    pub fn accrued_of(&self, recipient: &Pubkey) -> u64 {
+        let remainder = if &self.fee_handler == recipient {
+            self.remainder
+        } else {
+            0
+        };
+        self.share_of(recipient) + remainder
+    }
+
+    pub fn take_accrued(&mut self, recipient: &Pubkey) -> Result<u64> {
+        let is_fee_handler = &self.fee_handler == recipient;
+        let share = self.shares.iter_mut().find(|s| &s.recipient == recipient);
+        if share.is_none() && !is_fee_handler {
+            return Err(XcallError::InvalidFeeSplits.into());
+        }
+
+        let mut amount = 0;
+        if let Some(share) = share {
+            amount += share.accrued;
+            share.accrued = 0;
+        }
+        if is_fee_handler {
+            amount += self.remainder;
+            self.remainder = 0;
+        }
+        Ok(amount)
+    }
+
+    fn share_of(&self, recipient: &Pubkey) -> u64 {
+        self.shares
+            .iter()
+            .find(|s| &s.recipient == recipient)
+            .map(|s| s.accrued)
+            .unwrap_or(0)
+    }
+}
+
 #[derive(Debug)]
 #[account]
 pub struct RollbackAccount {
@@ -80,7 +197,8 @@ pub struct RollbackAccount {
 impl RollbackAccount {
     pub const SEED_PREFIX: &'static str = "rollback";
 
-    pub const SIZE: usize = 8 + 512 + 1;
+    // the rollback is followed by the flag and response hash of a `CallMessageWithResponse`
+    pub const SIZE: usize = 8 + 512 + 1 + 1 + 37;
 
     pub fn set(&mut self, rollback: Rollback, bump: u8) {
         self.rollback = rollback;
@@ -137,3 +255,47 @@ impl ProxyRequest {
         self.bump = bump
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_splits_remainder_accrues_to_fee_handler() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let fee_handler = Pubkey::new_unique();
+
+        let mut fee_splits = FeeSplits {
+            shares: vec![],
+            fee_handler,
+            remainder: 0,
+            bump: 0,
+        };
+        fee_splits
+            .set(
+                vec![
+                    FeeSplit {
+                        recipient: alice,
+                        basis_points: 6667,
+                    },
+                    FeeSplit {
+                        recipient: bob,
+                        basis_points: 3333,
+                    },
+                ],
+                0,
+            )
+            .unwrap();
+
+        fee_splits.accrue(100);
+        assert_eq!(fee_splits.accrued_of(&alice), 66);
+        assert_eq!(fee_splits.accrued_of(&bob), 33);
+        assert_eq!(fee_splits.accrued_of(&fee_handler), 1);
+
+        assert_eq!(fee_splits.take_accrued(&fee_handler).unwrap(), 1);
+        assert_eq!(fee_splits.accrued_of(&fee_handler), 0);
+        assert_eq!(fee_splits.take_accrued(&alice).unwrap(), 66);
+        assert!(fee_splits.take_accrued(&Pubkey::new_unique()).is_err());
+    }
+}
@@ -61,7 +61,7 @@ impl Decodable for CSMessage {
             return Err(rlp::DecoderError::RlpIncorrectListLen);
         }
 
-        let msg_type: u8 = rlp.val_at(0)?;
+        let msg_type = rlp::strict::decode_u8(&rlp.at(0)?)?;
 
         Ok(Self {
             message_type: match msg_type {
@@ -8,3 +8,194 @@ use anchor_lang::{
     solana_program, AnchorDeserialize, AnchorSerialize,
 };
 use rlp::{Decodable, Encodable};
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::Value;
+    use xcall_lib::{message::msg_type::MessageType, network_address::NetworkAddress};
+
+    use super::{
+        message::{CSMessage, CSMessageType},
+        request::CSMessageRequest,
+        result::{CSMessageResult, CSResponseType},
+    };
+
+    const VECTORS: &str = include_str!("../../../../../../test-vectors/xcall/messages.json");
+
+    fn bytes(value: &Value) -> Vec<u8> {
+        hex::decode(value.as_str().unwrap()).unwrap()
+    }
+
+    fn sn(value: &Value) -> u128 {
+        value.as_str().unwrap().parse().unwrap()
+    }
+
+    fn request(value: &Value) -> CSMessageRequest {
+        let mut request = CSMessageRequest::new(
+            NetworkAddress::from_str(value["from"].as_str().unwrap()).unwrap(),
+            value["to"].as_str().unwrap().to_string(),
+            sn(&value["sn"]),
+            MessageType::from_int(value["msg_type"].as_u64().unwrap() as u8),
+            bytes(&value["data"]),
+            value["protocols"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|protocol| protocol.as_str().unwrap().to_string())
+                .collect(),
+        );
+        if let (Some(height), Some(timestamp)) = (
+            value["timeout_height"].as_u64(),
+            value["timeout_timestamp"].as_u64(),
+        ) {
+            request.set_timeout(height, timestamp);
+        }
+        if let Some(gas_limit) = value["gas_limit"].as_u64() {
+            request.set_gas_limit(gas_limit);
+        }
+        request
+    }
+
+    fn result(value: &Value) -> CSMessageResult {
+        let message = bytes(&value["message"]);
+        CSMessageResult::new(
+            sn(&value["sn"]),
+            CSResponseType::try_from(value["code"].as_u64().unwrap() as u8).unwrap(),
+            (!message.is_empty()).then_some(message),
+        )
+    }
+
+    fn message(value: &Value) -> CSMessage {
+        let message_type = match value["type"].as_u64().unwrap() {
+            1 => CSMessageType::CSMessageRequest,
+            2 => CSMessageType::CSMessageResult,
+            _ => panic!("unknown message type"),
+        };
+        CSMessage::new(message_type, bytes(&value["payload"]))
+    }
+
+    #[test]
+    fn test_golden_vectors() {
+        let vectors: Vec<Value> = serde_json::from_str(VECTORS).unwrap();
+
+        for vector in vectors.iter() {
+            let name = vector["name"].as_str().unwrap();
+            let value = &vector["value"];
+            let encoded = bytes(&vector["hex"]);
+
+            match vector["type"].as_str().unwrap() {
+                "CSMessageRequest" => {
+                    let expected = request(value);
+                    assert_eq!(expected.as_bytes(), encoded, "{}", name);
+
+                    let decoded = CSMessageRequest::try_from(&encoded).unwrap();
+                    assert_eq!(
+                        decoded.from().to_string(),
+                        expected.from().to_string(),
+                        "{}",
+                        name
+                    );
+                    assert_eq!(decoded.to(), expected.to(), "{}", name);
+                    assert_eq!(decoded.sequence_no(), expected.sequence_no(), "{}", name);
+                    assert_eq!(decoded.msg_type(), expected.msg_type(), "{}", name);
+                    assert_eq!(decoded.data(), expected.data(), "{}", name);
+                    assert_eq!(decoded.protocols(), expected.protocols(), "{}", name);
+                    assert_eq!(
+                        decoded.timeout_height(),
+                        expected.timeout_height(),
+                        "{}",
+                        name
+                    );
+                    assert_eq!(
+                        decoded.timeout_timestamp(),
+                        expected.timeout_timestamp(),
+                        "{}",
+                        name
+                    );
+                    assert_eq!(decoded.gas_limit(), expected.gas_limit(), "{}", name);
+                }
+                "CSMessageResult" => {
+                    let expected = result(value);
+                    assert_eq!(expected.as_bytes(), encoded, "{}", name);
+
+                    let decoded = CSMessageResult::try_from(&encoded).unwrap();
+                    assert_eq!(decoded, expected, "{}", name);
+                }
+                "CSMessage" => {
+                    let expected = message(value);
+                    assert_eq!(expected.as_bytes(), encoded, "{}", name);
+
+                    let decoded = CSMessage::try_from(encoded).unwrap();
+                    assert_eq!(decoded.as_bytes(), expected.as_bytes(), "{}", name);
+                    assert_eq!(decoded.payload(), expected.payload(), "{}", name);
+                }
+                // envelope and call messages are tested in xcall-lib
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_unsupported_request() {
+        let mut stream = rlp::RlpStream::new_list(6);
+        stream.append(&"0x1.ETH/0xa".to_string());
+        stream.append(&"dapp".to_string());
+        stream.append(&1_u128);
+        stream.append(&7_u8);
+        stream.append(&vec![1_u8, 2, 3]);
+        stream.begin_list(1);
+        stream.append(&"connection".to_string());
+        let encoded = stream.out().to_vec();
+
+        // stored as is, to be answered with a failure
+        let req = CSMessageRequest::try_from(&encoded).unwrap();
+        assert_eq!(req.from().to_string(), "0x1.ETH/0xa");
+        assert_eq!(req.sequence_no(), 1);
+        assert!(req.msg_type().is_err());
+        assert!(!req.need_response());
+        assert_eq!(req.protocols(), vec!["connection".to_string()]);
+        assert_eq!(req.as_bytes(), encoded);
+    }
+
+    #[test]
+    fn test_request_has_timed_out() {
+        let mut req = request(&serde_json::json!({
+            "from": "0x1.ETH/0xa",
+            "to": "dapp",
+            "sn": "1",
+            "msg_type": 3,
+            "data": "010203",
+            "protocols": [],
+        }));
+        req.set_timeout(100, 0);
+
+        let decoded = CSMessageRequest::try_from(&req.as_bytes()).unwrap();
+        assert_eq!(decoded.timeout_height(), 100);
+        assert_eq!(decoded.timeout_timestamp(), 0);
+
+        // a zero timestamp is not checked
+        assert!(!decoded.has_timed_out(99, u64::MAX));
+        assert!(decoded.has_timed_out(100, 0));
+    }
+
+    #[test]
+    fn test_result_carries_returned_data() {
+        let req = request(&serde_json::json!({
+            "from": "0x1.ETH/0xa",
+            "to": "dapp",
+            "sn": "1",
+            "msg_type": 4,
+            "data": "010203",
+            "protocols": [],
+        }));
+        assert!(req.need_response());
+
+        // the returned data is not a reply, it is not decoded as a request
+        let res = result(&serde_json::json!({"sn": "1", "code": 1, "message": "0a0b0c"}));
+        let decoded = CSMessageResult::try_from(&res.as_bytes()).unwrap();
+        assert_eq!(decoded.message_bytes(), &[0x0a, 0x0b, 0x0c]);
+        assert!(decoded.message().is_none());
+    }
+}
@@ -9,9 +9,12 @@ pub struct CSMessageRequest {
     from: NetworkAddress,
     to: String,
     sequence_no: u128,
-    msg_type: MessageType,
+    msg_type: u8,
     data: Vec<u8>, // TODO: cosmos this is nullable??
     protocols: Vec<String>,
+    timeout_height: u64,
+    timeout_timestamp: u64,
+    gas_limit: u64,
 }
 
 impl CSMessageRequest {
@@ -27,9 +30,12 @@ impl CSMessageRequest {
             from,
             to,
             sequence_no,
-            msg_type,
+            msg_type: msg_type.as_int(),
             data,
             protocols,
+            timeout_height: 0,
+            timeout_timestamp: 0,
+            gas_limit: 0,
         }
     }
 
@@ -45,8 +51,10 @@ impl CSMessageRequest {
         self.sequence_no
     }
 
-    pub fn msg_type(&self) -> MessageType {
-        self.msg_type.clone()
+    /// The type of the message, failing for the types of newer versions which are stored to be
+    /// answered with a failure by `execute_call`.
+    pub fn msg_type(&self) -> Result<MessageType, rlp::DecoderError> {
+        MessageType::try_from(self.msg_type)
     }
 
     pub fn data(&self) -> Vec<u8> {
@@ -67,11 +75,44 @@ impl CSMessageRequest {
     }
 
     pub fn need_response(&self) -> bool {
-        self.msg_type == MessageType::CallMessageWithRollback
+        self.msg_type() == Ok(MessageType::CallMessageWithRollback)
+            || self.msg_type() == Ok(MessageType::CallMessageWithTimeout)
+            || self.msg_type() == Ok(MessageType::CallMessageWithResponse)
+    }
+
+    pub fn timeout_height(&self) -> u64 {
+        self.timeout_height
+    }
+
+    pub fn timeout_timestamp(&self) -> u64 {
+        self.timeout_timestamp
+    }
+
+    /// Sets the deadline of a `CallMessageWithTimeout`, only encoded for that message type.
+    pub fn set_timeout(&mut self, timeout_height: u64, timeout_timestamp: u64) {
+        self.timeout_height = timeout_height;
+        self.timeout_timestamp = timeout_timestamp;
+    }
+
+    /// Whether the request may no longer be executed at `slot` and `timestamp` (seconds since
+    /// the unix epoch) of this chain. A zero deadline is not checked.
+    pub fn has_timed_out(&self, slot: u64, timestamp: u64) -> bool {
+        (self.timeout_height > 0 && slot >= self.timeout_height)
+            || (self.timeout_timestamp > 0 && timestamp >= self.timeout_timestamp)
+    }
+
+    /// The gas the sender allows the destination dapp to use, zero for no limit. Solana has no
+    /// gas, the limit is emitted for relayers to size the compute budget of `execute_call`.
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    pub fn set_gas_limit(&mut self, gas_limit: u64) {
+        self.gas_limit = gas_limit;
     }
 
     pub fn allow_retry(&self) -> bool {
-        self.msg_type == MessageType::CallMessagePersisted
+        self.msg_type() == Ok(MessageType::CallMessagePersisted)
     }
 
     pub fn protocols(&self) -> Vec<String> {
@@ -83,41 +124,78 @@ impl CSMessageRequest {
     }
 }
 
+// the deadline of a `CallMessageWithTimeout` is appended to the fields of the other message
+// types, which keep their encoding, followed by the gas limit when there is one
 impl Encodable for CSMessageRequest {
     fn rlp_append(&self, stream: &mut rlp::RlpStream) {
-        stream.begin_list(6);
+        let with_timeout = self.msg_type() == Ok(MessageType::CallMessageWithTimeout);
+        let fields = if with_timeout { 8 } else { 6 };
+        stream.begin_list(if self.gas_limit > 0 {
+            fields + 1
+        } else {
+            fields
+        });
 
         stream.append(&self.from.to_string());
         stream.append(&self.to);
         stream.append(&self.sequence_no);
-        stream.append(&self.msg_type.as_int());
+        stream.append(&self.msg_type);
         stream.append(&self.data);
         stream.begin_list(self.protocols.len());
         for protocol in self.protocols.iter() {
             stream.append(protocol);
         }
+        if with_timeout {
+            stream.append(&self.timeout_height);
+            stream.append(&self.timeout_timestamp);
+        }
+        if self.gas_limit > 0 {
+            stream.append(&self.gas_limit);
+        }
     }
 }
 
 impl Decodable for CSMessageRequest {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
-        if rlp.item_count()? != 6 {
-            return Err(rlp::DecoderError::RlpIncorrectListLen);
-        }
+        // the types of newer versions are decoded too, to be answered with a failure
+        let msg_type = rlp::strict::decode_u8(&rlp.at(3)?)?;
+
+        let with_timeout =
+            MessageType::try_from(msg_type) == Ok(MessageType::CallMessageWithTimeout);
+        let fields = if with_timeout { 8 } else { 6 };
+        let gas_limit = match rlp.item_count()? {
+            count if count == fields => 0,
+            count if count == fields + 1 => match rlp::strict::decode_u64(&rlp.at(fields)?)? {
+                // a zero limit is not encoded
+                0 => return Err(rlp::DecoderError::Custom("Zero gas limit")),
+                gas_limit => gas_limit,
+            },
+            _ => return Err(rlp::DecoderError::RlpIncorrectListLen),
+        };
+        let (timeout_height, timeout_timestamp) = if with_timeout {
+            (
+                rlp::strict::decode_u64(&rlp.at(6)?)?,
+                rlp::strict::decode_u64(&rlp.at(7)?)?,
+            )
+        } else {
+            (0, 0)
+        };
 
         let rlp_protocols = rlp.at(5)?;
         let list: Vec<String> = rlp_protocols.as_list()?;
         let str_from: String = rlp.val_at(0)?;
-        let int_msg_type: u8 = rlp.val_at(3)?;
 
         Ok(Self {
             from: NetworkAddress::from_str(&str_from)
                 .map_err(|_e| rlp::DecoderError::RlpInvalidLength)?,
             to: rlp.val_at(1)?,
-            sequence_no: rlp.val_at(2)?,
-            msg_type: MessageType::from_int(int_msg_type),
+            sequence_no: rlp::strict::decode_u128(&rlp.at(2)?)?,
+            msg_type,
             data: rlp.val_at(4)?,
             protocols: list,
+            timeout_height,
+            timeout_timestamp,
+            gas_limit,
         })
     }
 }
@@ -58,6 +58,11 @@ impl CSMessageResult {
         rlp::decode(&self.message).ok()
     }
 
+    /// The raw message, the data returned by the destination of a `CallMessageWithResponse`.
+    pub fn message_bytes(&self) -> &[u8] {
+        &self.message
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         rlp::encode(&self.clone()).to_vec()
     }
@@ -75,10 +80,10 @@ impl Encodable for CSMessageResult {
 
 impl Decodable for CSMessageResult {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
-        let code: u8 = rlp.val_at(1)?;
+        let code = rlp::strict::decode_u8(&rlp.at(1)?)?;
 
         Ok(Self {
-            sequence_no: rlp.val_at(0)?,
+            sequence_no: rlp::strict::decode_u128(&rlp.at(0)?)?,
             response_code: CSResponseType::try_from(code)?,
             message: rlp.val_at(2).unwrap_or(vec![]),
         })
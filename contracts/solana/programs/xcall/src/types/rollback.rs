@@ -9,6 +9,8 @@ pub struct Rollback {
     enabled: bool,
     rollback: Vec<u8>,
     protocols: Vec<String>,
+    expects_response: bool,
+    response: Option<Vec<u8>>,
 }
 
 impl Rollback {
@@ -25,6 +27,8 @@ impl Rollback {
             rollback,
             protocols,
             enabled,
+            expects_response: false,
+            response: None,
         }
     }
 
@@ -51,4 +55,22 @@ impl Rollback {
     pub fn enable_rollback(&mut self) {
         self.enabled = true;
     }
+
+    pub fn expects_response(&self) -> bool {
+        self.expects_response
+    }
+
+    pub fn set_expects_response(&mut self) {
+        self.expects_response = true;
+    }
+
+    /// The hash of the data returned by the destination of a `CallMessageWithResponse`, set once
+    /// its successful result is received.
+    pub fn response(&self) -> Option<&Vec<u8>> {
+        self.response.as_ref()
+    }
+
+    pub fn set_response(&mut self, response: Vec<u8>) {
+        self.response = Some(response);
+    }
 }
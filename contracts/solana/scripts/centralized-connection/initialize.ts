@@ -6,10 +6,11 @@ import { connection, connectionProgram, wallet } from "..";
 import { TxnHelpers } from "../utils/transaction";
 
 const args = process.argv.slice(2);
-if (args.length != 2) throw new Error("Invalid arguments");
+if (args.length < 2 || args.length > 3) throw new Error("Invalid arguments");
 
 const xcallKey = new PublicKey(args[0]);
 const adminKey = new PublicKey(args[1]);
+const relayerKey = args[2] ? new PublicKey(args[2]) : adminKey;
 let txnHelpers = new TxnHelpers(connection, wallet.payer);
 
 const initializeContract = async () => {
@@ -18,7 +19,7 @@ const initializeContract = async () => {
   );
   if (!connConfig) {
     return await connectionProgram.methods
-      .initialize(xcallKey, adminKey)
+      .initialize(xcallKey, adminKey, relayerKey)
       .signers([wallet.payer])
       .accountsStrict({
         signer: wallet.publicKey,
@@ -80,6 +80,10 @@ describe("Initialize", () => {
       data.admin.toString(),
       connectionCtx.signer.publicKey.toString()
     );
+    assert.equal(
+      data.relayer.toString(),
+      connectionCtx.relayer.publicKey.toString()
+    );
     assert.equal(data.xcall.toString(), xcallProgram.programId.toString());
     assert.equal(data.sn.toString(), new anchor.BN(0).toString());
   });
@@ -72,6 +72,34 @@ describe("CentralizedConnection", () => {
     }
   });
 
+  it("[set_relayer]: should set the new relayer", async () => {
+    let relayer = ctx.relayer;
+    await ctx.setRelayer(Keypair.generate());
+    await sleep(2);
+
+    let config = await ctx.getConfig();
+    assert.equal(ctx.relayer.publicKey.toString(), config.relayer.toString());
+    assert.equal(ctx.admin.publicKey.toString(), config.admin.toString());
+
+    await ctx.setRelayer(relayer);
+    await sleep(2);
+  });
+
+  it("[set_relayer]: should fail if not called by admin", async () => {
+    try {
+      await ctx.program.methods
+        .setRelayer(Keypair.generate().publicKey)
+        .accountsStrict({
+          admin: ctx.relayer.publicKey,
+          config: ConnectionPDA.config().pda,
+        })
+        .signers([ctx.relayer])
+        .rpc();
+    } catch (err) {
+      expect(err.message).to.includes("Only admin");
+    }
+  });
+
   it("[set_fee]: should set the fee for network ID", async () => {
     let msg_fee = 50;
     let res_fee = 100;
@@ -95,7 +123,7 @@ describe("CentralizedConnection", () => {
     await sleep(2);
 
     const min_rent_exempt_balance =
-      await ctx.connection.getMinimumBalanceForRentExemption(90);
+      await ctx.connection.getMinimumBalanceForRentExemption(186);
     const before_pda_balance = (await ctx.connection.getAccountInfo(config))
       .lamports;
     assert.equal(min_rent_exempt_balance + transfer_amount, before_pda_balance);
@@ -103,10 +131,11 @@ describe("CentralizedConnection", () => {
     await ctx.program.methods
       .claimFees()
       .accountsStrict({
-        admin: ctx.admin.publicKey,
+        relayer: ctx.relayer.publicKey,
+        relayerInfo: null,
         config: ConnectionPDA.config().pda,
       })
-      .signers([ctx.admin])
+      .signers([ctx.relayer])
       .rpc();
 
     const after_pda_balance = (await ctx.connection.getAccountInfo(config))
@@ -114,25 +143,116 @@ describe("CentralizedConnection", () => {
     assert.equal(min_rent_exempt_balance, after_pda_balance);
   });
 
-  it("[claim_fees]: should fail if not called by admin", async () => {
-    let new_admin = Keypair.generate();
+  it("[claim_fees]: should fail if not called by relayer", async () => {
+    let non_relayer = Keypair.generate();
 
     try {
       await ctx.program.methods
         .claimFees()
         .accountsStrict({
-          admin: new_admin.publicKey,
+          relayer: non_relayer.publicKey,
+          relayerInfo: null,
           config: ConnectionPDA.config().pda,
         })
-        .signers([new_admin])
+        .signers([non_relayer])
         .rpc();
     } catch (err) {
-      expect(err.message).includes("OnlyAdmin");
+      expect(err.message).includes("OnlyRelayer");
     }
   });
 
-  it("[recv_message]: should fail if not called by an admin", async () => {
+  it("[add_relayer]: should add a relayer scoped to networks", async () => {
+    let relayer = Keypair.generate();
+    await ctx.addRelayer(relayer.publicKey, ["icon"]);
+    await sleep(2);
+
+    let { networks } = await ctx.getRelayer(relayer.publicKey);
+    assert.deepEqual(networks, ["icon"]);
+
+    await ctx.addRelayer(relayer.publicKey, []);
+    await sleep(2);
+
+    ({ networks } = await ctx.getRelayer(relayer.publicKey));
+    assert.deepEqual(networks, []);
+  });
+
+  it("[add_relayer]: should fail if not called by admin", async () => {
+    let non_admin = Keypair.generate();
+    let relayer = Keypair.generate();
+
+    try {
+      await ctx.program.methods
+        .addRelayer(relayer.publicKey, [])
+        .accountsStrict({
+          admin: non_admin.publicKey,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          config: ConnectionPDA.config().pda,
+          relayerInfo: ConnectionPDA.relayer(relayer.publicKey).pda,
+        })
+        .signers([non_admin])
+        .rpc();
+    } catch (err) {
+      expect(err.message).to.includes("Only admin");
+    }
+  });
+
+  it("[remove_relayer]: should close the relayer account and pay out its fees", async () => {
+    let relayer = Keypair.generate();
+    await ctx.addRelayer(relayer.publicKey, []);
+    await sleep(2);
+
+    let relayerInfo = ConnectionPDA.relayer(relayer.publicKey).pda;
+    let info_balance = (await ctx.connection.getAccountInfo(relayerInfo))
+      .lamports;
+
+    await ctx.removeRelayer(relayer.publicKey);
+    await sleep(2);
+
+    expect(await ctx.connection.getAccountInfo(relayerInfo)).to.be.null;
+    assert.equal(
+      await ctx.connection.getBalance(relayer.publicKey),
+      info_balance
+    );
+  });
+
+  it("[recv_message]: should fail if relayer is not allowed for the network", async () => {
+    const connSn = 1;
+    let relayer = Keypair.generate();
+    const fromNetwork = ctx.dstNetworkId;
+    let csMessage = new Uint8Array([1, 2, 3]);
+
+    await ctx.addRelayer(relayer.publicKey, ["icon"]);
+    await txnHelpers.airdrop(relayer.publicKey, 1e9);
+    await sleep(2);
+
+    try {
+      await ctx.program.methods
+        .recvMessage(
+          fromNetwork,
+          new anchor.BN(connSn),
+          Buffer.from(csMessage),
+          new anchor.BN(connSn)
+        )
+        .accountsStrict({
+          config: ConnectionPDA.config().pda,
+          relayer: relayer.publicKey,
+          relayerInfo: ConnectionPDA.relayer(relayer.publicKey).pda,
+          receipt: ConnectionPDA.receipt(fromNetwork, connSn).pda,
+          networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
+          authority: ConnectionPDA.authority().pda,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          feeAccount: null,
+        })
+        .signers([relayer])
+        .rpc();
+    } catch (err) {
+      expect(err.message).includes("Relayer not allowed for network");
+    }
+  });
+
+  it("[recv_message]: should fail if not called by the relayer", async () => {
     const connSn = 1;
+    let non_relayer = Keypair.generate();
     const fromNetwork = ctx.dstNetworkId;
     let csMessage = new Uint8Array([1, 2, 3]);
 
@@ -146,15 +266,18 @@ describe("CentralizedConnection", () => {
         )
         .accountsStrict({
           config: ConnectionPDA.config().pda,
-          admin: ctx.signer.publicKey,
+          relayer: non_relayer.publicKey,
+          relayerInfo: null,
           receipt: ConnectionPDA.receipt(fromNetwork, connSn).pda,
+          networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
           authority: ConnectionPDA.authority().pda,
           systemProgram: SYSTEM_PROGRAM_ID,
+          feeAccount: null,
         })
-        .signers([ctx.signer])
+        .signers([non_relayer])
         .rpc();
     } catch (err) {
-      expect(err.message).includes("Only admin");
+      expect(err.message).includes("Only relayer");
     }
   });
 
@@ -198,13 +321,16 @@ describe("CentralizedConnection", () => {
       )
       .accountsStrict({
         config: ConnectionPDA.config().pda,
-        admin: ctx.admin.publicKey,
+        relayer: ctx.relayer.publicKey,
+        relayerInfo: null,
         receipt: ConnectionPDA.receipt(fromNetwork, connSn).pda,
+        networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
         authority: ConnectionPDA.authority().pda,
         systemProgram: SYSTEM_PROGRAM_ID,
+        feeAccount: null,
       })
-      .remainingAccounts([...recvMessageAccounts.slice(4)])
-      .signers([ctx.admin])
+      .remainingAccounts([...recvMessageAccounts.slice(7)])
+      .signers([ctx.relayer])
       .rpc();
 
     await sleep(2);
@@ -264,6 +390,93 @@ describe("CentralizedConnection", () => {
       .rpc();
   });
 
+  it("[prune_receipts]: should close receipts and reject pruned connection sequences", async () => {
+    const connSn = 1;
+    const fromNetwork = "icon";
+    let receipt = ConnectionPDA.receipt(fromNetwork, connSn).pda;
+
+    await ctx.program.methods
+      .pruneReceipts(fromNetwork)
+      .accountsStrict({
+        relayer: ctx.relayer.publicKey,
+        config: ConnectionPDA.config().pda,
+        networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
+        relayerInfo: null,
+      })
+      .remainingAccounts([{ pubkey: receipt, isSigner: false, isWritable: true }])
+      .signers([ctx.relayer])
+      .rpc();
+
+    await sleep(2);
+
+    expect(await ctx.connection.getAccountInfo(receipt)).to.be.null;
+    expect(
+      (await ctx.getNetworkReceipts(fromNetwork)).watermark.toNumber()
+    ).to.equal(connSn);
+
+    try {
+      await ctx.program.methods
+        .recvMessage(
+          fromNetwork,
+          new anchor.BN(connSn),
+          Buffer.from(new Uint8Array([1, 2, 3])),
+          new anchor.BN(connSn)
+        )
+        .accountsStrict({
+          config: ConnectionPDA.config().pda,
+          relayer: ctx.relayer.publicKey,
+          relayerInfo: null,
+          receipt,
+          networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
+          authority: ConnectionPDA.authority().pda,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          feeAccount: null,
+        })
+        .signers([ctx.relayer])
+        .rpc();
+    } catch (err) {
+      expect(err.message).includes("Duplicate message");
+    }
+  });
+
+  it("[migrate_network_receipts]: should move the watermark over the gaps of the legacy sequence", async () => {
+    const fromNetwork = "legacy";
+    const legacyConnSn = 5;
+
+    try {
+      let non_admin = Keypair.generate();
+      await ctx.program.methods
+        .migrateNetworkReceipts(fromNetwork, new anchor.BN(legacyConnSn))
+        .accountsStrict({
+          admin: non_admin.publicKey,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          config: ConnectionPDA.config().pda,
+          networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
+        })
+        .signers([non_admin])
+        .rpc();
+    } catch (err) {
+      expect(err.message).to.includes("Only admin");
+    }
+
+    await ctx.program.methods
+      .migrateNetworkReceipts(fromNetwork, new anchor.BN(legacyConnSn))
+      .accountsStrict({
+        admin: ctx.admin.publicKey,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        config: ConnectionPDA.config().pda,
+        networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    await sleep(2);
+
+    expect(
+      (await ctx.getNetworkReceipts(fromNetwork)).watermark.toNumber()
+    ).to.equal(legacyConnSn);
+  });
+
   it("[recv_message]: should receive message and call xcall handle message result", async () => {
     // send rollback message using mock dapp
     await mockDappCtx.add_connection(
@@ -382,13 +595,16 @@ describe("CentralizedConnection", () => {
       )
       .accountsStrict({
         config: ConnectionPDA.config().pda,
-        admin: ctx.admin.publicKey,
+        relayer: ctx.relayer.publicKey,
+        relayerInfo: null,
         receipt: ConnectionPDA.receipt(ctx.dstNetworkId, connSn).pda,
+        networkReceipts: ConnectionPDA.networkReceipts(ctx.dstNetworkId).pda,
         authority: ConnectionPDA.authority().pda,
         systemProgram: SYSTEM_PROGRAM_ID,
+        feeAccount: null,
       })
-      .remainingAccounts([...recvMessageAccounts.slice(4)])
-      .signers([ctx.admin])
+      .remainingAccounts([...recvMessageAccounts.slice(7)])
+      .signers([ctx.relayer])
       .rpc();
     await sleep(2);
 
@@ -502,17 +718,20 @@ describe("CentralizedConnection", () => {
       )
       .accountsStrict({
         config: ConnectionPDA.config().pda,
-        admin: ctx.admin.publicKey,
+        relayer: ctx.relayer.publicKey,
+        relayerInfo: null,
         receipt: ConnectionPDA.receipt(ctx.dstNetworkId, connSn).pda,
+        networkReceipts: ConnectionPDA.networkReceipts(ctx.dstNetworkId).pda,
         authority: ConnectionPDA.authority().pda,
         systemProgram: SYSTEM_PROGRAM_ID,
+        feeAccount: null,
       })
-      .remainingAccounts([...recvMessageAccounts.slice(4)])
+      .remainingAccounts([...recvMessageAccounts.slice(7)])
       .instruction();
 
     let recvMessageTx = await txnHelpers.buildV0Txn(
       [recvMessageIx],
-      [ctx.admin]
+      [ctx.relayer]
     );
     await connection.sendTransaction(recvMessageTx);
     await sleep(2);
@@ -544,8 +763,9 @@ describe("CentralizedConnection", () => {
     await connection.sendTransaction(executeRollbackTx);
   });
 
-  it("[revert_message]: should fail if not called by an admin", async () => {
+  it("[revert_message]: should fail if not called by the relayer", async () => {
     let fromNetwork = ctx.dstNetworkId;
+    let non_relayer = Keypair.generate();
     let sequenceNo = 1;
 
     try {
@@ -553,15 +773,16 @@ describe("CentralizedConnection", () => {
         .revertMessage(new anchor.BN(sequenceNo))
         .accountsStrict({
           config: ConnectionPDA.config().pda,
-          admin: ctx.signer.publicKey,
+          relayer: non_relayer.publicKey,
+          relayerInfo: null,
           authority: ConnectionPDA.authority().pda,
           systemProgram: SYSTEM_PROGRAM_ID,
         })
         .remainingAccounts([])
-        .signers([ctx.signer])
+        .signers([non_relayer])
         .rpc();
     } catch (err) {
-      expect(err.message).includes("Only admin");
+      expect(err.message).includes("Only relayer");
     }
   });
 
@@ -642,16 +863,17 @@ describe("CentralizedConnection", () => {
       .revertMessage(new anchor.BN(nextSequenceNo))
       .accountsStrict({
         config: ConnectionPDA.config().pda,
-        admin: ctx.admin.publicKey,
+        relayer: ctx.relayer.publicKey,
+        relayerInfo: null,
         authority: ConnectionPDA.authority().pda,
         systemProgram: SYSTEM_PROGRAM_ID,
       })
-      .remainingAccounts([...revertMessageAccounts.slice(3)])
+      .remainingAccounts([...revertMessageAccounts.slice(4)])
       .instruction();
 
     let revertMessageTx = await txnHelpers.buildV0Txn(
       [revertMessageIx],
-      [ctx.admin]
+      [ctx.relayer]
     );
     await connection.sendTransaction(revertMessageTx);
     await sleep(2);
@@ -700,13 +922,16 @@ describe("CentralizedConnection", () => {
       )
       .accountsStrict({
         config: ConnectionPDA.config().pda,
-        admin: ctx.admin.publicKey,
+        relayer: ctx.relayer.publicKey,
+        relayerInfo: null,
         receipt: ConnectionPDA.receipt(fromNetwork, connSn).pda,
+        networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
         authority: ConnectionPDA.authority().pda,
         systemProgram: SYSTEM_PROGRAM_ID,
+        feeAccount: null,
       })
-      .remainingAccounts([...recvMessageAccounts.slice(4)])
-      .signers([ctx.admin])
+      .remainingAccounts([...recvMessageAccounts.slice(7)])
+      .signers([ctx.relayer])
       .rpc();
 
     await sleep(2);
@@ -779,4 +1004,83 @@ describe("CentralizedConnection", () => {
     await connection.sendTransaction(executeForcedRollbackTx);
     await sleep(2);
   });
+
+  it("[recv_message]: should credit the collected fees to the relayer that delivered last on takeover", async () => {
+    let xcallConfig = await xcallCtx.getConfig();
+
+    const connSn = 6;
+    const fromNetwork = "icon";
+    let nextSequenceNo = xcallConfig.sequenceNo.toNumber() + 1;
+
+    let backup = Keypair.generate();
+    await ctx.addRelayer(backup.publicKey, []);
+    await txnHelpers.airdrop(backup.publicKey, 1e9);
+    await txnHelpers.airdrop(ConnectionPDA.config().pda, 500_000);
+    await sleep(2);
+
+    let request = new CSMessageRequest(
+      "icon/abc",
+      mockDappProgram.programId.toString(),
+      nextSequenceNo,
+      MessageType.CallMessageWithRollback,
+      Buffer.from("rollback", "utf-8"),
+      [connectionProgram.programId.toString()]
+    );
+    let cs_message = new CSMessage(
+      CSMessageType.CSMessageRequest,
+      request.encode()
+    ).encode();
+
+    let recvMessageAccounts = await ctx.getRecvMessageAccounts(
+      fromNetwork,
+      connSn,
+      nextSequenceNo,
+      cs_message,
+      CSMessageType.CSMessageRequest
+    );
+
+    const min_rent_exempt_balance =
+      await ctx.connection.getMinimumBalanceForRentExemption(186);
+    const collected =
+      (await ctx.connection.getBalance(ConnectionPDA.config().pda)) -
+      min_rent_exempt_balance;
+    const before_relayer_balance = await ctx.connection.getBalance(
+      ctx.relayer.publicKey
+    );
+
+    await ctx.program.methods
+      .recvMessage(
+        fromNetwork,
+        new anchor.BN(connSn),
+        Buffer.from(cs_message),
+        new anchor.BN(nextSequenceNo)
+      )
+      .accountsStrict({
+        config: ConnectionPDA.config().pda,
+        relayer: backup.publicKey,
+        relayerInfo: ConnectionPDA.relayer(backup.publicKey).pda,
+        receipt: ConnectionPDA.receipt(fromNetwork, connSn).pda,
+        networkReceipts: ConnectionPDA.networkReceipts(fromNetwork).pda,
+        authority: ConnectionPDA.authority().pda,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        feeAccount: ctx.relayer.publicKey,
+      })
+      .remainingAccounts([...recvMessageAccounts.slice(7)])
+      .signers([backup])
+      .rpc();
+    await sleep(2);
+
+    assert.equal(
+      await ctx.connection.getBalance(ctx.relayer.publicKey),
+      before_relayer_balance + collected
+    );
+    assert.equal(
+      await ctx.connection.getBalance(ConnectionPDA.config().pda),
+      min_rent_exempt_balance
+    );
+    assert.equal(
+      (await ctx.getConfig()).feeRelayer.toString(),
+      backup.publicKey.toString()
+    );
+  });
 });
@@ -18,6 +18,7 @@ export class TestContext {
   program: anchor.Program<CentralizedConnection>;
   signer: Keypair;
   admin: Keypair;
+  relayer: Keypair;
   connection: Connection;
   networkId: string;
   dstNetworkId: string;
@@ -31,6 +32,7 @@ export class TestContext {
     this.program = anchor.workspace.CentralizedConnection;
     this.signer = admin;
     this.admin = admin;
+    this.relayer = admin;
     this.connection = connection;
     this.txnHelpers = txnHelpers;
     this.networkId = "solana";
@@ -39,7 +41,11 @@ export class TestContext {
 
   async initialize() {
     await this.program.methods
-      .initialize(xcallProgram.programId, this.signer.publicKey)
+      .initialize(
+        xcallProgram.programId,
+        this.signer.publicKey,
+        this.relayer.publicKey
+      )
       .signers([this.signer])
       .accountsStrict({
         signer: this.signer.publicKey,
@@ -63,6 +69,45 @@ export class TestContext {
     this.admin = keypair;
   }
 
+  async setRelayer(keypair: Keypair) {
+    await this.program.methods
+      .setRelayer(keypair.publicKey)
+      .accountsStrict({
+        admin: this.admin.publicKey,
+        config: ConnectionPDA.config().pda,
+      })
+      .signers([this.admin])
+      .rpc();
+
+    this.relayer = keypair;
+  }
+
+  async addRelayer(address: PublicKey, networks: string[]) {
+    await this.program.methods
+      .addRelayer(address, networks)
+      .accountsStrict({
+        admin: this.admin.publicKey,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        config: ConnectionPDA.config().pda,
+        relayerInfo: ConnectionPDA.relayer(address).pda,
+      })
+      .signers([this.admin])
+      .rpc();
+  }
+
+  async removeRelayer(address: PublicKey) {
+    await this.program.methods
+      .removeRelayer()
+      .accountsStrict({
+        admin: this.admin.publicKey,
+        config: ConnectionPDA.config().pda,
+        relayer: address,
+        relayerInfo: ConnectionPDA.relayer(address).pda,
+      })
+      .signers([this.admin])
+      .rpc();
+  }
+
   async setNetworkFee(networkId: string, msgFee: number, resFee) {
     await connectionProgram.methods
       .setFee(networkId, new anchor.BN(msgFee), new anchor.BN(resFee))
@@ -158,6 +203,13 @@ export class TestContext {
     );
   }
 
+  async getRelayer(address: PublicKey) {
+    return await this.program.account.relayer.fetch(
+      ConnectionPDA.relayer(address).pda,
+      "confirmed"
+    );
+  }
+
   async getFee(nid: string) {
     return await this.program.account.networkFee.fetch(
       ConnectionPDA.network_fee(nid).pda,
@@ -165,6 +217,13 @@ export class TestContext {
     );
   }
 
+  async getNetworkReceipts(networkId: string) {
+    return await this.program.account.networkReceipts.fetch(
+      ConnectionPDA.networkReceipts(networkId).pda,
+      "confirmed"
+    );
+  }
+
   async getReceipt(networkId: string, sequenceNo: number) {
     return await this.program.account.receipt.fetch(
       ConnectionPDA.receipt(networkId, sequenceNo).pda,
@@ -194,6 +253,15 @@ export class ConnectionPDA {
     return { pda, bump };
   }
 
+  static relayer(address: PublicKey) {
+    const [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer"), address.toBuffer()],
+      connectionProgram.programId
+    );
+
+    return { pda, bump };
+  }
+
   static receipt(networkId: string, sn: number) {
     const [pda, bump] = PublicKey.findProgramAddressSync(
       [Buffer.from("receipt"), Buffer.from(networkId), uint128ToArray(sn)],
@@ -203,6 +271,15 @@ export class ConnectionPDA {
     return { pda, bump };
   }
 
+  static networkReceipts(networkId: string) {
+    const [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipts"), Buffer.from(networkId)],
+      connectionProgram.programId
+    );
+
+    return { pda, bump };
+  }
+
   static authority() {
     let [pda, bump] = PublicKey.findProgramAddressSync(
       [Buffer.from("connection_authority")],
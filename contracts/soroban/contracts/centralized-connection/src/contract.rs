@@ -1,6 +1,11 @@
-use soroban_sdk::{contract, contractimpl, token, Address, Bytes, BytesN, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Map, String, Vec};
 
-use crate::{errors::ContractError, event, helpers, storage, types::InitializeMsg};
+use crate::{
+    errors::ContractError,
+    event, helpers, storage,
+    types::{InitializeMsg, NackRecord, ResponseFeeDeposit},
+};
+use soroban_xcall_lib::connection::NackReason;
 
 #[contract]
 pub struct CentralizedConnection;
@@ -12,7 +17,11 @@ impl CentralizedConnection {
 
         storage::store_native_token(&env, msg.native_token);
         storage::store_conn_sn(&env, 0);
-        storage::store_admin(&env, msg.relayer);
+        storage::store_relayers(
+            &env,
+            &Map::from_array(&env, [(msg.relayer, Vec::new(&env))]),
+        );
+        storage::store_admin(&env, msg.admin);
         storage::store_xcall(&env, msg.xcall_address);
         storage::store_upgrade_authority(&env, msg.upgrade_authority);
 
@@ -30,6 +39,48 @@ impl CentralizedConnection {
         Ok(())
     }
 
+    pub fn get_relayers(env: Env) -> Result<Map<Address, Vec<String>>, ContractError> {
+        storage::relayers(&env)
+    }
+
+    /// Replaces the relayer set with `address`, allowed for every network.
+    pub fn set_relayer(env: Env, address: Address) -> Result<(), ContractError> {
+        helpers::ensure_admin(&env)?;
+        helpers::settle_fee_relayer(&env)?;
+        storage::store_fee_relayer(&env, None);
+        storage::store_relayers(&env, &Map::from_array(&env, [(address, Vec::new(&env))]));
+        Ok(())
+    }
+
+    /// Adds `address` to the relayer set, or updates its networks when already present. An empty
+    /// `networks` allows the relayer to deliver from every network.
+    pub fn add_relayer(
+        env: Env,
+        address: Address,
+        networks: Vec<String>,
+    ) -> Result<(), ContractError> {
+        helpers::ensure_admin(&env)?;
+        let mut relayers = storage::relayers(&env)?;
+        relayers.set(address, networks);
+        storage::store_relayers(&env, &relayers);
+        Ok(())
+    }
+
+    pub fn remove_relayer(env: Env, address: Address) -> Result<(), ContractError> {
+        helpers::ensure_admin(&env)?;
+        let mut relayers = storage::relayers(&env)?;
+        if !relayers.contains_key(address.clone()) {
+            return Err(ContractError::RelayerNotFound);
+        }
+        if storage::fee_relayer(&env) == Some(address.clone()) {
+            helpers::settle_fee_relayer(&env)?;
+            storage::store_fee_relayer(&env, None);
+        }
+        relayers.remove(address);
+        storage::store_relayers(&env, &relayers);
+        Ok(())
+    }
+
     pub fn get_upgrade_authority(env: Env) -> Result<Address, ContractError> {
         let address = storage::get_upgrade_authority(&env)?;
         Ok(address)
@@ -51,8 +102,8 @@ impl CentralizedConnection {
     ) -> Result<(), ContractError> {
         helpers::ensure_xcall(&env)?;
 
-        let next_conn_sn = storage::get_next_conn_sn(&env);
-        storage::store_conn_sn(&env, next_conn_sn);
+        let next_conn_sn = storage::get_next_conn_sn(&env, &to);
+        storage::store_network_conn_sn(&env, &to, next_conn_sn);
 
         let mut fee: u128 = 0;
         if sn >= 0 {
@@ -61,35 +112,95 @@ impl CentralizedConnection {
         if fee > 0 {
             helpers::transfer_token(&env, &tx_origin, &env.current_contract_address(), &fee)?;
         }
+        if sn > 0 {
+            let response_fee = storage::get_res_fee(&env, to.clone())?;
+            if response_fee > 0 {
+                let deposit = ResponseFeeDeposit {
+                    payer: tx_origin,
+                    amount: response_fee,
+                };
+                storage::store_response_fee_deposit(&env, to.clone(), sn as u128, &deposit);
+                storage::store_reserved_fees(&env, storage::reserved_fees(&env) + response_fee);
+            }
+        }
         event::send_message(&env, to, next_conn_sn, msg);
 
         Ok(())
     }
 
+    pub fn refund_response_fee(
+        env: Env,
+        network_id: String,
+        sn: u128,
+    ) -> Result<(), ContractError> {
+        helpers::ensure_xcall(&env)?;
+
+        if let Some(deposit) = helpers::take_response_fee(&env, network_id, sn) {
+            helpers::transfer_token(
+                &env,
+                &env.current_contract_address(),
+                &deposit.payer,
+                &deposit.amount,
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn recv_message(
         env: Env,
+        relayer: Address,
         src_network: String,
         conn_sn: u128,
         msg: Bytes,
     ) -> Result<(), ContractError> {
-        helpers::ensure_admin(&env)?;
+        helpers::ensure_network_relayer(&env, &relayer, &src_network)?;
 
         if storage::get_sn_receipt(&env, src_network.clone(), conn_sn) {
             return Err(ContractError::DuplicateMessage);
         }
         storage::store_receipt(&env, src_network.clone(), conn_sn);
+        helpers::prune_receipts(&env, &src_network, helpers::RECEIPT_PRUNE_BATCH);
+        helpers::record_delivery(&env, &relayer)?;
+        helpers::release_response_fee(&env, &src_network, &msg);
 
         helpers::call_xcall_handle_message(&env, &src_network, msg)?;
         Ok(())
     }
 
-    pub fn revert_message(env: &Env, sn: u128) -> Result<(), ContractError> {
-        helpers::ensure_admin(&env)?;
+    pub fn revert_message(env: &Env, relayer: Address, sn: u128) -> Result<(), ContractError> {
+        helpers::ensure_relayer(&env, &relayer)?;
         helpers::call_xcall_handle_error(&env, sn)?;
 
         Ok(())
     }
 
+    /// Records why the message of `sn` could not be delivered and fails it on xCall, enabling
+    /// its rollback.
+    pub fn nack_message(
+        env: Env,
+        relayer: Address,
+        sn: u128,
+        reason: NackReason,
+        evidence: Bytes,
+    ) -> Result<(), ContractError> {
+        helpers::ensure_relayer(&env, &relayer)?;
+        if storage::get_nack(&env, sn).is_some() {
+            return Err(ContractError::AlreadyNacked);
+        }
+
+        storage::store_nack(
+            &env,
+            &NackRecord {
+                sn,
+                reason,
+                evidence: evidence.clone(),
+            },
+        );
+        event::nack_message(&env, sn, reason, evidence);
+        helpers::call_xcall_handle_error(&env, sn)?;
+        Ok(())
+    }
+
     pub fn set_fee(
         env: Env,
         network_id: String,
@@ -102,17 +213,28 @@ impl CentralizedConnection {
         Ok(())
     }
 
-    pub fn claim_fees(env: Env) -> Result<(), ContractError> {
-        let admin = helpers::ensure_admin(&env)?;
+    pub fn claim_fees(env: Env, relayer: Address) -> Result<(), ContractError> {
+        relayer.require_auth();
 
-        let token_addr = storage::native_token(&env)?;
-        let client = token::Client::new(&env, &token_addr);
-        let balance = client.balance(&env.current_contract_address());
+        let amount = helpers::claimable_fees(&env, &relayer)?;
+        if amount == 0 {
+            helpers::ensure_relayer(&env, &relayer)?;
+            return Ok(());
+        }
 
-        client.transfer(&env.current_contract_address(), &admin, &balance);
+        let credited = storage::relayer_fees(&env, &relayer);
+        if credited > 0 {
+            storage::store_relayer_fees(&env, &relayer, 0);
+            storage::store_credited_fees(&env, storage::credited_fees(&env) - credited);
+        }
+        helpers::transfer_token(&env, &env.current_contract_address(), &relayer, &amount)?;
         Ok(())
     }
 
+    pub fn get_claimable_fees(env: Env, relayer: Address) -> Result<u128, ContractError> {
+        helpers::claimable_fees(&env, &relayer)
+    }
+
     pub fn get_fee(env: Env, network_id: String, response: bool) -> Result<u128, ContractError> {
         helpers::get_network_fee(&env, network_id, response)
     }
@@ -121,6 +243,40 @@ impl CentralizedConnection {
         storage::get_sn_receipt(&env, network_id, sn)
     }
 
+    /// Moves the receipt watermark of `network_id` over up to `limit` stored receipts, pruning
+    /// them. Catches up with receipts stored before pruning existed; returns the new watermark.
+    pub fn prune_receipts(env: Env, network_id: String, limit: u32) -> u128 {
+        helpers::prune_receipts(&env, &network_id, limit)
+    }
+
+    pub fn get_receipt_watermark(env: Env, network_id: String) -> u128 {
+        storage::receipt_watermark(&env, &network_id)
+    }
+
+    /// Moves the receipt watermark of `network_id` up to `legacy_conn_sn`, the conn_sn the source
+    /// continued from when it started to number the messages of each network on their own. The
+    /// gaps the shared conn_sn left below it are never filled and would hold the watermark back.
+    pub fn migrate_receipt_watermark(
+        env: Env,
+        network_id: String,
+        legacy_conn_sn: u128,
+    ) -> Result<u128, ContractError> {
+        helpers::ensure_admin(&env)?;
+        if legacy_conn_sn > storage::receipt_watermark(&env, &network_id) {
+            storage::store_receipt_watermark(&env, &network_id, legacy_conn_sn);
+        }
+
+        Ok(helpers::prune_receipts(
+            &env,
+            &network_id,
+            helpers::RECEIPT_PRUNE_BATCH,
+        ))
+    }
+
+    pub fn get_conn_sn(env: Env, network_id: String) -> u128 {
+        storage::get_network_conn_sn(&env, &network_id)
+    }
+
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
         helpers::ensure_upgrade_authority(&env)?;
         env.deployer().update_current_contract_wasm(new_wasm_hash);
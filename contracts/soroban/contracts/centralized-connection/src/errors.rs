@@ -10,4 +10,8 @@ pub enum ContractError {
     InsufficientFund = 4,
     DuplicateMessage = 5,
     NetworkNotSupported = 6,
+    OnlyRelayer = 7,
+    RelayerNotAllowed = 8,
+    RelayerNotFound = 9,
+    AlreadyNacked = 10,
 }
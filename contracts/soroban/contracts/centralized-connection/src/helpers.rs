@@ -1,6 +1,14 @@
-use soroban_sdk::{token, Address, Bytes, Env, String};
+use soroban_sdk::{token, Address, Bytes, Env, String, Vec};
 
-use crate::{errors::ContractError, interfaces::interface_xcall::XcallClient, storage};
+use soroban_xcall_lib::connection;
+
+use crate::{
+    errors::ContractError, interfaces::interface_xcall::XcallClient, storage,
+    types::ResponseFeeDeposit,
+};
+
+/// Receipts pruned at most on every received message
+pub const RECEIPT_PRUNE_BATCH: u32 = 20;
 
 pub fn ensure_admin(e: &Env) -> Result<Address, ContractError> {
     let admin = storage::admin(&e)?;
@@ -9,6 +17,116 @@ pub fn ensure_admin(e: &Env) -> Result<Address, ContractError> {
     Ok(admin)
 }
 
+pub fn ensure_relayer(e: &Env, relayer: &Address) -> Result<Vec<String>, ContractError> {
+    let networks = storage::relayers(&e)?
+        .get(relayer.clone())
+        .ok_or(ContractError::OnlyRelayer)?;
+    relayer.require_auth();
+
+    Ok(networks)
+}
+
+pub fn ensure_network_relayer(
+    e: &Env,
+    relayer: &Address,
+    network_id: &String,
+) -> Result<(), ContractError> {
+    let networks = ensure_relayer(&e, &relayer)?;
+    if !networks.is_empty() && !networks.contains(network_id) {
+        return Err(ContractError::RelayerNotAllowed);
+    }
+
+    Ok(())
+}
+
+/// Fees held by the contract that are not credited to a relayer yet, owed to the relayer that
+/// delivers messages at the moment. Reserved response fees are left out until released.
+pub fn pending_fees(e: &Env) -> Result<u128, ContractError> {
+    let native_token = storage::native_token(&e)?;
+    let client = token::Client::new(&e, &native_token);
+    let balance = client.balance(&e.current_contract_address()) as u128;
+    let held = storage::credited_fees(&e) + storage::reserved_fees(&e);
+
+    Ok(balance.saturating_sub(held))
+}
+
+/// Removes the response fee deposited for `sn` from the reserved fees, returning the deposit.
+pub fn take_response_fee(e: &Env, network_id: String, sn: u128) -> Option<ResponseFeeDeposit> {
+    let deposit = storage::take_response_fee_deposit(&e, network_id, sn)?;
+    let reserved = storage::reserved_fees(&e).saturating_sub(deposit.amount);
+    storage::store_reserved_fees(&e, reserved);
+
+    Some(deposit)
+}
+
+/// Releases the response fee of the request `msg` is the result of to the relayer, a response
+/// having been delivered for it.
+pub fn release_response_fee(e: &Env, src_network: &String, msg: &Bytes) {
+    if let Some(sn) = connection::result_sequence_no(&e, msg.clone()) {
+        take_response_fee(&e, src_network.clone(), sn);
+    }
+}
+
+/// Credits the pending fees to the relayer that delivered until now, so they stay with it when
+/// another relayer takes over or it is removed from the relayer set.
+pub fn settle_fee_relayer(e: &Env) -> Result<(), ContractError> {
+    if let Some(previous) = storage::fee_relayer(&e) {
+        let amount = pending_fees(&e)?;
+        if amount > 0 {
+            let credited = storage::relayer_fees(&e, &previous) + amount;
+            storage::store_relayer_fees(&e, &previous, credited);
+            storage::store_credited_fees(&e, storage::credited_fees(&e) + amount);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn record_delivery(e: &Env, relayer: &Address) -> Result<(), ContractError> {
+    if storage::fee_relayer(&e).as_ref() == Some(relayer) {
+        return Ok(());
+    }
+    settle_fee_relayer(&e)?;
+    storage::store_fee_relayer(&e, Some(relayer.clone()));
+
+    Ok(())
+}
+
+/// Returns the fees `relayer` can claim: the ones credited to it and the pending fees when it is
+/// the relayer that delivered last, or when nothing was delivered yet.
+pub fn claimable_fees(e: &Env, relayer: &Address) -> Result<u128, ContractError> {
+    let mut amount = storage::relayer_fees(&e, &relayer);
+    let is_fee_relayer = match storage::fee_relayer(&e) {
+        Some(fee_relayer) => fee_relayer == *relayer,
+        None => storage::relayers(&e)?.contains_key(relayer.clone()),
+    };
+    if is_fee_relayer {
+        amount += pending_fees(&e)?;
+    }
+
+    Ok(amount)
+}
+
+/// Moves the receipt watermark of `network_id` over up to `limit` receipts that follow it and
+/// removes them, as the watermark covers them from then on. Returns the new watermark.
+///
+/// The watermark stops at the first conn_sn not received, so it relies on the source numbering
+/// the messages it sends to each network on their own.
+pub fn prune_receipts(e: &Env, network_id: &String, limit: u32) -> u128 {
+    let mut watermark = storage::receipt_watermark(&e, &network_id);
+    let mut pruned = 0;
+    while pruned < limit && storage::has_receipt(&e, network_id.clone(), watermark + 1) {
+        watermark += 1;
+        storage::remove_receipt(&e, network_id.clone(), watermark);
+        pruned += 1;
+    }
+    if pruned > 0 {
+        storage::store_receipt_watermark(&e, &network_id, watermark);
+    }
+
+    watermark
+}
+
 pub fn ensure_upgrade_authority(e: &Env) -> Result<Address, ContractError> {
     let authority = storage::get_upgrade_authority(&e)?;
     authority.require_auth();
@@ -1,8 +1,8 @@
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{Address, Env, Map, String, Vec};
 
 use crate::{
     errors::ContractError,
-    types::{NetworkFee, StorageKey},
+    types::{NackRecord, NetworkFee, ResponseFeeDeposit, StorageKey},
 };
 
 const DAY_IN_LEDGERS: u32 = 17280; // assumes 5s a ledger
@@ -29,6 +29,49 @@ pub fn admin(e: &Env) -> Result<Address, ContractError> {
         .ok_or(ContractError::Uninitialized)
 }
 
+pub fn relayer(e: &Env) -> Result<Address, ContractError> {
+    // instances initialized before the relayer role existed keep their admin as the relayer
+    e.storage()
+        .instance()
+        .get(&StorageKey::Relayer)
+        .map_or_else(|| admin(e), Ok)
+}
+
+pub fn relayers(e: &Env) -> Result<Map<Address, Vec<String>>, ContractError> {
+    if let Some(relayers) = e.storage().instance().get(&StorageKey::Relayers) {
+        return Ok(relayers);
+    }
+    // instances initialized before the relayer set existed keep their single relayer, allowed
+    // for every network
+    let mut relayers = Map::new(e);
+    relayers.set(relayer(e)?, Vec::new(e));
+    Ok(relayers)
+}
+
+pub fn fee_relayer(e: &Env) -> Option<Address> {
+    e.storage().instance().get(&StorageKey::FeeRelayer)
+}
+
+pub fn relayer_fees(e: &Env, relayer: &Address) -> u128 {
+    let key = StorageKey::RelayerFees(relayer.clone());
+    e.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn credited_fees(e: &Env) -> u128 {
+    e.storage()
+        .instance()
+        .get(&StorageKey::CreditedFees)
+        .unwrap_or(0)
+}
+
+/// Response fees held until a response is delivered for them or they are refunded.
+pub fn reserved_fees(e: &Env) -> u128 {
+    e.storage()
+        .instance()
+        .get(&StorageKey::ReservedFees)
+        .unwrap_or(0)
+}
+
 pub fn get_upgrade_authority(e: &Env) -> Result<Address, ContractError> {
     e.storage()
         .instance()
@@ -50,8 +93,24 @@ pub fn native_token(e: &Env) -> Result<Address, ContractError> {
         .ok_or(ContractError::Uninitialized)
 }
 
-pub fn get_next_conn_sn(e: &Env) -> u128 {
-    let mut sn = e.storage().instance().get(&StorageKey::ConnSn).unwrap_or(0);
+/// The conn_sn shared by every network before each got its own, the networks continue from it.
+pub fn get_conn_sn(e: &Env) -> u128 {
+    e.storage().instance().get(&StorageKey::ConnSn).unwrap_or(0)
+}
+
+pub fn get_network_conn_sn(e: &Env, network_id: &String) -> u128 {
+    let key = StorageKey::NetworkConnSn(network_id.clone());
+    match e.storage().persistent().get(&key) {
+        Some(sn) => {
+            extend_persistent(e, &key);
+            sn
+        }
+        None => get_conn_sn(e),
+    }
+}
+
+pub fn get_next_conn_sn(e: &Env, network_id: &String) -> u128 {
+    let mut sn = get_network_conn_sn(e, network_id);
     sn += 1;
     sn
 }
@@ -86,7 +145,11 @@ pub fn get_res_fee(e: &Env, network_id: String) -> Result<u128, ContractError> {
     Ok(network_fee.response_fee)
 }
 
+/// Receipts up to the watermark of a network are pruned, only the receipts above it are stored.
 pub fn get_sn_receipt(e: &Env, network_id: String, sn: u128) -> bool {
+    if sn > 0 && sn <= receipt_watermark(e, &network_id) {
+        return true;
+    }
     let key = StorageKey::Receipts(network_id, sn);
     let is_received = e.storage().persistent().get(&key).unwrap_or(false);
     if is_received {
@@ -96,6 +159,21 @@ pub fn get_sn_receipt(e: &Env, network_id: String, sn: u128) -> bool {
     is_received
 }
 
+pub fn has_receipt(e: &Env, network_id: String, sn: u128) -> bool {
+    let key = StorageKey::Receipts(network_id, sn);
+    e.storage().persistent().has(&key)
+}
+
+pub fn receipt_watermark(e: &Env, network_id: &String) -> u128 {
+    let key = StorageKey::ReceiptWatermark(network_id.clone());
+    let watermark = e.storage().persistent().get(&key).unwrap_or(0);
+    if watermark > 0 {
+        extend_persistent(e, &key);
+    }
+
+    watermark
+}
+
 pub fn get_contract_version(e: &Env) -> u32 {
     e.storage()
         .instance()
@@ -115,10 +193,64 @@ pub fn store_receipt(e: &Env, network_id: String, sn: u128) {
     extend_persistent(e, &key);
 }
 
+pub fn remove_receipt(e: &Env, network_id: String, sn: u128) {
+    let key = StorageKey::Receipts(network_id, sn);
+    e.storage().persistent().remove(&key);
+}
+
+pub fn get_nack(e: &Env, sn: u128) -> Option<NackRecord> {
+    let key = StorageKey::Nack(sn);
+    e.storage().persistent().get(&key)
+}
+
+pub fn store_nack(e: &Env, nack: &NackRecord) {
+    let key = StorageKey::Nack(nack.sn);
+    e.storage().persistent().set(&key, nack);
+    extend_persistent(e, &key);
+}
+
+pub fn store_receipt_watermark(e: &Env, network_id: &String, sn: u128) {
+    let key = StorageKey::ReceiptWatermark(network_id.clone());
+    e.storage().persistent().set(&key, &sn);
+    extend_persistent(e, &key);
+}
+
 pub fn store_admin(e: &Env, admin: Address) {
     e.storage().instance().set(&StorageKey::Admin, &admin);
 }
 
+pub fn store_relayers(e: &Env, relayers: &Map<Address, Vec<String>>) {
+    e.storage().instance().set(&StorageKey::Relayers, relayers);
+}
+
+pub fn store_fee_relayer(e: &Env, relayer: Option<Address>) {
+    match relayer {
+        Some(relayer) => e
+            .storage()
+            .instance()
+            .set(&StorageKey::FeeRelayer, &relayer),
+        None => e.storage().instance().remove(&StorageKey::FeeRelayer),
+    }
+}
+
+pub fn store_relayer_fees(e: &Env, relayer: &Address, amount: u128) {
+    let key = StorageKey::RelayerFees(relayer.clone());
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent(e, &key);
+}
+
+pub fn store_credited_fees(e: &Env, amount: u128) {
+    e.storage()
+        .instance()
+        .set(&StorageKey::CreditedFees, &amount);
+}
+
+pub fn store_reserved_fees(e: &Env, amount: u128) {
+    e.storage()
+        .instance()
+        .set(&StorageKey::ReservedFees, &amount);
+}
+
 pub fn store_upgrade_authority(e: &Env, address: Address) {
     e.storage()
         .instance()
@@ -137,6 +269,12 @@ pub fn store_conn_sn(e: &Env, sn: u128) {
     e.storage().instance().set(&StorageKey::ConnSn, &sn);
 }
 
+pub fn store_network_conn_sn(e: &Env, network_id: &String, sn: u128) {
+    let key = StorageKey::NetworkConnSn(network_id.clone());
+    e.storage().persistent().set(&key, &sn);
+    extend_persistent(e, &key);
+}
+
 pub fn store_network_fee(e: &Env, network_id: String, message_fee: u128, response_fee: u128) {
     let key = StorageKey::NetworkFee(network_id);
     let network_fee = NetworkFee {
@@ -147,6 +285,28 @@ pub fn store_network_fee(e: &Env, network_id: String, message_fee: u128, respons
     extend_persistent(e, &key);
 }
 
+pub fn store_response_fee_deposit(
+    e: &Env,
+    network_id: String,
+    sn: u128,
+    deposit: &ResponseFeeDeposit,
+) {
+    let key = StorageKey::ResponseFeeDeposit(network_id, sn);
+    e.storage().persistent().set(&key, deposit);
+    extend_persistent(e, &key);
+}
+
+pub fn take_response_fee_deposit(
+    e: &Env,
+    network_id: String,
+    sn: u128,
+) -> Option<ResponseFeeDeposit> {
+    let key = StorageKey::ResponseFeeDeposit(network_id, sn);
+    let deposit = e.storage().persistent().get(&key);
+    e.storage().persistent().remove(&key);
+    deposit
+}
+
 pub fn extend_instance(e: &Env) {
     e.storage()
         .instance()
@@ -14,18 +14,25 @@ mod connection {
 use crate::{
     contract::{CentralizedConnection, CentralizedConnectionClient},
     event::SendMsgEvent,
-    storage,
-    types::InitializeMsg,
+    helpers, storage,
+    types::{InitializeMsg, NackRecord},
 };
 use soroban_sdk::{
     bytes, symbol_short,
     testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
     token, vec, Address, Bytes, Env, IntoVal, String, Symbol, Vec,
 };
-use soroban_xcall_lib::{messages::msg_type::MessageType, network_address::NetworkAddress};
+use soroban_xcall_lib::{
+    connection::NackReason, messages::msg_type::MessageType, network_address::NetworkAddress,
+};
 use xcall::{
     storage as xcall_storage,
-    types::{message::CSMessage, request::CSMessageRequest, rollback::Rollback},
+    types::{
+        message::CSMessage,
+        request::CSMessageRequest,
+        result::{CSMessageResult, CSResponseType},
+        rollback::Rollback,
+    },
 };
 
 pub struct TestContext {
@@ -33,6 +40,7 @@ pub struct TestContext {
     xcall: Address,
     contract: Address,
     relayer: Address,
+    admin: Address,
     native_token: Address,
     token_admin: Address,
     nid: String,
@@ -48,6 +56,7 @@ impl TestContext {
             xcall: env.register_contract_wasm(None, xcall_module::WASM),
             contract: env.register_contract(None, CentralizedConnection),
             relayer: Address::generate(&env),
+            admin: Address::generate(&env),
             native_token: native_token_contract.address(),
             nid: String::from_str(&env, "icon"),
             upgrade_authority: Address::generate(&env),
@@ -61,6 +70,7 @@ impl TestContext {
 
         client.initialize(&InitializeMsg {
             relayer: self.relayer.clone(),
+            admin: self.admin.clone(),
             native_token: self.native_token.clone(),
             xcall_address: self.xcall.clone(),
             upgrade_authority: self.upgrade_authority.clone(),
@@ -97,6 +107,7 @@ fn get_dummy_initialize_msg(env: &Env) -> InitializeMsg {
 
     InitializeMsg {
         relayer: Address::generate(&env),
+        admin: Address::generate(&env),
         native_token: native_token_contract.address(),
         xcall_address: Address::generate(&env),
         upgrade_authority: Address::generate(&env),
@@ -111,7 +122,11 @@ fn test_initialize() {
     ctx.init_context(&client);
 
     let admin = client.get_admin();
-    assert_eq!(admin, ctx.relayer)
+    assert_eq!(admin, ctx.admin);
+
+    let relayers = client.get_relayers();
+    assert_eq!(relayers.keys(), vec![&ctx.env, ctx.relayer.clone()]);
+    assert_eq!(relayers.get(ctx.relayer).unwrap().len(), 0);
 }
 
 #[test]
@@ -137,7 +152,7 @@ fn test_set_admin() {
     assert_eq!(
         ctx.env.auths(),
         std::vec![(
-            ctx.relayer.clone(),
+            ctx.admin.clone(),
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
@@ -177,6 +192,110 @@ fn test_set_admin_fail() {
     )
 }
 
+#[test]
+fn test_set_relayer() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+
+    ctx.init_context(&client);
+
+    let new_relayer = Address::generate(&ctx.env);
+    client.set_relayer(&new_relayer);
+
+    assert_eq!(
+        ctx.env.auths(),
+        std::vec![(
+            ctx.admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&ctx.env, "set_relayer"),
+                    (new_relayer.clone(),).into_val(&ctx.env)
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(client.get_relayers().keys(), vec![&ctx.env, new_relayer]);
+    assert_eq!(client.get_admin(), ctx.admin);
+}
+
+#[test]
+fn test_add_relayer() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let new_relayer = Address::generate(&ctx.env);
+    let networks = vec![&ctx.env, String::from_str(&ctx.env, "0x2.icon")];
+    client.add_relayer(&new_relayer, &networks);
+
+    assert_eq!(
+        ctx.env.auths(),
+        std::vec![(
+            ctx.admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&ctx.env, "add_relayer"),
+                    (new_relayer.clone(), networks.clone()).into_val(&ctx.env)
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let relayers = client.get_relayers();
+    assert_eq!(relayers.len(), 2);
+    assert_eq!(relayers.get(new_relayer).unwrap(), networks);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_remove_relayer() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let new_relayer = Address::generate(&ctx.env);
+    client.add_relayer(&new_relayer, &vec![&ctx.env]);
+    client.remove_relayer(&new_relayer);
+    assert_eq!(
+        client.get_relayers().keys(),
+        vec![&ctx.env, ctx.relayer.clone()]
+    );
+
+    client.remove_relayer(&new_relayer);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_recv_message_fail_for_network_not_allowed() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let scoped = Address::generate(&ctx.env);
+    client.add_relayer(
+        &scoped,
+        &vec![&ctx.env, String::from_str(&ctx.env, "0x1.eth")],
+    );
+
+    let from_nid = String::from_str(&ctx.env, "0x2.icon");
+    client.recv_message(&scoped, &from_nid, &1, &bytes!(&ctx.env, 0xabc));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_recv_message_fail_for_unknown_relayer() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let from_nid = String::from_str(&ctx.env, "0x2.icon");
+    let sender = Address::generate(&ctx.env);
+    client.recv_message(&sender, &from_nid, &1, &bytes!(&ctx.env, 0xabc));
+}
+
 #[test]
 fn test_set_upgrade_authority() {
     let ctx = TestContext::default();
@@ -218,7 +337,7 @@ fn test_set_fee() {
     assert_eq!(
         ctx.env.auths(),
         std::vec![(
-            ctx.relayer,
+            ctx.admin,
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
@@ -260,7 +379,7 @@ fn test_claim_fees() {
     );
     assert_eq!(token_client.balance(&ctx.contract), 1000);
 
-    client.claim_fees();
+    client.claim_fees(&ctx.relayer);
     assert_eq!(
         ctx.env.auths(),
         std::vec![(
@@ -269,7 +388,7 @@ fn test_claim_fees() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&ctx.env, "claim_fees"),
-                    ().into_val(&ctx.env)
+                    (ctx.relayer.clone(),).into_val(&ctx.env)
                 )),
                 sub_invocations: std::vec![]
             }
@@ -280,6 +399,40 @@ fn test_claim_fees() {
     assert_eq!(ctx.env.auths(), std::vec![]);
 }
 
+#[test]
+fn test_fees_follow_delivering_relayer() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let backup = Address::generate(&ctx.env);
+    client.add_relayer(&backup, &vec![&ctx.env]);
+
+    let token_client = token::Client::new(&ctx.env, &ctx.native_token);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        helpers::record_delivery(&ctx.env, &ctx.relayer).unwrap();
+    });
+    asset_client.mint(&ctx.contract, &1000);
+
+    // the backup relayer takes over, fees accrued so far stay with the first relayer
+    ctx.env.as_contract(&ctx.contract, || {
+        helpers::record_delivery(&ctx.env, &backup).unwrap();
+    });
+    asset_client.mint(&ctx.contract, &400);
+
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 1000);
+    assert_eq!(client.get_claimable_fees(&backup), 400);
+
+    client.claim_fees(&ctx.relayer);
+    assert_eq!(token_client.balance(&ctx.relayer), 1000);
+
+    client.claim_fees(&backup);
+    assert_eq!(token_client.balance(&backup), 400);
+    assert_eq!(token_client.balance(&ctx.contract), 0);
+}
+
 #[test]
 fn test_send_message() {
     let ctx = TestContext::default();
@@ -341,6 +494,123 @@ fn test_send_message() {
     )
 }
 
+#[test]
+fn test_send_message_conn_sn_per_network() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_send_message(&client);
+    // conn_sn shared by every network before each got its own
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_conn_sn(&ctx.env, 5);
+    });
+
+    let tx_origin = Address::generate(&ctx.env);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+    asset_client.mint(&tx_origin, &1000);
+
+    let other = String::from_str(&ctx.env, "other");
+    let msg = Bytes::from_array(&ctx.env, &[1, 2, 3]);
+    for (network_id, conn_sn) in [(&ctx.nid, 6_u128), (&other, 6), (&ctx.nid, 7)] {
+        client.send_message(&tx_origin, network_id, &0, &msg);
+
+        let emit_msg = SendMsgEvent {
+            targetNetwork: network_id.clone(),
+            connSn: conn_sn,
+            msg: msg.clone(),
+        };
+        let event = vec![&ctx.env, ctx.env.events().all().last_unchecked()];
+        assert_eq!(
+            event,
+            vec![
+                &ctx.env,
+                (
+                    client.address.clone(),
+                    ("Message",).into_val(&ctx.env),
+                    emit_msg.into_val(&ctx.env)
+                )
+            ]
+        );
+    }
+
+    assert_eq!(client.get_conn_sn(&ctx.nid), 7);
+    assert_eq!(client.get_conn_sn(&other), 6);
+    assert_eq!(client.get_conn_sn(&String::from_str(&ctx.env, "unused")), 5);
+}
+
+#[test]
+fn test_refund_response_fee() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_send_message(&client);
+
+    let tx_origin = Address::generate(&ctx.env);
+    let token_client = token::Client::new(&ctx.env, &ctx.native_token);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+    asset_client.mint(&tx_origin, &1000);
+
+    let msg = Bytes::from_array(&ctx.env, &[1, 2, 3]);
+    client.send_message(&tx_origin, &ctx.nid, &1, &msg);
+    assert_eq!(token_client.balance(&tx_origin), 800);
+
+    client.refund_response_fee(&ctx.nid, &1);
+    assert_eq!(token_client.balance(&tx_origin), 900);
+    assert_eq!(token_client.balance(&ctx.contract), 100);
+
+    client.refund_response_fee(&ctx.nid, &1);
+    assert_eq!(token_client.balance(&tx_origin), 900);
+}
+
+#[test]
+fn test_refund_response_fee_after_claim() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_send_message(&client);
+
+    let tx_origin = Address::generate(&ctx.env);
+    let token_client = token::Client::new(&ctx.env, &ctx.native_token);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+    asset_client.mint(&tx_origin, &1000);
+
+    let msg = Bytes::from_array(&ctx.env, &[1, 2, 3]);
+    client.send_message(&tx_origin, &ctx.nid, &1, &msg);
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 100);
+
+    // the response fee stays reserved for the refund
+    client.claim_fees(&ctx.relayer);
+    assert_eq!(token_client.balance(&ctx.relayer), 100);
+
+    client.refund_response_fee(&ctx.nid, &1);
+    assert_eq!(token_client.balance(&tx_origin), 900);
+    assert_eq!(token_client.balance(&ctx.contract), 0);
+}
+
+#[test]
+fn test_response_fee_released_on_result() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_send_message(&client);
+
+    let tx_origin = Address::generate(&ctx.env);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+    asset_client.mint(&tx_origin, &1000);
+
+    let msg = Bytes::from_array(&ctx.env, &[1, 2, 3]);
+    client.send_message(&tx_origin, &ctx.nid, &1, &msg);
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 100);
+
+    let result = CSMessageResult::new(1, CSResponseType::CSResponseSuccess, Bytes::new(&ctx.env));
+    let result = CSMessage::from_result(&ctx.env, &result).encode(&ctx.env);
+    ctx.env.as_contract(&ctx.contract, || {
+        helpers::release_response_fee(&ctx.env, &ctx.nid, &result);
+        assert_eq!(storage::reserved_fees(&ctx.env), 0);
+    });
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 200);
+
+    // nothing is left to refund once the fee was released
+    client.refund_response_fee(&ctx.nid, &1);
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 200);
+}
+
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #10)")]
 fn test_send_message_fail_for_insufficient_fee() {
@@ -399,7 +669,7 @@ fn test_recv_message() {
 
     let conn_sn = 1;
     let from_nid = String::from_str(&ctx.env, "0x2.icon");
-    client.recv_message(&from_nid, &conn_sn, &encoded);
+    client.recv_message(&ctx.relayer, &from_nid, &conn_sn, &encoded);
 }
 
 #[test]
@@ -428,9 +698,9 @@ fn test_recv_message_duplicate_connection_sequence() {
 
     let conn_sn = 1;
     let from_nid = String::from_str(&ctx.env, "0x2.icon");
-    client.recv_message(&from_nid, &conn_sn, &encoded);
+    client.recv_message(&ctx.relayer, &from_nid, &conn_sn, &encoded);
 
-    client.recv_message(&from_nid, &conn_sn, &encoded);
+    client.recv_message(&ctx.relayer, &from_nid, &conn_sn, &encoded);
 }
 
 #[test]
@@ -457,7 +727,7 @@ pub fn test_revert_message() {
         xcall_storage::store_rollback(&ctx.env, sequence_no, &rollback);
     });
 
-    client.revert_message(&sequence_no);
+    client.revert_message(&ctx.relayer, &sequence_no);
 
     ctx.env.as_contract(&ctx.xcall, || {
         // rollback should be enabled
@@ -466,6 +736,76 @@ pub fn test_revert_message() {
     });
 }
 
+#[test]
+fn test_nack_message() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let sequence_no = 1;
+    let protocols: Vec<String> = vec![&ctx.env, ctx.contract.to_string()];
+    let to = NetworkAddress::new(
+        &ctx.env,
+        String::from_str(&ctx.env, "0x2.icon"),
+        ctx.xcall.to_string(),
+    );
+    let rollback = Rollback::new(
+        Address::generate(&ctx.env),
+        to,
+        protocols.clone(),
+        bytes!(&ctx.env, 0xabc),
+        false,
+    );
+    ctx.env.as_contract(&ctx.xcall, || {
+        xcall_storage::store_rollback(&ctx.env, sequence_no, &rollback);
+    });
+
+    let evidence = bytes!(&ctx.env, 0xdef);
+    client.nack_message(
+        &ctx.relayer,
+        &sequence_no,
+        &NackReason::ExceededSize,
+        &evidence,
+    );
+
+    ctx.env.as_contract(&ctx.xcall, || {
+        let rollback = xcall_storage::get_rollback(&ctx.env, sequence_no).unwrap();
+        assert_eq!(rollback.enabled, true);
+    });
+    ctx.env.as_contract(&ctx.contract, || {
+        assert_eq!(
+            storage::get_nack(&ctx.env, sequence_no),
+            Some(NackRecord {
+                sn: sequence_no,
+                reason: NackReason::ExceededSize,
+                evidence: evidence.clone(),
+            })
+        );
+    });
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #10)")]
+fn test_nack_message_fail_for_already_nacked() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let evidence = bytes!(&ctx.env, 0xdef);
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_nack(
+            &ctx.env,
+            &NackRecord {
+                sn: 1,
+                reason: NackReason::ExceededSize,
+                evidence: evidence.clone(),
+            },
+        );
+    });
+
+    client.nack_message(&ctx.relayer, &1, &NackReason::NetworkHalted, &evidence);
+}
+
 #[test]
 fn test_upgrade() {
     let ctx = TestContext::default();
@@ -478,3 +818,67 @@ fn test_upgrade() {
     client.upgrade(&wasm_hash);
     assert_eq!(client.version(), 2);
 }
+
+#[test]
+fn test_prune_receipts() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        for sn in [1, 2, 3, 5] {
+            storage::store_receipt(&ctx.env, ctx.nid.clone(), sn);
+        }
+    });
+
+    assert_eq!(client.prune_receipts(&ctx.nid, &2), 2);
+    assert_eq!(client.prune_receipts(&ctx.nid, &10), 3);
+    assert_eq!(client.get_receipt_watermark(&ctx.nid), 3);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        assert!(!storage::has_receipt(&ctx.env, ctx.nid.clone(), 1));
+        assert!(storage::has_receipt(&ctx.env, ctx.nid.clone(), 5));
+    });
+    for sn in [1, 2, 3, 5] {
+        assert!(client.get_receipt(&ctx.nid, &sn));
+    }
+    assert!(!client.get_receipt(&ctx.nid, &4));
+}
+
+#[test]
+fn test_migrate_receipt_watermark() {
+    let ctx = TestContext::default();
+    let client = CentralizedConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    // conn_sn 2 and 4 were sent to other networks by the shared conn_sn of the source
+    ctx.env.as_contract(&ctx.contract, || {
+        for sn in [1, 3, 5, 6] {
+            storage::store_receipt(&ctx.env, ctx.nid.clone(), sn);
+        }
+    });
+    assert_eq!(client.prune_receipts(&ctx.nid, &10), 1);
+
+    assert_eq!(client.migrate_receipt_watermark(&ctx.nid, &4), 6);
+    assert_eq!(
+        ctx.env.auths(),
+        std::vec![(
+            ctx.admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&ctx.env, "migrate_receipt_watermark"),
+                    (ctx.nid.clone(), 4_u128).into_val(&ctx.env)
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    for sn in 1..=6 {
+        assert!(client.get_receipt(&ctx.nid, &sn));
+    }
+    assert!(!client.get_receipt(&ctx.nid, &7));
+
+    // a lower conn_sn leaves the watermark where it is
+    assert_eq!(client.migrate_receipt_watermark(&ctx.nid, &2), 6);
+}
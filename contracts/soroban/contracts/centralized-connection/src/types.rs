@@ -1,26 +1,54 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, Bytes, String};
+use soroban_xcall_lib::connection::NackReason;
 
 #[contracttype]
 #[derive(Clone)]
 pub enum StorageKey {
     Xcall,
     Admin,
+    Relayer,
+    Relayers,
+    FeeRelayer,
+    RelayerFees(Address),
+    CreditedFees,
+    ReservedFees,
     UpgradeAuthority,
     Xlm,
     ConnSn,
+    NetworkConnSn(String),
     Version,
     NetworkFee(String),
     Receipts(String, u128),
+    ReceiptWatermark(String),
+    ResponseFeeDeposit(String, u128),
+    Nack(u128),
 }
 
 #[contracttype]
 pub struct InitializeMsg {
     pub relayer: Address,
+    pub admin: Address,
     pub native_token: Address,
     pub xcall_address: Address,
     pub upgrade_authority: Address,
 }
 
+/// Why the message of xCall sequence number `sn` could not be delivered, as reported by the
+/// relayer. `evidence` backs the claim, e.g. the hash of the rejected transaction.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NackRecord {
+    pub sn: u128,
+    pub reason: NackReason,
+    pub evidence: Bytes,
+}
+
+#[contracttype]
+pub struct ResponseFeeDeposit {
+    pub payer: Address,
+    pub amount: u128,
+}
+
 #[contracttype]
 pub struct NetworkFee {
     pub message_fee: u128,
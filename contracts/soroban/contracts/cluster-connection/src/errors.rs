@@ -15,4 +15,8 @@ pub enum ContractError {
     ValidatorNotFound = 9,
     ValidatorAlreadyAdded = 10,
     SignatureVerificationFailed = 11,
+    OnlyRelayer = 12,
+    RelayerNotAllowed = 13,
+    RelayerNotFound = 14,
+    AlreadyNacked = 15,
 }
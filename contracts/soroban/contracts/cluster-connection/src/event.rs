@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use soroban_sdk::{contracttype, Bytes, Env, String};
+use soroban_xcall_lib::connection::NackReason;
 
 #[contracttype]
 pub struct SendMsgEvent {
@@ -17,3 +18,19 @@ pub(crate) fn send_message(e: &Env, targetNetwork: String, connSn: u128, msg: By
     };
     e.events().publish(("Message",), emit_message);
 }
+
+#[contracttype]
+pub struct NackMsgEvent {
+    pub sn: u128,
+    pub reason: NackReason,
+    pub evidence: Bytes,
+}
+
+pub(crate) fn nack_message(e: &Env, sn: u128, reason: NackReason, evidence: Bytes) {
+    let emit_message = NackMsgEvent {
+        sn,
+        reason,
+        evidence,
+    };
+    e.events().publish(("NackMessage",), emit_message);
+}
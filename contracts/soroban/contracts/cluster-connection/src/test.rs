@@ -9,9 +9,11 @@ mod xcall {
 use crate::{
     contract::{ClusterConnection, ClusterConnectionClient},
     event::SendMsgEvent,
+    helpers,
     storage,
-    types::InitializeMsg,
+    types::{InitializeMsg, NackRecord},
 };
+use soroban_xcall_lib::connection::NackReason;
 use soroban_sdk::{
     bytesn, symbol_short, testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events}, token, vec, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Vec
 };
@@ -63,7 +65,7 @@ impl TestContext {
         self.init_context(&client);
         self.env.mock_all_auths_allowing_non_root_auth();
 
-        client.set_fee(&self.nid, &100, &100);
+        client.set_fee(&self.relayer, &self.nid, &100, &100);
     }
 }
 
@@ -188,7 +190,7 @@ fn test_set_fee() {
     ctx.init_context(&client);
 
     let nid = String::from_str(&ctx.env, "icon");
-    client.set_fee(&nid, &10, &10);
+    client.set_fee(&ctx.relayer, &nid, &10, &10);
 
     assert_eq!(
         ctx.env.auths(),
@@ -198,7 +200,7 @@ fn test_set_fee() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     symbol_short!("set_fee"),
-                    (nid.clone(), 10_u128, 10_u128).into_val(&ctx.env)
+                    (ctx.relayer.clone(), nid.clone(), 10_u128, 10_u128).into_val(&ctx.env)
                 )),
                 sub_invocations: std::vec![]
             }
@@ -235,7 +237,7 @@ fn test_claim_fees() {
     );
     assert_eq!(token_client.balance(&ctx.contract), 1000);
 
-    client.claim_fees();
+    client.claim_fees(&ctx.relayer);
     assert_eq!(
         ctx.env.auths(),
         std::vec![(
@@ -244,7 +246,7 @@ fn test_claim_fees() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&ctx.env, "claim_fees"),
-                    ().into_val(&ctx.env)
+                    (ctx.relayer.clone(),).into_val(&ctx.env)
                 )),
                 sub_invocations: std::vec![]
             }
@@ -255,6 +257,152 @@ fn test_claim_fees() {
     assert_eq!(ctx.env.auths(), std::vec![]);
 }
 
+#[test]
+fn test_refund_response_fee_after_claim() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_send_message(&client);
+
+    let tx_origin = Address::generate(&ctx.env);
+    let token_client = token::Client::new(&ctx.env, &ctx.native_token);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+    asset_client.mint(&tx_origin, &1000);
+
+    let msg = Bytes::from_array(&ctx.env, &[1, 2, 3]);
+    client.send_message(&tx_origin, &ctx.nid, &1, &msg);
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 100);
+
+    // the response fee stays reserved for the refund
+    client.claim_fees(&ctx.relayer);
+    assert_eq!(token_client.balance(&ctx.relayer), 100);
+
+    client.refund_response_fee(&ctx.nid, &1);
+    assert_eq!(token_client.balance(&tx_origin), 900);
+    assert_eq!(token_client.balance(&ctx.contract), 0);
+}
+
+#[test]
+fn test_add_relayer() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+
+    ctx.init_context(&client);
+
+    let relayer = Address::generate(&ctx.env);
+    let networks = vec![&ctx.env, String::from_str(&ctx.env, "icon")];
+    client.add_relayer(&relayer, &networks);
+
+    let relayers = client.get_relayers();
+    assert_eq!(relayers.len(), 2);
+    assert_eq!(relayers.get(relayer), Some(networks));
+    assert_eq!(relayers.get(ctx.relayer), Some(Vec::new(&ctx.env)));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #14)")]
+fn test_remove_relayer() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+
+    ctx.init_context(&client);
+
+    let relayer = Address::generate(&ctx.env);
+    client.add_relayer(&relayer, &Vec::new(&ctx.env));
+    client.remove_relayer(&relayer);
+    assert_eq!(client.get_relayers().len(), 1);
+
+    client.remove_relayer(&relayer);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_set_fee_fail_for_unknown_relayer() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+
+    ctx.init_context(&client);
+
+    let nid = String::from_str(&ctx.env, "icon");
+    client.set_fee(&Address::generate(&ctx.env), &nid, &10, &10);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #13)")]
+fn test_recv_message_fail_for_network_not_allowed() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+
+    ctx.init_context(&client);
+
+    let relayer = Address::generate(&ctx.env);
+    client.add_relayer(
+        &relayer,
+        &vec![&ctx.env, String::from_str(&ctx.env, "archway")],
+    );
+
+    let msg = Bytes::from_array(&ctx.env, &[104, 101, 108, 108, 111]);
+    let src_network = String::from_str(&ctx.env, "0x2.icon");
+    client.recv_message_with_signatures(&relayer, &src_network, &128, &msg, &Vec::new(&ctx.env));
+}
+
+#[test]
+fn test_claim_fees_before_any_delivery() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+
+    ctx.init_context(&client);
+
+    let relayer = Address::generate(&ctx.env);
+    client.add_relayer(&relayer, &Vec::new(&ctx.env));
+
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+    asset_client.mint(&ctx.contract, &1000);
+
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 1000);
+    assert_eq!(client.get_claimable_fees(&relayer), 1000);
+    assert_eq!(client.get_claimable_fees(&Address::generate(&ctx.env)), 0);
+
+    client.claim_fees(&relayer);
+
+    let token_client = token::Client::new(&ctx.env, &ctx.native_token);
+    assert_eq!(token_client.balance(&relayer), 1000);
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 0);
+}
+
+#[test]
+fn test_fees_follow_delivering_relayer() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let backup = Address::generate(&ctx.env);
+    client.add_relayer(&backup, &Vec::new(&ctx.env));
+
+    let token_client = token::Client::new(&ctx.env, &ctx.native_token);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        helpers::record_delivery(&ctx.env, &ctx.relayer).unwrap();
+    });
+    asset_client.mint(&ctx.contract, &1000);
+
+    // the backup relayer takes over, fees accrued so far stay with the first relayer
+    ctx.env.as_contract(&ctx.contract, || {
+        helpers::record_delivery(&ctx.env, &backup).unwrap();
+    });
+    asset_client.mint(&ctx.contract, &400);
+
+    assert_eq!(client.get_claimable_fees(&ctx.relayer), 1000);
+    assert_eq!(client.get_claimable_fees(&backup), 400);
+
+    client.claim_fees(&ctx.relayer);
+    assert_eq!(token_client.balance(&ctx.relayer), 1000);
+
+    client.claim_fees(&backup);
+    assert_eq!(token_client.balance(&backup), 400);
+    assert_eq!(token_client.balance(&ctx.contract), 0);
+}
+
 #[test]
 fn test_send_message() {
     let ctx = TestContext::default();
@@ -316,6 +464,49 @@ fn test_send_message() {
     )
 }
 
+#[test]
+fn test_send_message_conn_sn_per_network() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_send_message(&client);
+    // conn_sn shared by every network before each got its own
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_conn_sn(&ctx.env, 5);
+    });
+
+    let tx_origin = Address::generate(&ctx.env);
+    let asset_client = token::StellarAssetClient::new(&ctx.env, &ctx.native_token);
+    asset_client.mint(&tx_origin, &1000);
+
+    let other = String::from_str(&ctx.env, "other");
+    let msg = Bytes::from_array(&ctx.env, &[1, 2, 3]);
+    for (network_id, conn_sn) in [(&ctx.nid, 6_u128), (&other, 6), (&ctx.nid, 7)] {
+        client.send_message(&tx_origin, network_id, &0, &msg);
+
+        let emit_msg = SendMsgEvent {
+            targetNetwork: network_id.clone(),
+            connSn: conn_sn,
+            msg: msg.clone(),
+        };
+        let event = vec![&ctx.env, ctx.env.events().all().last_unchecked()];
+        assert_eq!(
+            event,
+            vec![
+                &ctx.env,
+                (
+                    client.address.clone(),
+                    ("Message",).into_val(&ctx.env),
+                    emit_msg.into_val(&ctx.env)
+                )
+            ]
+        );
+    }
+
+    assert_eq!(client.get_conn_sn(&ctx.nid), 7);
+    assert_eq!(client.get_conn_sn(&other), 6);
+    assert_eq!(client.get_conn_sn(&String::from_str(&ctx.env, "unused")), 5);
+}
+
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #10)")]
 fn test_send_message_fail_for_insufficient_fee() {
@@ -461,7 +652,7 @@ fn test_receive_message() {
     signatures.push_back(bytesn!(&ctx.env, 0x660d542b3f6de9cd08f238fd44133eeebfea290b21dae7322a63b516c57b8df12c4c0a340b60ed567c8da53578346c212b27b797eb42a75fb4b7076c567a6ff91c));
     signatures.push_back(bytesn!(&ctx.env, 0x8024de4c7b003df96bb699cfaa1bfb8a682787cd0853f555d48494c65c766f8104804848095890a9a6d15946da52dafb18e5c1d0dbe7f33fc7a5fa5cf8b1f6e21c));
 
-    client.recv_message_with_signatures(&src_network, &conn_sn, &msg, &signatures);
+    client.recv_message_with_signatures(&ctx.relayer, &src_network, &conn_sn, &msg, &signatures);
 }
 
 #[test]
@@ -491,7 +682,7 @@ fn test_receive_message_less_signatures() {
     let mut signatures = Vec::new(&ctx.env);
     signatures.push_back(bytesn!(&ctx.env, 0x660d542b3f6de9cd08f238fd44133eeebfea290b21dae7322a63b516c57b8df12c4c0a340b60ed567c8da53578346c212b27b797eb42a75fb4b7076c567a6ff91c));
 
-    client.recv_message_with_signatures(&src_network, &conn_sn, &msg, &signatures);
+    client.recv_message_with_signatures(&ctx.relayer, &src_network, &conn_sn, &msg, &signatures);
 }
 
 #[test]
@@ -522,6 +713,107 @@ fn test_receive_message_with_invalid_signature() {
     signatures.push_back(bytesn!(&ctx.env, 0x660d542b3f6de9cd08f238fd44133eeebfea290b21dae7322a63b516c57b8df12c4c0a340b60ed567c8da53578346c212b27b797eb42a75fb4b7076c567a6ff91c));
     signatures.push_back(bytesn!(&ctx.env, 0x660d542b3f6de9cd08f238fd44133eeebfea290b21dae7322a63b516c57b8df12c4c0a340b60ed567c8da53578346c212b27b797eb42a75fb4b7076c567a6ff91c));
 
-    client.recv_message_with_signatures(&src_network, &conn_sn, &msg, &signatures);
+    client.recv_message_with_signatures(&ctx.relayer, &src_network, &conn_sn, &msg, &signatures);
 }
 
+#[test]
+fn test_prune_receipts() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        for sn in [1, 2, 3, 5] {
+            storage::store_receipt(&ctx.env, ctx.nid.clone(), sn);
+        }
+    });
+
+    assert_eq!(client.prune_receipts(&ctx.nid, &2), 2);
+    assert_eq!(client.prune_receipts(&ctx.nid, &10), 3);
+    assert_eq!(client.get_receipt_watermark(&ctx.nid), 3);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        assert!(!storage::has_receipt(&ctx.env, ctx.nid.clone(), 1));
+        assert!(storage::has_receipt(&ctx.env, ctx.nid.clone(), 5));
+    });
+    for sn in [1, 2, 3, 5] {
+        assert!(client.get_receipt(&ctx.nid, &sn));
+    }
+    assert!(!client.get_receipt(&ctx.nid, &4));
+}
+
+#[test]
+fn test_migrate_receipt_watermark() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    // conn_sn 2 and 4 were sent to other networks by the shared conn_sn of the source
+    ctx.env.as_contract(&ctx.contract, || {
+        for sn in [1, 3, 5, 6] {
+            storage::store_receipt(&ctx.env, ctx.nid.clone(), sn);
+        }
+    });
+    assert_eq!(client.prune_receipts(&ctx.nid, &10), 1);
+
+    assert_eq!(client.migrate_receipt_watermark(&ctx.nid, &4), 6);
+    assert_eq!(
+        ctx.env.auths(),
+        std::vec![(
+            ctx.admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&ctx.env, "migrate_receipt_watermark"),
+                    (ctx.nid.clone(), 4_u128).into_val(&ctx.env)
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    for sn in 1..=6 {
+        assert!(client.get_receipt(&ctx.nid, &sn));
+    }
+    assert!(!client.get_receipt(&ctx.nid, &7));
+
+    // a lower conn_sn leaves the watermark where it is
+    assert_eq!(client.migrate_receipt_watermark(&ctx.nid, &2), 6);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #15)")]
+fn test_nack_message_fail_for_already_nacked() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let evidence = Bytes::from_array(&ctx.env, &[0xde, 0xf0]);
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_nack(
+            &ctx.env,
+            &NackRecord {
+                sn: 1,
+                reason: NackReason::ExceededSize,
+                evidence: evidence.clone(),
+            },
+        );
+    });
+
+    client.nack_message(&ctx.relayer, &1, &NackReason::NetworkHalted, &evidence);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_nack_message_fail_for_unknown_relayer() {
+    let ctx = TestContext::default();
+    let client = ClusterConnectionClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let evidence = Bytes::from_array(&ctx.env, &[0xde, 0xf0]);
+    client.nack_message(
+        &Address::generate(&ctx.env),
+        &1,
+        &NackReason::DestinationRejected,
+        &evidence,
+    );
+}
@@ -6,6 +6,7 @@ use soroban_sdk::{
 use soroban_xcall_lib::{
     messages::{
         call_message::CallMessage, call_message_persisted::CallMessagePersisted,
+        call_message_response::CallMessageWithResponse,
         call_message_rollback::CallMessageWithRollback, envelope::Envelope, msg_type::MessageType,
         AnyMessage,
     },
@@ -51,6 +52,7 @@ impl MockDapp {
             message,
             sources,
             destinations,
+            gas_limit: 0,
         };
 
         let xcall_address = storage::get_xcall_address(&env)?;
@@ -64,7 +66,7 @@ impl MockDapp {
         from: String,
         data: Bytes,
         _protocols: Option<Vec<String>>,
-    ) {
+    ) -> Bytes {
         let xcall_address = storage::get_xcall_address(&env)
             .unwrap_or_else(|_| panic_with_error!(&env, ContractError::Uninitialized));
 
@@ -73,10 +75,12 @@ impl MockDapp {
         let network_from = NetworkAddress::from_string(from);
         let (nid, account) = network_from.parse_network_address(&env);
         if xcall_address.to_string() == account {
-            return;
+            return Bytes::new(&env);
         }
 
-        let msg_data = decoder::decode_string(&env, data);
+        // data that is not a single rlp string, such as the replies it sends, is not a command
+        let msg_data = decoder::try_decode_string(&env, data.clone())
+            .unwrap_or_else(|_| String::from_str(&env, ""));
         if msg_data == String::from_str(&env, "rollback") {
             panic_with_error!(&env, ContractError::RevertFromDapp)
         } else {
@@ -92,6 +96,7 @@ impl MockDapp {
                     message,
                     sources,
                     destinations,
+                    gas_limit: 0,
                 };
                 Self::xcall_send_call(
                     &env,
@@ -102,6 +107,19 @@ impl MockDapp {
                 );
             }
         }
+
+        // the data is echoed back to the sender of a `CallMessageWithResponse`
+        data
+    }
+
+    pub fn handle_call_response(env: Env, from: String, sn: u128, data: Bytes) {
+        let xcall_address = storage::get_xcall_address(&env)
+            .unwrap_or_else(|_| panic_with_error!(&env, ContractError::Uninitialized));
+
+        xcall_address.require_auth();
+
+        env.events()
+            .publish(("ResponseReceived",), (from, sn, data));
     }
 
     pub fn add_connection(
@@ -140,7 +158,8 @@ impl MockDapp {
         data: Bytes,
         rollback: Option<Bytes>,
     ) -> Result<AnyMessage, ContractError> {
-        let msg_type: MessageType = message_type.into();
+        let msg_type =
+            MessageType::try_from(message_type).map_err(|_| ContractError::InvalidMessageType)?;
 
         let message = if msg_type == MessageType::CallMessagePersisted {
             AnyMessage::CallMessagePersisted(CallMessagePersisted { data })
@@ -150,6 +169,12 @@ impl MockDapp {
             } else {
                 return Err(ContractError::InvalidRollbackMessage);
             }
+        } else if msg_type == MessageType::CallMessageWithResponse {
+            if let Some(rollback) = rollback {
+                AnyMessage::CallMessageWithResponse(CallMessageWithResponse { data, rollback })
+            } else {
+                return Err(ContractError::InvalidRollbackMessage);
+            }
         } else {
             AnyMessage::CallMessage(CallMessage { data })
         };
@@ -11,4 +11,5 @@ pub enum ContractError {
     InvalidRollbackMessage = 5,
     RevertFromDapp = 6,
     OnlyXcall = 7,
+    InvalidMessageType = 8,
 }
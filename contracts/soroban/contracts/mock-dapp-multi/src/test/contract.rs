@@ -1,5 +1,5 @@
 use soroban_rlp::encoder;
-use soroban_sdk::{bytes, testutils::Address as _, vec, Address, String};
+use soroban_sdk::{bytes, testutils::Address as _, vec, Address, Bytes, String};
 use soroban_xcall_lib::network_address::NetworkAddress;
 
 use super::setup::*;
@@ -174,7 +174,7 @@ fn test_handle_call_message_pass() {
         &Some(vec![&ctx.env]),
     );
     assert!(res.is_ok());
-    assert_eq!(res.unwrap(), Ok(()))
+    assert_eq!(res.unwrap(), Ok(Bytes::new(&ctx.env)))
 }
 
 #[test]
@@ -95,6 +95,7 @@ impl TestContext {
         let initialize_msg = connection::InitializeMsg {
             native_token: self.native_token.clone(),
             relayer: Address::generate(&self.env),
+            admin: Address::generate(&self.env),
             xcall_address: self.xcall.clone(),
             upgrade_authority: self.upgrade_authority.clone(),
         };
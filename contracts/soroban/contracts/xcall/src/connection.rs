@@ -27,3 +27,15 @@ pub fn call_connection_send_message(
 
     Ok(())
 }
+
+pub fn call_connection_refund_response_fee(
+    e: &Env,
+    connection: &String,
+    nid: &String,
+    sn: u128,
+) -> Result<(), ContractError> {
+    let client = ConnectionClient::new(&e, &Address::from_string(&connection));
+    client.refund_response_fee(&nid, &sn);
+
+    Ok(())
+}
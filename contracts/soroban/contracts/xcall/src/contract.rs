@@ -4,7 +4,10 @@ use soroban_xcall_lib::messages::envelope::Envelope;
 use crate::{
     errors::ContractError,
     execute_call, handle_message, helpers, send_message, storage,
-    types::{message::InitializeMsg, storage_types::Config},
+    types::{
+        message::InitializeMsg,
+        storage_types::{Config, FeeSplit},
+    },
 };
 
 #[contract]
@@ -57,6 +60,14 @@ impl Xcall {
         Ok(())
     }
 
+    pub fn set_protocol_fee_splits(env: Env, splits: Vec<FeeSplit>) -> Result<(), ContractError> {
+        helpers::ensure_admin(&env)?;
+        helpers::ensure_fee_splits(&splits)?;
+        storage::store_fee_splits(&env, &splits);
+
+        Ok(())
+    }
+
     pub fn set_default_connection(
         env: &Env,
         nid: String,
@@ -91,6 +102,10 @@ impl Xcall {
         handle_message::handle_error(&env, sender, sequence_no)
     }
 
+    pub fn claim_response_fee_refund(env: Env, sn: u128) -> Result<(), ContractError> {
+        send_message::claim_response_fee_refund(&env, sn)
+    }
+
     pub fn execute_call(
         env: Env,
         sender: Address,
@@ -104,6 +119,10 @@ impl Xcall {
         execute_call::execute_rollback_message(&env, sequence_no)
     }
 
+    pub fn execute_response(env: Env, sequence_no: u128) -> Result<(), ContractError> {
+        execute_call::execute_response_message(&env, sequence_no)
+    }
+
     pub fn get_admin(env: Env) -> Result<Address, ContractError> {
         let admin = storage::admin(&env)?;
         Ok(admin)
@@ -140,6 +159,10 @@ impl Xcall {
         Ok(fee_handler)
     }
 
+    pub fn get_protocol_fee_splits(env: Env) -> Vec<FeeSplit> {
+        storage::get_fee_splits(&env)
+    }
+
     pub fn get_default_connection(env: Env, nid: String) -> Result<Address, ContractError> {
         let connection = storage::default_connection(&env, nid)?;
         Ok(connection)
@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, Bytes, Env, String, Vec};
+use soroban_sdk::{Address, Bytes, Env, String, TryFromVal, Vec};
 use soroban_xcall_lib::network_address::NetworkAddress;
 
 use crate::{event, interfaces::interface_dapp::DappClient, types::result::CSResponseType};
@@ -12,12 +12,14 @@ pub fn handle_call_message(
 ) {
     let client = DappClient::new(&e, &address);
     if protocols.len() > 0 {
-        client.handle_call_message(&from.to_string(), data, &Some(protocols))
+        client.handle_call_message(&from.to_string(), data, &Some(protocols));
     } else {
-        client.handle_call_message(&from.to_string(), data, &None)
+        client.handle_call_message(&from.to_string(), data, &None);
     }
 }
 
+/// Returns the response code of the call with the data returned by the dapp, empty when it
+/// failed or returned no `Bytes`.
 pub fn try_handle_call_message(
     e: &Env,
     req_id: u128,
@@ -25,7 +27,7 @@ pub fn try_handle_call_message(
     from: &NetworkAddress,
     data: &Bytes,
     _protocols: Vec<String>,
-) -> u8 {
+) -> (u8, Bytes) {
     let mut protocols: Option<Vec<String>> = None;
     if _protocols.len() > 0 {
         protocols = Some(_protocols)
@@ -35,22 +37,38 @@ pub fn try_handle_call_message(
     let res = client.try_handle_call_message(&from.to_string(), data, &protocols);
 
     match res {
-        Ok(_) => {
+        Ok(value) => {
             let code = CSResponseType::CSResponseSuccess.into();
             event::call_executed(&e, req_id, code, String::from_str(&e, "success"));
-            code
+
+            let returned = value
+                .ok()
+                .and_then(|value| Bytes::try_from_val(e, &value).ok())
+                .unwrap_or(Bytes::new(&e));
+            (code, returned)
         }
         Err(err) => match err {
             Ok(_error) => {
                 let code = CSResponseType::CSResponseFailure.into();
                 event::call_executed(&e, req_id, code, String::from_str(&e, "unknown error"));
-                code
+                (code, Bytes::new(&e))
             }
             Err(_error) => {
                 let code = CSResponseType::CSResponseFailure.into();
                 event::call_executed(&e, req_id, code, String::from_str(&e, "unknown error"));
-                code
+                (code, Bytes::new(&e))
             }
         },
     }
 }
+
+pub fn handle_call_response(
+    e: &Env,
+    address: Address,
+    from: &NetworkAddress,
+    sn: u128,
+    data: &Bytes,
+) {
+    let client = DappClient::new(&e, &address);
+    client.handle_call_response(&from.to_string(), &sn, data);
+}
@@ -1,4 +1,6 @@
+use soroban_rlp::decoder::DecodeError;
 use soroban_sdk::contracterror;
+use soroban_xcall_lib::network_address::NetworkAddressError;
 
 #[contracterror]
 #[repr(u32)]
@@ -23,4 +25,22 @@ pub enum ContractError {
     NoRollbackData = 17,
     NetworkIdMismatch = 18,
     InvalidSourceNetwork = 19,
+    InvalidFeeSplits = 20,
+    ResponseFeeNotRefundable = 21,
+    InvalidRlp = 22,
+    InvalidTimeout = 23,
+    CallResponseNotFound = 24,
+    InvalidNetworkAddress = 25,
+}
+
+impl From<DecodeError> for ContractError {
+    fn from(_: DecodeError) -> Self {
+        ContractError::InvalidRlp
+    }
+}
+
+impl From<NetworkAddressError> for ContractError {
+    fn from(_: NetworkAddressError) -> Self {
+        ContractError::InvalidNetworkAddress
+    }
 }
@@ -42,6 +42,16 @@ pub struct RollbackExecutedEvent {
     pub sn: u128,
 }
 
+#[contracttype]
+pub struct CallResponseMsgEvent {
+    pub sn: u128,
+}
+
+#[contracttype]
+pub struct CallResponseExecutedEvent {
+    pub sn: u128,
+}
+
 pub(crate) fn message_sent(e: &Env, from: Address, to: String, sn: u128) {
     let data = CallMsgSentEvent { from, to, sn };
     e.events().publish(("CallMessageSent",), data)
@@ -85,3 +95,13 @@ pub(crate) fn rollback_executed(e: &Env, sn: u128) {
     let data = RollbackExecutedEvent { sn };
     e.events().publish(("RollbackExecuted",), data)
 }
+
+pub(crate) fn call_response_message(e: &Env, sn: u128) {
+    let data = CallResponseMsgEvent { sn };
+    e.events().publish(("CallResponseMessage",), data)
+}
+
+pub(crate) fn call_response_executed(e: &Env, sn: u128) {
+    let data = CallResponseExecutedEvent { sn };
+    e.events().publish(("CallResponseExecuted",), data)
+}
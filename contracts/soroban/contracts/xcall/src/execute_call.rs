@@ -7,6 +7,7 @@ use crate::{
     event, helpers, storage,
     types::{
         message::CSMessage,
+        request::CSMessageRequest,
         result::{CSMessageResult, CSResponseType},
     },
 };
@@ -25,10 +26,20 @@ pub fn execute_message(
     }
     storage::remove_proxy_request(&env, req_id);
 
+    let ledger = env.ledger();
+    if req.has_timed_out(ledger.sequence() as u64, ledger.timestamp()) {
+        // the dapp is not called, the source rolls the message back
+        let code: u8 = CSResponseType::CSResponseFailure.into();
+        event::call_executed(&env, req_id, code, String::from_str(&env, "timed out"));
+
+        let response_code = CSResponseType::CSResponseFailure;
+        return send_result(&env, &sender, &req, response_code, Bytes::new(&env));
+    }
+
     let to = Address::from_string(&req.to());
 
     match req.msg_type() {
-        MessageType::CallMessage => {
+        Ok(MessageType::CallMessage) => {
             dapp::try_handle_call_message(
                 &env,
                 req_id,
@@ -38,7 +49,7 @@ pub fn execute_message(
                 req.protocols().clone(),
             );
         }
-        MessageType::CallMessagePersisted => {
+        Ok(MessageType::CallMessagePersisted) => {
             dapp::handle_call_message(
                 &env,
                 to.clone(),
@@ -50,8 +61,8 @@ pub fn execute_message(
             let code: u8 = CSResponseType::CSResponseSuccess.into();
             event::call_executed(&env, req_id, code, String::from_str(&env, "success"));
         }
-        MessageType::CallMessageWithRollback => {
-            let code = dapp::try_handle_call_message(
+        Ok(MessageType::CallMessageWithRollback | MessageType::CallMessageWithTimeout) => {
+            let (code, _) = dapp::try_handle_call_message(
                 &env,
                 req_id,
                 to,
@@ -61,32 +72,72 @@ pub fn execute_message(
             );
 
             let response_code = code.into();
-            let result = CSMessageResult::new(req.sequence_no(), response_code, Bytes::new(&env));
-            let cs_message = CSMessage::from_result(&env, &result).encode(&env);
-
-            let nid = req.from().nid(&env);
-            let mut destinations = req.protocols().clone();
-            if destinations.is_empty() {
-                let deafult_connection = storage::default_connection(&env, nid.clone())?;
-                destinations = vec![&env, deafult_connection.to_string()];
-            }
-
-            for to in destinations {
-                connection::call_connection_send_message(
-                    &env,
-                    &sender,
-                    &to,
-                    &nid,
-                    -(req.sequence_no() as i64),
-                    &cs_message,
-                )?;
-            }
+            send_result(&env, &sender, &req, response_code, Bytes::new(&env))?;
+        }
+        Ok(MessageType::CallMessageWithResponse) => {
+            let (code, returned) = dapp::try_handle_call_message(
+                &env,
+                req_id,
+                to,
+                &req.from(),
+                &data,
+                req.protocols().clone(),
+            );
+
+            // the data returned by the dapp is carried back to the sender in the result
+            let response_code = code.into();
+            send_result(&env, &sender, &req, response_code, returned)?;
+        }
+        // sent by a newer version, every message type added after `CallMessagePersisted`
+        // expects a response so the sender can roll back
+        Err(_) => {
+            let code: u8 = CSResponseType::CSResponseFailure.into();
+            event::call_executed(
+                &env,
+                req_id,
+                code,
+                String::from_str(&env, "unsupported message type"),
+            );
+
+            let response_code = CSResponseType::CSResponseFailure;
+            send_result(&env, &sender, &req, response_code, Bytes::new(&env))?;
         }
     };
 
     Ok(())
 }
 
+fn send_result(
+    env: &Env,
+    sender: &Address,
+    req: &CSMessageRequest,
+    response_code: CSResponseType,
+    message: Bytes,
+) -> Result<(), ContractError> {
+    let result = CSMessageResult::new(req.sequence_no(), response_code, message);
+    let cs_message = CSMessage::from_result(&env, &result).encode(&env);
+
+    let nid = req.from().nid(&env);
+    let mut destinations = req.protocols().clone();
+    if destinations.is_empty() {
+        let deafult_connection = storage::default_connection(&env, nid.clone())?;
+        destinations = vec![&env, deafult_connection.to_string()];
+    }
+
+    for to in destinations {
+        connection::call_connection_send_message(
+            &env,
+            &sender,
+            &to,
+            &nid,
+            -(req.sequence_no() as i64),
+            &cs_message,
+        )?;
+    }
+
+    Ok(())
+}
+
 pub fn execute_rollback_message(env: &Env, sequence_no: u128) -> Result<(), ContractError> {
     let rollback = storage::get_rollback(&env, sequence_no)?;
     helpers::ensure_rollback_enabled(&rollback)?;
@@ -103,3 +154,22 @@ pub fn execute_rollback_message(env: &Env, sequence_no: u128) -> Result<(), Cont
 
     Ok(())
 }
+
+pub fn execute_response_message(env: &Env, sequence_no: u128) -> Result<(), ContractError> {
+    let rollback = storage::get_rollback(&env, sequence_no)?;
+    let response = rollback
+        .response()
+        .ok_or(ContractError::CallResponseNotFound)?;
+    storage::remove_rollback(&env, sequence_no);
+
+    dapp::handle_call_response(
+        &env,
+        rollback.from().clone(),
+        &rollback.to(),
+        sequence_no,
+        &response,
+    );
+    event::call_response_executed(&env, sequence_no);
+
+    Ok(())
+}
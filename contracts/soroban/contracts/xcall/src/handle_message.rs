@@ -1,3 +1,4 @@
+use soroban_rlp::strict;
 use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec};
 
 use crate::{
@@ -24,7 +25,11 @@ pub fn handle_message(
         return Err(ContractError::InvalidSourceNetwork);
     }
 
+    // only canonical encodings are accepted so the hash of a message is the same for every
+    // connection delivering it
+    strict::validate(&msg)?;
     let cs_message: CSMessage = CSMessage::decode(&env, msg)?;
+    strict::validate(cs_message.payload())?;
     match cs_message.message_type() {
         CSMessageType::CSMessageRequest => {
             handle_request(&env, sender, from_nid, cs_message.payload().clone())
@@ -83,6 +88,11 @@ pub fn handle_request(
 }
 
 pub fn handle_result(env: &Env, sender: &Address, data: Bytes) -> Result<(), ContractError> {
+    process_result(env, sender, data)
+}
+
+/// Resolves the sequence number of a result, making the response fee prepaid for it refundable.
+fn process_result(env: &Env, sender: &Address, data: Bytes) -> Result<(), ContractError> {
     let result = CSMessageResult::decode(&env, data.clone())?;
 
     let source = sender.to_string();
@@ -108,13 +118,22 @@ pub fn handle_result(env: &Env, sender: &Address, data: Bytes) -> Result<(), Con
         }
         storage::remove_pending_response(&env, hash);
     }
+    resolve_response_fee(&env, sequence_no);
 
     event::response_message(&env, result.response_code().clone(), sequence_no);
 
     match result.response_code() {
         CSResponseType::CSResponseSuccess => {
-            storage::remove_rollback(&env, sequence_no);
             storage::save_success_response(&env, sequence_no);
+            // the returned data is kept with the rollback until delivered by `execute_response`
+            if rollback.expects_response() {
+                rollback.set_response(result.message_bytes().clone());
+                storage::store_rollback(&env, sequence_no, &rollback);
+
+                event::call_response_message(&env, sequence_no);
+                return Ok(());
+            }
+            storage::remove_rollback(&env, sequence_no);
 
             let result_msg = result.message(&env);
             if result_msg.is_some() {
@@ -166,7 +185,17 @@ pub fn handle_error(env: &Env, sender: Address, sequence_no: u128) -> Result<(),
         CSResponseType::CSResponseFailure,
         Bytes::new(&env),
     );
-    handle_result(&env, &sender, cs_message_result.encode(&env))
+    process_result(&env, &sender, cs_message_result.encode(&env))
+}
+
+/// Lets the original sender reclaim the response fee of `sequence_no` once it is resolved. The
+/// connections keep the fee reserved until they deliver a response for it, so only the part no
+/// response was delivered for is refunded.
+fn resolve_response_fee(env: &Env, sequence_no: u128) {
+    if let Ok(mut record) = storage::get_response_fee_record(&env, sequence_no) {
+        record.refundable = true;
+        storage::store_response_fee_record(&env, sequence_no, &record);
+    }
 }
 
 pub fn is_valid_source(
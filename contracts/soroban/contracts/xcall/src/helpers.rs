@@ -1,9 +1,9 @@
-use soroban_sdk::{token, xdr::ToXdr, Address, Bytes, Env};
+use soroban_sdk::{token, xdr::ToXdr, Address, Bytes, Env, Vec};
 
 use crate::{
     errors::ContractError,
-    storage::{self, MAX_DATA_SIZE, MAX_ROLLBACK_SIZE},
-    types::rollback::Rollback,
+    storage::{self, MAX_BASIS_POINTS, MAX_DATA_SIZE, MAX_ROLLBACK_SIZE},
+    types::{rollback::Rollback, storage_types::FeeSplit},
 };
 
 /** SC TYPES */
@@ -51,6 +51,21 @@ pub fn ensure_rollback_enabled(rollback: &Rollback) -> Result<(), ContractError>
     Ok(())
 }
 
+pub fn ensure_fee_splits(splits: &Vec<FeeSplit>) -> Result<(), ContractError> {
+    let mut total = 0_u32;
+    for split in splits.iter() {
+        if split.basis_points == 0 {
+            return Err(ContractError::InvalidFeeSplits);
+        }
+        total = total.saturating_add(split.basis_points);
+    }
+    if !splits.is_empty() && total != MAX_BASIS_POINTS {
+        return Err(ContractError::InvalidFeeSplits);
+    }
+
+    Ok(())
+}
+
 pub fn hash_data(e: &Env, data: &Bytes) -> Bytes {
     let hash = e.crypto().keccak256(&data);
     Bytes::from_array(&e, &hash.to_array())
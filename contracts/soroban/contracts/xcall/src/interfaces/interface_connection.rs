@@ -12,5 +12,7 @@ pub trait IConnection {
         msg: Bytes,
     ) -> Result<(), ContractError>;
 
+    fn refund_response_fee(env: Env, network_id: String, sn: u128) -> Result<(), ContractError>;
+
     fn get_fee(env: Env, network_id: String, response: bool) -> Result<u128, ContractError>;
 }
@@ -1,6 +1,15 @@
-use soroban_sdk::{contractclient, Bytes, Env, String, Vec};
+use soroban_sdk::{contractclient, Bytes, Env, String, Val, Vec};
 
 #[contractclient(name = "DappClient")]
 pub trait IDapp {
-    fn handle_call_message(env: Env, from: String, data: Bytes, protocols: Option<Vec<String>>);
+    /// Returns the data carried back to the sender of a `CallMessageWithResponse` as `Bytes`, any
+    /// other value is ignored.
+    fn handle_call_message(
+        env: Env,
+        from: String,
+        data: Bytes,
+        protocols: Option<Vec<String>>,
+    ) -> Val;
+
+    fn handle_call_response(env: Env, from: String, sn: u128, data: Bytes);
 }
@@ -8,8 +8,11 @@ use crate::{
     connection,
     errors::ContractError,
     event, helpers,
-    storage::{self, protocol_fee},
-    types::{message::CSMessage, request::CSMessageRequest, rollback::Rollback},
+    storage::{self, protocol_fee, MAX_BASIS_POINTS},
+    types::{
+        message::CSMessage, request::CSMessageRequest, rollback::Rollback,
+        storage_types::ResponseFeeRecord,
+    },
 };
 
 pub fn send_call(
@@ -25,13 +28,13 @@ pub fn send_call(
     let config = storage::get_config(&env)?;
     let sequence_no = storage::get_next_sn(&env);
 
-    let to = NetworkAddress::from_string(to.clone());
+    let to = NetworkAddress::parse(&env, to)?;
     let (nid_to, dst_account) = to.parse_network_address(&env);
     let from = NetworkAddress::new(&env, config.network_id, sender.to_string());
 
     process_message(&env, &to, sequence_no, &sender, &envelope)?;
 
-    let request = CSMessageRequest::new(
+    let mut request = CSMessageRequest::new(
         from,
         dst_account,
         sequence_no,
@@ -39,6 +42,10 @@ pub fn send_call(
         envelope.message.msg_type(),
         envelope.message.data(),
     );
+    if let AnyMessage::CallMessageWithTimeout(msg) = &envelope.message {
+        request.set_timeout(msg.timeout_height, msg.timeout_timestamp);
+    }
+    request.set_gas_limit(envelope.gas_limit);
 
     let need_response = request.need_response();
 
@@ -46,7 +53,7 @@ pub fn send_call(
     let encode_msg = cs_message.encode(&env);
     helpers::ensure_data_size(encode_msg.len() as usize)?;
 
-    call_connection(
+    let sources = call_connection(
         &env,
         &tx_origin,
         &nid_to,
@@ -55,6 +62,15 @@ pub fn send_call(
         need_response,
         encode_msg.clone(),
     )?;
+    if need_response {
+        let record = ResponseFeeRecord {
+            payer: tx_origin.clone(),
+            nid: nid_to,
+            sources,
+            refundable: false,
+        };
+        storage::store_response_fee_record(&env, sequence_no, &record);
+    }
     claim_protocol_fee(&env, &tx_origin)?;
 
     event::message_sent(&env, sender, to.to_string(), sequence_no);
@@ -65,8 +81,19 @@ pub fn send_call(
 pub fn claim_protocol_fee(e: &Env, tx_origin: &Address) -> Result<(), ContractError> {
     let protocol_fee = protocol_fee(&e);
     if protocol_fee > 0 {
-        let fee_handler = storage::get_fee_handler(&e)?;
-        helpers::transfer_token(&e, &tx_origin, &fee_handler, &protocol_fee)?;
+        let mut remaining = protocol_fee;
+        for split in storage::get_fee_splits(&e).iter() {
+            let share = protocol_fee * split.basis_points as u128 / MAX_BASIS_POINTS as u128;
+            if share > 0 {
+                remaining -= share;
+                helpers::transfer_token(&e, &tx_origin, &split.address, &share)?;
+            }
+        }
+
+        if remaining > 0 {
+            let fee_handler = storage::get_fee_handler(&e)?;
+            helpers::transfer_token(&e, &tx_origin, &fee_handler, &remaining)?;
+        }
     }
 
     Ok(())
@@ -80,7 +107,7 @@ pub fn call_connection(
     sources: Vec<String>,
     rollback: bool,
     msg: Bytes,
-) -> Result<(), ContractError> {
+) -> Result<Vec<String>, ContractError> {
     let mut sources = sources;
     let sn = if rollback { sequence_no as i64 } else { 0 };
     if sources.is_empty() {
@@ -92,6 +119,21 @@ pub fn call_connection(
         connection::call_connection_send_message(&e, tx_origin, &source, &nid, sn, &msg)?;
     }
 
+    Ok(sources)
+}
+
+pub fn claim_response_fee_refund(e: &Env, sn: u128) -> Result<(), ContractError> {
+    let record = storage::get_response_fee_record(&e, sn)?;
+    if !record.refundable {
+        return Err(ContractError::ResponseFeeNotRefundable);
+    }
+    record.payer.require_auth();
+    storage::remove_response_fee_record(&e, sn);
+
+    for source in record.sources.iter() {
+        connection::call_connection_refund_response_fee(&e, &source, &record.nid, sn)?;
+    }
+
     Ok(())
 }
 
@@ -120,6 +162,44 @@ pub fn process_message(
             );
             storage::store_rollback(&e, sequence_no, &rollback);
 
+            Ok(())
+        }
+        AnyMessage::CallMessageWithTimeout(msg) => {
+            if !helpers::is_contract(&e, sender) {
+                return Err(ContractError::RollbackNotPossible);
+            }
+            helpers::ensure_rollback_size(&msg.rollback().unwrap())?;
+            if msg.timeout_height == 0 && msg.timeout_timestamp == 0 {
+                return Err(ContractError::InvalidTimeout);
+            }
+
+            let rollback = Rollback::new(
+                sender.clone(),
+                to.clone(),
+                envelope.sources.clone(),
+                msg.rollback.clone(),
+                false,
+            );
+            storage::store_rollback(&e, sequence_no, &rollback);
+
+            Ok(())
+        }
+        AnyMessage::CallMessageWithResponse(msg) => {
+            if !helpers::is_contract(&e, sender) {
+                return Err(ContractError::RollbackNotPossible);
+            }
+            helpers::ensure_rollback_size(&msg.rollback().unwrap())?;
+
+            let mut rollback = Rollback::new(
+                sender.clone(),
+                to.clone(),
+                envelope.sources.clone(),
+                msg.rollback.clone(),
+                false,
+            );
+            rollback.set_expects_response();
+            storage::store_rollback(&e, sequence_no, &rollback);
+
             Ok(())
         }
     }
@@ -6,7 +6,7 @@ use crate::{
     types::{
         request::CSMessageRequest,
         rollback::Rollback,
-        storage_types::{Config, StorageKey},
+        storage_types::{Config, FeeSplit, ResponseFeeRecord, StorageKey},
     },
 };
 
@@ -23,6 +23,7 @@ const LEDGER_BUMP_REQUEST: u32 = LEDGER_THRESHOLD_REQUEST + DAY_IN_LEDGERS; // ~
 
 pub const MAX_ROLLBACK_SIZE: u64 = 1024;
 pub const MAX_DATA_SIZE: u64 = 2048;
+pub const MAX_BASIS_POINTS: u32 = 10_000;
 
 pub fn is_initialized(e: &Env) -> Result<(), ContractError> {
     let initialized = e.storage().instance().has(&StorageKey::Admin);
@@ -54,6 +55,13 @@ pub fn get_fee_handler(e: &Env) -> Result<Address, ContractError> {
         .ok_or(ContractError::Uninitialized)
 }
 
+pub fn get_fee_splits(e: &Env) -> Vec<FeeSplit> {
+    e.storage()
+        .instance()
+        .get(&StorageKey::FeeSplits)
+        .unwrap_or(Vec::new(&e))
+}
+
 pub fn get_upgrade_authority(e: &Env) -> Result<Address, ContractError> {
     e.storage()
         .instance()
@@ -89,6 +97,14 @@ pub fn get_rollback(e: &Env, sequence_no: u128) -> Result<Rollback, ContractErro
     rollback
 }
 
+pub fn get_response_fee_record(e: &Env, sn: u128) -> Result<ResponseFeeRecord, ContractError> {
+    let key = StorageKey::ResponseFee(sn);
+    e.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(ContractError::ResponseFeeNotRefundable)
+}
+
 pub fn get_successful_response(e: &Env, sn: u128) -> bool {
     let key = StorageKey::SuccessfulResponses(sn);
     let res = e.storage().persistent().get(&key).unwrap_or(false);
@@ -165,6 +181,11 @@ pub fn store_fee_handler(e: &Env, address: &Address) {
     extend_instance(e)
 }
 
+pub fn store_fee_splits(e: &Env, splits: &Vec<FeeSplit>) {
+    e.storage().instance().set(&StorageKey::FeeSplits, splits);
+    extend_instance(e)
+}
+
 pub fn store_upgrade_authority(e: &Env, address: &Address) {
     e.storage()
         .instance()
@@ -192,6 +213,18 @@ pub fn remove_rollback(e: &Env, sn: u128) {
     e.storage().temporary().remove(&StorageKey::Rollback(sn));
 }
 
+pub fn store_response_fee_record(e: &Env, sn: u128, record: &ResponseFeeRecord) {
+    let key = StorageKey::ResponseFee(sn);
+    e.storage().persistent().set(&key, record);
+    extend_persistent(e, &key)
+}
+
+pub fn remove_response_fee_record(e: &Env, sn: u128) {
+    e.storage()
+        .persistent()
+        .remove(&StorageKey::ResponseFee(sn));
+}
+
 pub fn store_proxy_request(e: &Env, req_id: u128, request: &CSMessageRequest) {
     let key = StorageKey::ProxyRequest(req_id);
     e.storage().temporary().set(&key, request);
@@ -1,5 +1,6 @@
 mod contract;
 mod execute_call;
+mod golden_vectors;
 mod handle_message;
 mod send_message;
 pub mod setup;
@@ -3,14 +3,14 @@
 use soroban_rlp::encoder;
 use soroban_sdk::{
     bytes,
-    testutils::{Address as _, Events},
+    testutils::{Address as _, Events, Ledger},
     vec, Address, Bytes, IntoVal, String, Vec,
 };
 use soroban_xcall_lib::{messages::msg_type::MessageType, network_address::NetworkAddress};
 
 use crate::{
     contract::XcallClient,
-    event::{CallExecutedEvent, RollbackExecutedEvent},
+    event::{CallExecutedEvent, CallResponseExecutedEvent, RollbackExecutedEvent},
     storage,
     types::{request::CSMessageRequest, rollback::Rollback},
 };
@@ -166,6 +166,162 @@ fn test_execute_call_with_rollback_message_type() {
     });
 }
 
+#[test]
+fn test_execute_call_with_response_message_type() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let msg_data = encoder::encode_string(&ctx.env, String::from_str(&ctx.env, "abc"));
+
+    let req_id = 1;
+    let sequence_no = 1;
+    let mut req = CSMessageRequest::new(
+        ctx.network_address,
+        ctx.dapp.to_string(),
+        sequence_no,
+        Vec::new(&ctx.env),
+        MessageType::CallMessageWithResponse,
+        msg_data.clone(),
+    );
+    req.hash_data(&ctx.env);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_proxy_request(&ctx.env, req_id.clone(), &req);
+    });
+
+    client.execute_call(&ctx.admin, &req_id, &msg_data);
+
+    let call_executed_event = CallExecutedEvent {
+        reqId: req_id,
+        code: 1,
+        msg: String::from_str(&ctx.env, "success"),
+    };
+    let events = vec![&ctx.env, ctx.env.events().all().get(1).unwrap()];
+    assert_eq!(
+        events,
+        vec![
+            &ctx.env,
+            (
+                client.address.clone(),
+                ("CallExecuted",).into_val(&ctx.env),
+                call_executed_event.into_val(&ctx.env)
+            ),
+        ]
+    );
+
+    ctx.env.as_contract(&ctx.contract, || {
+        assert!(storage::get_proxy_request(&ctx.env, req_id).is_err());
+    });
+}
+
+#[test]
+fn test_execute_call_with_unsupported_message_type() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let msg_data = encoder::encode_string(&ctx.env, String::from_str(&ctx.env, "abc"));
+
+    let req_id = 1;
+    let sequence_no = 1;
+    let list = vec![
+        &ctx.env,
+        encoder::encode_string(&ctx.env, ctx.network_address.to_string()),
+        encoder::encode_string(&ctx.env, ctx.dapp.to_string()),
+        encoder::encode_u128(&ctx.env, sequence_no),
+        encoder::encode_u8(&ctx.env, 7),
+        encoder::encode(&ctx.env, msg_data.clone()),
+        encoder::encode_strings(&ctx.env, Vec::new(&ctx.env)),
+    ];
+    let encoded = encoder::encode_list(&ctx.env, list, false);
+    let mut req = CSMessageRequest::decode(&ctx.env, encoded).unwrap();
+    assert!(req.msg_type().is_err());
+    req.hash_data(&ctx.env);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_proxy_request(&ctx.env, req_id.clone(), &req);
+    });
+
+    client.execute_call(&ctx.admin, &req_id, &msg_data);
+
+    // the dapp is not called, the failure is sent back right away
+    let call_executed_event = CallExecutedEvent {
+        reqId: req_id,
+        code: 0,
+        msg: String::from_str(&ctx.env, "unsupported message type"),
+    };
+    let events = vec![&ctx.env, ctx.env.events().all().get(0).unwrap()];
+    assert_eq!(
+        events,
+        vec![
+            &ctx.env,
+            (
+                client.address.clone(),
+                ("CallExecuted",).into_val(&ctx.env),
+                call_executed_event.into_val(&ctx.env)
+            ),
+        ]
+    );
+
+    ctx.env.as_contract(&ctx.contract, || {
+        // request should be removed
+        assert!(storage::get_proxy_request(&ctx.env, req_id).is_err());
+    });
+}
+
+#[test]
+fn test_execute_call_after_timeout() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let msg_data = encoder::encode_string(&ctx.env, String::from_str(&ctx.env, "abc"));
+
+    let req_id = 1;
+    let sequence_no = 1;
+    let mut req = CSMessageRequest::new(
+        ctx.network_address,
+        ctx.dapp.to_string(),
+        sequence_no,
+        Vec::new(&ctx.env),
+        MessageType::CallMessageWithTimeout,
+        msg_data.clone(),
+    );
+    req.set_timeout(0, 1_000);
+    req.hash_data(&ctx.env);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_proxy_request(&ctx.env, req_id.clone(), &req);
+    });
+    ctx.env.ledger().with_mut(|ledger| ledger.timestamp = 1_000);
+
+    client.execute_call(&ctx.admin, &req_id, &msg_data);
+
+    // the dapp is not called, the failure is sent back right away
+    let call_executed_event = CallExecutedEvent {
+        reqId: req_id,
+        code: 0,
+        msg: String::from_str(&ctx.env, "timed out"),
+    };
+    let events = vec![&ctx.env, ctx.env.events().all().get(0).unwrap()];
+    assert_eq!(
+        events,
+        vec![
+            &ctx.env,
+            (
+                client.address.clone(),
+                ("CallExecuted",).into_val(&ctx.env),
+                call_executed_event.into_val(&ctx.env)
+            ),
+        ]
+    );
+
+    ctx.env.as_contract(&ctx.contract, || {
+        assert!(storage::get_proxy_request(&ctx.env, req_id).is_err());
+    });
+}
+
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #11)")]
 fn test_execute_call_data_mismatch() {
@@ -255,3 +411,69 @@ fn test_execute_rollback_success() {
         assert!(storage::get_rollback(&ctx.env, sequence_no).is_err());
     });
 }
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #24)")]
+fn test_execute_response_fail_before_result() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+
+    let sequence_no = 1;
+    let mut rollback = Rollback::new(
+        ctx.dapp,
+        ctx.network_address,
+        get_dummy_sources(&ctx.env),
+        Bytes::new(&ctx.env),
+        false,
+    );
+    rollback.set_expects_response();
+
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_rollback(&ctx.env, sequence_no, &rollback);
+    });
+
+    client.execute_response(&sequence_no);
+}
+
+#[test]
+fn test_execute_response_success() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let sequence_no = 1;
+    let mut rollback = Rollback::new(
+        ctx.dapp,
+        ctx.network_address,
+        get_dummy_sources(&ctx.env),
+        Bytes::new(&ctx.env),
+        false,
+    );
+    rollback.set_expects_response();
+    rollback.set_response(bytes!(&ctx.env, 0xabcdef));
+
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_rollback(&ctx.env, sequence_no, &rollback);
+    });
+
+    client.execute_response(&sequence_no);
+
+    let call_response_executed_event = CallResponseExecutedEvent { sn: sequence_no };
+    let events = vec![&ctx.env, ctx.env.events().all().last_unchecked()];
+    assert_eq!(
+        events,
+        vec![
+            &ctx.env,
+            (
+                client.address.clone(),
+                ("CallResponseExecuted",).into_val(&ctx.env),
+                call_response_executed_event.into_val(&ctx.env)
+            ),
+        ]
+    );
+
+    ctx.env.as_contract(&ctx.contract, || {
+        // rollback should be removed
+        assert!(storage::get_rollback(&ctx.env, sequence_no).is_err());
+    });
+}
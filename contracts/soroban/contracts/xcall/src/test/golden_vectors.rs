@@ -0,0 +1,138 @@
+#![cfg(test)]
+
+extern crate std;
+
+use serde_json::Value;
+use soroban_sdk::{Bytes, Env, String, Vec};
+use soroban_xcall_lib::{messages::msg_type::MessageType, network_address::NetworkAddress};
+
+use crate::types::{
+    message::CSMessage,
+    request::CSMessageRequest,
+    result::{CSMessageResult, CSResponseType},
+};
+
+const VECTORS: &str = include_str!("../../../../../../test-vectors/xcall/messages.json");
+
+fn bytes(env: &Env, value: &Value) -> Bytes {
+    let hex = value.as_str().unwrap();
+    let mut bytes = Bytes::new(&env);
+    for i in (0..hex.len()).step_by(2) {
+        bytes.push_back(u8::from_str_radix(&hex[i..i + 2], 16).unwrap());
+    }
+    bytes
+}
+
+fn string(env: &Env, value: &Value) -> String {
+    String::from_str(&env, value.as_str().unwrap())
+}
+
+fn sn(value: &Value) -> u128 {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+fn request(env: &Env, value: &Value) -> CSMessageRequest {
+    let mut protocols = Vec::new(&env);
+    for protocol in value["protocols"].as_array().unwrap() {
+        protocols.push_back(string(&env, protocol));
+    }
+
+    let mut request = CSMessageRequest::new(
+        NetworkAddress::from_string(string(&env, &value["from"])),
+        string(&env, &value["to"]),
+        sn(&value["sn"]),
+        protocols,
+        MessageType::try_from(value["msg_type"].as_u64().unwrap() as u8).unwrap(),
+        bytes(&env, &value["data"]),
+    );
+    if let (Some(height), Some(timestamp)) = (
+        value["timeout_height"].as_u64(),
+        value["timeout_timestamp"].as_u64(),
+    ) {
+        request.set_timeout(height, timestamp);
+    }
+    if let Some(gas_limit) = value["gas_limit"].as_u64() {
+        request.set_gas_limit(gas_limit);
+    }
+    request
+}
+
+fn result(env: &Env, value: &Value) -> CSMessageResult {
+    CSMessageResult::new(
+        sn(&value["sn"]),
+        CSResponseType::from(value["code"].as_u64().unwrap() as u8),
+        bytes(&env, &value["message"]),
+    )
+}
+
+#[test]
+fn test_golden_vectors() {
+    let env = Env::default();
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+
+    for vector in vectors.as_array().unwrap() {
+        let name = vector["name"].as_str().unwrap();
+        let value = &vector["value"];
+        let encoded = bytes(&env, &vector["hex"]);
+
+        match vector["type"].as_str().unwrap() {
+            "CSMessageRequest" => {
+                let expected = request(&env, value);
+                assert_eq!(expected.encode(&env), encoded, "{}", name);
+
+                let decoded = CSMessageRequest::decode(&env, encoded.clone()).unwrap();
+                assert_eq!(decoded.from(), expected.from(), "{}", name);
+                assert_eq!(decoded.to(), expected.to(), "{}", name);
+                assert_eq!(decoded.sequence_no(), expected.sequence_no(), "{}", name);
+                assert_eq!(decoded.msg_type(), expected.msg_type(), "{}", name);
+                assert_eq!(decoded.data(), expected.data(), "{}", name);
+                assert_eq!(decoded.protocols(), expected.protocols(), "{}", name);
+                assert_eq!(
+                    decoded.timeout_height(),
+                    expected.timeout_height(),
+                    "{}",
+                    name
+                );
+                assert_eq!(
+                    decoded.timeout_timestamp(),
+                    expected.timeout_timestamp(),
+                    "{}",
+                    name
+                );
+                assert_eq!(decoded.gas_limit(), expected.gas_limit(), "{}", name);
+            }
+            "CSMessageResult" => {
+                let expected = result(&env, value);
+                assert_eq!(expected.encode(&env), encoded, "{}", name);
+
+                let decoded = CSMessageResult::decode(&env, encoded.clone()).unwrap();
+                assert_eq!(decoded.sequence_no(), expected.sequence_no(), "{}", name);
+                assert_eq!(
+                    u8::from(*decoded.response_code()),
+                    u8::from(*expected.response_code()),
+                    "{}",
+                    name
+                );
+                assert_eq!(decoded.encode(&env), encoded, "{}", name);
+            }
+            "CSMessage" => {
+                let decoded = CSMessage::decode(&env, encoded.clone()).unwrap();
+                assert_eq!(
+                    u32::from(*decoded.message_type()) as u64,
+                    value["type"].as_u64().unwrap(),
+                    "{}",
+                    name
+                );
+                assert_eq!(
+                    decoded.payload(),
+                    &bytes(&env, &value["payload"]),
+                    "{}",
+                    name
+                );
+                assert_eq!(decoded.encode(&env), encoded, "{}", name);
+            }
+            // envelope and call messages are tested in soroban-xcall-lib
+            _ => {}
+        }
+    }
+}
@@ -11,13 +11,14 @@ use soroban_xcall_lib::{messages::msg_type::MessageType, network_address::Networ
 
 use crate::{
     contract::XcallClient,
-    event::{CallMsgEvent, ResponseMsgEvent, RollbackMsgEvent},
+    event::{CallMsgEvent, CallResponseMsgEvent, ResponseMsgEvent, RollbackMsgEvent},
     storage,
     types::{
         message::CSMessage,
         request::CSMessageRequest,
         result::{CSMessageResult, CSResponseType},
         rollback::Rollback,
+        storage_types::ResponseFeeRecord,
     },
 };
 
@@ -37,6 +38,31 @@ fn test_handle_message_fail_for_same_network_id() {
     );
 }
 
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #22)")]
+fn test_handle_message_fail_for_malformed_rlp() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let request = CSMessageRequest::new(
+        ctx.network_address,
+        Address::generate(&ctx.env).to_string(),
+        1,
+        vec![&ctx.env],
+        MessageType::CallMessage,
+        bytes!(&ctx.env, 0xabc),
+    );
+    let cs_message = CSMessage::from_request(&ctx.env, &request).encode(&ctx.env);
+    let truncated = cs_message.slice(0..cs_message.len() - 1);
+
+    client.handle_message(
+        &ctx.centralized_connection,
+        &String::from_str(&ctx.env, "cosmos"),
+        &truncated,
+    );
+}
+
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #18)")]
 fn test_handle_message_request_fail_for_invalid_network_id() {
@@ -511,6 +537,57 @@ fn test_handle_message_result_when_response_is_success_from_dst_chain() {
     );
 }
 
+#[test]
+fn test_handle_message_result_keeps_returned_data() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let sequence_no = 1;
+    let mut rollback = get_dummy_rollback(&ctx.env);
+    rollback.set_expects_response();
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_rollback(&ctx.env, sequence_no, &rollback);
+    });
+
+    let returned = bytes!(&ctx.env, 0xabcdef);
+    let result = CSMessageResult::new(
+        sequence_no,
+        CSResponseType::CSResponseSuccess,
+        returned.clone(),
+    );
+    let cs_message = CSMessage::from_result(&ctx.env, &result).encode(&ctx.env);
+
+    client.handle_message(
+        &ctx.centralized_connection,
+        &String::from_str(&ctx.env, "cosmos"),
+        &cs_message,
+    );
+
+    ctx.env.as_contract(&ctx.contract, || {
+        // the rollback is kept with the returned data until it is delivered
+        let rollback = storage::get_rollback(&ctx.env, sequence_no).unwrap();
+        assert_eq!(rollback.response(), Some(returned));
+
+        let success_res = storage::get_successful_response(&ctx.env, sequence_no);
+        assert_eq!(success_res, true);
+    });
+
+    let call_response_event = CallResponseMsgEvent { sn: sequence_no };
+    let events = vec![&ctx.env, ctx.env.events().all().last_unchecked()];
+    assert_eq!(
+        events,
+        vec![
+            &ctx.env,
+            (
+                client.address.clone(),
+                ("CallResponseMessage",).into_val(&ctx.env),
+                call_response_event.into_val(&ctx.env)
+            ),
+        ]
+    );
+}
+
 #[test]
 fn test_handle_error() {
     let ctx = TestContext::default();
@@ -571,3 +648,49 @@ fn test_handle_error() {
         assert_eq!(rollback.enabled, true);
     });
 }
+
+fn store_response_fee_record(ctx: &TestContext, sequence_no: u128) {
+    let record = ResponseFeeRecord {
+        payer: Address::generate(&ctx.env),
+        nid: ctx.nid.clone(),
+        sources: vec![&ctx.env, ctx.centralized_connection.to_string()],
+        refundable: false,
+    };
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_response_fee_record(&ctx.env, sequence_no, &record);
+    });
+}
+
+#[test]
+fn test_handle_error_makes_response_fee_refundable() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let sequence_no = 1;
+    let rollback = get_dummy_rollback(&ctx.env);
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_rollback(&ctx.env, sequence_no, &rollback);
+    });
+    store_response_fee_record(&ctx, sequence_no);
+
+    client.handle_error(&ctx.centralized_connection, &sequence_no);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        let record = storage::get_response_fee_record(&ctx.env, sequence_no).unwrap();
+        assert!(record.refundable);
+    });
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #21)")]
+fn test_claim_response_fee_refund_fail_before_resolution() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let sequence_no = 1;
+    store_response_fee_record(&ctx, sequence_no);
+
+    client.claim_response_fee_refund(&sequence_no);
+}
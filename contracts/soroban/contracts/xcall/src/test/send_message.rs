@@ -9,13 +9,15 @@ use soroban_sdk::{
 };
 use soroban_xcall_lib::messages::{
     call_message::CallMessage, call_message_persisted::CallMessagePersisted,
-    call_message_rollback::CallMessageWithRollback, envelope::Envelope, AnyMessage,
+    call_message_rollback::CallMessageWithRollback, call_message_timeout::CallMessageWithTimeout,
+    envelope::Envelope, AnyMessage,
 };
 
 use super::setup::*;
 use crate::{
     contract::{Xcall, XcallClient},
     send_message, storage,
+    types::storage_types::FeeSplit,
 };
 
 #[test]
@@ -39,6 +41,7 @@ fn test_send_call_message() {
         message: AnyMessage::CallMessage(CallMessage {
             data: bytes!(&ctx.env, 0xabc),
         }),
+        gas_limit: 0,
     };
 
     let protocol_fee = client.get_protocol_fee();
@@ -70,6 +73,7 @@ fn test_send_call_message() {
                                 message: AnyMessage::CallMessage(CallMessage {
                                     data: bytes!(&ctx.env, 0xabc),
                                 }),
+                                gas_limit: 0,
                             },
                             ctx.network_address.to_string().clone()
                         )
@@ -155,6 +159,27 @@ fn test_send_message_with_greater_than_max_data_size() {
     );
 }
 
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #25)")]
+fn test_send_message_to_invalid_network_address() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let tx_origin = Address::generate(&ctx.env);
+    let msg = AnyMessage::CallMessage(CallMessage {
+        data: bytes!(&ctx.env, 0xab),
+    });
+    let envelope = get_dummy_envelope_msg(&ctx.env, msg);
+
+    client.send_call(
+        &tx_origin,
+        &ctx.admin,
+        &envelope,
+        &String::from_str(&ctx.env, "stellar/GCX7EUFDXJUZ/EWHT5UGH2ZISTKXSUQ"),
+    );
+}
+
 #[test]
 #[should_panic(expected = "RollbackNotPossible")]
 fn test_process_rollback_message_with_invalid_contract_address() {
@@ -211,6 +236,26 @@ fn test_process_rollback_message_with_empty_rollback_data() {
         .unwrap();
 }
 
+#[test]
+#[should_panic(expected = "InvalidTimeout")]
+fn test_process_timeout_message_without_deadline() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let msg = CallMessageWithTimeout {
+        data: bytes!(&ctx.env, 0xab),
+        rollback: bytes!(&ctx.env, 0xcd),
+        timeout_height: 0,
+        timeout_timestamp: 0,
+    };
+    let message = AnyMessage::CallMessageWithTimeout(msg);
+    let envelope = &get_dummy_envelope_msg(&ctx.env, message);
+
+    send_message::process_message(&ctx.env, &ctx.network_address, 1, &ctx.contract, envelope)
+        .unwrap();
+}
+
 #[test]
 fn test_process_persisted_message() {
     let ctx = TestContext::default();
@@ -393,3 +438,94 @@ fn test_claim_protocol_fail_for_insufficient_amount_sent() {
         send_message::claim_protocol_fee(&ctx.env, &sender).unwrap();
     });
 }
+
+#[test]
+fn test_claim_protocol_fee_with_fee_splits() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+    ctx.env.mock_all_auths_allowing_non_root_auth();
+
+    let treasury = Address::generate(&ctx.env);
+    let splits = vec![
+        &ctx.env,
+        FeeSplit {
+            address: treasury.clone(),
+            basis_points: 7000,
+        },
+        FeeSplit {
+            address: ctx.fee_handler.clone(),
+            basis_points: 3000,
+        },
+    ];
+    client.set_protocol_fee_splits(&splits);
+    assert_eq!(client.get_protocol_fee_splits(), splits);
+
+    let protocol_fee = client.get_protocol_fee();
+    let sender = Address::generate(&ctx.env);
+    ctx.mint_native_token(&sender, protocol_fee);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        send_message::claim_protocol_fee(&ctx.env, &sender).unwrap();
+    });
+
+    assert_eq!(
+        ctx.get_native_token_balance(&treasury),
+        protocol_fee * 7000 / 10_000
+    );
+    assert_eq!(
+        ctx.get_native_token_balance(&ctx.fee_handler),
+        protocol_fee * 3000 / 10_000
+    );
+}
+
+#[test]
+fn test_claim_protocol_fee_splits_remainder_to_fee_handler() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+    ctx.env.mock_all_auths_allowing_non_root_auth();
+
+    let alice = Address::generate(&ctx.env);
+    let bob = Address::generate(&ctx.env);
+    let splits = vec![
+        &ctx.env,
+        FeeSplit {
+            address: alice.clone(),
+            basis_points: 6667,
+        },
+        FeeSplit {
+            address: bob.clone(),
+            basis_points: 3333,
+        },
+    ];
+    client.set_protocol_fee_splits(&splits);
+
+    let sender = Address::generate(&ctx.env);
+    ctx.mint_native_token(&sender, 100);
+
+    ctx.env.as_contract(&ctx.contract, || {
+        send_message::claim_protocol_fee(&ctx.env, &sender).unwrap();
+    });
+
+    assert_eq!(ctx.get_native_token_balance(&alice), 66);
+    assert_eq!(ctx.get_native_token_balance(&bob), 33);
+    assert_eq!(ctx.get_native_token_balance(&ctx.fee_handler), 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #20)")]
+fn test_set_protocol_fee_splits_not_adding_up() {
+    let ctx = TestContext::default();
+    let client = XcallClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let splits = vec![
+        &ctx.env,
+        FeeSplit {
+            address: Address::generate(&ctx.env),
+            basis_points: 7000,
+        },
+    ];
+    client.set_protocol_fee_splits(&splits);
+}
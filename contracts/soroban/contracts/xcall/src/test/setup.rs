@@ -109,6 +109,7 @@ pub fn get_dummy_envelope_msg(env: &Env, message: AnyMessage) -> Envelope {
         message,
         sources,
         destinations,
+        gas_limit: 0,
     };
 
     envelope
@@ -174,6 +175,7 @@ impl TestContext {
         let initialize_msg = connection::InitializeMsg {
             native_token: self.native_token.clone(),
             relayer: self.admin.clone(),
+            admin: self.admin.clone(),
             xcall_address: self.contract.clone(),
             upgrade_authority: self.upgrade_authority.clone(),
         };
@@ -1,4 +1,4 @@
-use soroban_rlp::{decoder, encoder};
+use soroban_rlp::{decoder, encoder, strict};
 use soroban_sdk::{contracttype, vec, Address, Bytes, Env, String};
 
 use crate::errors::ContractError;
@@ -71,12 +71,12 @@ impl CSMessage {
     }
 
     pub fn decode(env: &Env, bytes: Bytes) -> Result<Self, ContractError> {
-        let decoded = decoder::decode_list(&env, bytes);
+        let decoded = decoder::try_decode_list(&env, bytes)?;
         if decoded.len() != 2 {
             return Err(ContractError::InvalidRlpLength);
         }
 
-        let message_type = decoder::decode_u32(&env, decoded.get(0).unwrap()).into();
+        let message_type = strict::payload_to_u32(decoded.get(0).unwrap())?.into();
         let payload = decoded.get(1).unwrap();
 
         Ok(Self {
@@ -1,4 +1,7 @@
-use soroban_rlp::{decoder, encoder};
+use soroban_rlp::{
+    decoder::{self, DecodeError},
+    encoder, strict,
+};
 use soroban_sdk::{contracttype, Bytes, Env, String, Vec};
 use soroban_xcall_lib::{messages::msg_type::MessageType, network_address::NetworkAddress};
 
@@ -13,6 +16,9 @@ pub struct CSMessageRequest {
     msg_type: u32,
     data: Bytes,
     protocols: Vec<String>,
+    timeout_height: u64,
+    timeout_timestamp: u64,
+    gas_limit: u64,
 }
 
 impl CSMessageRequest {
@@ -32,6 +38,9 @@ impl CSMessageRequest {
             msg_type: msg_type as u32,
             data,
             protocols,
+            timeout_height: 0,
+            timeout_timestamp: 0,
+            gas_limit: 0,
         }
     }
 
@@ -51,14 +60,52 @@ impl CSMessageRequest {
         &self.protocols
     }
 
-    pub fn msg_type(&self) -> MessageType {
-        let msg_type: u8 = self.msg_type as u8;
-        msg_type.into()
+    /// Fails for the message types of newer versions, which are kept to answer them with a
+    /// failure when executed.
+    pub fn msg_type(&self) -> Result<MessageType, DecodeError> {
+        MessageType::try_from(self.msg_type as u8)
     }
 
     pub fn need_response(&self) -> bool {
-        let msg_type: MessageType = (self.msg_type as u8).into();
-        msg_type == MessageType::CallMessageWithRollback
+        self.msg_type() == Ok(MessageType::CallMessageWithRollback)
+            || self.msg_type() == Ok(MessageType::CallMessageWithTimeout)
+            || self.msg_type() == Ok(MessageType::CallMessageWithResponse)
+    }
+
+    pub fn timeout_height(&self) -> u64 {
+        self.timeout_height
+    }
+
+    pub fn timeout_timestamp(&self) -> u64 {
+        self.timeout_timestamp
+    }
+
+    /// Sets the deadline of a `CallMessageWithTimeout`, only encoded for that message type.
+    pub fn set_timeout(&mut self, timeout_height: u64, timeout_timestamp: u64) {
+        self.timeout_height = timeout_height;
+        self.timeout_timestamp = timeout_timestamp;
+    }
+
+    /// Whether the request may no longer be executed at ledger `sequence` and `timestamp`
+    /// (seconds since the unix epoch). A zero deadline is not checked.
+    pub fn has_timed_out(&self, sequence: u64, timestamp: u64) -> bool {
+        (self.timeout_height > 0 && sequence >= self.timeout_height)
+            || (self.timeout_timestamp > 0 && timestamp >= self.timeout_timestamp)
+    }
+
+    /// The gas the sender allows the destination dapp to use, zero for no limit. It is carried
+    /// to keep the encoding of the other chains but not enforced, a dapp call is bounded by the
+    /// resources of the transaction.
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    pub fn set_gas_limit(&mut self, gas_limit: u64) {
+        self.gas_limit = gas_limit;
+    }
+
+    fn with_timeout(&self) -> bool {
+        self.msg_type() == Ok(MessageType::CallMessageWithTimeout)
     }
 
     pub fn data(&self) -> &Bytes {
@@ -83,31 +130,67 @@ impl CSMessageRequest {
         list.push_back(encoder::encode_u8(&e, self.msg_type as u8));
         list.push_back(encoder::encode(&e, self.data.clone()));
         list.push_back(encoder::encode_strings(&e, self.protocols.clone()));
+        // the deadline of a `CallMessageWithTimeout` is appended to the fields of the other
+        // message types, which keep their encoding, followed by the gas limit when there is one
+        if self.with_timeout() {
+            list.push_back(encoder::encode_u64(&e, self.timeout_height));
+            list.push_back(encoder::encode_u64(&e, self.timeout_timestamp));
+        }
+        if self.gas_limit > 0 {
+            list.push_back(encoder::encode_u64(&e, self.gas_limit));
+        }
 
         let encoded = encoder::encode_list(&e, list, false);
         encoded
     }
 
     pub fn decode(e: &Env, bytes: Bytes) -> Result<CSMessageRequest, ContractError> {
-        let decoded = decoder::decode_list(&e, bytes);
-        if decoded.len() != 6 {
+        let decoded = decoder::try_decode_list(&e, bytes)?;
+        if decoded.len() < 4 {
             return Err(ContractError::InvalidRlpLength);
         }
 
-        let from = decoder::decode_string(e, decoded.get(0).unwrap());
-        let to = decoder::decode_string(&e, decoded.get(1).unwrap());
-        let sequence_no = decoder::decode_u128(&e, decoded.get(2).unwrap());
-        let msg_type = decoder::decode_u8(&e, decoded.get(3).unwrap()).into();
+        let msg_type = strict::payload_to_u8(decoded.get(3).unwrap())?;
+        let with_timeout =
+            MessageType::try_from(msg_type) == Ok(MessageType::CallMessageWithTimeout);
+        let fields = if with_timeout { 8 } else { 6 };
+        let gas_limit = if decoded.len() == fields {
+            0
+        } else if decoded.len() == fields + 1 {
+            let gas_limit = strict::payload_to_u64(decoded.get(fields).unwrap())?;
+            // a zero limit is not encoded
+            if gas_limit == 0 {
+                return Err(ContractError::InvalidRlp);
+            }
+            gas_limit
+        } else {
+            return Err(ContractError::InvalidRlpLength);
+        };
+
+        let from = decoder::payload_to_string(&e, decoded.get(0).unwrap());
+        let to = decoder::payload_to_string(&e, decoded.get(1).unwrap());
+        let sequence_no = strict::payload_to_u128(decoded.get(2).unwrap())?;
         let data = decoded.get(4).unwrap();
-        let protocols = decoder::decode_strings(&e, decoded.get(5).unwrap());
+        let protocols = decoder::try_decode_strings(&e, decoded.get(5).unwrap())?;
+        let (timeout_height, timeout_timestamp) = if with_timeout {
+            (
+                strict::payload_to_u64(decoded.get(6).unwrap())?,
+                strict::payload_to_u64(decoded.get(7).unwrap())?,
+            )
+        } else {
+            (0, 0)
+        };
 
         Ok(Self {
             from,
             to,
             sequence_no,
-            msg_type,
+            msg_type: msg_type.into(),
             data,
             protocols,
+            timeout_height,
+            timeout_timestamp,
+            gas_limit,
         })
     }
 }
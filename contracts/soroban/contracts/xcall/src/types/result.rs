@@ -1,4 +1,4 @@
-use soroban_rlp::{decoder, encoder};
+use soroban_rlp::{decoder, encoder, strict};
 use soroban_sdk::{contracttype, Bytes, Env, Vec};
 
 use super::request::CSMessageRequest;
@@ -55,6 +55,11 @@ impl CSMessageResult {
         &self.response_code
     }
 
+    /// The raw message, the data returned by the destination of a `CallMessageWithResponse`.
+    pub fn message_bytes(&self) -> &Bytes {
+        &self.message
+    }
+
     pub fn message(&self, e: &Env) -> Option<CSMessageRequest> {
         if self.message.is_empty() {
             return None;
@@ -75,13 +80,13 @@ impl CSMessageResult {
     }
 
     pub fn decode(e: &Env, bytes: Bytes) -> Result<Self, ContractError> {
-        let decoded = decoder::decode_list(&e, bytes);
+        let decoded = decoder::try_decode_list(&e, bytes)?;
         if decoded.len() != 3 {
             return Err(ContractError::InvalidRlpLength);
         }
 
-        let sequence_no = decoder::decode_u128(&e, decoded.get(0).unwrap());
-        let response_code = decoder::decode_u8(&e, decoded.get(1).unwrap()).into();
+        let sequence_no = strict::payload_to_u128(decoded.get(0).unwrap())?;
+        let response_code = strict::payload_to_u8(decoded.get(1).unwrap())?.into();
         let message = decoded.get(2).unwrap();
 
         Ok(Self {
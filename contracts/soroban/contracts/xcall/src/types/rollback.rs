@@ -9,6 +9,8 @@ pub struct Rollback {
     pub protocols: Vec<String>,
     pub rollback: Bytes,
     pub enabled: bool,
+    pub expects_response: bool,
+    pub response: Option<Bytes>,
 }
 
 impl Rollback {
@@ -25,6 +27,8 @@ impl Rollback {
             protocols,
             rollback,
             enabled,
+            expects_response: false,
+            response: None,
         }
     }
 
@@ -51,4 +55,22 @@ impl Rollback {
     pub fn enable(&mut self) {
         self.enabled = true
     }
+
+    pub fn expects_response(&self) -> bool {
+        self.expects_response
+    }
+
+    pub fn set_expects_response(&mut self) {
+        self.expects_response = true
+    }
+
+    /// The data returned by the destination of a `CallMessageWithResponse`, set once its
+    /// successful result is received.
+    pub fn response(&self) -> Option<Bytes> {
+        self.response.clone()
+    }
+
+    pub fn set_response(&mut self, response: Bytes) {
+        self.response = Some(response)
+    }
 }
@@ -1,15 +1,17 @@
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 #[contracttype]
 pub enum StorageKey {
     Admin,
     Config,
     FeeHandler,
+    FeeSplits,
     ProtocolFee,
     DefaultConnections(String),
     SuccessfulResponses(u128),
     Sn,
     Rollback(u128),
+    ResponseFee(u128),
     ProxyRequest(u128),
     PendingRequests(BytesN<32>),
     PendingResponses(BytesN<32>),
@@ -23,3 +25,19 @@ pub struct Config {
     pub network_id: String,
     pub native_token: Address,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeSplit {
+    pub address: Address,
+    pub basis_points: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponseFeeRecord {
+    pub payer: Address,
+    pub nid: String,
+    pub sources: Vec<String>,
+    pub refundable: bool,
+}
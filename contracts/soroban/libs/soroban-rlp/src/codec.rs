@@ -0,0 +1,293 @@
+//! Typed rlp encoding for the payloads of dapps, laid out as the `Encodable` and `Decodable`
+//! derives of `common::rlp` lay out the same types on CosmWasm: a struct is the list of its
+//! fields in declaration order, a unit enum its integer discriminant and `None` the `0xf800`
+//! null. Decoding is strict, a value having a single valid encoding.
+//!
+//! ```ignore
+//! #[contracttype]
+//! pub struct Transfer {
+//!     pub to: String,
+//!     pub amount: u128,
+//!     pub memo: Option<Bytes>,
+//! }
+//! soroban_rlp::rlp_struct!(Transfer { to, amount, memo });
+//!
+//! let data = transfer.rlp_encode(&env);
+//! let transfer = Transfer::rlp_decode(&env, data)?;
+//! ```
+
+use super::decoder::{decode_header, DecodeError};
+use super::encoder;
+use super::strict;
+use super::utils::{bytes_to_string, string_to_bytes};
+use soroban_sdk::{bytes, Bytes, Env, IntoVal, String, TryFromVal, Val, Vec};
+
+pub trait Encodable {
+    fn rlp_encode(&self, env: &Env) -> Bytes;
+}
+
+pub trait Decodable: Sized {
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError>;
+}
+
+/// Decodes the items of a list one at a time, as the fields of a struct are decoded.
+pub struct ListDecoder {
+    env: Env,
+    items: Vec<Bytes>,
+    next: u32,
+}
+
+impl ListDecoder {
+    pub fn new(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        Ok(ListDecoder {
+            env: env.clone(),
+            items: decode_items(env, bytes)?,
+            next: 0,
+        })
+    }
+
+    pub fn next_item<T: Decodable>(&mut self) -> Result<T, DecodeError> {
+        let item = self
+            .items
+            .get(self.next)
+            .ok_or(DecodeError::InvalidListLength)?;
+        self.next += 1;
+        T::rlp_decode(&self.env, item)
+    }
+
+    /// Fails if items of the list were left undecoded.
+    pub fn finish(self) -> Result<(), DecodeError> {
+        if self.next != self.items.len() {
+            return Err(DecodeError::InvalidListLength);
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a list spanning the whole input into the encodings of its items, prefix included.
+fn decode_items(env: &Env, bytes: Bytes) -> Result<Vec<Bytes>, DecodeError> {
+    strict::validate(&bytes)?;
+    let header = decode_header(&bytes, 0)?;
+    if !header.list || is_null(&bytes) {
+        return Err(DecodeError::ExpectedList);
+    }
+
+    let mut items = Vec::new(env);
+    let mut i = header.offset;
+    while i < header.end() {
+        let item = decode_header(&bytes, i)?;
+        items.push_back(bytes.slice(i..item.end()));
+        i = item.end();
+    }
+    Ok(items)
+}
+
+/// Decodes the payload of a string spanning the whole input.
+fn decode_string_payload(bytes: Bytes) -> Result<Bytes, DecodeError> {
+    let header = decode_header(&bytes, 0)?;
+    if header.list {
+        return Err(DecodeError::InvalidValue);
+    }
+    if header.end() != bytes.len() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    Ok(bytes.slice(header.offset..header.end()))
+}
+
+fn is_null(bytes: &Bytes) -> bool {
+    bytes.len() == 2 && bytes.get(0) == Some(0xf8) && bytes.get(1) == Some(0)
+}
+
+impl Encodable for u8 {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        encoder::encode_u128(env, *self as u128)
+    }
+}
+
+impl Decodable for u8 {
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        strict::decode_u8(env, bytes)
+    }
+}
+
+impl Encodable for u32 {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        encoder::encode_u32(env, *self)
+    }
+}
+
+impl Decodable for u32 {
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        strict::decode_u32(env, bytes)
+    }
+}
+
+impl Encodable for u64 {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        encoder::encode_u64(env, *self)
+    }
+}
+
+impl Decodable for u64 {
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        strict::decode_u64(env, bytes)
+    }
+}
+
+impl Encodable for u128 {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        encoder::encode_u128(env, *self)
+    }
+}
+
+impl Decodable for u128 {
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        strict::decode_u128(env, bytes)
+    }
+}
+
+impl Encodable for bool {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        encoder::encode_bool(env, *self)
+    }
+}
+
+impl Decodable for bool {
+    fn rlp_decode(_env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        if bytes.len() != 1 {
+            return Err(DecodeError::InvalidValue);
+        }
+        match bytes.get(0) {
+            Some(0) => Ok(false),
+            Some(1) => Ok(true),
+            _ => Err(DecodeError::InvalidValue),
+        }
+    }
+}
+
+impl Encodable for Bytes {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        encoder::encode(env, self.clone())
+    }
+}
+
+impl Decodable for Bytes {
+    fn rlp_decode(_env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        decode_string_payload(bytes)
+    }
+}
+
+impl Encodable for String {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        encoder::encode(env, string_to_bytes(env, self.clone()))
+    }
+}
+
+impl Decodable for String {
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        Ok(bytes_to_string(env, decode_string_payload(bytes)?))
+    }
+}
+
+impl<T> Encodable for Vec<T>
+where
+    T: Encodable + IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        let mut list = Vec::new(env);
+        for item in self.iter() {
+            list.push_back(item.rlp_encode(env));
+        }
+        encoder::encode_list(env, list, false)
+    }
+}
+
+impl<T> Decodable for Vec<T>
+where
+    T: Decodable + IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        let mut decoded = Vec::new(env);
+        for item in decode_items(env, bytes)? {
+            decoded.push_back(T::rlp_decode(env, item)?);
+        }
+        Ok(decoded)
+    }
+}
+
+impl<T: Encodable> Encodable for Option<T> {
+    fn rlp_encode(&self, env: &Env) -> Bytes {
+        match self {
+            Some(value) => value.rlp_encode(env),
+            None => bytes!(env, 0xf800),
+        }
+    }
+}
+
+impl<T: Decodable> Decodable for Option<T> {
+    fn rlp_decode(env: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        if is_null(&bytes) {
+            return Ok(None);
+        }
+        T::rlp_decode(env, bytes).map(Some)
+    }
+}
+
+/// Implements `Encodable` and `Decodable` for a struct as the list of the given fields, which
+/// must be all of its fields in declaration order to match the `common::rlp` derive.
+#[macro_export]
+macro_rules! rlp_struct {
+    ($name:ident { $($field:ident),* $(,)? }) => {
+        impl $crate::codec::Encodable for $name {
+            fn rlp_encode(&self, env: &$crate::soroban_sdk::Env) -> $crate::soroban_sdk::Bytes {
+                #[allow(unused_mut)]
+                let mut list = $crate::soroban_sdk::Vec::new(env);
+                $(list.push_back($crate::codec::Encodable::rlp_encode(&self.$field, env));)*
+                $crate::encoder::encode_list(env, list, false)
+            }
+        }
+
+        impl $crate::codec::Decodable for $name {
+            fn rlp_decode(
+                env: &$crate::soroban_sdk::Env,
+                bytes: $crate::soroban_sdk::Bytes,
+            ) -> Result<Self, $crate::decoder::DecodeError> {
+                #[allow(unused_mut)]
+                let mut list = $crate::codec::ListDecoder::new(env, bytes)?;
+                let value = $name {
+                    $($field: list.next_item()?,)*
+                };
+                list.finish()?;
+                Ok(value)
+            }
+        }
+    };
+}
+
+/// Implements `Encodable` and `Decodable` for a unit enum as the integer value of its variants.
+#[macro_export]
+macro_rules! rlp_enum {
+    ($name:ident { $($variant:ident = $value:expr),* $(,)? }) => {
+        impl $crate::codec::Encodable for $name {
+            fn rlp_encode(&self, env: &$crate::soroban_sdk::Env) -> $crate::soroban_sdk::Bytes {
+                let value: u32 = match self {
+                    $($name::$variant => $value,)*
+                };
+                $crate::codec::Encodable::rlp_encode(&value, env)
+            }
+        }
+
+        impl $crate::codec::Decodable for $name {
+            fn rlp_decode(
+                env: &$crate::soroban_sdk::Env,
+                bytes: $crate::soroban_sdk::Bytes,
+            ) -> Result<Self, $crate::decoder::DecodeError> {
+                let value = <u32 as $crate::codec::Decodable>::rlp_decode(env, bytes)?;
+                $(if value == $value {
+                    return Ok($name::$variant);
+                })*
+                Err($crate::decoder::DecodeError::InvalidValue)
+            }
+        }
+    };
+}
@@ -1,105 +1,235 @@
 use super::utils::*;
 use soroban_sdk::{vec, Bytes, Env, String, Vec};
 
-pub fn decode(env: &Env, bytes: Bytes) -> Bytes {
-    assert!(bytes.len() > 0);
-
-    let rlp_byte = bytes.get(0).unwrap();
-
-    #[allow(unused_comparisons)]
-    let decoded = if rlp_byte == 0x80 || rlp_byte == 0xc0 {
-        Bytes::new(&env)
-    } else if rlp_byte < 0x80 {
-        bytes
-    } else if rlp_byte < 0xb8 {
-        let data_len = rlp_byte - 0x80;
-        slice_vector(&env, bytes, 1, data_len as u64)
-    } else if rlp_byte > 0xb7 && rlp_byte < 0xc0 {
-        let data_bytes_len = rlp_byte - 0xb7;
-        let len_bytes = slice_vector(&env, bytes.clone(), 1, data_bytes_len as u64);
-
-        let data_len = bytes_to_u64(len_bytes.clone());
-        let data_start = len_bytes.len() + 1;
-
-        slice_vector(&env, bytes, data_start as u64, data_len)
-    } else if rlp_byte > 0xc0 && rlp_byte <= 0xf7 {
-        let data_len = rlp_byte - 0xc0;
-        slice_vector(&env, bytes, 1, data_len as u64)
-    } else if rlp_byte > 0xf7 && rlp_byte <= 0xff {
-        let data_bytes_len = rlp_byte - 0xf7;
-        let len_bytes = slice_vector(&env, bytes.clone(), 1, data_bytes_len as u64);
-
-        let data_len = bytes_to_u64(len_bytes.clone());
-        let data_start = len_bytes.len() + 1;
-
-        slice_vector(&env, bytes, data_start as u64, data_len)
+/// Reasons an rlp payload can be rejected by the `try_decode*` functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input ends before the item announced by its prefix.
+    Truncated,
+    /// A length is not encoded in its shortest form.
+    NonCanonicalLength,
+    /// The input continues after the decoded item.
+    TrailingBytes,
+    /// A list was expected but a string was found.
+    ExpectedList,
+    /// The decoded integer does not fit in the requested type.
+    Overflow,
+    /// An integer is not encoded in its minimal two's complement form.
+    NonCanonicalInteger,
+    /// A list does not have the number of items of the decoded type.
+    InvalidListLength,
+    /// The decoded value is not one of the values of the decoded type.
+    InvalidValue,
+}
+
+pub(crate) struct Header {
+    pub list: bool,
+    pub offset: u32,
+    pub len: u32,
+}
+
+impl Header {
+    pub fn end(&self) -> u32 {
+        self.offset + self.len
+    }
+}
+
+pub(crate) fn decode_header(bytes: &Bytes, start: u32) -> Result<Header, DecodeError> {
+    let prefix = bytes.get(start).ok_or(DecodeError::Truncated)?;
+
+    let header = if prefix < 0x80 {
+        Header {
+            list: false,
+            offset: start,
+            len: 1,
+        }
+    } else if prefix < 0xb8 {
+        let len = (prefix - 0x80) as u32;
+        if len == 1 && bytes.get(start + 1).map_or(false, |byte| byte < 0x80) {
+            return Err(DecodeError::NonCanonicalLength);
+        }
+        Header {
+            list: false,
+            offset: start + 1,
+            len,
+        }
+    } else if prefix < 0xc0 {
+        let len_bytes = (prefix - 0xb7) as u32;
+        Header {
+            list: false,
+            offset: start + 1 + len_bytes,
+            len: decode_long_length(bytes, start + 1, len_bytes)?,
+        }
+    } else if prefix < 0xf8 {
+        Header {
+            list: true,
+            offset: start + 1,
+            len: (prefix - 0xc0) as u32,
+        }
+    } else if prefix == 0xf8 && bytes.get(start + 1) == Some(0) {
+        // 0xf800 is how the other xcall implementations encode null
+        Header {
+            list: true,
+            offset: start + 2,
+            len: 0,
+        }
     } else {
-        panic!("invalid rlp byte length")
+        let len_bytes = (prefix - 0xf7) as u32;
+        Header {
+            list: true,
+            offset: start + 1 + len_bytes,
+            len: decode_long_length(bytes, start + 1, len_bytes)?,
+        }
     };
 
-    decoded
+    if header.offset as u64 + header.len as u64 > bytes.len() as u64 {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(header)
 }
 
-pub fn decode_list(env: &Env, list: Bytes) -> Vec<Bytes> {
-    let data_len = decode_length(&env, list.clone(), 0xc0);
-    let start = list.len() as u64 - data_len;
-    let encoded = slice_vector(&env, list, start, data_len);
+fn decode_long_length(bytes: &Bytes, start: u32, len_bytes: u32) -> Result<u32, DecodeError> {
+    if start + len_bytes > bytes.len() || len_bytes > 4 {
+        return Err(DecodeError::Truncated);
+    }
+    if bytes.get(start) == Some(0) {
+        return Err(DecodeError::NonCanonicalLength);
+    }
+
+    let len = bytes_to_u32(bytes.slice(start..start + len_bytes));
+    if len < 56 {
+        return Err(DecodeError::NonCanonicalLength);
+    }
+    Ok(len)
+}
+
+/// Decodes the rlp item at the start of `bytes`, rejecting any bytes following it when `strict`.
+fn decode_item(bytes: &Bytes, strict: bool) -> Result<Bytes, DecodeError> {
+    let header = decode_header(bytes, 0)?;
+    if strict && header.end() != bytes.len() {
+        return Err(DecodeError::TrailingBytes);
+    }
+
+    Ok(bytes.slice(header.offset..header.end()))
+}
+
+fn decode_items(env: &Env, list: &Bytes, strict: bool) -> Result<Vec<Bytes>, DecodeError> {
+    let header = decode_header(list, 0)?;
+    if !header.list {
+        return Err(DecodeError::ExpectedList);
+    }
+    if strict && header.end() != list.len() {
+        return Err(DecodeError::TrailingBytes);
+    }
 
     let mut decoded: Vec<Bytes> = Vec::new(&env);
-    let mut i = 0;
-    while i < encoded.len() {
-        let byte = encoded.get(i).unwrap();
-
-        #[allow(unused_comparisons)]
-        if byte == 0x80 || byte == 0xc0 {
-            decoded.push_back(Bytes::new(&env));
-            i = i + 1;
-        } else if byte < 0x80 {
-            let mut singleton = Bytes::new(&env);
-            singleton.push_back(byte);
-            decoded.push_back(singleton);
-            i = i + 1;
-        } else if byte > 0x80 && byte < 0xB8 {
-            let len = (byte - 0x80) as u64;
-            decoded.push_back(slice_vector(&env, encoded.clone(), i as u64 + 1, len));
-            i = i + (len as u32 + 1);
-        } else if byte > 0xc0 && byte < 0xf7 {
-            let len = (byte - 0xc0) as u64;
-            decoded.push_back(slice_vector(&env, encoded.clone(), i as u64, len + 1));
-            i = i + (len as u32 + 1)
-        } else if byte > 0xb7 && byte < 0xc0 {
-            let data_bytes_len = (byte - 0xb7) as u64;
-            let len_bytes = slice_vector(&env, encoded.clone(), i as u64 + 1, data_bytes_len);
-            let len = bytes_to_u64(len_bytes);
-            decoded.push_back(slice_vector(
-                &env,
-                encoded.clone(),
-                i as u64 + data_bytes_len + 1,
-                len,
-            ));
-            i = i + (data_bytes_len + len + 1) as u32
-        } else if byte > 0xf7 && byte <= 0xff {
-            let data_bytes_len = (byte - 0xf7) as u64;
-            let len_bytes = slice_vector(&env, encoded.clone(), i as u64 + 1, data_bytes_len);
-            let len = bytes_to_u64(len_bytes);
-            if byte == 0xf8 && len == 0 {
-                decoded.push_back(Bytes::new(&env));
-            } else {
-                decoded.push_back(slice_vector(
-                    &env,
-                    encoded.clone(),
-                    i as u64,
-                    data_bytes_len + len + 1,
-                ));
-            }
-            i = i + (data_bytes_len + len + 1) as u32
+    let mut i = header.offset;
+    while i < header.end() {
+        let item = decode_header(&list, i)?;
+        if item.end() > header.end() {
+            return Err(DecodeError::Truncated);
+        }
+
+        if item.list && item.len > 0 {
+            decoded.push_back(list.slice(i..item.end()));
         } else {
-            panic!("invalid rlp byte length")
+            decoded.push_back(list.slice(item.offset..item.end()));
         }
+        i = item.end();
+    }
+    Ok(decoded)
+}
+
+fn decode_uint(env: &Env, bytes: &Bytes, size: u32, strict: bool) -> Result<u128, DecodeError> {
+    // signed encoders prefix integers with a zero byte, only the significant bytes must fit
+    let decoded = truncate_zeros(&env, decode_item(bytes, strict)?);
+    if decoded.len() > size {
+        return Err(DecodeError::Overflow);
+    }
+
+    Ok(bytes_to_u128(decoded))
+}
+
+fn decode_string_list(env: &Env, bytes: &Bytes, strict: bool) -> Result<Vec<String>, DecodeError> {
+    let mut strings: Vec<String> = vec![&env];
+    if bytes.is_empty() {
+        return Ok(strings);
+    }
+
+    for item in decode_items(&env, bytes, strict)? {
+        strings.push_back(bytes_to_string(&env, item))
     }
-    decoded
+
+    Ok(strings)
+}
+
+/// Decodes a single rlp item spanning the whole input and returns its payload, the payload of a
+/// list being returned without its prefix.
+pub fn try_decode(_env: &Env, bytes: Bytes) -> Result<Bytes, DecodeError> {
+    decode_item(&bytes, true)
+}
+
+/// Decodes an rlp list spanning the whole input. String items are returned decoded while nested
+/// lists keep their prefix so they can be decoded in turn, empty lists being returned as empty
+/// bytes.
+pub fn try_decode_list(env: &Env, list: Bytes) -> Result<Vec<Bytes>, DecodeError> {
+    decode_items(&env, &list, true)
+}
+
+fn try_decode_uint(env: &Env, bytes: Bytes, size: u32) -> Result<u128, DecodeError> {
+    decode_uint(&env, &bytes, size, true)
+}
+
+pub fn try_decode_u8(env: &Env, bytes: Bytes) -> Result<u8, DecodeError> {
+    try_decode_uint(&env, bytes, 1).map(|num| num as u8)
+}
+
+pub fn try_decode_u32(env: &Env, bytes: Bytes) -> Result<u32, DecodeError> {
+    try_decode_uint(&env, bytes, 4).map(|num| num as u32)
+}
+
+pub fn try_decode_u64(env: &Env, bytes: Bytes) -> Result<u64, DecodeError> {
+    try_decode_uint(&env, bytes, 8).map(|num| num as u64)
 }
 
+pub fn try_decode_u128(env: &Env, bytes: Bytes) -> Result<u128, DecodeError> {
+    try_decode_uint(&env, bytes, 16)
+}
+
+pub fn try_decode_string(env: &Env, bytes: Bytes) -> Result<String, DecodeError> {
+    let decoded = try_decode(&env, bytes)?;
+    Ok(bytes_to_string(&env, decoded))
+}
+
+/// Converts the payload of a string, as `try_decode_list` returns its items.
+pub fn payload_to_string(env: &Env, payload: Bytes) -> String {
+    bytes_to_string(&env, payload)
+}
+
+/// Decodes a list of strings, accepting the empty bytes `try_decode_list` returns for an empty
+/// list.
+pub fn try_decode_strings(env: &Env, bytes: Bytes) -> Result<Vec<String>, DecodeError> {
+    decode_string_list(&env, &bytes, true)
+}
+
+/// Decodes the rlp item at the start of `bytes` and returns its payload, ignoring any bytes
+/// following it. Panics on malformed input, `try_decode` rejects trailing bytes instead.
+///
+/// The panicking decoders are kept for bytes the caller encoded itself. Bytes coming from a
+/// relayer or another chain are decoded with the `try_*` functions, so that malformed or padded
+/// messages are rejected with an error rather than aborting the call or being partly read.
+pub fn decode(_env: &Env, bytes: Bytes) -> Bytes {
+    decode_item(&bytes, false).unwrap()
+}
+
+/// Decodes the rlp list at the start of `list`, ignoring any bytes following it. Panics on
+/// malformed input, `try_decode_list` rejects trailing bytes instead.
+pub fn decode_list(env: &Env, list: Bytes) -> Vec<Bytes> {
+    decode_items(&env, &list, false).unwrap()
+}
+
+/// Reads the length of the rlp prefix at the start of `bytes`. Panics on truncated input, it
+/// trusts the prefix and is only meant for bytes the caller encoded.
 pub fn decode_length(env: &Env, bytes: Bytes, offset: u8) -> u64 {
     let bytes_len = bytes.len();
 
@@ -116,6 +246,7 @@ pub fn decode_length(env: &Env, bytes: Bytes, offset: u8) -> u64 {
     len
 }
 
+/// Returns whether the payload `bytes` is `1`, any other payload being read as `false`.
 pub fn decode_bool(bytes: Bytes) -> bool {
     if bytes.len() > 0 && bytes.get(0).unwrap() == 1 {
         return true;
@@ -124,40 +255,33 @@ pub fn decode_bool(bytes: Bytes) -> bool {
     false
 }
 
+/// Decodes the integer at the start of `bytes`, ignoring any bytes following it. Panics on
+/// malformed input or when it does not fit, `try_decode_u8` rejects trailing bytes instead. The
+/// same goes for `decode_u32`, `decode_u64` and `decode_u128`.
 pub fn decode_u8(env: &Env, bytes: Bytes) -> u8 {
-    decode(&env, bytes).get(0).unwrap_or(0)
+    decode_uint(&env, &bytes, 1, false).unwrap() as u8
 }
 
 pub fn decode_u32(env: &Env, bytes: Bytes) -> u32 {
-    let decoded = decode(&env, bytes);
-    bytes_to_u32(decoded)
+    decode_uint(&env, &bytes, 4, false).unwrap() as u32
 }
 
 pub fn decode_u64(env: &Env, bytes: Bytes) -> u64 {
-    let decoded = decode(&env, bytes);
-    bytes_to_u64(decoded)
+    decode_uint(&env, &bytes, 8, false).unwrap() as u64
 }
 
 pub fn decode_u128(env: &Env, bytes: Bytes) -> u128 {
-    if bytes.len() == 1 {
-        return bytes_to_u128(bytes);
-    }
-    let decoded = decode(&env, bytes);
-    bytes_to_u128(decoded)
+    decode_uint(&env, &bytes, 16, false).unwrap()
 }
 
+/// Decodes the string at the start of `bytes`, ignoring any bytes following it. Panics on
+/// malformed input, `try_decode_string` rejects trailing bytes instead.
 pub fn decode_string(env: &Env, bytes: Bytes) -> String {
-    let decoded = decode(&env, bytes);
-    bytes_to_string(&env, decoded)
+    bytes_to_string(&env, decode(&env, bytes))
 }
 
+/// Decodes the list of strings at the start of `bytes`, ignoring any bytes following it. Panics on
+/// malformed input, `try_decode_strings` rejects trailing bytes instead.
 pub fn decode_strings(env: &Env, bytes: Bytes) -> Vec<String> {
-    let list = decode_list(&env, bytes);
-
-    let mut strings: Vec<String> = vec![&env];
-    for byte in list {
-        strings.push_back(bytes_to_string(&env, byte))
-    }
-
-    strings
+    decode_string_list(&env, &bytes, false).unwrap()
 }
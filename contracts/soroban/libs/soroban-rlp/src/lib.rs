@@ -1,8 +1,13 @@
 #![no_std]
 
+pub mod codec;
 pub mod decoder;
 pub mod encoder;
+pub mod strict;
 mod utils;
 
+#[doc(hidden)]
+pub use soroban_sdk;
+
 #[cfg(test)]
 mod test;
@@ -0,0 +1,81 @@
+//! Strict decoding, accepting only the canonical encoding of a value so that a message has a
+//! single valid byte representation and a single hash.
+
+use super::decoder::{decode_header, try_decode, DecodeError};
+use super::utils::bytes_to_u128;
+use soroban_sdk::{Bytes, Env};
+
+/// Checks that `bytes` holds a single item in canonical form, the items of nested lists included.
+pub fn validate(bytes: &Bytes) -> Result<(), DecodeError> {
+    let end = validate_item(bytes, 0, bytes.len())?;
+    if end != bytes.len() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    Ok(())
+}
+
+fn validate_item(bytes: &Bytes, start: u32, limit: u32) -> Result<u32, DecodeError> {
+    let header = decode_header(bytes, start)?;
+    if header.end() > limit {
+        return Err(DecodeError::Truncated);
+    }
+
+    if header.list {
+        let mut next = header.offset;
+        while next < header.end() {
+            next = validate_item(bytes, next, header.end())?;
+        }
+    }
+    Ok(header.end())
+}
+
+/// Decodes an unsigned integer encoded as its minimal two's complement big endian bytes, zero
+/// being a single zero byte.
+pub fn decode_u128(env: &Env, bytes: Bytes) -> Result<u128, DecodeError> {
+    if bytes.get(0).map_or(false, |prefix| prefix >= 0xc0) {
+        return Err(DecodeError::NonCanonicalInteger);
+    }
+
+    payload_to_u128(try_decode(&env, bytes)?)
+}
+
+pub fn decode_u8(env: &Env, bytes: Bytes) -> Result<u8, DecodeError> {
+    u8::try_from(decode_u128(&env, bytes)?).map_err(|_| DecodeError::Overflow)
+}
+
+pub fn decode_u32(env: &Env, bytes: Bytes) -> Result<u32, DecodeError> {
+    u32::try_from(decode_u128(&env, bytes)?).map_err(|_| DecodeError::Overflow)
+}
+
+pub fn decode_u64(env: &Env, bytes: Bytes) -> Result<u64, DecodeError> {
+    u64::try_from(decode_u128(&env, bytes)?).map_err(|_| DecodeError::Overflow)
+}
+
+/// Same as `decode_u128` for the payload of an integer, as `try_decode_list` returns its items.
+pub fn payload_to_u128(payload: Bytes) -> Result<u128, DecodeError> {
+    let mut payload = payload;
+    match (payload.get(0), payload.get(1)) {
+        (None, _) => return Err(DecodeError::NonCanonicalInteger),
+        (Some(first), _) if first >= 0x80 => return Err(DecodeError::NonCanonicalInteger),
+        (Some(0), Some(next)) if next < 0x80 => return Err(DecodeError::NonCanonicalInteger),
+        (Some(0), Some(_)) => payload = payload.slice(1..),
+        _ => {}
+    }
+
+    if payload.len() > 16 {
+        return Err(DecodeError::Overflow);
+    }
+    Ok(bytes_to_u128(payload))
+}
+
+pub fn payload_to_u8(payload: Bytes) -> Result<u8, DecodeError> {
+    u8::try_from(payload_to_u128(payload)?).map_err(|_| DecodeError::Overflow)
+}
+
+pub fn payload_to_u32(payload: Bytes) -> Result<u32, DecodeError> {
+    u32::try_from(payload_to_u128(payload)?).map_err(|_| DecodeError::Overflow)
+}
+
+pub fn payload_to_u64(payload: Bytes) -> Result<u64, DecodeError> {
+    u64::try_from(payload_to_u128(payload)?).map_err(|_| DecodeError::Overflow)
+}
@@ -1,3 +1,5 @@
+mod codec;
 mod decoder;
 mod encoder;
+mod strict;
 mod utils;
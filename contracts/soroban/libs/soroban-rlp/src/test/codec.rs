@@ -0,0 +1,195 @@
+use soroban_sdk::{bytes, contracttype, vec, Bytes, Env, String, Vec};
+
+use crate::codec::{Decodable, Encodable};
+use crate::decoder::DecodeError;
+use crate::encoder;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Kind {
+    Transfer = 0,
+    Burn = 1,
+}
+crate::rlp_enum!(Kind {
+    Transfer = 0,
+    Burn = 1
+});
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transfer {
+    pub to: String,
+    pub amount: u128,
+    pub memo: Option<Bytes>,
+}
+crate::rlp_struct!(Transfer { to, amount, memo });
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Batch {
+    pub kind: Kind,
+    pub transfers: Vec<Transfer>,
+    pub deadline: u64,
+    pub settled: bool,
+}
+crate::rlp_struct!(Batch {
+    kind,
+    transfers,
+    deadline,
+    settled
+});
+
+fn transfer(env: &Env, to: &str, amount: u128, memo: Option<Bytes>) -> Transfer {
+    Transfer {
+        to: String::from_str(env, to),
+        amount,
+        memo,
+    }
+}
+
+#[test]
+fn test_encode_struct_as_list_of_fields() {
+    let env = Env::default();
+
+    let value = transfer(&env, "ab", 128, None);
+    assert_eq!(value.rlp_encode(&env), bytes!(&env, 0xc8826162820080f800));
+
+    let value = transfer(&env, "hx01", 1000, Some(bytes!(&env, 0x010203)));
+    let expected = encoder::encode_list(
+        &env,
+        vec![
+            &env,
+            encoder::encode_string(&env, value.to.clone()),
+            encoder::encode_u128(&env, value.amount),
+            encoder::encode(&env, bytes!(&env, 0x010203)),
+        ],
+        false,
+    );
+    assert_eq!(value.rlp_encode(&env), expected);
+}
+
+#[test]
+fn test_struct_roundtrip() {
+    let env = Env::default();
+
+    let value = Batch {
+        kind: Kind::Burn,
+        transfers: vec![
+            &env,
+            transfer(&env, "0x1.icon/hx01", 0, None),
+            transfer(&env, "0x1.icon/hx02", u128::MAX, Some(Bytes::new(&env))),
+        ],
+        deadline: 1_700_000_000,
+        settled: true,
+    };
+    let encoded = value.rlp_encode(&env);
+    assert_eq!(Batch::rlp_decode(&env, encoded), Ok(value));
+
+    let value = Batch {
+        kind: Kind::Transfer,
+        transfers: Vec::new(&env),
+        deadline: 0,
+        settled: false,
+    };
+    let encoded = value.rlp_encode(&env);
+    assert_eq!(encoded, bytes!(&env, 0xc400c00000));
+    assert_eq!(Batch::rlp_decode(&env, encoded), Ok(value));
+}
+
+#[test]
+fn test_decode_struct_with_wrong_number_of_fields() {
+    let env = Env::default();
+
+    let missing = bytes!(&env, 0xc6826162820080);
+    assert_eq!(
+        Transfer::rlp_decode(&env, missing),
+        Err(DecodeError::InvalidListLength)
+    );
+
+    let extra = bytes!(&env, 0xc9826162820080f80001);
+    assert_eq!(
+        Transfer::rlp_decode(&env, extra),
+        Err(DecodeError::InvalidListLength)
+    );
+
+    let not_a_list = bytes!(&env, 0x826162);
+    assert_eq!(
+        Transfer::rlp_decode(&env, not_a_list),
+        Err(DecodeError::ExpectedList)
+    );
+
+    let trailing = bytes!(&env, 0xc8826162820080f80000);
+    assert_eq!(
+        Transfer::rlp_decode(&env, trailing),
+        Err(DecodeError::TrailingBytes)
+    );
+}
+
+#[test]
+fn test_decode_struct_with_invalid_field() {
+    let env = Env::default();
+
+    // the amount is not a canonical integer
+    let value = bytes!(&env, 0xc982616283000080f800);
+    assert_eq!(
+        Transfer::rlp_decode(&env, value),
+        Err(DecodeError::NonCanonicalInteger)
+    );
+
+    // the recipient is a list
+    let value = bytes!(&env, 0xc6c0820080f800);
+    assert_eq!(
+        Transfer::rlp_decode(&env, value),
+        Err(DecodeError::InvalidValue)
+    );
+}
+
+#[test]
+fn test_enum_as_discriminant() {
+    let env = Env::default();
+
+    assert_eq!(Kind::Transfer.rlp_encode(&env), bytes!(&env, 0x00));
+    assert_eq!(Kind::Burn.rlp_encode(&env), bytes!(&env, 0x01));
+    assert_eq!(Kind::rlp_decode(&env, bytes!(&env, 0x01)), Ok(Kind::Burn));
+    assert_eq!(
+        Kind::rlp_decode(&env, bytes!(&env, 0x02)),
+        Err(DecodeError::InvalidValue)
+    );
+}
+
+#[test]
+fn test_option_null_is_not_an_empty_list() {
+    let env = Env::default();
+
+    let none: Option<Vec<u32>> = None;
+    assert_eq!(none.rlp_encode(&env), bytes!(&env, 0xf800));
+    assert_eq!(
+        Option::<Vec<u32>>::rlp_decode(&env, bytes!(&env, 0xf800)),
+        Ok(None)
+    );
+    assert_eq!(
+        Option::<Vec<u32>>::rlp_decode(&env, bytes!(&env, 0xc0)),
+        Ok(Some(Vec::new(&env)))
+    );
+    assert_eq!(
+        Vec::<u32>::rlp_decode(&env, bytes!(&env, 0xf800)),
+        Err(DecodeError::ExpectedList)
+    );
+}
+
+#[test]
+fn test_decode_bool() {
+    let env = Env::default();
+
+    assert_eq!(bool::rlp_decode(&env, bytes!(&env, 0x00)), Ok(false));
+    assert_eq!(bool::rlp_decode(&env, bytes!(&env, 0x01)), Ok(true));
+    assert_eq!(
+        bool::rlp_decode(&env, bytes!(&env, 0x02)),
+        Err(DecodeError::InvalidValue)
+    );
+    assert_eq!(
+        bool::rlp_decode(&env, bytes!(&env, 0x80)),
+        Err(DecodeError::InvalidValue)
+    );
+}
@@ -102,3 +102,87 @@ fn test_decode_list() {
 
     assert_eq!(list, decoded)
 }
+
+#[test]
+fn test_try_decode_errors() {
+    let env = Env::default();
+
+    let truncated = bytes!(&env, 0x833548);
+    assert_eq!(try_decode(&env, truncated), Err(DecodeError::Truncated));
+
+    let trailing = bytes!(&env, 0x820DA3F1);
+    assert_eq!(try_decode(&env, trailing), Err(DecodeError::TrailingBytes));
+
+    let single_byte = bytes!(&env, 0x8105);
+    assert_eq!(
+        try_decode(&env, single_byte),
+        Err(DecodeError::NonCanonicalLength)
+    );
+
+    let short_long_form = bytes!(&env, 0xB8020DA3);
+    assert_eq!(
+        try_decode(&env, short_long_form),
+        Err(DecodeError::NonCanonicalLength)
+    );
+
+    let overflow = bytes!(&env, 0x830DA3F1);
+    assert_eq!(try_decode_u8(&env, overflow), Err(DecodeError::Overflow));
+
+    let string = encoder::encode_string(&env, String::from_str(&env, "soroban-rlp"));
+    assert_eq!(
+        try_decode_list(&env, string),
+        Err(DecodeError::ExpectedList)
+    );
+}
+
+#[test]
+fn test_decode_ignores_trailing_bytes() {
+    let env = Env::default();
+
+    let trailing = bytes!(&env, 0x820DA3F1);
+    assert_eq!(decode(&env, trailing.clone()), bytes!(&env, 0x0DA3));
+    assert_eq!(decode_u32(&env, trailing.clone()), 0x0DA3);
+    assert_eq!(
+        try_decode_u32(&env, trailing),
+        Err(DecodeError::TrailingBytes)
+    );
+
+    let list = bytes!(&env, 0xC20A0BF1);
+    assert_eq!(
+        decode_list(&env, list.clone()),
+        vec![&env, bytes!(&env, 0x0A), bytes!(&env, 0x0B)]
+    );
+    assert_eq!(try_decode_list(&env, list), Err(DecodeError::TrailingBytes));
+}
+
+#[test]
+fn test_try_decode_list_truncated_item() {
+    let env = Env::default();
+
+    let list = bytes!(&env, 0xC3830DA3);
+    assert_eq!(try_decode_list(&env, list), Err(DecodeError::Truncated));
+}
+
+#[test]
+fn test_decode_list_with_null() {
+    let env = Env::default();
+
+    let list = bytes!(&env, 0xC30AF800);
+    let decoded = decode_list(&env, list);
+    assert_eq!(decoded, vec![&env, bytes!(&env, 0x0A), Bytes::new(&env)]);
+}
+
+#[test]
+fn test_try_decode_strings_of_empty_list_item() {
+    let env = Env::default();
+
+    let mut list = Vec::new(&env);
+    list.push_back(encoder::encode_strings(&env, vec![&env]));
+    let encoded = encoder::encode_list(&env, list, false);
+
+    let items = try_decode_list(&env, encoded).unwrap();
+    assert_eq!(
+        try_decode_strings(&env, items.get(0).unwrap()),
+        Ok(vec![&env])
+    );
+}
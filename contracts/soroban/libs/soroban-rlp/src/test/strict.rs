@@ -0,0 +1,85 @@
+use soroban_sdk::{bytes, Bytes, Env};
+
+use crate::decoder::DecodeError;
+use crate::encoder;
+use crate::strict::*;
+
+const VECTORS: &str = include_str!("../../../../../../test-vectors/rlp/canonical.txt");
+
+fn from_hex(env: &Env, hex: &str) -> Bytes {
+    let mut bytes = Bytes::new(&env);
+    for i in (0..hex.len()).step_by(2) {
+        bytes.push_back(u8::from_str_radix(&hex[i..i + 2], 16).unwrap());
+    }
+    bytes
+}
+
+#[test]
+fn test_canonical_vectors() {
+    let env = Env::default();
+
+    for line in VECTORS.lines() {
+        let line = line.split('#').next().unwrap();
+        let mut fields = line.split_whitespace();
+        let (verdict, kind, hex) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(verdict), Some(kind), Some(hex)) => (verdict, kind, hex),
+            _ => continue,
+        };
+        let bytes = from_hex(&env, hex);
+
+        match (verdict, kind) {
+            ("valid", "item") => assert_eq!(validate(&bytes), Ok(()), "{}", hex),
+            ("invalid", "item") => assert!(validate(&bytes).is_err(), "{}", hex),
+            ("valid", "uint") => {
+                let value: u128 = fields.next().unwrap().parse().unwrap();
+                assert_eq!(decode_u128(&env, bytes), Ok(value), "{}", hex)
+            }
+            ("invalid", "uint") => assert!(decode_u128(&env, bytes).is_err(), "{}", hex),
+            _ => panic!("unknown vector {}", line),
+        }
+    }
+}
+
+#[test]
+fn test_strict_decode_encoded_integers() {
+    let env = Env::default();
+
+    for num in [0, 1, 127, 128, 255, 256, u32::MAX as u128, u128::MAX] {
+        let encoded = encoder::encode_u128(&env, num);
+        assert_eq!(decode_u128(&env, encoded), Ok(num));
+    }
+}
+
+#[test]
+fn test_strict_decode_overflow() {
+    let env = Env::default();
+
+    let bytes = bytes!(&env, 0x820100);
+    assert_eq!(decode_u8(&env, bytes), Err(DecodeError::Overflow));
+}
+
+#[test]
+fn test_strict_payload_of_list_items() {
+    let env = Env::default();
+
+    let nums = [0, 1, 127, 128, 255, 256, u32::MAX as u128, u128::MAX];
+    let mut list = soroban_sdk::Vec::new(&env);
+    for num in nums {
+        list.push_back(encoder::encode_u128(&env, num));
+    }
+    let encoded = encoder::encode_list(&env, list, false);
+
+    let items = crate::decoder::try_decode_list(&env, encoded).unwrap();
+    for (item, num) in items.iter().zip(nums) {
+        assert_eq!(payload_to_u128(item), Ok(num));
+    }
+
+    assert_eq!(
+        payload_to_u128(bytes!(&env, 0x0001)),
+        Err(DecodeError::NonCanonicalInteger)
+    );
+    assert_eq!(
+        payload_to_u8(bytes!(&env, 0x0100)),
+        Err(DecodeError::Overflow)
+    );
+}
@@ -27,7 +27,8 @@ pub fn to_signed_bytes(env: &Env, bytes: Bytes, signed: bool) -> Bytes {
 
     let first_byte = truncated.get(0).unwrap_or(0);
 
-    if first_byte >= 128 {
+    // zero is a single zero byte, as in the other xcall implementations
+    if truncated.is_empty() || first_byte >= 128 {
         let mut prefix = bytes!(&env, 0x00);
         prefix.append(&truncated);
         prefix
@@ -0,0 +1,330 @@
+use soroban_sdk::{Bytes, Env, String};
+
+use crate::network_address::{
+    NetworkAddress, NetworkAddressError, MAX_ACCOUNT_LENGTH, MAX_NID_LENGTH,
+};
+
+/// The longest CAIP-10 account id: an 8 byte namespace, a 32 byte reference and an account.
+const MAX_CAIP10_LENGTH: u32 = 8 + 1 + 32 + 1 + MAX_ACCOUNT_LENGTH;
+const CAIP_SEPARATOR: u8 = b':';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaipError {
+    InvalidCaip10,
+    UnknownChain,
+    UnknownNetwork,
+    InvalidAccount,
+    InvalidNetworkAddress,
+}
+
+impl From<NetworkAddressError> for CaipError {
+    fn from(_: NetworkAddressError) -> Self {
+        CaipError::InvalidNetworkAddress
+    }
+}
+
+/// How the accounts of a network are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFamily {
+    /// `0x` followed by 20 hex encoded bytes.
+    Hex,
+    /// `hx` for wallets or `cx` for contracts followed by 20 hex encoded bytes.
+    Icon,
+    /// Bech32 with the human readable part of the network.
+    Bech32(&'static str),
+    /// A base58 encoded 32 byte public key.
+    Base58,
+    /// A Stellar account or contract strkey.
+    Strkey,
+}
+
+/// A network known by its xcall network id and its CAIP-2 chain id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Network {
+    pub nid: &'static str,
+    pub caip2: &'static str,
+    pub family: AddressFamily,
+}
+
+pub const NETWORKS: &[Network] = &[
+    Network {
+        nid: "0x1.icon",
+        caip2: "icon:0x1",
+        family: AddressFamily::Icon,
+    },
+    Network {
+        nid: "0x2.icon",
+        caip2: "icon:0x2",
+        family: AddressFamily::Icon,
+    },
+    Network {
+        nid: "0x1.eth",
+        caip2: "eip155:1",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0xa4b1.arbitrum",
+        caip2: "eip155:42161",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0xa.optimism",
+        caip2: "eip155:10",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0x2105.base",
+        caip2: "eip155:8453",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0x38.bsc",
+        caip2: "eip155:56",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "0xa86a.avax",
+        caip2: "eip155:43114",
+        family: AddressFamily::Hex,
+    },
+    Network {
+        nid: "archway-1",
+        caip2: "cosmos:archway-1",
+        family: AddressFamily::Bech32("archway"),
+    },
+    Network {
+        nid: "injective-1",
+        caip2: "cosmos:injective-1",
+        family: AddressFamily::Bech32("inj"),
+    },
+    Network {
+        nid: "solana",
+        caip2: "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp",
+        family: AddressFamily::Base58,
+    },
+    Network {
+        nid: "stellar",
+        caip2: "stellar:pubnet",
+        family: AddressFamily::Strkey,
+    },
+];
+
+impl Network {
+    pub fn by_nid(nid: &[u8]) -> Result<&'static Network, CaipError> {
+        NETWORKS
+            .iter()
+            .find(|network| network.nid.as_bytes() == nid)
+            .ok_or(CaipError::UnknownNetwork)
+    }
+
+    pub fn by_caip2(caip2: &[u8]) -> Result<&'static Network, CaipError> {
+        NETWORKS
+            .iter()
+            .find(|network| network.caip2.as_bytes() == caip2)
+            .ok_or(CaipError::UnknownChain)
+    }
+}
+
+/// Converts a network address to its CAIP-10 account id, the account being normalized.
+pub fn to_caip10(env: &Env, address: &NetworkAddress) -> Result<String, CaipError> {
+    address.validate(&env)?;
+    let (nid, account) = address.get_parts(&env);
+
+    let mut nid_buffer = [0_u8; MAX_NID_LENGTH as usize];
+    let nid_slice = &mut nid_buffer[..nid.len() as usize];
+    nid.copy_into_slice(nid_slice);
+    let network = Network::by_nid(nid_slice)?;
+
+    let mut account_buffer = [0_u8; MAX_ACCOUNT_LENGTH as usize];
+    let account_slice = &mut account_buffer[..account.len() as usize];
+    account.copy_into_slice(account_slice);
+    network.family.normalize(account_slice)?;
+
+    let mut caip10 = Bytes::from_slice(&env, network.caip2.as_bytes());
+    caip10.push_back(CAIP_SEPARATOR);
+    caip10.extend_from_slice(account_slice);
+    Ok(NetworkAddress::get_string_from_bytes(&env, caip10))
+}
+
+/// Converts a CAIP-10 account id of a known chain to a network address, the account being
+/// normalized.
+pub fn from_caip10(env: &Env, value: String) -> Result<NetworkAddress, CaipError> {
+    if value.len() > MAX_CAIP10_LENGTH {
+        return Err(CaipError::InvalidCaip10);
+    }
+    let bytes = NetworkAddress::get_bytes_from_string(&env, value);
+    let mut buffer = [0_u8; MAX_CAIP10_LENGTH as usize];
+    let value = &mut buffer[..bytes.len() as usize];
+    bytes.copy_into_slice(value);
+
+    let separator = value
+        .iter()
+        .rposition(|&c| c == CAIP_SEPARATOR)
+        .ok_or(CaipError::InvalidCaip10)?;
+    let (caip2, account) = value.split_at_mut(separator);
+    let account = &mut account[1..];
+    let (namespace, reference) = match caip2.iter().position(|&c| c == CAIP_SEPARATOR) {
+        Some(index) => (&caip2[..index], &caip2[index + 1..]),
+        None => return Err(CaipError::InvalidCaip10),
+    };
+    if namespace.is_empty()
+        || reference.is_empty()
+        || account.is_empty()
+        || reference.contains(&CAIP_SEPARATOR)
+    {
+        return Err(CaipError::InvalidCaip10);
+    }
+
+    let network = Network::by_caip2(caip2)?;
+    network.family.normalize(account)?;
+    let account = core::str::from_utf8(account).map_err(|_| CaipError::InvalidAccount)?;
+    Ok(NetworkAddress::try_new(
+        &env,
+        String::from_str(&env, network.nid),
+        String::from_str(&env, account),
+    )?)
+}
+
+impl AddressFamily {
+    /// Normalizes `account` in place, failing if it is not an account of the family.
+    pub fn normalize(&self, account: &mut [u8]) -> Result<(), CaipError> {
+        let valid = match self {
+            AddressFamily::Hex => hex_account(account, &[b"0x"]),
+            AddressFamily::Icon => hex_account(account, &[b"hx", b"cx"]),
+            AddressFamily::Bech32(hrp) => bech32_account(account, hrp.as_bytes()),
+            AddressFamily::Base58 => base58_account(account),
+            AddressFamily::Strkey => strkey_account(account),
+        };
+        if !valid {
+            return Err(CaipError::InvalidAccount);
+        }
+        Ok(())
+    }
+}
+
+fn hex_account(account: &mut [u8], prefixes: &[&[u8]]) -> bool {
+    account.make_ascii_lowercase();
+    account.len() == 42
+        && prefixes.iter().any(|prefix| account.starts_with(prefix))
+        && account[2..].iter().all(|c| c.is_ascii_hexdigit())
+}
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn bech32_polymod_step(checksum: u32, value: u8) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let top = checksum >> 25;
+    let mut checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+    for (i, generator) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            checksum ^= generator;
+        }
+    }
+    checksum
+}
+
+fn bech32_account(account: &mut [u8], hrp: &[u8]) -> bool {
+    // bech32 is either lowercase or uppercase
+    if account.iter().any(|c| c.is_ascii_lowercase())
+        && account.iter().any(|c| c.is_ascii_uppercase())
+    {
+        return false;
+    }
+    account.make_ascii_lowercase();
+    let separator = match account.iter().rposition(|&c| c == b'1') {
+        Some(separator) => separator,
+        None => return false,
+    };
+    let (prefix, data) = (&account[..separator], &account[separator + 1..]);
+    if prefix != hrp || data.len() < 6 {
+        return false;
+    }
+
+    let mut checksum = 1;
+    for c in prefix.iter() {
+        checksum = bech32_polymod_step(checksum, c >> 5);
+    }
+    checksum = bech32_polymod_step(checksum, 0);
+    for c in prefix.iter() {
+        checksum = bech32_polymod_step(checksum, c & 31);
+    }
+    for c in data.iter() {
+        match BECH32_CHARSET.iter().position(|v| v == c) {
+            Some(value) => checksum = bech32_polymod_step(checksum, value as u8),
+            None => return false,
+        }
+    }
+    checksum == 1
+}
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base58_account(account: &[u8]) -> bool {
+    let mut key = [0_u8; 32];
+    for c in account.iter() {
+        let mut carry = match BASE58_ALPHABET.iter().position(|v| v == c) {
+            Some(value) => value as u32,
+            None => return false,
+        };
+        for byte in key.iter_mut().rev() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        if carry > 0 {
+            return false;
+        }
+    }
+    // every leading zero byte of the key is encoded as a leading `1`
+    let zeros = key.iter().take_while(|&&byte| byte == 0).count();
+    let ones = account.iter().take_while(|&&c| c == b'1').count();
+    !account.is_empty() && zeros == ones
+}
+
+const STRKEY_ACCOUNT: u8 = 6 << 3;
+const STRKEY_CONTRACT: u8 = 2 << 3;
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc = 0_u16;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn strkey_account(account: &mut [u8]) -> bool {
+    account.make_ascii_uppercase();
+    if account.len() != 56 {
+        return false;
+    }
+
+    // a version byte, the 32 byte key and a crc16 checksum, base32 encoded
+    let mut data = [0_u8; 35];
+    let (mut buffer, mut bits, mut index) = (0_u32, 0_u32, 0);
+    for c in account.iter() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return false,
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            data[index] = (buffer >> bits) as u8;
+            buffer &= (1 << bits) - 1;
+            index += 1;
+        }
+    }
+
+    let checksum = u16::from_le_bytes([data[33], data[34]]);
+    (data[0] == STRKEY_ACCOUNT || data[0] == STRKEY_CONTRACT)
+        && crc16_xmodem(&data[..33]) == checksum
+}
@@ -0,0 +1,24 @@
+use soroban_rlp::{decoder, strict};
+use soroban_sdk::{contracttype, Bytes, Env};
+
+/// Why a connection could not deliver a message to its destination, reported by the relayer
+/// instead of a response. The codes match the `NackReason` of the CosmWasm connections.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum NackReason {
+    DestinationRejected = 1,
+    ExceededSize = 2,
+    NetworkHalted = 3,
+}
+
+/// Returns the sequence number of the request an xCall result message answers, or `None` when
+/// `msg` is not a result.
+pub fn result_sequence_no(env: &Env, msg: Bytes) -> Option<u128> {
+    let decoded = decoder::try_decode_list(&env, msg).ok()?;
+    if decoded.len() != 2 || strict::payload_to_u32(decoded.get(0)?).ok()? != 2 {
+        return None;
+    }
+    let result = decoder::try_decode_list(&env, decoded.get(1)?).ok()?;
+    strict::payload_to_u128(result.get(0)?).ok()
+}
@@ -1,5 +1,7 @@
 #![no_std]
 
+pub mod caip;
+pub mod connection;
 pub mod messages;
 pub mod network_address;
 
@@ -1,4 +1,8 @@
-use soroban_sdk::{contracttype, Bytes};
+use soroban_rlp::{
+    decoder::{self, DecodeError},
+    encoder,
+};
+use soroban_sdk::{contracttype, Bytes, Env, Vec};
 
 use super::msg_trait::IMessage;
 
@@ -16,3 +20,23 @@ impl IMessage for CallMessage {
         None
     }
 }
+
+impl CallMessage {
+    pub fn encode(&self, e: &Env) -> Bytes {
+        let mut list: Vec<Bytes> = Vec::new(&e);
+        list.push_back(encoder::encode(&e, self.data.clone()));
+
+        encoder::encode_list(&e, list, false)
+    }
+
+    pub fn decode(e: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        let decoded = decoder::try_decode_list(&e, bytes)?;
+        if decoded.len() != 1 {
+            return Err(DecodeError::InvalidListLength);
+        }
+
+        Ok(Self {
+            data: decoded.get(0).unwrap(),
+        })
+    }
+}
@@ -0,0 +1,47 @@
+use soroban_rlp::{
+    decoder::{self, DecodeError},
+    encoder,
+};
+use soroban_sdk::{contracttype, Bytes, Env, Vec};
+
+use super::msg_trait::IMessage;
+
+/// A call whose destination dapp returns data, carried back in the result and delivered to the
+/// sender through `handle_call_response`. The sender is rolled back when the call fails.
+#[contracttype]
+pub struct CallMessageWithResponse {
+    pub data: Bytes,
+    pub rollback: Bytes,
+}
+
+impl IMessage for CallMessageWithResponse {
+    fn data(&self) -> Bytes {
+        self.data.clone()
+    }
+
+    fn rollback(&self) -> Option<Bytes> {
+        Some(self.rollback.clone())
+    }
+}
+
+impl CallMessageWithResponse {
+    pub fn encode(&self, e: &Env) -> Bytes {
+        let mut list: Vec<Bytes> = Vec::new(&e);
+        list.push_back(encoder::encode(&e, self.data.clone()));
+        list.push_back(encoder::encode(&e, self.rollback.clone()));
+
+        encoder::encode_list(&e, list, false)
+    }
+
+    pub fn decode(e: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        let decoded = decoder::try_decode_list(&e, bytes)?;
+        if decoded.len() != 2 {
+            return Err(DecodeError::InvalidListLength);
+        }
+
+        Ok(Self {
+            data: decoded.get(0).unwrap(),
+            rollback: decoded.get(1).unwrap(),
+        })
+    }
+}
@@ -1,4 +1,8 @@
-use soroban_sdk::{contracttype, Bytes};
+use soroban_rlp::{
+    decoder::{self, DecodeError},
+    encoder,
+};
+use soroban_sdk::{contracttype, Bytes, Env, Vec};
 
 use super::msg_trait::IMessage;
 
@@ -17,3 +21,25 @@ impl IMessage for CallMessageWithRollback {
         Some(self.rollback.clone())
     }
 }
+
+impl CallMessageWithRollback {
+    pub fn encode(&self, e: &Env) -> Bytes {
+        let mut list: Vec<Bytes> = Vec::new(&e);
+        list.push_back(encoder::encode(&e, self.data.clone()));
+        list.push_back(encoder::encode(&e, self.rollback.clone()));
+
+        encoder::encode_list(&e, list, false)
+    }
+
+    pub fn decode(e: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        let decoded = decoder::try_decode_list(&e, bytes)?;
+        if decoded.len() != 2 {
+            return Err(DecodeError::InvalidListLength);
+        }
+
+        Ok(Self {
+            data: decoded.get(0).unwrap(),
+            rollback: decoded.get(1).unwrap(),
+        })
+    }
+}
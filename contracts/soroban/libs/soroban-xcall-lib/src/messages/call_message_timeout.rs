@@ -0,0 +1,54 @@
+use soroban_rlp::{
+    decoder::{self, DecodeError},
+    encoder, strict,
+};
+use soroban_sdk::{contracttype, Bytes, Env, Vec};
+
+use super::msg_trait::IMessage;
+
+/// A call rolled back when the destination can no longer execute it: once its block height
+/// reaches `timeout_height` or its time, in seconds since the unix epoch, reaches
+/// `timeout_timestamp`. A zero deadline is not checked, at least one must be set.
+#[contracttype]
+pub struct CallMessageWithTimeout {
+    pub data: Bytes,
+    pub rollback: Bytes,
+    pub timeout_height: u64,
+    pub timeout_timestamp: u64,
+}
+
+impl IMessage for CallMessageWithTimeout {
+    fn data(&self) -> Bytes {
+        self.data.clone()
+    }
+
+    fn rollback(&self) -> Option<Bytes> {
+        Some(self.rollback.clone())
+    }
+}
+
+impl CallMessageWithTimeout {
+    pub fn encode(&self, e: &Env) -> Bytes {
+        let mut list: Vec<Bytes> = Vec::new(&e);
+        list.push_back(encoder::encode(&e, self.data.clone()));
+        list.push_back(encoder::encode(&e, self.rollback.clone()));
+        list.push_back(encoder::encode_u64(&e, self.timeout_height));
+        list.push_back(encoder::encode_u64(&e, self.timeout_timestamp));
+
+        encoder::encode_list(&e, list, false)
+    }
+
+    pub fn decode(e: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        let decoded = decoder::try_decode_list(&e, bytes)?;
+        if decoded.len() != 4 {
+            return Err(DecodeError::InvalidListLength);
+        }
+
+        Ok(Self {
+            data: decoded.get(0).unwrap(),
+            rollback: decoded.get(1).unwrap(),
+            timeout_height: strict::payload_to_u64(decoded.get(2).unwrap())?,
+            timeout_timestamp: strict::payload_to_u64(decoded.get(3).unwrap())?,
+        })
+    }
+}
@@ -1,10 +1,55 @@
-use soroban_sdk::{contracttype, String, Vec};
+use soroban_rlp::{
+    decoder::{self, DecodeError},
+    encoder, strict,
+};
+use soroban_sdk::{contracttype, Bytes, Env, String, Vec};
 
-use super::AnyMessage;
+use super::{msg_type::MessageType, AnyMessage};
 
+/// `gas_limit` bounds the gas the destination dapp may use to execute the message, a zero limit
+/// leaves it unbounded and is not encoded.
 #[contracttype]
 pub struct Envelope {
     pub message: AnyMessage,
     pub sources: Vec<String>,
     pub destinations: Vec<String>,
+    pub gas_limit: u64,
+}
+
+impl Envelope {
+    /// Encodes the envelope as the other xcall implementations do, the message being nested as
+    /// the bytes of its own encoding.
+    pub fn encode(&self, e: &Env) -> Bytes {
+        let mut list: Vec<Bytes> = Vec::new(&e);
+        list.push_back(encoder::encode_u8(&e, self.message.msg_type().into()));
+        list.push_back(encoder::encode(&e, self.message.encode(&e)));
+        list.push_back(encoder::encode_strings(&e, self.sources.clone()));
+        list.push_back(encoder::encode_strings(&e, self.destinations.clone()));
+        if self.gas_limit > 0 {
+            list.push_back(encoder::encode_u64(&e, self.gas_limit));
+        }
+
+        encoder::encode_list(&e, list, false)
+    }
+
+    pub fn decode(e: &Env, bytes: Bytes) -> Result<Self, DecodeError> {
+        let decoded = decoder::try_decode_list(&e, bytes)?;
+        let gas_limit = match decoded.len() {
+            4 => 0,
+            5 => strict::payload_to_u64(decoded.get(4).unwrap())?,
+            _ => return Err(DecodeError::InvalidListLength),
+        };
+
+        let msg_type = MessageType::try_from(strict::payload_to_u8(decoded.get(0).unwrap())?)?;
+        let message = AnyMessage::decode(&e, msg_type, decoded.get(1).unwrap())?;
+        let sources = decoder::try_decode_strings(&e, decoded.get(2).unwrap())?;
+        let destinations = decoder::try_decode_strings(&e, decoded.get(3).unwrap())?;
+
+        Ok(Self {
+            message,
+            sources,
+            destinations,
+            gas_limit,
+        })
+    }
 }
@@ -1,15 +1,19 @@
-use soroban_sdk::{contracttype, Bytes};
+use soroban_rlp::decoder::DecodeError;
+use soroban_sdk::{contracttype, Bytes, Env};
 
 pub mod call_message;
 pub mod call_message_persisted;
+pub mod call_message_response;
 pub mod call_message_rollback;
+pub mod call_message_timeout;
 pub mod envelope;
 pub mod msg_trait;
 pub mod msg_type;
 
 use self::{
     call_message::CallMessage, call_message_persisted::CallMessagePersisted,
-    call_message_rollback::CallMessageWithRollback, msg_trait::IMessage, msg_type::MessageType,
+    call_message_response::CallMessageWithResponse, call_message_rollback::CallMessageWithRollback,
+    call_message_timeout::CallMessageWithTimeout, msg_trait::IMessage, msg_type::MessageType,
 };
 
 #[contracttype]
@@ -17,6 +21,8 @@ pub enum AnyMessage {
     CallMessage(CallMessage),
     CallMessageWithRollback(CallMessageWithRollback),
     CallMessagePersisted(CallMessagePersisted),
+    CallMessageWithTimeout(CallMessageWithTimeout),
+    CallMessageWithResponse(CallMessageWithResponse),
 }
 
 impl IMessage for AnyMessage {
@@ -25,6 +31,8 @@ impl IMessage for AnyMessage {
             AnyMessage::CallMessage(msg) => msg.data(),
             AnyMessage::CallMessagePersisted(msg) => msg.data(),
             AnyMessage::CallMessageWithRollback(msg) => msg.data(),
+            AnyMessage::CallMessageWithTimeout(msg) => msg.data(),
+            AnyMessage::CallMessageWithResponse(msg) => msg.data(),
         }
     }
 
@@ -33,6 +41,8 @@ impl IMessage for AnyMessage {
             AnyMessage::CallMessage(_) => None,
             AnyMessage::CallMessagePersisted(_) => None,
             AnyMessage::CallMessageWithRollback(msg) => msg.rollback(),
+            AnyMessage::CallMessageWithTimeout(msg) => msg.rollback(),
+            AnyMessage::CallMessageWithResponse(msg) => msg.rollback(),
         }
     }
 }
@@ -43,6 +53,37 @@ impl AnyMessage {
             AnyMessage::CallMessage(_) => MessageType::CallMessage,
             AnyMessage::CallMessageWithRollback(_) => MessageType::CallMessageWithRollback,
             AnyMessage::CallMessagePersisted(_) => MessageType::CallMessagePersisted,
+            AnyMessage::CallMessageWithTimeout(_) => MessageType::CallMessageWithTimeout,
+            AnyMessage::CallMessageWithResponse(_) => MessageType::CallMessageWithResponse,
         }
     }
+
+    pub fn encode(&self, e: &Env) -> Bytes {
+        match self {
+            AnyMessage::CallMessage(msg) => msg.encode(&e),
+            AnyMessage::CallMessageWithRollback(msg) => msg.encode(&e),
+            AnyMessage::CallMessagePersisted(msg) => msg.encode(&e),
+            AnyMessage::CallMessageWithTimeout(msg) => msg.encode(&e),
+            AnyMessage::CallMessageWithResponse(msg) => msg.encode(&e),
+        }
+    }
+
+    pub fn decode(e: &Env, msg_type: MessageType, bytes: Bytes) -> Result<Self, DecodeError> {
+        let message = match msg_type {
+            MessageType::CallMessage => AnyMessage::CallMessage(CallMessage::decode(&e, bytes)?),
+            MessageType::CallMessageWithRollback => {
+                AnyMessage::CallMessageWithRollback(CallMessageWithRollback::decode(&e, bytes)?)
+            }
+            MessageType::CallMessagePersisted => {
+                AnyMessage::CallMessagePersisted(CallMessagePersisted::decode(&e, bytes)?)
+            }
+            MessageType::CallMessageWithTimeout => {
+                AnyMessage::CallMessageWithTimeout(CallMessageWithTimeout::decode(&e, bytes)?)
+            }
+            MessageType::CallMessageWithResponse => {
+                AnyMessage::CallMessageWithResponse(CallMessageWithResponse::decode(&e, bytes)?)
+            }
+        };
+        Ok(message)
+    }
 }
@@ -1,3 +1,4 @@
+use soroban_rlp::decoder::DecodeError;
 use soroban_sdk::contracttype;
 
 #[contracttype]
@@ -7,6 +8,8 @@ pub enum MessageType {
     CallMessage = 0,
     CallMessageWithRollback = 1,
     CallMessagePersisted = 2,
+    CallMessageWithTimeout = 3,
+    CallMessageWithResponse = 4,
 }
 
 impl From<MessageType> for u8 {
@@ -15,17 +18,25 @@ impl From<MessageType> for u8 {
             MessageType::CallMessage => 0,
             MessageType::CallMessageWithRollback => 1,
             MessageType::CallMessagePersisted => 2,
+            MessageType::CallMessageWithTimeout => 3,
+            MessageType::CallMessageWithResponse => 4,
         }
     }
 }
 
-impl From<u8> for MessageType {
-    fn from(value: u8) -> Self {
+/// Fails for the message types of newer versions, so they can be answered instead of aborting
+/// the invocation.
+impl TryFrom<u8> for MessageType {
+    type Error = DecodeError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => MessageType::CallMessage,
-            1 => MessageType::CallMessageWithRollback,
-            2 => MessageType::CallMessagePersisted,
-            _ => panic!("invalid message type"),
+            0 => Ok(MessageType::CallMessage),
+            1 => Ok(MessageType::CallMessageWithRollback),
+            2 => Ok(MessageType::CallMessagePersisted),
+            3 => Ok(MessageType::CallMessageWithTimeout),
+            4 => Ok(MessageType::CallMessageWithResponse),
+            _ => Err(DecodeError::InvalidValue),
         }
     }
 }
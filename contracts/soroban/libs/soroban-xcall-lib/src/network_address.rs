@@ -9,11 +9,26 @@ const SC_DATA_BEGIN: u32 = 8;
 const SC_STRING: u8 = 14;
 const SEPERATOR: u8 = 47;
 
+pub const MAX_NID_LENGTH: u32 = 64;
+pub const MAX_ACCOUNT_LENGTH: u32 = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkAddressError {
+    MissingSeparator,
+    TooManySeparators,
+    EmptyNetworkId,
+    EmptyAccount,
+    NetworkIdTooLong,
+    AccountTooLong,
+}
+
 #[contracttype]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NetworkAddress(String);
 
 impl NetworkAddress {
+    /// Joins parts known to be valid, such as the network id and an address of this chain; use
+    /// `try_new` for parts received from a caller.
     pub fn new(env: &Env, nid: String, account: String) -> Self {
         let mut address = Bytes::new(&env);
 
@@ -27,6 +42,38 @@ impl NetworkAddress {
         Self(Self::get_string_from_bytes(&env, address))
     }
 
+    pub fn try_new(env: &Env, nid: String, account: String) -> Result<Self, NetworkAddressError> {
+        let address = Self::new(&env, nid, account);
+        address.validate(&env)?;
+        Ok(address)
+    }
+
+    /// Parses a network id and an account separated by a single `/`, neither being empty nor
+    /// longer than `MAX_NID_LENGTH` and `MAX_ACCOUNT_LENGTH` bytes.
+    pub fn parse(env: &Env, value: String) -> Result<Self, NetworkAddressError> {
+        let address = Self(value);
+        address.validate(&env)?;
+        Ok(address)
+    }
+
+    /// Checks an address that was not parsed, such as one received as a contract argument.
+    pub fn validate(&self, env: &Env) -> Result<(), NetworkAddressError> {
+        let (nid, account) = self.split(&env)?;
+        if nid.is_empty() {
+            return Err(NetworkAddressError::EmptyNetworkId);
+        }
+        if account.is_empty() {
+            return Err(NetworkAddressError::EmptyAccount);
+        }
+        if nid.len() > MAX_NID_LENGTH {
+            return Err(NetworkAddressError::NetworkIdTooLong);
+        }
+        if account.len() > MAX_ACCOUNT_LENGTH {
+            return Err(NetworkAddressError::AccountTooLong);
+        }
+        Ok(())
+    }
+
     pub fn nid(&self, env: &Env) -> String {
         let (nid, _) = self.get_parts(&env);
         Self::get_string_from_bytes(&env, nid)
@@ -54,47 +101,35 @@ impl NetworkAddress {
     }
 
     pub fn get_parts(&self, env: &Env) -> (Bytes, Bytes) {
-        let mut nid = Bytes::new(&env);
-        let mut account = Bytes::new(&env);
+        match self.split(&env) {
+            Ok(parts) => parts,
+            Err(_) => panic!("Invalid network address"),
+        }
+    }
 
+    fn split(&self, env: &Env) -> Result<(Bytes, Bytes), NetworkAddressError> {
         let addr_slice = Self::get_bytes_from_string(&env, self.0.clone());
 
-        let mut has_seperator = false;
-        for (index, value) in addr_slice.clone().iter().enumerate() {
-            if has_seperator {
-                account.append(&addr_slice.slice(index as u32..addr_slice.len()));
-                break;
-            } else if value == SEPERATOR {
-                has_seperator = true;
-            } else {
-                nid.push_back(value)
-            }
-        }
-
-        if !has_seperator {
-            panic!("Invalid network address")
+        let index = match addr_slice.iter().position(|value| value == SEPERATOR) {
+            Some(index) => index as u32,
+            None => return Err(NetworkAddressError::MissingSeparator),
+        };
+        let nid = addr_slice.slice(..index);
+        let account = addr_slice.slice(index + 1..);
+        if account.iter().any(|value| value == SEPERATOR) {
+            return Err(NetworkAddressError::TooManySeparators);
         }
 
-        (nid, account)
+        Ok((nid, account))
     }
 
     /// It converts string value to xdr and extract the slice of string bytes
     ///
-    /// Note: This function assumes that the string is less than 256. If string is more than 256
-    /// bytes, this will panic
-    ///
     /// Returns:
     /// a `sequence of bytes`
     pub fn get_bytes_from_string(env: &Env, value: String) -> Bytes {
         let bytes = value.to_xdr(&env);
-
-        if bytes.get(6).unwrap() > 0 {
-            panic!("Invalid network address length")
-        }
-
-        let value_len = bytes.get(7).unwrap();
-        let slice = bytes.slice(SC_DATA_BEGIN..value_len as u32 + SC_DATA_BEGIN);
-        slice
+        bytes.slice(SC_DATA_BEGIN..SC_DATA_BEGIN + value.len())
     }
 
     /// It converts sequence of bytes to xdr and convert xdr of type `bytes` to `string`
@@ -1 +1,5 @@
+mod caip;
+mod connection;
+mod golden_vectors;
+mod msg_type;
 mod network_address;
@@ -0,0 +1,67 @@
+use soroban_sdk::{Env, String};
+
+use crate::{
+    caip::{from_caip10, to_caip10, CaipError, Network, NETWORKS},
+    network_address::NetworkAddress,
+};
+
+const VECTORS: &str = include_str!("../../../../../../test-vectors/caip/addresses.txt");
+
+fn error(name: &str) -> CaipError {
+    match name {
+        "InvalidCaip10" => CaipError::InvalidCaip10,
+        "UnknownChain" => CaipError::UnknownChain,
+        "UnknownNetwork" => CaipError::UnknownNetwork,
+        "InvalidAccount" => CaipError::InvalidAccount,
+        "InvalidNetworkAddress" => CaipError::InvalidNetworkAddress,
+        _ => panic!("unknown error {}", name),
+    }
+}
+
+#[test]
+fn test_caip_vectors() {
+    let env = Env::default();
+
+    for line in VECTORS.lines() {
+        let line = line.split('#').next().unwrap();
+        let mut fields = line.split_whitespace();
+        let (verdict, first, second) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(verdict), Some(first), Some(second)) => (verdict, first, second),
+            _ => continue,
+        };
+
+        match verdict {
+            "valid" => {
+                let caip10 = String::from_str(&env, first);
+                let address = NetworkAddress::from_string(String::from_str(&env, second));
+                assert_eq!(from_caip10(&env, caip10.clone()), Ok(address.clone()));
+                assert_eq!(to_caip10(&env, &address), Ok(caip10));
+            }
+            "normalized" => {
+                let caip10 = String::from_str(&env, first);
+                let address = NetworkAddress::from_string(String::from_str(&env, second));
+                assert_eq!(from_caip10(&env, caip10), Ok(address));
+            }
+            "invalid" => {
+                let caip10 = String::from_str(&env, first);
+                assert_eq!(from_caip10(&env, caip10), Err(error(second)), "{}", first);
+            }
+            "unmapped" => {
+                let address = NetworkAddress::from_string(String::from_str(&env, first));
+                assert_eq!(to_caip10(&env, &address), Err(error(second)), "{}", first);
+            }
+            _ => panic!("unknown vector {}", line),
+        }
+    }
+}
+
+#[test]
+fn test_networks_are_unique() {
+    for (i, network) in NETWORKS.iter().enumerate() {
+        assert_eq!(Network::by_nid(network.nid.as_bytes()), Ok(&NETWORKS[i]));
+        assert_eq!(
+            Network::by_caip2(network.caip2.as_bytes()),
+            Ok(&NETWORKS[i])
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use soroban_rlp::encoder;
+use soroban_sdk::{bytes, vec, Bytes, Env};
+
+use crate::connection::result_sequence_no;
+
+fn message(env: &Env, msg_type: u32, sn: u128) -> Bytes {
+    let payload = encoder::encode_list(
+        &env,
+        vec![
+            &env,
+            encoder::encode_u128(&env, sn),
+            encoder::encode_u8(&env, 1),
+        ],
+        false,
+    );
+    encoder::encode_list(
+        &env,
+        vec![
+            &env,
+            encoder::encode_u32(&env, msg_type),
+            encoder::encode(&env, payload),
+        ],
+        false,
+    )
+}
+
+#[test]
+fn test_result_sequence_no() {
+    let env = Env::default();
+
+    assert_eq!(result_sequence_no(&env, message(&env, 2, 7)), Some(7));
+    assert_eq!(result_sequence_no(&env, message(&env, 2, 200)), Some(200));
+    assert_eq!(result_sequence_no(&env, message(&env, 1, 7)), None);
+    assert_eq!(result_sequence_no(&env, bytes!(&env, 0x010203)), None);
+}
@@ -0,0 +1,114 @@
+use serde_json::Value;
+use soroban_rlp::strict;
+use soroban_sdk::{Bytes, Env, String, Vec};
+
+use crate::messages::{
+    call_message::CallMessage, call_message_persisted::CallMessagePersisted,
+    call_message_response::CallMessageWithResponse, call_message_rollback::CallMessageWithRollback,
+    call_message_timeout::CallMessageWithTimeout, envelope::Envelope, msg_trait::IMessage,
+    AnyMessage,
+};
+
+const VECTORS: &str = include_str!("../../../../../../test-vectors/xcall/messages.json");
+
+fn bytes(env: &Env, value: &Value) -> Bytes {
+    let hex = value.as_str().unwrap();
+    let mut bytes = Bytes::new(&env);
+    for i in (0..hex.len()).step_by(2) {
+        bytes.push_back(u8::from_str_radix(&hex[i..i + 2], 16).unwrap());
+    }
+    bytes
+}
+
+fn strings(env: &Env, value: &Value) -> Vec<String> {
+    let mut strings = Vec::new(&env);
+    for value in value.as_array().unwrap() {
+        strings.push_back(String::from_str(&env, value.as_str().unwrap()));
+    }
+    strings
+}
+
+fn any_message(env: &Env, msg_type: &str, value: &Value) -> AnyMessage {
+    let data = bytes(&env, &value["data"]);
+    match msg_type {
+        "CallMessage" => AnyMessage::CallMessage(CallMessage { data }),
+        "CallMessageWithRollback" => AnyMessage::CallMessageWithRollback(CallMessageWithRollback {
+            data,
+            rollback: bytes(&env, &value["rollback"]),
+        }),
+        "CallMessagePersisted" => AnyMessage::CallMessagePersisted(CallMessagePersisted { data }),
+        "CallMessageWithTimeout" => AnyMessage::CallMessageWithTimeout(CallMessageWithTimeout {
+            data,
+            rollback: bytes(&env, &value["rollback"]),
+            timeout_height: value["timeout_height"].as_u64().unwrap(),
+            timeout_timestamp: value["timeout_timestamp"].as_u64().unwrap(),
+        }),
+        "CallMessageWithResponse" => AnyMessage::CallMessageWithResponse(CallMessageWithResponse {
+            data,
+            rollback: bytes(&env, &value["rollback"]),
+        }),
+        _ => panic!("unknown message type {}", msg_type),
+    }
+}
+
+fn assert_message_eq(actual: &AnyMessage, expected: &AnyMessage, name: &str) {
+    assert_eq!(actual.msg_type(), expected.msg_type(), "{}", name);
+    assert_eq!(actual.data(), expected.data(), "{}", name);
+    assert_eq!(actual.rollback(), expected.rollback(), "{}", name);
+    if let (
+        AnyMessage::CallMessageWithTimeout(actual),
+        AnyMessage::CallMessageWithTimeout(expected),
+    ) = (actual, expected)
+    {
+        assert_eq!(actual.timeout_height, expected.timeout_height, "{}", name);
+        assert_eq!(
+            actual.timeout_timestamp, expected.timeout_timestamp,
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_golden_vectors() {
+    let env = Env::default();
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+
+    for vector in vectors.as_array().unwrap() {
+        let name = vector["name"].as_str().unwrap();
+        let value = &vector["value"];
+        let encoded = bytes(&env, &vector["hex"]);
+
+        match vector["type"].as_str().unwrap() {
+            "Envelope" => {
+                let message = &value["message"];
+                let envelope = Envelope {
+                    message: any_message(&env, message["type"].as_str().unwrap(), message),
+                    sources: strings(&env, &value["sources"]),
+                    destinations: strings(&env, &value["destinations"]),
+                    gas_limit: 0,
+                };
+                assert_eq!(envelope.encode(&env), encoded, "{}", name);
+
+                let decoded = Envelope::decode(&env, encoded.clone()).unwrap();
+                assert_message_eq(&decoded.message, &envelope.message, name);
+                assert_eq!(decoded.sources, envelope.sources, "{}", name);
+                assert_eq!(decoded.destinations, envelope.destinations, "{}", name);
+            }
+            msg_type @ ("CallMessage"
+            | "CallMessageWithRollback"
+            | "CallMessagePersisted"
+            | "CallMessageWithTimeout"
+            | "CallMessageWithResponse") => {
+                let msg = any_message(&env, msg_type, value);
+                assert_eq!(msg.encode(&env), encoded, "{}", name);
+
+                let decoded = AnyMessage::decode(&env, msg.msg_type(), encoded.clone()).unwrap();
+                assert_message_eq(&decoded, &msg, name);
+            }
+            // wire types of the xcall contract, tested there
+            _ => continue,
+        }
+        assert_eq!(strict::validate(&encoded), Ok(()), "{}", name);
+    }
+}
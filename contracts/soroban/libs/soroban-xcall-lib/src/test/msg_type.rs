@@ -0,0 +1,75 @@
+use soroban_rlp::{
+    decoder::{self, DecodeError},
+    encoder,
+};
+use soroban_sdk::{bytes, vec, Bytes, Env};
+
+use crate::messages::{
+    call_message::CallMessage, envelope::Envelope, msg_trait::IMessage, msg_type::MessageType,
+    AnyMessage,
+};
+
+#[test]
+fn test_message_type_try_from() {
+    assert_eq!(MessageType::try_from(0_u8), Ok(MessageType::CallMessage));
+    assert_eq!(
+        MessageType::try_from(1_u8),
+        Ok(MessageType::CallMessageWithRollback)
+    );
+    assert_eq!(
+        MessageType::try_from(2_u8),
+        Ok(MessageType::CallMessagePersisted)
+    );
+    assert_eq!(
+        MessageType::try_from(3_u8),
+        Ok(MessageType::CallMessageWithTimeout)
+    );
+    assert_eq!(
+        MessageType::try_from(4_u8),
+        Ok(MessageType::CallMessageWithResponse)
+    );
+    assert_eq!(MessageType::try_from(7_u8), Err(DecodeError::InvalidValue));
+}
+
+#[test]
+fn test_envelope_unsupported_message_type() {
+    let env = Env::default();
+
+    let list = vec![
+        &env,
+        encoder::encode_u8(&env, 7),
+        encoder::encode(&env, bytes!(&env, 0xc483010203)),
+        encoder::encode_list(&env, vec![&env], false),
+        encoder::encode_list(&env, vec![&env], false),
+    ];
+    let encoded: Bytes = encoder::encode_list(&env, list, false);
+
+    assert!(matches!(
+        Envelope::decode(&env, encoded),
+        Err(DecodeError::InvalidValue)
+    ));
+}
+
+#[test]
+fn test_envelope_with_gas_limit() {
+    let env = Env::default();
+
+    let mut envelope = Envelope {
+        message: AnyMessage::CallMessage(CallMessage {
+            data: bytes!(&env, 0x010203),
+        }),
+        sources: vec![&env],
+        destinations: vec![&env],
+        gas_limit: 0,
+    };
+    let without_limit = envelope.encode(&env);
+    assert_eq!(decoder::decode_list(&env, without_limit).len(), 4);
+
+    envelope.gas_limit = 200_000;
+    let encoded = envelope.encode(&env);
+    assert_eq!(decoder::decode_list(&env, encoded.clone()).len(), 5);
+
+    let decoded = Envelope::decode(&env, encoded).unwrap();
+    assert_eq!(decoded.message.data(), envelope.message.data());
+    assert_eq!(decoded.gas_limit, 200_000);
+}
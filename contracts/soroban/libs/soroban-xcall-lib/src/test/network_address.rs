@@ -1,6 +1,20 @@
 use soroban_sdk::{Env, String};
 
-use crate::network_address::NetworkAddress;
+use crate::network_address::{NetworkAddress, NetworkAddressError};
+
+const VECTORS: &str = include_str!("../../../../../../test-vectors/network-address/addresses.txt");
+
+fn error(name: &str) -> NetworkAddressError {
+    match name {
+        "MissingSeparator" => NetworkAddressError::MissingSeparator,
+        "TooManySeparators" => NetworkAddressError::TooManySeparators,
+        "EmptyNetworkId" => NetworkAddressError::EmptyNetworkId,
+        "EmptyAccount" => NetworkAddressError::EmptyAccount,
+        "NetworkIdTooLong" => NetworkAddressError::NetworkIdTooLong,
+        "AccountTooLong" => NetworkAddressError::AccountTooLong,
+        _ => panic!("unknown error {}", name),
+    }
+}
 
 #[test]
 fn test_network_address_new() {
@@ -62,3 +76,33 @@ fn test_parse_network_address_fail_if_separator_not_found() {
     ));
     network_address.parse_network_address(&env);
 }
+
+#[test]
+fn test_network_address_vectors() {
+    let env = Env::default();
+
+    for line in VECTORS.lines() {
+        let line = line.split('#').next().unwrap();
+        let mut fields = line.split_whitespace();
+        let (verdict, address) = match (fields.next(), fields.next()) {
+            (Some(verdict), Some(address)) => (verdict, String::from_str(&env, address)),
+            _ => continue,
+        };
+
+        match verdict {
+            "valid" => {
+                let nid = String::from_str(&env, fields.next().unwrap());
+                let account = String::from_str(&env, fields.next().unwrap());
+                let parsed = NetworkAddress::parse(&env, address.clone()).unwrap();
+                assert_eq!(parsed.nid(&env), nid);
+                assert_eq!(parsed.account(&env), account);
+                assert_eq!(NetworkAddress::try_new(&env, nid, account), Ok(parsed));
+            }
+            "invalid" => {
+                let expected = error(fields.next().unwrap());
+                assert_eq!(NetworkAddress::parse(&env, address), Err(expected));
+            }
+            _ => panic!("unknown vector {}", line),
+        }
+    }
+}
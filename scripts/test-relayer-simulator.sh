@@ -0,0 +1,10 @@
+#!/bin/bash
+set -e
+
+# The relayer simulator loads the soroban contracts from their wasm build
+cd contracts/soroban
+cargo build --target wasm32-unknown-unknown --release -p xcall -p cluster-connection -p mock-dapp-multi
+cd -
+
+cd tools/relayer-simulator
+cargo test "$@"
@@ -0,0 +1,264 @@
+use std::{collections::VecDeque, str::FromStr};
+
+use anyhow::anyhow;
+use cosmwasm_std::{Addr, Empty, Event};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_xcall_lib::{network_address::NetworkAddress, xcall_msg::ExecuteMsg as XcallExecuteMsg};
+use serde::Serialize;
+
+use crate::{Chain, Packet, TestValidators};
+
+const DENOM: &str = "ucosm";
+
+fn xcall_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(cw_xcall::execute, cw_xcall::instantiate, cw_xcall::query)
+        .with_reply(cw_xcall::reply);
+    Box::new(contract)
+}
+
+fn connection_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cluster_connection::execute,
+        cluster_connection::instantiate,
+        cluster_connection::query,
+    );
+    Box::new(contract)
+}
+
+fn dapp_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_mock_dapp_multi::execute,
+        cw_mock_dapp_multi::instantiate,
+        cw_mock_dapp_multi::query,
+    );
+    Box::new(contract)
+}
+
+fn attribute(event: &Event, key: &str) -> Option<String> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.clone())
+}
+
+/// A CosmWasm chain running xCall, the cluster connection and the multi protocol mock dapp in a
+/// `cw-multi-test` app.
+pub struct CwChain {
+    pub app: App,
+    pub nid: String,
+    pub admin: Addr,
+    pub relayer: Addr,
+    pub xcall: Addr,
+    pub connection: Addr,
+    pub dapp: Addr,
+    packets: VecDeque<Packet>,
+    requests: VecDeque<u128>,
+    rollbacks: VecDeque<u128>,
+}
+
+impl CwChain {
+    pub fn new(nid: &str, validators: &TestValidators) -> Self {
+        let mut app = App::default();
+        let admin = Addr::unchecked("admin");
+        let relayer = Addr::unchecked("relayer");
+
+        let code_id = app.store_code(xcall_contract());
+        let xcall = app
+            .instantiate_contract(
+                code_id,
+                admin.clone(),
+                &cw_xcall::msg::InstantiateMsg {
+                    network_id: nid.to_string(),
+                    denom: DENOM.to_string(),
+                },
+                &[],
+                "XCall",
+                None,
+            )
+            .unwrap();
+
+        let code_id = app.store_code(connection_contract());
+        let connection = app
+            .instantiate_contract(
+                code_id,
+                admin.clone(),
+                &cluster_connection::InstantiateMsg {
+                    relayer: relayer.to_string(),
+                    xcall_address: xcall.to_string(),
+                    denom: DENOM.to_string(),
+                },
+                &[],
+                "ClusterConnection",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            admin.clone(),
+            connection.clone(),
+            &cluster_connection::msg::ExecuteMsg::SetValidators {
+                validators: validators.public_keys(),
+                threshold: validators.threshold(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let code_id = app.store_code(dapp_contract());
+        let dapp = app
+            .instantiate_contract(
+                code_id,
+                admin.clone(),
+                &cw_mock_dapp_multi::types::InstantiateMsg {
+                    address: xcall.to_string(),
+                },
+                &[],
+                "MockDapp",
+                None,
+            )
+            .unwrap();
+
+        Self {
+            app,
+            nid: nid.to_string(),
+            admin,
+            relayer,
+            xcall,
+            connection,
+            dapp,
+            packets: VecDeque::new(),
+            requests: VecDeque::new(),
+            rollbacks: VecDeque::new(),
+        }
+    }
+
+    /// Network address of the mock dapp, the target of messages sent to this chain.
+    pub fn dapp_network_address(&self) -> String {
+        format!("{}/{}", self.nid, self.dapp)
+    }
+
+    /// Executes `msg` on `contract` and records the packets, requests and rollbacks it emitted.
+    pub fn execute<T: Serialize + std::fmt::Debug>(
+        &mut self,
+        sender: &Addr,
+        contract: &Addr,
+        msg: &T,
+    ) -> anyhow::Result<AppResponse> {
+        let res = self
+            .app
+            .execute_contract(sender.clone(), contract.clone(), msg, &[])?;
+        self.record(&res)?;
+        Ok(res)
+    }
+
+    /// Sends `data` to `to` from the mock dapp, with a rollback when `rollback` is set.
+    pub fn send_call_message(
+        &mut self,
+        to: &str,
+        data: Vec<u8>,
+        rollback: Option<Vec<u8>>,
+    ) -> anyhow::Result<AppResponse> {
+        let msg = cw_mock_dapp_multi::msg::ExecuteMsg::SendCallMessage {
+            to: NetworkAddress::from_str(to)?,
+            data,
+            rollback,
+        };
+        let (admin, dapp) = (self.admin.clone(), self.dapp.clone());
+        self.execute(&admin, &dapp, &msg)
+    }
+
+    pub fn execute_call(&mut self, request_id: u128, data: Vec<u8>) -> anyhow::Result<AppResponse> {
+        let msg = XcallExecuteMsg::ExecuteCall { request_id, data };
+        let (admin, xcall) = (self.admin.clone(), self.xcall.clone());
+        self.execute(&admin, &xcall, &msg)
+    }
+
+    pub fn execute_rollback(&mut self, sequence_no: u128) -> anyhow::Result<AppResponse> {
+        let msg = XcallExecuteMsg::ExecuteRollback { sequence_no };
+        let (admin, xcall) = (self.admin.clone(), self.xcall.clone());
+        self.execute(&admin, &xcall, &msg)
+    }
+
+    /// Takes the oldest request received by xCall and not executed yet.
+    pub fn take_request(&mut self) -> Option<u128> {
+        self.requests.pop_front()
+    }
+
+    /// Takes the oldest sequence number xCall enabled the rollback of.
+    pub fn take_rollback(&mut self) -> Option<u128> {
+        self.rollbacks.pop_front()
+    }
+
+    fn record(&mut self, res: &AppResponse) -> anyhow::Result<()> {
+        for event in res.events.iter() {
+            let contract = attribute(event, "_contract_address").unwrap_or_default();
+            // xCall names its rollback event with a trailing space
+            match event.ty.trim_end() {
+                "wasm-Message" if contract == self.connection.as_str() => {
+                    let msg = attribute(event, "msg").unwrap_or_default();
+                    let msg = match msg.as_str() {
+                        "null" => vec![],
+                        hex_msg => hex::decode(hex_msg)?,
+                    };
+                    self.packets.push_back(Packet {
+                        src_network: self.nid.clone(),
+                        dst_network: attribute(event, "targetNetwork").unwrap_or_default(),
+                        conn_sn: attribute(event, "connSn").unwrap_or_default().parse()?,
+                        msg,
+                    });
+                }
+                "wasm-CallMessage" if contract == self.xcall.as_str() => {
+                    let req_id = attribute(event, "reqId").unwrap_or_default();
+                    self.requests.push_back(req_id.parse()?);
+                }
+                "wasm-RollbackMessage" if contract == self.xcall.as_str() => {
+                    let sn = attribute(event, "sn").unwrap_or_default();
+                    self.rollbacks.push_back(sn.parse()?);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Chain for CwChain {
+    fn nid(&self) -> &str {
+        &self.nid
+    }
+
+    fn connection(&self) -> String {
+        self.connection.to_string()
+    }
+
+    fn add_connection(&mut self, nid: &str, connection: &str) {
+        let msg = cw_mock_dapp_multi::msg::ExecuteMsg::AddConnection {
+            src_endpoint: self.connection.to_string(),
+            dest_endpoint: connection.to_string(),
+            network_id: nid.to_string(),
+        };
+        let (admin, dapp) = (self.admin.clone(), self.dapp.clone());
+        self.execute(&admin, &dapp, &msg).unwrap();
+    }
+
+    fn take_packets(&mut self) -> Vec<Packet> {
+        self.packets.drain(..).collect()
+    }
+
+    fn deliver(&mut self, packet: &Packet, validators: &TestValidators) -> anyhow::Result<()> {
+        let msg = cluster_connection::msg::ExecuteMsg::RecvMessage {
+            src_network: packet.src_network.as_str().into(),
+            conn_sn: packet.conn_sn,
+            msg: hex::encode(&packet.msg),
+            signatures: validators
+                .sign(packet)
+                .into_iter()
+                .map(|signature| signature.to_vec())
+                .collect(),
+        };
+        let (relayer, connection) = (self.relayer.clone(), self.connection.clone());
+        self.execute(&relayer, &connection, &msg)
+            .map_err(|err| anyhow!("{} failed to receive {:?}: {}", self.nid, packet, err))?;
+        Ok(())
+    }
+}
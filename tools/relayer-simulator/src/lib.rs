@@ -0,0 +1,43 @@
+//! Off-chain relayer for in-process end to end tests of xCall across chains.
+
+pub mod cosmwasm;
+pub mod relayer;
+pub mod soroban;
+pub mod validators;
+
+pub use cosmwasm::CwChain;
+pub use relayer::Relayer;
+pub use soroban::SorobanChain;
+pub use validators::TestValidators;
+
+/// A message emitted by the connection of `src_network` for `dst_network`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub src_network: String,
+    pub dst_network: String,
+    pub conn_sn: u128,
+    pub msg: Vec<u8>,
+}
+
+/// A chain the relayer can pick up packets from and deliver packets to.
+pub trait Chain {
+    fn nid(&self) -> &str;
+
+    /// Address of the cluster connection, as used in the protocols of a message.
+    fn connection(&self) -> String;
+
+    /// Registers `connection` of `nid` as the destination of the mock dapp.
+    fn add_connection(&mut self, nid: &str, connection: &str);
+
+    /// Returns the packets emitted since the last call.
+    fn take_packets(&mut self) -> Vec<Packet>;
+
+    /// Receives `packet` on the connection with the signatures of `validators`.
+    fn deliver(&mut self, packet: &Packet, validators: &TestValidators) -> anyhow::Result<()>;
+}
+
+/// Connects the mock dapps of `a` and `b` through their cluster connections.
+pub fn connect<A: Chain, B: Chain>(a: &mut A, b: &mut B) {
+    a.add_connection(b.nid(), &b.connection());
+    b.add_connection(a.nid(), &a.connection());
+}
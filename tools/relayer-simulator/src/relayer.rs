@@ -0,0 +1,45 @@
+use anyhow::bail;
+
+use crate::{Chain, Packet, TestValidators};
+
+/// Relays packets between two chains, signing them with the validators both connections trust.
+pub struct Relayer {
+    validators: TestValidators,
+}
+
+impl Relayer {
+    pub fn new(validators: TestValidators) -> Self {
+        Self { validators }
+    }
+
+    pub fn validators(&self) -> &TestValidators {
+        &self.validators
+    }
+
+    /// Delivers the packets of `a` and `b` to each other until neither has any left, as the
+    /// delivery of a packet can emit new ones. Returns the number of delivered packets.
+    pub fn relay<A: Chain, B: Chain>(&self, a: &mut A, b: &mut B) -> anyhow::Result<usize> {
+        let mut delivered = 0;
+        loop {
+            let packets: Vec<Packet> = a
+                .take_packets()
+                .into_iter()
+                .chain(b.take_packets())
+                .collect();
+            if packets.is_empty() {
+                return Ok(delivered);
+            }
+
+            for packet in packets.iter() {
+                if packet.dst_network == a.nid() {
+                    a.deliver(packet, &self.validators)?;
+                } else if packet.dst_network == b.nid() {
+                    b.deliver(packet, &self.validators)?;
+                } else {
+                    bail!("no chain for network {}", packet.dst_network);
+                }
+                delivered += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::VecDeque;
+
+use anyhow::anyhow;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token::StellarAssetClient,
+    Address, Bytes, BytesN, Env, String as SorobanString, Symbol, TryFromVal, Vec,
+};
+
+use crate::{Chain, Packet, TestValidators};
+
+mod xcall {
+    soroban_sdk::contractimport!(
+        file = "../../contracts/soroban/target/wasm32-unknown-unknown/release/xcall.wasm"
+    );
+}
+
+mod connection {
+    soroban_sdk::contractimport!(
+        file =
+            "../../contracts/soroban/target/wasm32-unknown-unknown/release/cluster_connection.wasm"
+    );
+}
+
+mod dapp {
+    soroban_sdk::contractimport!(
+        file = "../../contracts/soroban/target/wasm32-unknown-unknown/release/mock_dapp_multi.wasm"
+    );
+}
+
+fn to_string(value: &SorobanString) -> String {
+    let mut buf = vec![0u8; value.len() as usize];
+    value.copy_into_slice(&mut buf);
+    String::from_utf8(buf).unwrap()
+}
+
+/// A Soroban chain running xCall, the cluster connection and the multi protocol mock dapp in a
+/// test `Env`.
+pub struct SorobanChain {
+    pub env: Env,
+    pub nid: String,
+    pub admin: Address,
+    pub relayer: Address,
+    pub native_token: Address,
+    pub xcall: Address,
+    pub connection: Address,
+    pub dapp: Address,
+    packets: VecDeque<Packet>,
+    requests: VecDeque<u128>,
+    rollbacks: VecDeque<u128>,
+    // events of the last invocation are kept in the env, skip the ones already recorded
+    last_conn_sn: u128,
+    last_req_id: u128,
+    last_rollback: u128,
+}
+
+impl SorobanChain {
+    pub fn new(nid: &str, validators: &TestValidators) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+
+        let admin = Address::generate(&env);
+        let relayer = Address::generate(&env);
+        let native_token = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        StellarAssetClient::new(&env, &native_token).mint(&admin, &1_000_000_000);
+
+        let xcall = env.register_contract_wasm(None, xcall::WASM);
+        let connection = env.register_contract_wasm(None, connection::WASM);
+        let dapp = env.register_contract_wasm(None, dapp::WASM);
+
+        xcall::Client::new(&env, &xcall).initialize(&xcall::InitializeMsg {
+            network_id: SorobanString::from_str(&env, nid),
+            sender: admin.clone(),
+            native_token: native_token.clone(),
+            upgrade_authority: admin.clone(),
+        });
+
+        let connection_client = connection::Client::new(&env, &connection);
+        connection_client.initialize(&connection::InitializeMsg {
+            relayer: relayer.clone(),
+            admin: admin.clone(),
+            native_token: native_token.clone(),
+            xcall_address: xcall.clone(),
+            upgrade_authority: admin.clone(),
+        });
+        let mut pub_keys = Vec::new(&env);
+        for key in validators.public_keys() {
+            let key: [u8; 65] = key.try_into().unwrap();
+            pub_keys.push_back(BytesN::from_array(&env, &key));
+        }
+        connection_client.update_validators(&pub_keys, &(validators.threshold() as u32));
+
+        dapp::Client::new(&env, &dapp).init(&admin, &xcall, &native_token);
+
+        Self {
+            env,
+            nid: nid.to_string(),
+            admin,
+            relayer,
+            native_token,
+            xcall,
+            connection,
+            dapp,
+            packets: VecDeque::new(),
+            requests: VecDeque::new(),
+            rollbacks: VecDeque::new(),
+            last_conn_sn: 0,
+            last_req_id: 0,
+            last_rollback: 0,
+        }
+    }
+
+    /// Network address of the mock dapp, the target of messages sent to this chain.
+    pub fn dapp_network_address(&self) -> String {
+        format!("{}/{}", self.nid, to_string(&self.dapp.to_string()))
+    }
+
+    /// Sends `data` to `to` from the mock dapp, with a rollback when `rollback` is set.
+    pub fn send_call_message(
+        &mut self,
+        to: &str,
+        data: &[u8],
+        rollback: Option<&[u8]>,
+    ) -> anyhow::Result<u128> {
+        let env = &self.env;
+        let msg_type = if rollback.is_some() { 1 } else { 0 };
+        let sn = dapp::Client::new(env, &self.dapp)
+            .try_send_call_message(
+                &dapp::NetworkAddress(SorobanString::from_str(env, to)),
+                &Bytes::from_slice(env, data),
+                &msg_type,
+                &rollback.map(|rollback| Bytes::from_slice(env, rollback)),
+                &self.admin,
+            )
+            .map_err(|err| anyhow!("send_call_message failed: {:?}", err))?
+            .map_err(|err| anyhow!("send_call_message failed: {:?}", err))?;
+        self.collect();
+        Ok(sn)
+    }
+
+    pub fn execute_call(&mut self, req_id: u128, data: &[u8]) -> anyhow::Result<()> {
+        let env = &self.env;
+        xcall::Client::new(env, &self.xcall)
+            .try_execute_call(&self.admin, &req_id, &Bytes::from_slice(env, data))
+            .map_err(|err| anyhow!("execute_call failed: {:?}", err))?
+            .map_err(|err| anyhow!("execute_call failed: {:?}", err))?;
+        self.collect();
+        Ok(())
+    }
+
+    pub fn execute_rollback(&mut self, sequence_no: u128) -> anyhow::Result<()> {
+        xcall::Client::new(&self.env, &self.xcall)
+            .try_execute_rollback(&sequence_no)
+            .map_err(|err| anyhow!("execute_rollback failed: {:?}", err))?
+            .map_err(|err| anyhow!("execute_rollback failed: {:?}", err))?;
+        self.collect();
+        Ok(())
+    }
+
+    /// Takes the oldest request received by xCall and not executed yet.
+    pub fn take_request(&mut self) -> Option<u128> {
+        self.requests.pop_front()
+    }
+
+    /// Takes the oldest sequence number xCall enabled the rollback of.
+    pub fn take_rollback(&mut self) -> Option<u128> {
+        self.rollbacks.pop_front()
+    }
+
+    fn collect(&mut self) {
+        let env = &self.env;
+        for (contract, topics, data) in env.events().all().iter() {
+            let topic = match topics.get(0).map(|topic| Symbol::try_from_val(env, &topic)) {
+                Some(Ok(topic)) => topic,
+                _ => continue,
+            };
+
+            if contract == self.connection && topic == Symbol::new(env, "Message") {
+                let event = connection::SendMsgEvent::try_from_val(env, &data).unwrap();
+                if event.connSn <= self.last_conn_sn {
+                    continue;
+                }
+                self.last_conn_sn = event.connSn;
+                self.packets.push_back(Packet {
+                    src_network: self.nid.clone(),
+                    dst_network: to_string(&event.targetNetwork),
+                    conn_sn: event.connSn,
+                    msg: event.msg.to_alloc_vec(),
+                });
+            } else if contract == self.xcall && topic == Symbol::new(env, "CallMessage") {
+                let event = xcall::CallMsgEvent::try_from_val(env, &data).unwrap();
+                if event.reqId <= self.last_req_id {
+                    continue;
+                }
+                self.last_req_id = event.reqId;
+                self.requests.push_back(event.reqId);
+            } else if contract == self.xcall && topic == Symbol::new(env, "RollbackMessage") {
+                let event = xcall::RollbackMsgEvent::try_from_val(env, &data).unwrap();
+                if event.sn <= self.last_rollback {
+                    continue;
+                }
+                self.last_rollback = event.sn;
+                self.rollbacks.push_back(event.sn);
+            }
+        }
+    }
+}
+
+impl Chain for SorobanChain {
+    fn nid(&self) -> &str {
+        &self.nid
+    }
+
+    fn connection(&self) -> String {
+        to_string(&self.connection.to_string())
+    }
+
+    fn add_connection(&mut self, nid: &str, connection: &str) {
+        let env = &self.env;
+        dapp::Client::new(env, &self.dapp).add_connection(
+            &self.connection.to_string(),
+            &SorobanString::from_str(env, connection),
+            &SorobanString::from_str(env, nid),
+        );
+    }
+
+    fn take_packets(&mut self) -> std::vec::Vec<Packet> {
+        self.packets.drain(..).collect()
+    }
+
+    fn deliver(&mut self, packet: &Packet, validators: &TestValidators) -> anyhow::Result<()> {
+        let env = &self.env;
+        let mut signatures = Vec::new(env);
+        for signature in validators.sign(packet) {
+            signatures.push_back(BytesN::from_array(env, &signature));
+        }
+        connection::Client::new(env, &self.connection)
+            .try_recv_message_with_signatures(
+                &self.relayer,
+                &SorobanString::from_str(env, &packet.src_network),
+                &packet.conn_sn,
+                &Bytes::from_slice(env, &packet.msg),
+                &signatures,
+            )
+            .map_err(|err| anyhow!("{} failed to receive {:?}: {:?}", self.nid, packet, err))?
+            .map_err(|err| anyhow!("{} failed to receive {:?}: {:?}", self.nid, packet, err))?;
+        self.collect();
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use cluster_connection::keccak256;
+use cw_xcall_lib::xcall_connection_msg::SignableMsg;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+
+use crate::Packet;
+
+/// Validator keys shared by the cluster connections of the simulated chains. The keys are
+/// derived from their index so runs are reproducible.
+pub struct TestValidators {
+    keys: Vec<SigningKey>,
+    threshold: u8,
+}
+
+impl TestValidators {
+    pub fn new(count: u8, threshold: u8) -> Self {
+        assert!(threshold > 0 && threshold <= count, "invalid threshold");
+        let keys = (1..=count)
+            .map(|i| SigningKey::from_bytes(&[i; 32].into()).unwrap())
+            .collect();
+        Self { keys, threshold }
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Uncompressed public keys of the validators.
+    pub fn public_keys(&self) -> Vec<Vec<u8>> {
+        self.keys
+            .iter()
+            .map(|key| {
+                VerifyingKey::from(key)
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    /// Signs `packet` with `threshold` validators, as `r || s || v` signatures.
+    pub fn sign(&self, packet: &Packet) -> Vec<[u8; 65]> {
+        let signed_msg = SignableMsg {
+            src_network: packet.src_network.clone(),
+            conn_sn: packet.conn_sn,
+            data: packet.msg.clone(),
+            dst_network: packet.dst_network.clone(),
+        }
+        .encode_utf8_bytes();
+        let digest = keccak256(&signed_msg);
+
+        self.keys
+            .iter()
+            .take(self.threshold as usize)
+            .map(|key| {
+                let (signature, recovery_id) = key.sign_digest_recoverable(digest.clone()).unwrap();
+                let mut bytes = [0u8; 65];
+                bytes[..64].copy_from_slice(&signature.to_bytes());
+                bytes[64] = recovery_id.to_byte();
+                bytes
+            })
+            .collect()
+    }
+}
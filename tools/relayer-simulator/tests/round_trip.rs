@@ -0,0 +1,87 @@
+use relayer_simulator::{connect, CwChain, Relayer, SorobanChain, TestValidators};
+
+fn setup() -> (CwChain, SorobanChain, Relayer) {
+    let validators = TestValidators::new(3, 2);
+    let mut cw = CwChain::new("cosmwasm", &validators);
+    let mut soroban = SorobanChain::new("stellar", &validators);
+    connect(&mut cw, &mut soroban);
+    (cw, soroban, Relayer::new(validators))
+}
+
+// the soroban mock dapp decodes the call data as an rlp string
+fn rlp_string(value: &str) -> Vec<u8> {
+    let mut encoded = vec![0x80 + value.len() as u8];
+    encoded.extend_from_slice(value.as_bytes());
+    encoded
+}
+
+#[test]
+fn test_cosmwasm_to_soroban_call() {
+    let (mut cw, mut soroban, relayer) = setup();
+    let data = rlp_string("hello");
+
+    cw.send_call_message(&soroban.dapp_network_address(), data.clone(), None)
+        .unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 1);
+
+    let req_id = soroban.take_request().unwrap();
+    soroban.execute_call(req_id, &data).unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 0);
+}
+
+#[test]
+fn test_cosmwasm_to_soroban_rollback() {
+    let (mut cw, mut soroban, relayer) = setup();
+    let data = rlp_string("rollback");
+
+    cw.send_call_message(
+        &soroban.dapp_network_address(),
+        data.clone(),
+        Some(b"undo".to_vec()),
+    )
+    .unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 1);
+
+    // the dapp reverts, xcall answers with a failure response
+    let req_id = soroban.take_request().unwrap();
+    soroban.execute_call(req_id, &data).unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 1);
+
+    let sn = cw.take_rollback().unwrap();
+    cw.execute_rollback(sn).unwrap();
+}
+
+#[test]
+fn test_soroban_to_cosmwasm_call() {
+    let (mut cw, mut soroban, relayer) = setup();
+    let data = b"hello";
+
+    soroban
+        .send_call_message(&cw.dapp_network_address(), data, None)
+        .unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 1);
+
+    let req_id = cw.take_request().unwrap();
+    cw.execute_call(req_id, data.to_vec()).unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 0);
+}
+
+#[test]
+fn test_soroban_to_cosmwasm_rollback() {
+    let (mut cw, mut soroban, relayer) = setup();
+    // the cosmwasm mock dapp reverts on this data
+    let data = b"rollback";
+
+    let sn = soroban
+        .send_call_message(&cw.dapp_network_address(), data, Some(b"undo"))
+        .unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 1);
+
+    // xcall answers the reverted call with a failure response
+    let req_id = cw.take_request().unwrap();
+    cw.execute_call(req_id, data.to_vec()).unwrap();
+    assert_eq!(relayer.relay(&mut cw, &mut soroban).unwrap(), 1);
+
+    assert_eq!(soroban.take_rollback(), Some(sn));
+    soroban.execute_rollback(sn).unwrap();
+}